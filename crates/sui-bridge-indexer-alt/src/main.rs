@@ -64,6 +64,7 @@ async fn main() -> Result<(), anyhow::Error> {
         ClientArgs {
             remote_store_url: Some(remote_store_url),
             local_ingestion_path: None,
+            local_ingestion_consumer: None,
             rpc_api_url: None,
             rpc_username: None,
             rpc_password: None,
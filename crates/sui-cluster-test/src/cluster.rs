@@ -378,6 +378,8 @@ pub fn new_wallet_context_from_cluster(
         }],
         active_address: Some(address),
         active_env: Some("localnet".to_string()),
+        address_book: Default::default(),
+        templates: Default::default(),
     }
     .persisted(&wallet_config_path)
     .save()
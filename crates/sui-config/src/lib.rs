@@ -32,6 +32,8 @@ pub const SUI_FULLNODE_CONFIG: &str = "fullnode.yaml";
 pub const SUI_CLIENT_CONFIG: &str = "client.yaml";
 pub const SUI_KEYSTORE_FILENAME: &str = "sui.keystore";
 pub const SUI_KEYSTORE_ALIASES_FILENAME: &str = "sui.aliases";
+pub const SUI_ACTIVITY_LOG_FILENAME: &str = "activity.jsonl";
+pub const SUI_COMPLETION_CACHE_FILENAME: &str = "completion_cache.json";
 pub const SUI_BENCHMARK_GENESIS_GAS_KEYSTORE_FILENAME: &str = "benchmark.keystore";
 pub const SUI_GENESIS_FILENAME: &str = "genesis.blob";
 pub const SUI_DEV_NET_URL: &str = "https://fullnode.devnet.sui.io:443";
@@ -17,7 +17,8 @@ use simulacrum::Simulacrum;
 use sui_indexer_alt::{config::IndexerConfig, setup_indexer};
 use sui_indexer_alt_framework::{ingestion::ClientArgs, postgres::schema::watermarks, IndexerArgs};
 use sui_indexer_alt_graphql::{
-    config::RpcConfig as GraphQlConfig, start_rpc as start_graphql, RpcArgs as GraphQlArgs,
+    config::RpcConfig as GraphQlConfig, start_rpc as start_graphql, FullnodeArgs,
+    RpcArgs as GraphQlArgs,
 };
 use sui_indexer_alt_jsonrpc::{
     config::RpcConfig as JsonRpcConfig, start_rpc as start_jsonrpc, NodeArgs as JsonRpcNodeArgs,
@@ -136,6 +137,7 @@ impl FullCluster {
 
         let client_args = ClientArgs {
             local_ingestion_path: Some(temp_dir.path().to_owned()),
+            local_ingestion_consumer: None,
             remote_store_url: None,
             rpc_api_url: None,
             rpc_username: None,
@@ -340,6 +342,7 @@ impl OffchainCluster {
             DbArgs::default(),
             BigtableArgs::default(),
             graphql_args,
+            FullnodeArgs::default(),
             SystemPackageTaskArgs::default(),
             "0.0.0",
             graphql_config,
@@ -0,0 +1,124 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// A publishes a package that mints three FAKE coins for it, and sends some of its FAKE and SUI
+// coins to B. Balances are ordered by the BCS serialization of their coin type, so SUI (defined at
+// 0x2) comes before FAKE.
+
+//# init --protocol-version 70 --accounts A B --addresses P=0x0 --simulator
+
+//# publish --sender A
+module P::fake {
+  use sui::coin;
+
+  public struct FAKE has drop {}
+
+  fun init(witness: FAKE, ctx: &mut TxContext){
+    let (mut treasury_cap, metadata) = coin::create_currency(
+      witness,
+      2,
+      b"FAKE",
+      b"",
+      b"",
+      option::none(),
+      ctx,
+    );
+
+    let c1 = coin::mint(&mut treasury_cap, 1, ctx);
+    let c2 = coin::mint(&mut treasury_cap, 2, ctx);
+    let c3 = coin::mint(&mut treasury_cap, 3, ctx);
+
+    transfer::public_freeze_object(metadata);
+    transfer::public_transfer(treasury_cap, ctx.sender());
+    transfer::public_transfer(c1, ctx.sender());
+    transfer::public_transfer(c2, ctx.sender());
+    transfer::public_transfer(c3, ctx.sender());
+  }
+}
+
+//# create-checkpoint
+
+//# programmable --sender A --inputs 100 200 @B
+//> SplitCoins(Gas, [Input(0), Input(1)]);
+//> TransferObjects([NestedResult(0,0), NestedResult(0,1)], Input(2))
+
+//# create-checkpoint
+
+//# run-graphql
+fragment B on Balance {
+  coinType
+  coinObjectCount
+  totalBalance
+}
+
+{ # A's balances, in full, by coin type, and paginated from both ends
+  address(address: "@{A}") {
+    sui: balance(coinType: "0x2::sui::SUI") { ...B }
+    fake: balance(coinType: "@{P}::fake::FAKE") { ...B }
+    none: balance(coinType: "@{P}::fake::NOT_FAKE") { ...B }
+
+    all: balances {
+      pageInfo { hasPreviousPage hasNextPage }
+      edges { cursor node { ...B } }
+    }
+
+    first: balances(first: 1) {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...B }
+    }
+
+    last: balances(last: 1) {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...B }
+    }
+  }
+}
+
+//# run-graphql
+fragment B on Balance {
+  coinType
+  coinObjectCount
+  totalBalance
+}
+
+{ # Pagination relative to the SUI balance's cursor
+  address(address: "@{A}") {
+    after: balances(after: "KgcAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAgNzdWkDU1VJAA==") {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...B }
+    }
+
+    before: balances(before: "KgcAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAgNzdWkDU1VJAA==") {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...B }
+    }
+  }
+}
+
+//# run-graphql
+fragment B on Balance {
+  coinType
+  coinObjectCount
+  totalBalance
+}
+
+{ # B only holds SUI, and did not hold any before the transfer
+  address(address: "@{B}") {
+    balances { nodes { ...B } }
+  }
+
+  checkpoint(sequenceNumber: 1) {
+    query {
+      address(address: "@{B}") {
+        balances { nodes { ...B } }
+      }
+    }
+  }
+}
+
+//# run-graphql
+{ # The coin type must be a valid type
+  address(address: "@{B}") {
+    balance(coinType: "not a type") { totalBalance }
+  }
+}
@@ -0,0 +1,122 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// A publishes a package that mints three FAKE coins for it, and sends B three SUI coins. Coins are
+// paginated in (roughly) descending order of balance, optionally filtered by coin type.
+
+//# init --protocol-version 70 --accounts A B --addresses P=0x0 --simulator
+
+//# publish --sender A
+module P::fake {
+  use sui::coin;
+
+  public struct FAKE has drop {}
+
+  fun init(witness: FAKE, ctx: &mut TxContext){
+    let (mut treasury_cap, metadata) = coin::create_currency(
+      witness,
+      2,
+      b"FAKE",
+      b"",
+      b"",
+      option::none(),
+      ctx,
+    );
+
+    let c1 = coin::mint(&mut treasury_cap, 100, ctx);
+    let c2 = coin::mint(&mut treasury_cap, 20000, ctx);
+    let c3 = coin::mint(&mut treasury_cap, 3000000, ctx);
+
+    transfer::public_freeze_object(metadata);
+    transfer::public_transfer(treasury_cap, ctx.sender());
+    transfer::public_transfer(c1, ctx.sender());
+    transfer::public_transfer(c2, ctx.sender());
+    transfer::public_transfer(c3, ctx.sender());
+  }
+}
+
+//# programmable --sender A --inputs 100 20000 3000000 @B
+//> SplitCoins(Gas, [Input(0), Input(1), Input(2)]);
+//> TransferObjects([NestedResult(0,0), NestedResult(0,1), NestedResult(0,2)], Input(3))
+
+//# create-checkpoint
+
+//# run-graphql
+fragment C on Coin {
+  coinType
+  coinBalance
+}
+
+{ # All of A's coins, and just its FAKE coins
+  address(address: "@{A}") {
+    all: coins { nodes { ...C } }
+    fake: coins(coinType: "@{P}::fake::FAKE") { nodes { ...C } }
+  }
+}
+
+//# run-graphql
+fragment C on Coin {
+  coinType
+  coinBalance
+}
+
+{ # B's coins, paginated from the front and from the back
+  address(address: "@{B}") {
+    all: coins {
+      pageInfo { hasPreviousPage hasNextPage }
+      edges { cursor node { ...C } }
+    }
+
+    first: coins(first: 2) {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...C }
+    }
+
+    last: coins(last: 2) {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...C }
+    }
+  }
+}
+
+//# run-graphql
+fragment C on Coin {
+  coinType
+  coinBalance
+}
+
+{ # Cursors are (balance bucket, checkpoint, ID). B's coins are all in checkpoint 1, in buckets 2, 4
+  # and 6, so cursors with an empty ID can split them by bucket without knowing their IDs.
+  address(address: "@{B}") {
+    # After (4, 2, []), from the front -- the coins in buckets 4 and 2
+    after: coins(first: 2, after: "BAAAAAAAAAACAAAAAAAAAAA=") {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...C }
+    }
+
+    # Before (4, 0, []), from the back -- the coins in buckets 6 and 4
+    before: coins(last: 2, before: "BAAAAAAAAAAAAAAAAAAAAAA=") {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...C }
+    }
+  }
+}
+
+//# run-graphql
+{ # No coins of a type that doesn't exist
+  address(address: "@{B}") {
+    coins(coinType: "@{P}::fake::NOT_FAKE") {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { coinBalance }
+    }
+  }
+}
+
+//# run-graphql
+{ # The coin type must be a valid type
+  address(address: "@{B}") {
+    coins(coinType: "not a type") {
+      nodes { coinBalance }
+    }
+  }
+}
@@ -0,0 +1,154 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Parent gets two dynamic fields (named 1 and 2) and a dynamic object field (named 3). Dynamic
+// fields are paginated by the IDs of their `Field` objects, which are derived from their parent's
+// ID and their name.
+
+//# init --protocol-version 70 --accounts A --addresses P=0x0 --simulator
+
+//# publish
+module P::M {
+  use sui::dynamic_field as df;
+  use sui::dynamic_object_field as dof;
+
+  public struct Parent has key, store {
+    id: UID,
+  }
+
+  public struct Child has key, store {
+    id: UID,
+    count: u64,
+  }
+
+  public fun parent(ctx: &mut TxContext): Parent {
+    Parent { id: object::new(ctx) }
+  }
+
+  public fun child(count: u64, ctx: &mut TxContext): Child {
+    Child { id: object::new(ctx), count }
+  }
+
+  public fun add_field(parent: &mut Parent, name: u64, value: u64) {
+    df::add(&mut parent.id, name, value);
+  }
+
+  public fun add_child(parent: &mut Parent, name: u64, child: Child) {
+    dof::add(&mut parent.id, name, child);
+  }
+}
+
+//# programmable --sender A --inputs @A
+//> 0: P::M::parent();
+//> TransferObjects([Result(0)], Input(0))
+
+//# programmable --sender A --inputs object(2,0) 1 42
+//> P::M::add_field(Input(0), Input(1), Input(2))
+
+//# programmable --sender A --inputs object(2,0) 2 43
+//> P::M::add_field(Input(0), Input(1), Input(2))
+
+//# create-checkpoint
+
+//# programmable --sender A --inputs 44 @A
+//> 0: P::M::child(Input(0));
+//> TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs object(2,0) 3 object(6,0)
+//> P::M::add_child(Input(0), Input(1), Input(2))
+
+//# create-checkpoint
+
+//# run-graphql
+fragment F on DynamicField {
+  address
+  name { type bcs }
+  value {
+    ... on MoveValue { type bcs }
+    ... on Object { address version }
+  }
+}
+
+{ # All dynamic fields, and individual fields by name
+  object(address: "@{obj_2_0}") {
+    dynamicFields {
+      pageInfo { hasPreviousPage hasNextPage }
+      edges { cursor node { ...F } }
+    }
+
+    df1: dynamicField(name: { type: "u64", bcs: "AQAAAAAAAAA=" }) { ...F }
+    dof3: dynamicField(name: { type: "u64", bcs: "AwAAAAAAAAA=" }) { ...F }
+    missing: dynamicField(name: { type: "u64", bcs: "BAAAAAAAAAA=" }) { ...F }
+    wrongType: dynamicField(name: { type: "u32", bcs: "AQAAAA==" }) { ...F }
+  }
+}
+
+//# run-graphql
+fragment F on DynamicField {
+  address
+  name { bcs }
+}
+
+{ # Limits from the front and the back
+  object(address: "@{obj_2_0}") {
+    first: dynamicFields(first: 2) {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...F }
+    }
+
+    last: dynamicFields(last: 2) {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...F }
+    }
+  }
+}
+
+//# run-graphql --cursors bcs(@{obj_3_0}) bcs(@{obj_4_0})
+fragment F on DynamicField {
+  address
+  name { bcs }
+}
+
+{ # Relative to the `Field` objects for names 1 and 2
+  object(address: "@{obj_2_0}") {
+    after1: dynamicFields(after: "@{cursor_0}") {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...F }
+    }
+
+    before1: dynamicFields(before: "@{cursor_0}") {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...F }
+    }
+
+    after2: dynamicFields(first: 1, after: "@{cursor_1}") {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...F }
+    }
+
+    before2: dynamicFields(last: 1, before: "@{cursor_1}") {
+      pageInfo { hasPreviousPage hasNextPage }
+      nodes { ...F }
+    }
+  }
+}
+
+//# run-graphql
+fragment F on DynamicField {
+  name { bcs }
+  value {
+    ... on MoveValue { bcs }
+    ... on Object { address }
+  }
+}
+
+{ # At the first checkpoint, the dynamic object field had not been added yet
+  checkpoint(sequenceNumber: 1) {
+    query {
+      object(address: "@{obj_2_0}") {
+        dynamicFields { nodes { ...F } }
+        dof3: dynamicField(name: { type: "u64", bcs: "AwAAAAAAAAA=" }) { ...F }
+      }
+    }
+  }
+}
@@ -0,0 +1,122 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// 1. Events filtered by sender
+// 2. ...by emitting package
+// 3. ...by emitting module
+// 4. ...by the package defining the event type
+// 5. ...by the module defining the event type
+// 6. ...by type, matching all its instantiations
+// 7. ...by a fully instantiated type
+// 8. ...by sender and type
+// 9. Filtering by both emitting module and type is an error
+
+//# init --protocol-version 70 --accounts A B --addresses P=0x0 --simulator
+
+//# publish
+module P::M {
+  public struct E(u64) has copy, drop;
+  public struct G<phantom T>(u64) has copy, drop;
+
+  public fun emit(n: u64) {
+    sui::event::emit(E(n))
+  }
+
+  public fun emit_u64(n: u64) {
+    sui::event::emit(G<u64>(n))
+  }
+
+  public fun emit_bool(n: u64) {
+    sui::event::emit(G<bool>(n))
+  }
+}
+
+module P::N {
+  public fun emit(n: u64) {
+    P::M::emit(n)
+  }
+}
+
+//# programmable --sender A --inputs 1
+//> P::M::emit(Input(0))
+
+//# programmable --sender B --inputs 2
+//> P::N::emit(Input(0))
+
+//# programmable --sender A --inputs 3
+//> P::M::emit_u64(Input(0))
+
+//# programmable --sender B --inputs 4
+//> P::M::emit_bool(Input(0))
+
+//# create-checkpoint
+
+//# run-graphql
+{ # By sender
+  a: events(filter: { sender: "@{A}" }) { nodes { sendingModule contents { type bcs } } }
+  b: events(filter: { sender: "@{B}" }) { nodes { sendingModule contents { type bcs } } }
+}
+
+//# run-graphql
+{ # By emitting package
+  events(filter: { emittingModule: "@{P}" }) {
+    nodes { sendingModule contents { type bcs } }
+  }
+}
+
+//# run-graphql
+{ # By emitting module -- P::N emits an event whose type is defined in P::M
+  m: events(filter: { emittingModule: "@{P}::M" }) {
+    nodes { sendingModule contents { type bcs } }
+  }
+
+  n: events(filter: { emittingModule: "@{P}::N" }) {
+    nodes { sendingModule contents { type bcs } }
+  }
+}
+
+//# run-graphql
+{ # By the package defining the event type
+  events(filter: { eventType: "@{P}" }) {
+    nodes { sendingModule contents { type bcs } }
+  }
+}
+
+//# run-graphql
+{ # By the module defining the event type -- includes the event emitted from P::N
+  events(filter: { eventType: "@{P}::M" }) {
+    nodes { sendingModule contents { type bcs } }
+  }
+}
+
+//# run-graphql
+{ # By type, matching all its instantiations
+  e: events(filter: { eventType: "@{P}::M::E" }) {
+    nodes { sendingModule contents { type bcs } }
+  }
+
+  g: events(filter: { eventType: "@{P}::M::G" }) {
+    nodes { sendingModule contents { type bcs } }
+  }
+}
+
+//# run-graphql
+{ # By a fully instantiated type
+  events(filter: { eventType: "@{P}::M::G<u64>" }) {
+    nodes { sendingModule contents { type bcs } }
+  }
+}
+
+//# run-graphql
+{ # By sender and type
+  events(filter: { sender: "@{B}", eventType: "@{P}::M::E" }) {
+    nodes { sendingModule contents { type bcs } }
+  }
+}
+
+//# run-graphql
+{ # Emitting module and type filters cannot be combined
+  events(filter: { emittingModule: "@{P}::M", eventType: "@{P}::M::E" }) {
+    nodes { sendingModule }
+  }
+}
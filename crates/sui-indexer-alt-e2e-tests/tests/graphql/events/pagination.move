@@ -0,0 +1,149 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Events are ordered by the transaction that emitted them, and then by their position within that
+// transaction. Transaction 1 publishes P, and the remaining transactions emit:
+//
+// - tx 2: (2, 0)
+// - tx 3: (3, 0), (3, 1)
+// - tx 4: (4, 0)
+// - tx 5: (5, 0)
+
+//# init --protocol-version 70 --accounts A --addresses P=0x0 --simulator
+
+//# publish
+module P::M {
+  public struct E(u64) has copy, drop;
+
+  public fun emit(n: u64) {
+    sui::event::emit(E(n))
+  }
+
+  public fun emit_two(n: u64) {
+    sui::event::emit(E(n));
+    sui::event::emit(E(n + 1));
+  }
+}
+
+//# programmable --sender A --inputs 1
+//> P::M::emit(Input(0))
+
+//# programmable --sender A --inputs 2
+//> P::M::emit_two(Input(0))
+
+//# create-checkpoint
+
+//# programmable --sender A --inputs 4
+//> P::M::emit(Input(0))
+
+//# programmable --sender A --inputs 5
+//> P::M::emit(Input(0))
+
+//# create-checkpoint
+
+//# run-graphql
+{ # All events, across checkpoints
+  events(filter: { emittingModule: "@{P}::M" }) {
+    pageInfo {
+      hasPreviousPage
+      hasNextPage
+    }
+    edges {
+      cursor
+      node {
+        sequenceNumber
+        sendingModule
+        contents { type bcs }
+        transaction { digest }
+      }
+    }
+  }
+}
+
+//# run-graphql
+{ # Limit from the front
+  events(first: 2, filter: { emittingModule: "@{P}::M" }) {
+    pageInfo {
+      hasPreviousPage
+      hasNextPage
+    }
+    nodes {
+      sequenceNumber
+      contents { bcs }
+    }
+  }
+}
+
+//# run-graphql
+{ # Limit from the back
+  events(last: 2, filter: { emittingModule: "@{P}::M" }) {
+    pageInfo {
+      hasPreviousPage
+      hasNextPage
+    }
+    nodes {
+      sequenceNumber
+      contents { bcs }
+    }
+  }
+}
+
+//# run-graphql
+{ # After (2, 0), from the front -- starts at the first event of transaction 3
+  events(first: 2, after: "AgAAAAAAAAAAAAAAAAAAAA==", filter: { emittingModule: "@{P}::M" }) {
+    pageInfo {
+      hasPreviousPage
+      hasNextPage
+    }
+    nodes {
+      sequenceNumber
+      contents { bcs }
+    }
+  }
+}
+
+//# run-graphql
+{ # Before (4, 0), from the back -- ends at the second event of transaction 3
+  events(last: 2, before: "BAAAAAAAAAAAAAAAAAAAAA==", filter: { emittingModule: "@{P}::M" }) {
+    pageInfo {
+      hasPreviousPage
+      hasNextPage
+    }
+    nodes {
+      sequenceNumber
+      contents { bcs }
+    }
+  }
+}
+
+//# run-graphql
+{ # Between (2, 0) and (4, 0) -- both events of transaction 3
+  events(
+    after: "AgAAAAAAAAAAAAAAAAAAAA==",
+    before: "BAAAAAAAAAAAAAAAAAAAAA==",
+    filter: { emittingModule: "@{P}::M" }
+  ) {
+    pageInfo {
+      hasPreviousPage
+      hasNextPage
+    }
+    nodes {
+      sequenceNumber
+      contents { bcs }
+    }
+  }
+}
+
+//# run-graphql
+{ # Time travel -- only the events from the first checkpoint
+  checkpoint(sequenceNumber: 1) {
+    query {
+      events(filter: { emittingModule: "@{P}::M" }) {
+        nodes {
+          sequenceNumber
+          contents { bcs }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,44 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// The RPC in these tests is not connected to a fullnode, so transactions can't be executed or
+// simulated. Successful execution and simulation are tested against a fullnode in
+// `graphql_execution_tests.rs`.
+
+//# init --protocol-version 70 --accounts A --simulator
+
+//# programmable --sender A --inputs @A
+//> 0: TransferObjects([Gas], Input(0))
+
+//# create-checkpoint
+
+//# run-graphql
+mutation { # Execution requires a fullnode
+  executeTransaction(transactionDataBcs: "AAAA", signatures: ["AAAA"]) {
+    digest
+  }
+}
+
+//# run-graphql
+{ # Simulation requires a fullnode
+  simulateTransaction(transactionDataBcs: "AAAA") {
+    digest
+  }
+}
+
+//# run-graphql
+{ # Effects that are not visible at the checkpoint being viewed have no checkpoint
+  checkpoint(sequenceNumber: 0) {
+    query {
+      transactionEffects(digest: "@{digest_1}") {
+        digest
+        checkpoint { sequenceNumber }
+      }
+    }
+  }
+
+  transactionEffects(digest: "@{digest_1}") {
+    digest
+    checkpoint { sequenceNumber }
+  }
+}
@@ -0,0 +1,129 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Checkpoint | Transactions (by task)
+// -----------|-----------------------
+// 0          | genesis
+// 1          | 1, 2
+// 2          | 4, 5, 6
+// 3          | 8
+
+//# init --protocol-version 70 --accounts A B --simulator
+
+//# programmable --sender A --inputs 1 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 2 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# programmable --sender B --inputs 3 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 4 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender B --inputs 5 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# programmable --sender A --inputs 6 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-graphql
+fragment T on TransactionConnection {
+  pageInfo { hasPreviousPage hasNextPage }
+  edges {
+    cursor
+    node { digest }
+  }
+}
+
+{ # Bounding the scan by checkpoint
+  all: transactions { ...T }
+  at1: transactions(filter: { atCheckpoint: 1 }) { ...T }
+  after1: transactions(filter: { afterCheckpoint: 1 }) { ...T }
+  before2: transactions(filter: { beforeCheckpoint: 2 }) { ...T }
+  between1And3: transactions(filter: { afterCheckpoint: 1, beforeCheckpoint: 3 }) { ...T }
+  atAndAfter: transactions(filter: { atCheckpoint: 2, afterCheckpoint: 1 }) { ...T }
+}
+
+//# run-graphql
+fragment T on TransactionConnection {
+  pageInfo { hasPreviousPage hasNextPage }
+  nodes { digest }
+}
+
+{ # Checkpoint bounds that can't match anything
+  beforeGenesis: transactions(filter: { beforeCheckpoint: 0 }) { ...T }
+  emptyInterval: transactions(filter: { afterCheckpoint: 1, beforeCheckpoint: 2 }) { ...T }
+  atOutsideInterval: transactions(filter: { atCheckpoint: 3, beforeCheckpoint: 3 }) { ...T }
+  inTheFuture: transactions(filter: { atCheckpoint: 100 }) { ...T }
+}
+
+//# run-graphql
+fragment T on TransactionConnection {
+  pageInfo { hasPreviousPage hasNextPage }
+  nodes { digest sender { address } }
+}
+
+{ # Checkpoint bounds combined with other filters
+  sentByBIn2: transactions(filter: { atCheckpoint: 2, sentAddress: "@{B}" }) { ...T }
+  affectingBAfter1: transactions(filter: { afterCheckpoint: 1, affectedAddress: "@{B}" }) { ...T }
+}
+
+//# run-graphql
+fragment T on TransactionConnection {
+  pageInfo { hasPreviousPage hasNextPage }
+  edges {
+    cursor
+    node { digest }
+  }
+}
+
+{ # Limits within a checkpoint bound, from the front and the back
+  first: transactions(first: 2, filter: { atCheckpoint: 2 }) { ...T }
+  last: transactions(last: 2, filter: { atCheckpoint: 2 }) { ...T }
+}
+
+//# run-graphql --cursors 2 4
+fragment T on TransactionConnection {
+  pageInfo { hasPreviousPage hasNextPage }
+  edges {
+    cursor
+    node { digest }
+  }
+}
+
+{ # Cursors combined with checkpoint bounds -- cursors outside the bound don't widen it
+  afterInBound: transactions(after: "@{cursor_0}", filter: { afterCheckpoint: 0 }) { ...T }
+  beforeInBound: transactions(before: "@{cursor_1}", filter: { afterCheckpoint: 0 }) { ...T }
+  afterOutOfBound: transactions(after: "@{cursor_0}", filter: { atCheckpoint: 3 }) { ...T }
+  beforeOutOfBound: transactions(before: "@{cursor_1}", filter: { atCheckpoint: 1 }) { ...T }
+  between: transactions(after: "@{cursor_0}", before: "@{cursor_1}") { ...T }
+}
+
+//# run-graphql
+fragment T on TransactionConnection {
+  pageInfo { hasPreviousPage hasNextPage }
+  nodes { digest }
+}
+
+{ # Time travel -- transactions after the checkpoint being viewed are not visible
+  checkpoint(sequenceNumber: 1) {
+    query {
+      all: transactions { ...T }
+      after1: transactions(filter: { afterCheckpoint: 1 }) { ...T }
+    }
+  }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Task | Sender | Transaction
+// -----|--------|------------------------------------
+// 1    | A      | publish P
+// 2    | A      | call P::M::f
+// 3    | B      | call P::N::g
+// 4    | A      | transfer SUI to B
+// 5    | A      | call P::M::new, creating O
+// 6    | A      | call P::N::touch on O
+
+//# init --protocol-version 70 --accounts A B --addresses P=0x0 --simulator
+
+//# publish --sender A
+module P::M {
+  public struct O has key, store {
+    id: UID,
+    count: u64,
+  }
+
+  public fun f() {}
+
+  public fun new(ctx: &mut TxContext): O {
+    O { id: object::new(ctx), count: 0 }
+  }
+
+  public fun bump(o: &mut O) {
+    o.count = o.count + 1;
+  }
+}
+
+module P::N {
+  public fun g() {}
+
+  public fun touch(o: &mut P::M::O) {
+    P::M::bump(o)
+  }
+}
+
+//# programmable --sender A
+//> P::M::f()
+
+//# programmable --sender B
+//> P::N::g()
+
+//# programmable --sender A --inputs 42 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs @A
+//> 0: P::M::new();
+//> 1: TransferObjects([Result(0)], Input(0))
+
+//# programmable --sender A --inputs object(5,0)
+//> P::N::touch(Input(0))
+
+//# create-checkpoint
+
+//# run-graphql
+fragment T on TransactionConnection {
+  nodes {
+    digest
+    sender { address }
+  }
+}
+
+{ # Filtering by the function called, at package, module and function granularity
+  package: transactions(filter: { function: "@{P}" }) { ...T }
+  moduleM: transactions(filter: { function: "@{P}::M" }) { ...T }
+  moduleN: transactions(filter: { function: "@{P}::N" }) { ...T }
+  function: transactions(filter: { function: "@{P}::M::f" }) { ...T }
+  noMatch: transactions(filter: { function: "@{P}::M::bump" }) { ...T }
+}
+
+//# run-graphql
+fragment T on TransactionConnection {
+  nodes {
+    digest
+    sender { address }
+  }
+}
+
+{ # Filtering by kind -- genesis is the only system transaction
+  system: transactions(filter: { kind: SYSTEM_TX }) { ...T }
+  programmable: transactions(filter: { kind: PROGRAMMABLE_TX }) { ...T }
+}
+
+//# run-graphql
+fragment T on TransactionConnection {
+  nodes {
+    digest
+    sender { address }
+  }
+}
+
+{ # Filtering by sender, and by affected address -- B is affected by its own transaction, and by
+  # the transfer from A.
+  sentByA: transactions(filter: { sentAddress: "@{A}" }) { ...T }
+  sentByB: transactions(filter: { sentAddress: "@{B}" }) { ...T }
+  affectingB: transactions(filter: { affectedAddress: "@{B}" }) { ...T }
+}
+
+//# run-graphql
+fragment T on TransactionConnection {
+  nodes {
+    digest
+    sender { address }
+  }
+}
+
+{ # Filtering by affected object -- O was created by task 5 and modified by task 6
+  transactions(filter: { affectedObject: "@{obj_5_0}" }) { ...T }
+}
+
+//# run-graphql
+fragment T on TransactionConnection {
+  nodes {
+    digest
+    sender { address }
+  }
+}
+
+{ # Intersections of filters
+  sentByAToP: transactions(filter: { sentAddress: "@{A}", function: "@{P}" }) { ...T }
+  sentByAAffectingB: transactions(filter: { sentAddress: "@{A}", affectedAddress: "@{B}" }) { ...T }
+  sentByBToM: transactions(filter: { sentAddress: "@{B}", function: "@{P}::M" }) { ...T }
+  affectingOToN: transactions(filter: { affectedObject: "@{obj_5_0}", function: "@{P}::N" }) { ...T }
+  systemFromA: transactions(filter: { kind: SYSTEM_TX, sentAddress: "@{A}" }) { ...T }
+  programmableFromB: transactions(filter: { kind: PROGRAMMABLE_TX, sentAddress: "@{B}" }) { ...T }
+}
+
+//# run-graphql
+fragment T on TransactionConnection {
+  nodes {
+    digest
+    sender { address }
+  }
+}
+
+{ # Filters are intersected with the checkpoint's own bound when paginating its transactions
+  checkpoint(sequenceNumber: 1) {
+    all: transactions { ...T }
+    sentByB: transactions(filter: { sentAddress: "@{B}" }) { ...T }
+    otherCheckpoint: transactions(filter: { atCheckpoint: 0 }) { ...T }
+  }
+}
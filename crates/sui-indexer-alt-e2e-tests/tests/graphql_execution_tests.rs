@@ -0,0 +1,349 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use anyhow::Context;
+use fastcrypto::encoding::{Base64, Encoding};
+use prometheus::Registry;
+use reqwest::Client;
+use serde_json::{json, Value};
+use sui_indexer_alt_graphql::{config::RpcConfig, start_rpc, FullnodeArgs, RpcArgs};
+use sui_indexer_alt_reader::{
+    bigtable_reader::BigtableArgs, system_package_task::SystemPackageTaskArgs,
+};
+use sui_macros::sim_test;
+use sui_pg_db::{temp::get_available_port, DbArgs};
+use sui_swarm_config::genesis_config::AccountConfig;
+use sui_types::{
+    effects::{TransactionEffects, TransactionEffectsAPI},
+    transaction::TransactionData,
+};
+use test_cluster::{TestCluster, TestClusterBuilder};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+/// Fields of `TransactionEffects` that are requested by every query in these tests.
+const EFFECTS: &str = r#"
+    digest
+    effectsBcs
+    effectsDigest
+    checkpoint { sequenceNumber }
+    transaction {
+        digest
+        sender { address }
+        transactionBcs
+    }
+    objectChanges {
+        nodes {
+            address
+            idCreated
+            idDeleted
+        }
+    }
+"#;
+
+struct GraphQlTestCluster {
+    onchain_cluster: TestCluster,
+    url: Url,
+    handle: JoinHandle<()>,
+    client: Client,
+    cancel: CancellationToken,
+}
+
+impl GraphQlTestCluster {
+    /// Creates a new test cluster with a GraphQL RPC that forwards transactions to its fullnode,
+    /// and has no database to read from.
+    async fn new() -> anyhow::Result<Self> {
+        let onchain_cluster = TestClusterBuilder::new()
+            .with_num_validators(1)
+            .with_epoch_duration_ms(2000)
+            .with_accounts(vec![
+                AccountConfig {
+                    address: None,
+                    gas_amounts: vec![1_000_000_000_000; 2],
+                };
+                4
+            ])
+            .build()
+            .await;
+
+        let fullnode_rpc_url = Url::parse(onchain_cluster.rpc_url())?;
+
+        let cancel = CancellationToken::new();
+
+        let rpc_listen_address =
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), get_available_port());
+        let url = Url::parse(&format!("http://{}/graphql", rpc_listen_address))
+            .expect("Failed to parse RPC URL");
+
+        // We don't expose metrics in these tests, but we create a registry to collect them anyway.
+        let registry = Registry::new();
+
+        let rpc_args = RpcArgs {
+            rpc_listen_address,
+            ..Default::default()
+        };
+
+        let handle = start_rpc(
+            None,
+            None,
+            DbArgs::default(),
+            BigtableArgs::default(),
+            rpc_args,
+            FullnodeArgs {
+                fullnode_rpc_url: Some(fullnode_rpc_url),
+            },
+            SystemPackageTaskArgs::default(),
+            "0.0.0",
+            RpcConfig::default(),
+            vec![],
+            &registry,
+            cancel.child_token(),
+        )
+        .await
+        .expect("Failed to start GraphQL server");
+
+        Ok(Self {
+            onchain_cluster,
+            url,
+            handle,
+            client: Client::new(),
+            cancel,
+        })
+    }
+
+    /// Builds a transfer of SUI between two addresses, returning its digest, its Base64-encoded
+    /// `TransactionData`, and its signatures.
+    async fn transfer_transaction(&self) -> (String, String, Vec<String>) {
+        let addresses = self.onchain_cluster.wallet.get_addresses();
+
+        let recipient = addresses[1];
+        let tx = self
+            .onchain_cluster
+            .test_transaction_builder()
+            .await
+            .transfer_sui(Some(1_000), recipient)
+            .build();
+
+        self.sign(tx)
+    }
+
+    /// Builds a transaction that aborts during execution, because it is not sent by a validator.
+    async fn aborting_transaction(&self) -> (String, String, Vec<String>) {
+        let tx = self
+            .onchain_cluster
+            .test_transaction_builder()
+            .await
+            .call_request_remove_validator()
+            .build();
+
+        self.sign(tx)
+    }
+
+    fn sign(&self, tx: TransactionData) -> (String, String, Vec<String>) {
+        let digest = tx.digest().to_string();
+        let signed_tx = self.onchain_cluster.wallet.sign_transaction(&tx);
+        let (tx_bytes, sigs) = signed_tx.to_tx_bytes_and_signatures();
+        let sigs = sigs.iter().map(|sig| sig.encoded()).collect();
+        (digest, tx_bytes.encoded(), sigs)
+    }
+
+    async fn query(&self, query: String, variables: Value) -> anyhow::Result<Value> {
+        let response = self
+            .client
+            .post(self.url.clone())
+            .json(&json!({
+                "query": query,
+                "variables": variables,
+            }))
+            .send()
+            .await
+            .context("Request to GraphQL server failed")?;
+
+        response
+            .json()
+            .await
+            .context("Failed to parse GraphQL response")
+    }
+
+    async fn execute(&self, tx_bytes: &str, sigs: &[String]) -> anyhow::Result<Value> {
+        self.query(
+            format!(
+                r#"mutation ($tx: Base64!, $sigs: [Base64!]!) {{
+                    executeTransaction(transactionDataBcs: $tx, signatures: $sigs) {{ {EFFECTS} }}
+                }}"#
+            ),
+            json!({ "tx": tx_bytes, "sigs": sigs }),
+        )
+        .await
+    }
+
+    async fn simulate(&self, tx_bytes: &str) -> anyhow::Result<Value> {
+        self.query(
+            format!(
+                r#"query ($tx: Base64!) {{
+                    simulateTransaction(transactionDataBcs: $tx) {{ {EFFECTS} }}
+                }}"#
+            ),
+            json!({ "tx": tx_bytes }),
+        )
+        .await
+    }
+
+    async fn stopped(self) {
+        self.cancel.cancel();
+        let _ = self.handle.await;
+    }
+}
+
+/// Decode the `effectsBcs` field of a `TransactionEffects` response.
+fn effects(response: &Value) -> TransactionEffects {
+    let bytes = Base64::decode(response["effectsBcs"].as_str().unwrap()).unwrap();
+    bcs::from_bytes(&bytes).unwrap()
+}
+
+/// Check the fields of an executed or simulated transaction's effects that can be derived from
+/// the transaction itself.
+fn assert_effects(response: &Value, digest: &str, tx_bytes: &str) {
+    assert_eq!(response["digest"], digest);
+    assert_eq!(response["transaction"]["digest"], digest);
+    assert_eq!(response["transaction"]["transactionBcs"], tx_bytes);
+    assert!(response["effectsDigest"].is_string());
+    assert!(response["transaction"]["sender"]["address"].is_string());
+    assert!(response["objectChanges"]["nodes"].is_array());
+
+    // The effects are not associated with a checkpoint yet.
+    assert!(response["checkpoint"].is_null());
+
+    let effects = effects(response);
+    assert_eq!(effects.transaction_digest().to_string(), digest);
+}
+
+#[sim_test]
+async fn test_execute_transaction() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = GraphQlTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    let (digest, tx_bytes, sigs) = cluster.transfer_transaction().await;
+    let response = cluster.execute(&tx_bytes, &sigs).await.unwrap();
+
+    assert!(response["errors"].is_null(), "{response:#?}");
+    let effects_json = &response["data"]["executeTransaction"];
+    assert_effects(effects_json, &digest, &tx_bytes);
+
+    let effects = effects(effects_json);
+    assert!(effects.status().is_ok());
+
+    // The transfer creates a coin for the recipient.
+    let created = effects_json["objectChanges"]["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|change| change["idCreated"] == json!(true))
+        .count();
+    assert_eq!(created, 1);
+
+    cluster.stopped().await;
+}
+
+#[sim_test]
+async fn test_execute_aborting_transaction() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = GraphQlTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    // A transaction that fails during execution is still finalized, and its effects are returned.
+    let (digest, tx_bytes, sigs) = cluster.aborting_transaction().await;
+    let response = cluster.execute(&tx_bytes, &sigs).await.unwrap();
+
+    assert!(response["errors"].is_null(), "{response:#?}");
+    let effects_json = &response["data"]["executeTransaction"];
+    assert_effects(effects_json, &digest, &tx_bytes);
+    assert!(effects(effects_json).status().is_err());
+
+    cluster.stopped().await;
+}
+
+#[sim_test]
+async fn test_execute_rejected_transaction() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = GraphQlTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    let (_, tx_bytes, sigs) = cluster.transfer_transaction().await;
+
+    // No signatures: the fullnode rejects the transaction.
+    let response = cluster.execute(&tx_bytes, &[]).await.unwrap();
+    assert!(response["data"].is_null(), "{response:#?}");
+    let message = response["errors"][0]["message"].as_str().unwrap();
+    assert!(
+        message.starts_with("Transaction rejected by fullnode"),
+        "{message}"
+    );
+
+    // Signatures that can't be deserialized are rejected before reaching the fullnode.
+    let bad_sigs = vec![sigs[0].clone(), Base64::encode([0u8; 4])];
+    let response = cluster.execute(&tx_bytes, &bad_sigs).await.unwrap();
+    assert!(response["data"].is_null(), "{response:#?}");
+    let message = response["errors"][0]["message"].as_str().unwrap();
+    assert!(
+        message.starts_with("Invalid signature at position 1"),
+        "{message}"
+    );
+
+    // As is transaction data that can't be deserialized.
+    let response = cluster
+        .execute(&Base64::encode([0u8; 4]), &sigs)
+        .await
+        .unwrap();
+    assert!(response["data"].is_null(), "{response:#?}");
+    let message = response["errors"][0]["message"].as_str().unwrap();
+    assert!(message.starts_with("Invalid transaction data"), "{message}");
+
+    cluster.stopped().await;
+}
+
+#[sim_test]
+async fn test_simulate_transaction() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = GraphQlTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    let (digest, tx_bytes, sigs) = cluster.transfer_transaction().await;
+
+    // Simulation does not need signatures.
+    let response = cluster.simulate(&tx_bytes).await.unwrap();
+    assert!(response["errors"].is_null(), "{response:#?}");
+    let simulated = &response["data"]["simulateTransaction"];
+    assert_effects(simulated, &digest, &tx_bytes);
+    assert!(effects(simulated).status().is_ok());
+
+    // Simulation does not commit the transaction, so its inputs are still available, and
+    // executing it produces the same effects.
+    let response = cluster.execute(&tx_bytes, &sigs).await.unwrap();
+    assert!(response["errors"].is_null(), "{response:#?}");
+    let executed = &response["data"]["executeTransaction"];
+    assert_effects(executed, &digest, &tx_bytes);
+    assert_eq!(
+        effects(simulated).status(),
+        effects(executed).status(),
+        "{simulated:#?} vs {executed:#?}"
+    );
+
+    // Simulating a transaction that aborts returns effects with a failure status.
+    let (_, tx_bytes, _) = cluster.aborting_transaction().await;
+    let response = cluster.simulate(&tx_bytes).await.unwrap();
+    assert!(response["errors"].is_null(), "{response:#?}");
+    assert!(effects(&response["data"]["simulateTransaction"])
+        .status()
+        .is_err());
+
+    cluster.stopped().await;
+}
@@ -0,0 +1,58 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//  1. All checkpoints, in ascending and descending order
+//  2. Paginating with a limit and a cursor, in both directions
+//  3. A cursor past the latest checkpoint
+
+//# init --protocol-version 70 --accounts A --simulator
+
+//# programmable --inputs 42 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# programmable --inputs 42 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": [null, null, false]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": [null, null, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": [null, 2, false]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": ["1", 2, false]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": ["2", 1, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": ["10", null, false]
+}
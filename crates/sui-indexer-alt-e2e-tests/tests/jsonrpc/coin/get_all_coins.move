@@ -0,0 +1,65 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//  1. All of an address's coins, across coin types
+//  2. Paginating with a limit and a cursor
+//  3. An address with no coins
+
+//# init --protocol-version 70 --accounts A --addresses P=0x0 --simulator
+
+//# publish --sender A
+module P::fake {
+  use sui::coin;
+
+  public struct FAKE has drop {}
+
+  fun init(witness: FAKE, ctx: &mut TxContext){
+    let (mut treasury_cap, metadata) = coin::create_currency(
+      witness,
+      2,
+      b"FAKE",
+      b"",
+      b"",
+      option::none(),
+      ctx,
+    );
+
+    let c1 = coin::mint(&mut treasury_cap, 10, ctx);
+    let c2 = coin::mint(&mut treasury_cap, 2000, ctx);
+
+    transfer::public_freeze_object(metadata);
+    transfer::public_transfer(treasury_cap, ctx.sender());
+    transfer::public_transfer(c1, ctx.sender());
+    transfer::public_transfer(c2, ctx.sender());
+  }
+}
+
+//# programmable --sender A --inputs 300 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}", null, 2]
+}
+
+//# run-jsonrpc --cursors bcs(@{obj_2_0},1,2)
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}", "@{cursor_0}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllCoins",
+  "params": ["0x42"]
+}
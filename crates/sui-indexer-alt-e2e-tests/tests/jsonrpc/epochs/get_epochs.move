@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//  1. All epochs, in ascending and descending order
+//  2. Paginating with a limit and a cursor
+//  3. The latest protocol config, and the config for a specific version
+//  4. The config for a version that doesn't exist
+
+//# init --protocol-version 70 --accounts A --simulator
+
+//# programmable --sender A --inputs 42 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# advance-epoch
+
+//# programmable --sender A --inputs 42 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# advance-epoch
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getEpochs",
+  "params": []
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getEpochs",
+  "params": [null, null, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getEpochs",
+  "params": [null, 1]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getEpochs",
+  "params": ["0", 1]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getEpochs",
+  "params": ["2", 1, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getProtocolConfig",
+  "params": []
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getProtocolConfig",
+  "params": ["69"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getProtocolConfig",
+  "params": ["100000"]
+}
@@ -0,0 +1,162 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//  1. Fetching the events emitted by a transaction
+//  2. Querying all events, in ascending and descending order
+//  3. Paginating with an event ID as the cursor
+//  4. Querying by sender
+//  5. ...by transaction
+//  6. ...by emitting package
+//  7. ...by emitting module (P::N emits an event whose type is defined in P::M)
+//  8. ...by event type, with and without type parameters
+//  9. ...by the module that defines the event type
+// 10. Unsupported filters are rejected, naming the filter
+// 11. Cursors that refer to unknown transactions are rejected
+
+//# init --protocol-version 70 --accounts A B --addresses P=0x0 --simulator
+
+//# publish
+module P::M {
+  public struct E(u64) has copy, drop;
+  public struct G<phantom T>(u64) has copy, drop;
+
+  public fun emit(n: u64) {
+    sui::event::emit(E(n))
+  }
+
+  public fun emit_two(n: u64) {
+    sui::event::emit(E(n));
+    sui::event::emit(G<u64>(n + 1));
+  }
+}
+
+module P::N {
+  public fun emit(n: u64) {
+    P::M::emit(n)
+  }
+}
+
+//# programmable --sender A --inputs 1
+//> P::M::emit(Input(0))
+
+//# programmable --sender B --inputs 2
+//> P::M::emit_two(Input(0))
+
+//# programmable --sender A --inputs 4
+//> P::N::emit(Input(0))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getEvents",
+  "params": ["@{digest_3}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, null, 2, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, { "txDigest": "@{digest_3}", "eventSeq": "0" }, 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, { "txDigest": "@{digest_3}", "eventSeq": "1" }, 2, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{B}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Transaction": "@{digest_2}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Package": "@{P}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveModule": { "package": "@{P}", "module": "N" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventType": "@{P}::M::G" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventType": "@{P}::M::G<u64>" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventType": "@{P}::M::G<u32>" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventModule": { "package": "@{P}", "module": "M" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Any": [{ "Sender": "@{A}" }, { "Sender": "@{B}" }] }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "And": [{ "Sender": "@{A}" }, { "Package": "@{P}" }] }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Or": [{ "Sender": "@{A}" }, { "Package": "@{P}" }] }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventField": { "path": "/pos0", "value": "1" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "TimeRange": { "startTime": "0", "endTime": "1000" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, { "txDigest": "11111111111111111111111111111111", "eventSeq": "0" }]
+}
@@ -0,0 +1,105 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//  1. All modules in a package
+//  2. A single module
+//  3. A struct, and a generic struct with abilities and phantom parameters
+//  4. An enum is not a struct
+//  5. A module that doesn't exist
+//  6. A struct that doesn't exist
+//  7. A package that doesn't exist
+//  8. An object that is not a package
+
+//# init --protocol-version 70 --accounts A --addresses P=0x0 --simulator
+
+//# publish --sender A
+module P::M {
+  public struct O has key, store {
+    id: UID,
+    n: u64,
+  }
+
+  public struct G<phantom T, U: copy> has copy, drop {
+    u: U,
+    v: vector<U>,
+  }
+
+  public enum E has drop {
+    A,
+    B(u64),
+  }
+
+  public fun new(ctx: &mut TxContext): O {
+    O { id: object::new(ctx), n: 0 }
+  }
+
+  entry fun bump(o: &mut O) {
+    o.n = o.n + 1;
+  }
+}
+
+module P::N {
+  public struct S has drop {
+    m: P::M::G<bool, u8>,
+  }
+}
+
+//# programmable --sender A --inputs @A
+//> 0: P::M::new();
+//> TransferObjects([Result(0)], Input(0))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModulesByPackage",
+  "params": ["@{P}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModule",
+  "params": ["@{P}", "N"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveStruct",
+  "params": ["@{P}", "M", "O"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveStruct",
+  "params": ["@{P}", "M", "G"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveStruct",
+  "params": ["@{P}", "M", "E"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModule",
+  "params": ["@{P}", "O"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveStruct",
+  "params": ["@{P}", "N", "T"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModulesByPackage",
+  "params": ["0x42"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModulesByPackage",
+  "params": ["@{obj_2_0}"]
+}
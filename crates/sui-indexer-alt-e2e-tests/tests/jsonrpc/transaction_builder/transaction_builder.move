@@ -0,0 +1,102 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Transactions are built using objects and the reference gas price from the RPC's stores. A's
+// gas coin is `obj_0_0`, and transfers are sent to 0x42.
+//
+//  1. Transferring an object, with an explicit gas coin
+//  2. Transferring an object, picking a gas coin automatically
+//  3. Transferring SUI, with and without an amount
+//  4. Paying SUI to multiple recipients
+//  5. Calling a Move function
+//  6. Splitting and merging coins
+//  7. Building with an object the signer doesn't own
+//  8. Building with an object that doesn't exist
+
+//# init --protocol-version 70 --accounts A --addresses P=0x0 --simulator
+
+//# publish --sender A
+module P::M {
+  public struct O has key, store {
+    id: UID,
+    n: u64,
+  }
+
+  public fun new(ctx: &mut TxContext): O {
+    O { id: object::new(ctx), n: 0 }
+  }
+
+  public fun set(o: &mut O, n: u64) {
+    o.n = n;
+  }
+}
+
+//# programmable --sender A --inputs @A
+//> 0: P::M::new();
+//> TransferObjects([Result(0)], Input(0))
+
+//# programmable --sender A --inputs 100 200 @A
+//> 0: SplitCoins(Gas, [Input(0), Input(1)]);
+//> TransferObjects([NestedResult(0,0), NestedResult(0,1)], Input(2))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "unsafe_transferObject",
+  "params": ["@{A}", "@{obj_2_0}", "@{obj_0_0}", "5000000", "0x42"]
+}
+
+//# run-jsonrpc
+{
+  "method": "unsafe_transferObject",
+  "params": ["@{A}", "@{obj_2_0}", null, "5000000", "0x42"]
+}
+
+//# run-jsonrpc
+{
+  "method": "unsafe_transferSui",
+  "params": ["@{A}", "@{obj_0_0}", "5000000", "0x42", "1000"]
+}
+
+//# run-jsonrpc
+{
+  "method": "unsafe_transferSui",
+  "params": ["@{A}", "@{obj_0_0}", "5000000", "0x42"]
+}
+
+//# run-jsonrpc
+{
+  "method": "unsafe_paySui",
+  "params": ["@{A}", ["@{obj_0_0}"], ["@{A}", "0x42"], ["10", "20"], "5000000"]
+}
+
+//# run-jsonrpc
+{
+  "method": "unsafe_moveCall",
+  "params": ["@{A}", "@{P}", "M", "set", [], ["@{obj_2_0}", "42"], "@{obj_0_0}", "5000000"]
+}
+
+//# run-jsonrpc
+{
+  "method": "unsafe_splitCoin",
+  "params": ["@{A}", "@{obj_0_0}", ["1", "2"], null, "5000000"]
+}
+
+//# run-jsonrpc
+{
+  "method": "unsafe_mergeCoins",
+  "params": ["@{A}", "@{obj_3_0}", "@{obj_3_1}", "@{obj_0_0}", "5000000"]
+}
+
+//# run-jsonrpc
+{
+  "method": "unsafe_transferObject",
+  "params": ["0x42", "@{obj_2_0}", null, "5000000", "0x42"]
+}
+
+//# run-jsonrpc
+{
+  "method": "unsafe_transferObject",
+  "params": ["@{A}", "0x43", "@{obj_0_0}", "5000000", "0x42"]
+}
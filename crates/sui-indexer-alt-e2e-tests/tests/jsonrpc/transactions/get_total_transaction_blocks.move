@@ -0,0 +1,46 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// The total includes genesis, and only counts transactions that have been checkpointed.
+
+//# init --protocol-version 70 --accounts A --simulator
+
+//# run-jsonrpc
+{
+  "method": "sui_getTotalTransactionBlocks",
+  "params": []
+}
+
+//# programmable --sender A --inputs 42 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 43 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getTotalTransactionBlocks",
+  "params": []
+}
+
+//# programmable --sender A --inputs 44 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# run-jsonrpc
+{
+  "method": "sui_getTotalTransactionBlocks",
+  "params": []
+}
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getTotalTransactionBlocks",
+  "params": []
+}
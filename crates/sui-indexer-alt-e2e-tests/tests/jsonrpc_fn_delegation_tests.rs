@@ -5,6 +5,7 @@ use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 
 use anyhow::Context;
+use fastcrypto::encoding::{Base64, Encoding};
 use prometheus::Registry;
 use reqwest::Client;
 use serde_json::{json, Value};
@@ -396,6 +397,63 @@ async fn test_dry_run_with_invalid_tx() {
     test_cluster.stopped().await;
 }
 
+#[sim_test]
+async fn test_dev_inspect() {
+    let test_cluster = FnDelegationTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    let recipient = test_cluster.onchain_cluster.wallet.get_addresses()[1];
+    let tx = test_cluster
+        .onchain_cluster
+        .test_transaction_builder()
+        .await
+        .transfer_sui(Some(1_000), recipient)
+        .build();
+
+    // Dev-inspect accepts a transaction kind, rather than full transaction data.
+    let tx_kind = Base64::encode(bcs::to_bytes(tx.kind()).unwrap());
+
+    let response = test_cluster
+        .execute_jsonrpc(
+            "sui_devInspectTransactionBlock".to_string(),
+            json!({
+                "sender_address": tx.sender().to_string(),
+                "tx_bytes": tx_kind,
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response["result"]["effects"]["status"]["status"], "success");
+
+    test_cluster.stopped().await;
+}
+
+#[sim_test]
+async fn test_dev_inspect_with_invalid_tx() {
+    let test_cluster = FnDelegationTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    let sender = test_cluster.onchain_cluster.wallet.get_addresses()[0];
+    let response = test_cluster
+        .execute_jsonrpc(
+            "sui_devInspectTransactionBlock".to_string(),
+            json!({
+                "sender_address": sender.to_string(),
+                "tx_bytes": "invalid_tx_bytes",
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["message"], "Invalid params");
+
+    test_cluster.stopped().await;
+}
+
 #[sim_test]
 async fn test_get_all_balances() {
     let test_cluster = FnDelegationTestCluster::new()
@@ -137,6 +137,7 @@ async fn cluster(config: &OffChainConfig) -> Arc<OffchainCluster> {
 
     let client_args = ClientArgs {
         local_ingestion_path: Some(config.data_ingestion_path.clone()),
+        local_ingestion_consumer: None,
         remote_store_url: None,
         rpc_api_url: None,
         rpc_username: None,
@@ -0,0 +1,436 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Context;
+use tokio::{sync::mpsc, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::{
+    ingestion::IngestionService, metrics::IndexerMetrics,
+    types::full_checkpoint_content::CheckpointData,
+};
+
+/// The number of checkpoints from live ingestion that a pipeline will accept while its backfill is
+/// still catching up. Live checkpoints cannot be folded into the pipeline's watermark until the
+/// backfill reaches them, so this bounds how many pending watermark parts build up (staying below
+/// the number that the watermark task warns about).
+const MAX_LIVE_LEAD: usize = 5000;
+
+/// A pipeline that is catching up: its history is fetched by an ingestion service of its own,
+/// while its tip is fed by the indexer's live ingestion service. Checkpoints from both sources are
+/// merged into the pipeline's input, and because the concurrent pipeline's watermark task already
+/// tolerates out-of-order commits, its watermark only starts advancing past the backfilled range
+/// once the two ranges meet.
+///
+/// Until then, the watermark parts of every live checkpoint the pipeline commits are held by its
+/// watermark task, so only [MAX_LIVE_LEAD] live checkpoints are let through while the backfill is
+/// catching up.
+pub(crate) struct Backfill {
+    pub(crate) pipeline: &'static str,
+
+    /// The first checkpoint the pipeline has not indexed yet, according to its watermark.
+    pub(crate) first_checkpoint: u64,
+
+    /// Service for fetching the historical checkpoints, separate from the live ingestion service.
+    pub(crate) ingestion_service: IngestionService,
+
+    /// Checkpoints from the backfill ingestion service.
+    pub(crate) backfill_rx: mpsc::Receiver<Arc<CheckpointData>>,
+
+    /// Checkpoints from the live ingestion service.
+    pub(crate) live_rx: mpsc::Receiver<Arc<CheckpointData>>,
+
+    /// The pipeline's input.
+    pub(crate) checkpoint_tx: mpsc::Sender<Arc<CheckpointData>>,
+}
+
+impl Backfill {
+    /// Start feeding the pipeline, given that live ingestion will cover `live_first_checkpoint`
+    /// onwards, and that nothing past `last_checkpoint` should be indexed. The backfill ingestion
+    /// service only runs if the pipeline's watermark is behind `live_first_checkpoint`.
+    pub(crate) async fn run(
+        self,
+        live_first_checkpoint: u64,
+        last_checkpoint: u64,
+        metrics: Arc<IndexerMetrics>,
+        cancel: CancellationToken,
+    ) -> anyhow::Result<Vec<JoinHandle<()>>> {
+        let Backfill {
+            pipeline,
+            first_checkpoint,
+            ingestion_service,
+            backfill_rx,
+            live_rx,
+            checkpoint_tx,
+        } = self;
+
+        let backfill_hi = live_first_checkpoint.saturating_sub(1).min(last_checkpoint);
+
+        if first_checkpoint >= live_first_checkpoint || first_checkpoint > backfill_hi {
+            info!(pipeline, "Nothing to backfill");
+            return Ok(vec![merge(
+                pipeline,
+                None,
+                live_rx,
+                backfill_rx,
+                checkpoint_tx,
+                MAX_LIVE_LEAD,
+                metrics,
+                cancel,
+            )]);
+        }
+
+        info!(
+            pipeline,
+            first_checkpoint,
+            last_checkpoint = backfill_hi,
+            "Backfill range"
+        );
+
+        metrics
+            .backfill_target_checkpoint
+            .with_label_values(&[pipeline])
+            .set(backfill_hi as i64);
+        metrics
+            .backfill_catching_up
+            .with_label_values(&[pipeline])
+            .set(1);
+
+        let (regulator_handle, broadcaster_handle) = ingestion_service
+            .run(first_checkpoint..=backfill_hi)
+            .await
+            .with_context(|| format!("Failed to start backfill ingestion for {pipeline}"))?;
+
+        Ok(vec![
+            regulator_handle,
+            broadcaster_handle,
+            merge(
+                pipeline,
+                Some(backfill_hi),
+                live_rx,
+                backfill_rx,
+                checkpoint_tx,
+                MAX_LIVE_LEAD,
+                metrics,
+                cancel,
+            ),
+        ])
+    }
+}
+
+/// Forward checkpoints from `live_rx` and `backfill_rx` to `checkpoint_tx`, in whatever order they
+/// arrive in, tracking backfill progress (up to `backfill_hi`, if there is anything to backfill)
+/// in `metrics`.
+///
+/// Neither source is favoured over the other until `max_live_lead` live checkpoints have been
+/// forwarded while the backfill is still running. After that, live checkpoints are only accepted
+/// again once the backfill is done, and like any other subscriber that falls behind, the pipeline
+/// holds up live ingestion for every pipeline in the meantime.
+///
+/// The task will shut down if the `cancel` token is signalled, if the pipeline stops accepting
+/// checkpoints, or once both sources are exhausted.
+fn merge(
+    pipeline: &'static str,
+    backfill_hi: Option<u64>,
+    mut live_rx: mpsc::Receiver<Arc<CheckpointData>>,
+    mut backfill_rx: mpsc::Receiver<Arc<CheckpointData>>,
+    checkpoint_tx: mpsc::Sender<Arc<CheckpointData>>,
+    max_live_lead: usize,
+    metrics: Arc<IndexerMetrics>,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut live_done = false;
+        let mut backfill_done = backfill_hi.is_none();
+        let mut latest_backfilled = None;
+
+        // Live checkpoints forwarded while the backfill is still running.
+        let mut live_lead = 0;
+
+        while !(live_done && backfill_done) {
+            let accept_live = !live_done && (backfill_done || live_lead < max_live_lead);
+            let checkpoint = tokio::select! {
+                _ = cancel.cancelled() => {
+                    info!(pipeline, "Shutdown received, stopping backfill");
+                    break;
+                }
+
+                checkpoint = live_rx.recv(), if accept_live => match checkpoint {
+                    Some(checkpoint) => {
+                        if !backfill_done {
+                            live_lead += 1;
+                        }
+
+                        checkpoint
+                    }
+
+                    None => {
+                        live_done = true;
+                        continue;
+                    }
+                },
+
+                checkpoint = backfill_rx.recv(), if !backfill_done => match checkpoint {
+                    Some(checkpoint) => {
+                        let cp = checkpoint.checkpoint_summary.sequence_number;
+                        let latest = latest_backfilled.map_or(cp, |l: u64| l.max(cp));
+                        latest_backfilled = Some(latest);
+
+                        metrics
+                            .total_backfilled_checkpoints
+                            .with_label_values(&[pipeline])
+                            .inc();
+                        metrics
+                            .latest_backfilled_checkpoint
+                            .with_label_values(&[pipeline])
+                            .set(latest as i64);
+
+                        checkpoint
+                    }
+
+                    None => {
+                        backfill_done = true;
+                        metrics
+                            .backfill_catching_up
+                            .with_label_values(&[pipeline])
+                            .set(0);
+
+                        if latest_backfilled == backfill_hi {
+                            info!(
+                                pipeline,
+                                last_checkpoint = ?latest_backfilled,
+                                "Backfill complete, pipeline is following live ingestion"
+                            );
+                        } else {
+                            info!(
+                                pipeline,
+                                latest_backfilled = ?latest_backfilled,
+                                "Backfill ingestion stopped before reaching its target"
+                            );
+                        }
+
+                        continue;
+                    }
+                },
+            };
+
+            if checkpoint_tx.send(checkpoint).await.is_err() {
+                info!(pipeline, "Pipeline closed, stopping backfill");
+                break;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use prometheus::Registry;
+    use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
+    use tokio::time::timeout;
+
+    use super::*;
+
+    fn checkpoint(cp: u64) -> Arc<CheckpointData> {
+        Arc::new(TestCheckpointDataBuilder::new(cp).build_checkpoint())
+    }
+
+    #[tokio::test]
+    async fn test_merge_backfill_and_live() {
+        let metrics = IndexerMetrics::new(&Registry::default());
+        let (live_tx, live_rx) = mpsc::channel(10);
+        let (backfill_tx, backfill_rx) = mpsc::channel(10);
+        let (checkpoint_tx, mut checkpoint_rx) = mpsc::channel(20);
+
+        metrics
+            .backfill_catching_up
+            .with_label_values(&["test"])
+            .set(1);
+
+        let handle = merge(
+            "test",
+            Some(4),
+            live_rx,
+            backfill_rx,
+            checkpoint_tx,
+            MAX_LIVE_LEAD,
+            metrics.clone(),
+            CancellationToken::new(),
+        );
+
+        for cp in 5..10 {
+            live_tx.send(checkpoint(cp)).await.unwrap();
+        }
+
+        // Backfill checkpoints can arrive out of order.
+        for cp in [1, 0, 3, 2, 4] {
+            backfill_tx.send(checkpoint(cp)).await.unwrap();
+        }
+
+        drop(live_tx);
+        drop(backfill_tx);
+
+        let mut received = vec![];
+        while let Some(checkpoint) = timeout(Duration::from_secs(1), checkpoint_rx.recv())
+            .await
+            .unwrap()
+        {
+            received.push(checkpoint.checkpoint_summary.sequence_number);
+        }
+
+        handle.await.unwrap();
+        received.sort();
+        assert_eq!(received, (0..10).collect::<Vec<_>>());
+
+        let label = &["test"];
+        assert_eq!(
+            metrics
+                .total_backfilled_checkpoints
+                .with_label_values(label)
+                .get(),
+            5
+        );
+        assert_eq!(
+            metrics
+                .latest_backfilled_checkpoint
+                .with_label_values(label)
+                .get(),
+            4
+        );
+        assert_eq!(
+            metrics.backfill_catching_up.with_label_values(label).get(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_without_backfill() {
+        let metrics = IndexerMetrics::new(&Registry::default());
+        let (live_tx, live_rx) = mpsc::channel(10);
+        let (backfill_tx, backfill_rx) = mpsc::channel(10);
+        let (checkpoint_tx, mut checkpoint_rx) = mpsc::channel(20);
+
+        let handle = merge(
+            "test",
+            None,
+            live_rx,
+            backfill_rx,
+            checkpoint_tx,
+            MAX_LIVE_LEAD,
+            metrics.clone(),
+            CancellationToken::new(),
+        );
+
+        // With nothing to backfill, the backfill channel is never read, and the task finishes
+        // once live ingestion does.
+        live_tx.send(checkpoint(7)).await.unwrap();
+        drop(live_tx);
+
+        let received = timeout(Duration::from_secs(1), checkpoint_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.checkpoint_summary.sequence_number, 7);
+
+        timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(checkpoint_rx.recv().await.is_none());
+        drop(backfill_tx);
+
+        assert_eq!(
+            metrics
+                .total_backfilled_checkpoints
+                .with_label_values(&["test"])
+                .get(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_bounds_live_lead() {
+        let metrics = IndexerMetrics::new(&Registry::default());
+        let (live_tx, live_rx) = mpsc::channel(10);
+        let (backfill_tx, backfill_rx) = mpsc::channel(10);
+        let (checkpoint_tx, mut checkpoint_rx) = mpsc::channel(20);
+
+        let handle = merge(
+            "test",
+            Some(4),
+            live_rx,
+            backfill_rx,
+            checkpoint_tx,
+            2,
+            metrics,
+            CancellationToken::new(),
+        );
+
+        for cp in 5..10 {
+            live_tx.send(checkpoint(cp)).await.unwrap();
+        }
+
+        // Only the first two live checkpoints are let through while the backfill is running.
+        for cp in [5, 6] {
+            let received = timeout(Duration::from_secs(1), checkpoint_rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(received.checkpoint_summary.sequence_number, cp);
+        }
+
+        timeout(Duration::from_millis(200), checkpoint_rx.recv())
+            .await
+            .unwrap_err();
+
+        // Once the backfill is done, the rest of the live checkpoints follow.
+        for cp in 0..5 {
+            backfill_tx.send(checkpoint(cp)).await.unwrap();
+        }
+
+        drop(live_tx);
+        drop(backfill_tx);
+
+        let mut received = vec![];
+        while let Some(checkpoint) = timeout(Duration::from_secs(1), checkpoint_rx.recv())
+            .await
+            .unwrap()
+        {
+            received.push(checkpoint.checkpoint_summary.sequence_number);
+        }
+
+        handle.await.unwrap();
+        assert_eq!(received, vec![0, 1, 2, 3, 4, 7, 8, 9]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_stops_when_pipeline_closes() {
+        let metrics = IndexerMetrics::new(&Registry::default());
+        let (live_tx, live_rx) = mpsc::channel(10);
+        let (_backfill_tx, backfill_rx) = mpsc::channel(10);
+        let (checkpoint_tx, checkpoint_rx) = mpsc::channel(20);
+
+        let handle = merge(
+            "test",
+            Some(4),
+            live_rx,
+            backfill_rx,
+            checkpoint_tx,
+            MAX_LIVE_LEAD,
+            metrics,
+            CancellationToken::new(),
+        );
+
+        drop(checkpoint_rx);
+        live_tx.send(checkpoint(5)).await.unwrap();
+
+        timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
@@ -324,6 +324,7 @@ mod tests {
         let args = Args {
             client_args: ClientArgs {
                 local_ingestion_path: Some(checkpoint_dir.path().to_owned()),
+                local_ingestion_consumer: None,
                 remote_store_url: None,
                 rpc_api_url: None,
                 rpc_username: None,
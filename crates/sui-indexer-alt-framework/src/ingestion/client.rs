@@ -5,7 +5,6 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
-use backoff::backoff::Constant;
 use backoff::Error as BE;
 use backoff::ExponentialBackoff;
 use sui_rpc_api::client::AuthInterceptor;
@@ -37,6 +36,19 @@ const SLOW_OPERATION_WARNING_THRESHOLD: Duration = Duration::from_secs(60);
 #[async_trait::async_trait]
 pub(crate) trait IngestionClientTrait: Send + Sync {
     async fn fetch(&self, checkpoint: u64) -> FetchResult;
+
+    /// Called between attempts to fetch a `checkpoint` that was not found. By default this waits
+    /// for `retry_interval`, but clients that can be told when new checkpoints arrive may return
+    /// earlier.
+    async fn wait_for_retry(&self, _checkpoint: u64, retry_interval: Duration) {
+        tokio::time::sleep(retry_interval).await;
+    }
+
+    /// Record that the client's consumer no longer needs checkpoints below `checkpoint`. Only
+    /// local ingestion directories keep track of their consumers, so by default this does nothing.
+    async fn record_watermark(&self, _checkpoint: u64) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -73,8 +85,12 @@ impl IngestionClient {
         Ok(Self::new_impl(client, metrics))
     }
 
-    pub(crate) fn new_local(path: PathBuf, metrics: Arc<IndexerMetrics>) -> Self {
-        let client = Arc::new(LocalIngestionClient::new(path));
+    pub(crate) fn new_local(
+        path: PathBuf,
+        consumer: Option<String>,
+        metrics: Arc<IndexerMetrics>,
+    ) -> Self {
+        let client = Arc::new(LocalIngestionClient::new(path, consumer));
         Self::new_impl(client, metrics)
     }
 
@@ -105,30 +121,32 @@ impl IngestionClient {
         }
     }
 
+    /// Record that the consumer this client fetches checkpoints for no longer needs checkpoints
+    /// below `checkpoint`, so that they can be garbage collected from its source.
+    pub(crate) async fn record_consumer_watermark(&self, checkpoint: u64) -> anyhow::Result<()> {
+        self.client.record_watermark(checkpoint).await
+    }
+
     /// Fetch checkpoint data by sequence number.
     ///
     /// This function behaves like `IngestionClient::fetch`, but will repeatedly retry the fetch if
-    /// the checkpoint is not found, on a constant back-off. The time between fetches is controlled
-    /// by the `retry_interval` parameter.
+    /// the checkpoint is not found. The time between fetches is controlled by the `retry_interval`
+    /// parameter, although clients that are notified of new checkpoints may retry sooner.
     pub async fn wait_for(
         &self,
         checkpoint: u64,
         retry_interval: Duration,
     ) -> IngestionResult<Arc<CheckpointData>> {
-        let backoff = Constant::new(retry_interval);
-        let fetch = || async move {
-            use backoff::Error as BE;
-            self.fetch(checkpoint).await.map_err(|e| match e {
-                IngestionError::NotFound(checkpoint) => {
+        loop {
+            match self.fetch(checkpoint).await {
+                Err(IngestionError::NotFound(checkpoint)) => {
                     debug!(checkpoint, "Checkpoint not found, retrying...");
                     self.metrics.total_ingested_not_found_retries.inc();
-                    BE::transient(e)
+                    self.client.wait_for_retry(checkpoint, retry_interval).await;
                 }
-                e => BE::permanent(e),
-            })
-        };
-
-        backoff::future::retry(backoff, fetch).await
+                result => return result,
+            }
+        }
     }
 
     /// Fetch checkpoint data by sequence number.
@@ -1,26 +1,93 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::ingestion::client::{FetchData, FetchError, FetchResult, IngestionClientTrait};
-use axum::body::Bytes;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::ingestion::client::{
+    FetchData, FetchError, FetchResult, IngestionClient, IngestionClientTrait,
+};
+use crate::ingestion::local_layout::{
+    write_atomically, DirWatcher, Layout, CONSUMERS_DIR, WATCH_FALLBACK_INTERVAL,
+};
+use crate::store::{Connection, Store};
+use anyhow::Context;
+use axum::body::Bytes;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
-// FIXME: To productionize this, we need to add garbage collection to remove old checkpoint files.
+/// How often a consumer's watermark is read back from its pipelines' committed watermarks and
+/// recorded in the ingestion directory.
+const CONSUMER_WATERMARK_INTERVAL: Duration = Duration::from_secs(5);
 
+/// Reads checkpoints from a local directory, either written directly by a fullnode (as
+/// uncompressed `<seq>.chk` files), or by a [`LocalIngestionWriter`], which may shard and compress
+/// them.
+///
+/// When the directory can be watched, the client is woken up as soon as new files are written,
+/// rather than waiting for its retry interval to elapse.
+///
+/// [`LocalIngestionWriter`]: crate::ingestion::local_writer::LocalIngestionWriter
 pub struct LocalIngestionClient {
     path: PathBuf,
+
+    /// Cached once the directory's layout file has been found. Until then, the directory is
+    /// assumed to contain flat, uncompressed checkpoint files.
+    layout: OnceLock<Layout>,
+
+    watcher: Option<DirWatcher>,
+
+    /// Where this client's consumer records its watermark, if it is a named consumer of the
+    /// directory (see [`consumer_watermark`]).
+    consumer: Option<PathBuf>,
 }
 
 impl LocalIngestionClient {
-    pub fn new(path: PathBuf) -> Self {
-        LocalIngestionClient { path }
+    pub fn new(path: PathBuf, consumer: Option<String>) -> Self {
+        let watcher = DirWatcher::new(&path);
+        let consumer = consumer.map(|name| path.join(CONSUMERS_DIR).join(name));
+
+        LocalIngestionClient {
+            path,
+            layout: OnceLock::new(),
+            watcher,
+            consumer,
+        }
+    }
+
+    async fn layout(&self) -> anyhow::Result<Layout> {
+        if let Some(layout) = self.layout.get() {
+            return Ok(*layout);
+        }
+
+        Ok(match Layout::read(&self.path).await? {
+            Some(layout) => *self.layout.get_or_init(|| layout),
+            None => Layout::default(),
+        })
+    }
+
+    async fn exists(&self, checkpoint: u64) -> bool {
+        let Ok(layout) = self.layout().await else {
+            return false;
+        };
+
+        let path = self.path.join(layout.checkpoint_path(checkpoint));
+        tokio::fs::try_exists(path).await.unwrap_or(false)
     }
 }
 
 #[async_trait::async_trait]
 impl IngestionClientTrait for LocalIngestionClient {
     async fn fetch(&self, checkpoint: u64) -> FetchResult {
-        let path = self.path.join(format!("{}.chk", checkpoint));
+        let layout = self.layout().await.map_err(|error| FetchError::Transient {
+            reason: "layout",
+            error,
+        })?;
+
+        let path = self.path.join(layout.checkpoint_path(checkpoint));
         let bytes = tokio::fs::read(path).await.map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
                 FetchError::NotFound
@@ -31,16 +98,127 @@ impl IngestionClientTrait for LocalIngestionClient {
                 }
             }
         })?;
+
+        let bytes = if layout.compressed {
+            zstd::decode_all(&bytes[..]).map_err(|e| FetchError::Transient {
+                reason: "decompression",
+                error: e.into(),
+            })?
+        } else {
+            bytes
+        };
+
         Ok(FetchData::Raw(Bytes::from(bytes)))
     }
+
+    async fn wait_for_retry(&self, checkpoint: u64, retry_interval: Duration) {
+        match &self.watcher {
+            Some(watcher) => {
+                watcher
+                    .wait_for_change(
+                        self.exists(checkpoint),
+                        retry_interval.max(WATCH_FALLBACK_INTERVAL),
+                    )
+                    .await
+            }
+
+            None => tokio::time::sleep(retry_interval).await,
+        }
+    }
+
+    async fn record_watermark(&self, checkpoint: u64) -> anyhow::Result<()> {
+        let Some(path) = &self.consumer else {
+            return Ok(());
+        };
+
+        write_atomically(path, checkpoint.to_string().as_bytes())
+            .await
+            .with_context(|| format!("Failed to record consumer watermark {}", path.display()))
+    }
+}
+
+/// Start a task that periodically records the watermark of a local ingestion directory's consumer
+/// through `client`: the lowest checkpoint that has not been committed by all of `pipelines`,
+/// which is where the consumer would resume from if it restarted. Writers to the directory only
+/// remove checkpoints below the slowest consumer's watermark, so it must never run ahead of what
+/// has been committed. Pipelines that have not committed anything yet hold the watermark at
+/// `first_checkpoint`, where the current run started.
+///
+/// The watermark is recorded one last time before the task stops, which happens when `cancel` is
+/// triggered. Failures to read or record the watermark are logged but otherwise ignored, as they
+/// only delay garbage collection.
+pub(crate) fn consumer_watermark<S: Store>(
+    client: IngestionClient,
+    store: S,
+    pipelines: Vec<&'static str>,
+    first_checkpoint: u64,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut poll = interval(CONSUMER_WATERMARK_INTERVAL);
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let mut recorded = None;
+        loop {
+            let stopping = tokio::select! {
+                _ = cancel.cancelled() => true,
+                _ = poll.tick() => false,
+            };
+
+            match committed_watermark(&store, &pipelines, first_checkpoint).await {
+                Ok(watermark) if recorded != Some(watermark) => {
+                    match client.record_consumer_watermark(watermark).await {
+                        Ok(()) => recorded = Some(watermark),
+                        Err(e) => warn!(checkpoint = watermark, "{e:#}"),
+                    }
+                }
+
+                Ok(_) => {}
+                Err(e) => warn!("Failed to read consumer watermark: {e:#}"),
+            }
+
+            if stopping {
+                info!(watermark = ?recorded, "Shutdown received, stopping consumer watermark");
+                break;
+            }
+        }
+    })
+}
+
+/// The lowest checkpoint that has not been committed by all of `pipelines`.
+async fn committed_watermark<S: Store>(
+    store: &S,
+    pipelines: &[&'static str],
+    first_checkpoint: u64,
+) -> anyhow::Result<u64> {
+    let mut conn = store.connect().await?;
+
+    let mut watermark = u64::MAX;
+    for pipeline in pipelines {
+        let next = conn
+            .committer_watermark(pipeline)
+            .await?
+            .map_or(first_checkpoint, |w| w.checkpoint_hi_inclusive + 1);
+
+        watermark = watermark.min(next);
+    }
+
+    Ok(watermark)
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use std::time::Duration;
+
     use crate::ingestion::client::IngestionClient;
+    use crate::ingestion::local_writer::{LocalIngestionWriter, LocalWriterConfig};
     use crate::ingestion::test_utils::test_checkpoint_data;
     use crate::metrics::tests::test_metrics;
+    use crate::testing::mock_store::MockStore;
     use sui_storage::blob::{Blob, BlobEncoding};
+    use tokio_util::sync::CancellationToken;
+
+    use super::consumer_watermark;
 
     #[tokio::test]
     async fn local_test_fetch() {
@@ -49,7 +227,7 @@ pub(crate) mod tests {
         let test_checkpoint = test_checkpoint_data(1);
         tokio::fs::write(&path, &test_checkpoint).await.unwrap();
 
-        let local_client = IngestionClient::new_local(tempdir, test_metrics());
+        let local_client = IngestionClient::new_local(tempdir, None, test_metrics());
         let checkpoint = local_client.fetch(1).await.unwrap();
 
         assert_eq!(
@@ -59,4 +237,95 @@ pub(crate) mod tests {
             test_checkpoint
         );
     }
+
+    #[tokio::test]
+    async fn local_test_fetch_sharded_compressed() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let writer = LocalIngestionWriter::new(
+            tempdir.clone(),
+            LocalWriterConfig {
+                shard_size: Some(10),
+                compress: true,
+                retention: 0,
+            },
+        )
+        .await
+        .unwrap();
+
+        for cp in [1, 12] {
+            writer
+                .write_bytes(cp, &test_checkpoint_data(cp))
+                .await
+                .unwrap();
+        }
+
+        assert!(tempdir.join("10").join("12.chk.zst").exists());
+
+        let local_client = IngestionClient::new_local(tempdir, None, test_metrics());
+        for cp in [1, 12] {
+            let checkpoint = local_client.fetch(cp).await.unwrap();
+            assert_eq!(
+                Blob::encode(&*checkpoint, BlobEncoding::Bcs)
+                    .unwrap()
+                    .to_bytes(),
+                test_checkpoint_data(cp)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn local_test_wait_for_wakes_on_write() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let local_client = IngestionClient::new_local(tempdir.clone(), None, test_metrics());
+
+        let path = tempdir.join("1.chk");
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            tokio::fs::write(&path, &test_checkpoint_data(1))
+                .await
+                .unwrap();
+        });
+
+        // The retry interval is much longer than the test's timeout, so the checkpoint can only be
+        // picked up in time if the client is woken up by the write.
+        let checkpoint = tokio::time::timeout(
+            Duration::from_secs(10),
+            local_client.wait_for(1, Duration::from_secs(60)),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(checkpoint.checkpoint_summary.sequence_number, 1);
+    }
+
+    #[tokio::test]
+    async fn local_test_consumer_watermark() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let writer = LocalIngestionWriter::new(tempdir.clone(), LocalWriterConfig::default())
+            .await
+            .unwrap();
+
+        let client = IngestionClient::new_local(tempdir, Some("indexer".into()), test_metrics());
+        let store = MockStore::default();
+        store.watermarks.lock().unwrap().checkpoint_hi_inclusive = 9;
+
+        let cancel = CancellationToken::new();
+        let handle = consumer_watermark(client, store.clone(), vec!["a", "b"], 0, cancel.clone());
+
+        // The consumer's watermark is the first checkpoint its pipelines have not committed.
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while writer.watermark().await.unwrap() != Some(10) {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // It is recorded one last time when the task stops.
+        store.watermarks.lock().unwrap().checkpoint_hi_inclusive = 19;
+        cancel.cancel();
+        handle.await.unwrap();
+        assert_eq!(writer.watermark().await.unwrap(), Some(20));
+    }
 }
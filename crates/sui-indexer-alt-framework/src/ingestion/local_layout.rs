@@ -0,0 +1,158 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Context;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tracing::warn;
+
+/// Name of the file, at the root of a local ingestion directory, that describes how checkpoint
+/// files are laid out within it. Directories without this file contain uncompressed checkpoint
+/// files directly under the root (the layout a fullnode uses for its `data_ingestion_dir`).
+pub(crate) const LAYOUT_FILE: &str = "layout.json";
+
+/// Name of the directory, at the root of a local ingestion directory, where consumers record the
+/// lowest checkpoint they may still need, so that writers know which checkpoints can be removed.
+pub(crate) const CONSUMERS_DIR: &str = "consumers";
+
+/// How checkpoint files are laid out in a local ingestion directory.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Layout {
+    /// If set, checkpoints are grouped into sub-directories of this many checkpoints each, named
+    /// after the first checkpoint in their range.
+    pub shard_size: Option<u64>,
+
+    /// Whether checkpoint files are compressed with zstd.
+    pub compressed: bool,
+}
+
+/// When watching a directory, how long to wait for a change before checking again anyway, in case
+/// a notification was missed.
+pub(crate) const WATCH_FALLBACK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Wakes up tasks waiting for changes to a local ingestion directory.
+pub(crate) struct DirWatcher {
+    notify: Arc<Notify>,
+    // Held to keep the watch alive. Wrapped in a mutex, because the watcher is not `Sync` on all
+    // platforms.
+    _watcher: Mutex<RecommendedWatcher>,
+}
+
+impl Layout {
+    /// Read the layout of the directory at `root`, if it has been recorded.
+    pub(crate) async fn read(root: &Path) -> anyhow::Result<Option<Self>> {
+        let bytes = match tokio::fs::read(root.join(LAYOUT_FILE)).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).context("Failed to read layout"),
+        };
+
+        Ok(Some(
+            serde_json::from_slice(&bytes).context("Failed to deserialize layout")?,
+        ))
+    }
+
+    /// Path of the file containing `checkpoint`, relative to the directory's root.
+    pub(crate) fn checkpoint_path(&self, checkpoint: u64) -> PathBuf {
+        let file = if self.compressed {
+            format!("{checkpoint}.chk.zst")
+        } else {
+            format!("{checkpoint}.chk")
+        };
+
+        match self.shard_size {
+            Some(size) => PathBuf::from((checkpoint - checkpoint % size).to_string()).join(file),
+            None => PathBuf::from(file),
+        }
+    }
+
+    /// The sequence number of the checkpoint stored in the file called `name`, if it is a
+    /// checkpoint file.
+    pub(crate) fn checkpoint_from_file_name(name: &str) -> Option<u64> {
+        name.strip_suffix(".chk")
+            .or_else(|| name.strip_suffix(".chk.zst"))
+            .and_then(|s| s.parse().ok())
+    }
+}
+
+impl DirWatcher {
+    /// Start watching the directory at `root` and all its sub-directories. Returns `None` if the
+    /// watch could not be set up, in which case callers should fall back to polling.
+    pub(crate) fn new(root: &Path) -> Option<Self> {
+        let notify = Arc::new(Notify::new());
+
+        let n = notify.clone();
+        let watcher = notify::recommended_watcher(move |_: notify::Result<notify::Event>| {
+            n.notify_waiters();
+        });
+
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                warn!("Failed to create watcher, falling back to polling: {e}");
+                return None;
+            }
+        };
+
+        if let Err(e) = watcher.watch(root, RecursiveMode::Recursive) {
+            warn!(
+                ?root,
+                "Failed to watch directory, falling back to polling: {e}"
+            );
+            return None;
+        }
+
+        Some(Self {
+            notify,
+            _watcher: Mutex::new(watcher),
+        })
+    }
+
+    /// Wait until `ready` resolves to `true`, or there is a change in the watched directory, or
+    /// `timeout` elapses, whichever comes first. `ready` is checked after starting to listen for
+    /// changes, so that changes that happen while it is being checked are not missed.
+    pub(crate) async fn wait_for_change(
+        &self,
+        ready: impl Future<Output = bool>,
+        timeout: Duration,
+    ) {
+        let notified = self.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        if ready.await {
+            return;
+        }
+
+        let _ = tokio::time::timeout(timeout, notified).await;
+    }
+}
+
+/// Write `bytes` to `path` so that readers never observe a partially written file: The bytes are
+/// written to a temporary file in the same directory first, and then moved into place.
+pub(crate) async fn write_atomically(path: &Path, bytes: &[u8]) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+
+    tokio::fs::write(&tmp, bytes)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    tokio::fs::rename(&tmp, path)
+        .await
+        .with_context(|| format!("Failed to move {} into place", path.display()))?;
+
+    Ok(())
+}
@@ -0,0 +1,503 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context};
+use sui_storage::blob::{Blob, BlobEncoding};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::ingestion::local_layout::{
+    write_atomically, DirWatcher, Layout, CONSUMERS_DIR, LAYOUT_FILE, WATCH_FALLBACK_INTERVAL,
+};
+use crate::types::full_checkpoint_content::CheckpointData;
+
+/// How many checkpoints are relayed between garbage collection passes.
+const GC_INTERVAL: u64 = 1000;
+
+/// How long to wait before checking the source directory for new checkpoints again, when it
+/// cannot be watched.
+const RELAY_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// By default, keep this many checkpoints below the slowest consumer's watermark.
+pub const DEFAULT_RETENTION: u64 = 1000;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct LocalWriterConfig {
+    /// Group checkpoint files into sub-directories of this many checkpoints each.
+    #[arg(long)]
+    pub shard_size: Option<u64>,
+
+    /// Compress checkpoint files with zstd.
+    #[arg(long)]
+    pub compress: bool,
+
+    /// Number of checkpoints to keep below the slowest consumer's watermark when garbage
+    /// collecting. Consumers record the lowest checkpoint their pipelines have not all committed,
+    /// which is where they resume from after a restart, so nothing they need is removed even
+    /// without retention. It is a safety margin, e.g. for a consumer that is restarted with an
+    /// earlier `--first-checkpoint`.
+    #[arg(long, default_value_t = DEFAULT_RETENTION)]
+    pub retention: u64,
+}
+
+/// Writes checkpoints into a local ingestion directory, to be read by any number of
+/// [`LocalIngestionClient`]s, and removes them once all of the directory's consumers have committed
+/// them, keeping the most recent `retention` of those as a safety margin.
+///
+/// Checkpoint files are encoded in the same format as a fullnode writes to its
+/// `data_ingestion_dir` (and as the `sui-data-ingestion` blob worker uploads), optionally sharded
+/// and compressed, according to the layout recorded at the root of the directory.
+///
+/// [`LocalIngestionClient`]: crate::ingestion::local_client::LocalIngestionClient
+pub struct LocalIngestionWriter {
+    root: PathBuf,
+    layout: Layout,
+    retention: u64,
+}
+
+impl LocalIngestionWriter {
+    /// Set-up a writer for the directory at `root`, creating it if it does not exist. Fails if the
+    /// directory has already been set-up with a different layout.
+    pub async fn new(root: PathBuf, config: LocalWriterConfig) -> anyhow::Result<Self> {
+        if config.shard_size == Some(0) {
+            bail!("Shard size must be positive");
+        }
+
+        let layout = Layout {
+            shard_size: config.shard_size,
+            compressed: config.compress,
+        };
+
+        tokio::fs::create_dir_all(&root)
+            .await
+            .with_context(|| format!("Failed to create directory {}", root.display()))?;
+
+        match Layout::read(&root).await? {
+            Some(existing) if existing != layout => bail!(
+                "Directory {} already has layout {existing:?}, expected {layout:?}",
+                root.display(),
+            ),
+
+            Some(_) => {}
+
+            None => {
+                let bytes = serde_json::to_vec(&layout).context("Failed to serialize layout")?;
+                write_atomically(&root.join(LAYOUT_FILE), &bytes).await?;
+            }
+        }
+
+        Ok(Self {
+            root,
+            layout,
+            retention: config.retention,
+        })
+    }
+
+    /// Write `checkpoint` to the directory.
+    pub async fn write(&self, checkpoint: &CheckpointData) -> anyhow::Result<()> {
+        let bytes = Blob::encode(checkpoint, BlobEncoding::Bcs)
+            .context("Failed to encode checkpoint")?
+            .to_bytes();
+
+        self.write_bytes(checkpoint.checkpoint_summary.sequence_number, &bytes)
+            .await
+    }
+
+    /// Write the encoded contents of `checkpoint` to the directory. `bytes` must already be in
+    /// the blob format that a fullnode writes to its `data_ingestion_dir`.
+    pub async fn write_bytes(&self, checkpoint: u64, bytes: &[u8]) -> anyhow::Result<()> {
+        let path = self.root.join(self.layout.checkpoint_path(checkpoint));
+
+        if self.layout.compressed {
+            let compressed = zstd::encode_all(bytes, 0).context("Failed to compress checkpoint")?;
+            write_atomically(&path, &compressed).await
+        } else {
+            write_atomically(&path, bytes).await
+        }
+    }
+
+    /// The lowest watermark recorded by any of the directory's consumers, or `None` if no
+    /// consumers have recorded a watermark yet. A consumer's watermark is the lowest checkpoint it
+    /// may still need.
+    pub async fn watermark(&self) -> anyhow::Result<Option<u64>> {
+        let mut entries = match tokio::fs::read_dir(self.root.join(CONSUMERS_DIR)).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).context("Failed to list consumers"),
+        };
+
+        let mut watermark: Option<u64> = None;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+
+            // Skip files that are still being written.
+            if path.extension().is_some_and(|ext| ext == "tmp") {
+                continue;
+            }
+
+            let contents = tokio::fs::read_to_string(&path)
+                .await
+                .with_context(|| format!("Failed to read consumer watermark {}", path.display()))?;
+
+            let Ok(checkpoint) = contents.trim().parse::<u64>() else {
+                warn!(path = %path.display(), "Ignoring malformed consumer watermark");
+                continue;
+            };
+
+            watermark = Some(watermark.map_or(checkpoint, |w| w.min(checkpoint)));
+        }
+
+        Ok(watermark)
+    }
+
+    /// Remove all checkpoints more than `retention` checkpoints below the slowest consumer's
+    /// watermark, as well as any shards that are left empty as a result. Does nothing if no
+    /// consumers have recorded a watermark yet. Returns the number of checkpoints removed.
+    pub async fn gc(&self) -> anyhow::Result<usize> {
+        let Some(watermark) = self.watermark().await? else {
+            return Ok(0);
+        };
+
+        let threshold = watermark.saturating_sub(self.retention);
+        if self.layout.shard_size.is_none() {
+            return remove_checkpoints_below(&self.root, threshold).await;
+        }
+
+        let mut shards = tokio::fs::read_dir(&self.root)
+            .await
+            .context("Failed to list shards")?;
+
+        let mut removed = 0;
+        while let Some(entry) = shards.next_entry().await? {
+            let Some(start) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<u64>().ok())
+            else {
+                continue;
+            };
+
+            if start >= threshold || !entry.file_type().await?.is_dir() {
+                continue;
+            }
+
+            let shard = entry.path();
+            removed += remove_checkpoints_below(&shard, threshold).await?;
+
+            // This only succeeds if the shard is now empty.
+            if tokio::fs::remove_dir(&shard).await.is_ok() {
+                debug!(shard = start, "Removed empty shard");
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Move checkpoints from `source` (a fullnode's `data_ingestion_dir`) into this directory, in
+    /// order, starting from the lowest checkpoint found in `source`, until `cancel` is triggered.
+    /// Garbage collection runs periodically as checkpoints are relayed.
+    pub async fn relay(&self, source: &Path, cancel: CancellationToken) -> anyhow::Result<()> {
+        let watcher = DirWatcher::new(source);
+
+        let mut next = None;
+        let mut relayed = 0u64;
+
+        loop {
+            if cancel.is_cancelled() {
+                info!("Shutdown received, stopping relay");
+                return Ok(());
+            }
+
+            if next.is_none() {
+                next = lowest_checkpoint(source).await?;
+            }
+
+            if let Some(checkpoint) = next {
+                let path = source.join(format!("{checkpoint}.chk"));
+                if let Some(bytes) = read_complete_checkpoint(&path).await? {
+                    self.write_bytes(checkpoint, &bytes).await?;
+                    tokio::fs::remove_file(&path)
+                        .await
+                        .with_context(|| format!("Failed to remove {}", path.display()))?;
+
+                    debug!(checkpoint, "Relayed checkpoint");
+                    next = Some(checkpoint + 1);
+                    relayed += 1;
+
+                    if relayed % GC_INTERVAL == 0 {
+                        let removed = self.gc().await?;
+                        debug!(removed, "Garbage collected checkpoints");
+                    }
+
+                    continue;
+                }
+            }
+
+            let ready = async {
+                match next {
+                    Some(checkpoint) => {
+                        tokio::fs::try_exists(source.join(format!("{checkpoint}.chk")))
+                            .await
+                            .unwrap_or(false)
+                    }
+                    None => false,
+                }
+            };
+
+            tokio::select! {
+                _ = cancel.cancelled() => {}
+                _ = async {
+                    match &watcher {
+                        Some(watcher) => watcher.wait_for_change(ready, WATCH_FALLBACK_INTERVAL).await,
+                        None => tokio::time::sleep(RELAY_POLL_INTERVAL).await,
+                    }
+                } => {}
+            }
+        }
+    }
+}
+
+/// Remove checkpoint files in `dir` (but not its sub-directories) for checkpoints below
+/// `threshold`. Returns the number of files removed.
+async fn remove_checkpoints_below(dir: &Path, threshold: u64) -> anyhow::Result<usize> {
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .with_context(|| format!("Failed to list {}", dir.display()))?;
+
+    let mut removed = 0;
+    while let Some(entry) = entries.next_entry().await? {
+        let Some(checkpoint) = entry
+            .file_name()
+            .to_str()
+            .and_then(Layout::checkpoint_from_file_name)
+        else {
+            continue;
+        };
+
+        if checkpoint < threshold {
+            tokio::fs::remove_file(entry.path()).await?;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// The lowest checkpoint with a file in `dir`, if there are any.
+async fn lowest_checkpoint(dir: &Path) -> anyhow::Result<Option<u64>> {
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .with_context(|| format!("Failed to list {}", dir.display()))?;
+
+    let mut lowest: Option<u64> = None;
+    while let Some(entry) = entries.next_entry().await? {
+        if let Some(checkpoint) = entry
+            .file_name()
+            .to_str()
+            .and_then(Layout::checkpoint_from_file_name)
+        {
+            lowest = Some(lowest.map_or(checkpoint, |l| l.min(checkpoint)));
+        }
+    }
+
+    Ok(lowest)
+}
+
+/// Read the checkpoint file at `path`, if it exists and has been completely written. Fullnodes do
+/// not write their checkpoint files atomically, so a file that fails to decode is assumed to still
+/// be in the process of being written.
+async fn read_complete_checkpoint(path: &Path) -> anyhow::Result<Option<Vec<u8>>> {
+    let bytes = match tokio::fs::read(path).await {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    Ok(Blob::from_bytes::<CheckpointData>(&bytes)
+        .is_ok()
+        .then_some(bytes))
+}
+
+impl Default for LocalWriterConfig {
+    fn default() -> Self {
+        Self {
+            shard_size: None,
+            compress: false,
+            retention: DEFAULT_RETENTION,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::ingestion::client::IngestionClient;
+    use crate::ingestion::test_utils::test_checkpoint_data;
+    use crate::metrics::tests::test_metrics;
+
+    use super::*;
+
+    fn config(shard_size: Option<u64>, retention: u64) -> LocalWriterConfig {
+        LocalWriterConfig {
+            shard_size,
+            compress: false,
+            retention,
+        }
+    }
+
+    async fn checkpoints_on_disk(writer: &LocalIngestionWriter, checkpoints: u64) -> Vec<u64> {
+        let mut on_disk = vec![];
+        for cp in 0..checkpoints {
+            if writer.root.join(writer.layout.checkpoint_path(cp)).exists() {
+                on_disk.push(cp);
+            }
+        }
+        on_disk
+    }
+
+    #[tokio::test]
+    async fn test_layout_mismatch() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        LocalIngestionWriter::new(tempdir.clone(), config(Some(10), 0))
+            .await
+            .unwrap();
+
+        LocalIngestionWriter::new(tempdir.clone(), config(Some(10), 0))
+            .await
+            .unwrap();
+
+        assert!(LocalIngestionWriter::new(tempdir, config(None, 0))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gc_without_consumers() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let writer = LocalIngestionWriter::new(tempdir, config(None, 0))
+            .await
+            .unwrap();
+
+        for cp in 0..5 {
+            writer
+                .write_bytes(cp, &test_checkpoint_data(cp))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(writer.gc().await.unwrap(), 0);
+        assert_eq!(checkpoints_on_disk(&writer, 5).await, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_gc_slowest_consumer() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let writer = LocalIngestionWriter::new(tempdir.clone(), config(Some(4), 2))
+            .await
+            .unwrap();
+
+        for cp in 0..20 {
+            writer
+                .write_bytes(cp, &test_checkpoint_data(cp))
+                .await
+                .unwrap();
+        }
+
+        let fast = IngestionClient::new_local(tempdir.clone(), Some("fast".into()), test_metrics());
+        let slow = IngestionClient::new_local(tempdir.clone(), Some("slow".into()), test_metrics());
+
+        fast.record_consumer_watermark(15).await.unwrap();
+        slow.record_consumer_watermark(10).await.unwrap();
+
+        assert_eq!(writer.watermark().await.unwrap(), Some(10));
+        assert_eq!(writer.gc().await.unwrap(), 8);
+        assert_eq!(
+            checkpoints_on_disk(&writer, 20).await,
+            (8..20).collect::<Vec<_>>()
+        );
+
+        // Shards that were emptied are removed as well.
+        assert!(!tempdir.join("0").exists());
+        assert!(!tempdir.join("4").exists());
+        assert!(tempdir.join("8").exists());
+    }
+
+    #[tokio::test]
+    async fn test_gc_ignores_fetches() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let writer = LocalIngestionWriter::new(tempdir.clone(), config(None, 0))
+            .await
+            .unwrap();
+
+        for cp in 0..20 {
+            writer
+                .write_bytes(cp, &test_checkpoint_data(cp))
+                .await
+                .unwrap();
+        }
+
+        // Fetching a checkpoint does not mean it has been committed, so it does not move the
+        // consumer's watermark: only what the consumer records does.
+        let client = IngestionClient::new_local(tempdir, Some("indexer".into()), test_metrics());
+        client.fetch(15).await.unwrap();
+        assert_eq!(writer.watermark().await.unwrap(), None);
+        assert_eq!(writer.gc().await.unwrap(), 0);
+
+        client.record_consumer_watermark(5).await.unwrap();
+        assert_eq!(writer.watermark().await.unwrap(), Some(5));
+        assert_eq!(writer.gc().await.unwrap(), 5);
+        assert_eq!(
+            checkpoints_on_disk(&writer, 20).await,
+            (5..20).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_relay() {
+        let source = tempfile::tempdir().unwrap().keep();
+        let target = tempfile::tempdir().unwrap().keep();
+
+        for cp in 3..6 {
+            tokio::fs::write(source.join(format!("{cp}.chk")), test_checkpoint_data(cp))
+                .await
+                .unwrap();
+        }
+
+        let writer = LocalIngestionWriter::new(
+            target.clone(),
+            LocalWriterConfig {
+                shard_size: Some(2),
+                compress: true,
+                retention: 0,
+            },
+        )
+        .await
+        .unwrap();
+
+        let cancel = CancellationToken::new();
+        let relay = tokio::spawn({
+            let cancel = cancel.clone();
+            async move { writer.relay(&source, cancel).await }
+        });
+
+        let client = IngestionClient::new_local(target, None, test_metrics());
+        for cp in 3..6 {
+            let checkpoint = tokio::time::timeout(
+                Duration::from_secs(10),
+                client.wait_for(cp, Duration::from_millis(100)),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+            assert_eq!(checkpoint.checkpoint_summary.sequence_number, cp);
+        }
+
+        cancel.cancel();
+        relay.await.unwrap().unwrap();
+    }
+}
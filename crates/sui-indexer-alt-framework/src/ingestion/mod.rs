@@ -24,6 +24,8 @@ mod broadcaster;
 pub mod client;
 pub mod error;
 mod local_client;
+mod local_layout;
+pub mod local_writer;
 mod regulator;
 mod remote_client;
 mod rpc_client;
@@ -31,6 +33,11 @@ mod slow_future_monitor;
 #[cfg(test)]
 mod test_utils;
 
+pub(crate) use local_client::consumer_watermark;
+
+/// Default for [IngestionConfig::checkpoint_buffer_size].
+pub(crate) const DEFAULT_CHECKPOINT_BUFFER_SIZE: usize = 5000;
+
 #[derive(clap::Args, Clone, Debug)]
 #[group(required = true)]
 pub struct ClientArgs {
@@ -43,6 +50,12 @@ pub struct ClientArgs {
     #[clap(long, group = "source")]
     pub local_ingestion_path: Option<PathBuf>,
 
+    /// Name to record this indexer's progress under in the local ingestion directory, so that the
+    /// process writing to that directory can remove checkpoints once every consumer has committed
+    /// them. Only used with local_ingestion_path.
+    #[clap(long)]
+    pub local_ingestion_consumer: Option<String>,
+
     /// Sui fullnode gRPC url to fetch checkpoints from.
     /// If all remote_store_url, local_ingestion_path and rpc_api_url are provided, remote_store_url will be used.
     #[clap(long, env, group = "source")]
@@ -97,7 +110,11 @@ impl IngestionService {
         let client = if let Some(url) = args.remote_store_url.as_ref() {
             IngestionClient::new_remote(url.clone(), metrics.clone())?
         } else if let Some(path) = args.local_ingestion_path.as_ref() {
-            IngestionClient::new_local(path.clone(), metrics.clone())
+            IngestionClient::new_local(
+                path.clone(),
+                args.local_ingestion_consumer.clone(),
+                metrics.clone(),
+            )
         } else if let Some(rpc_api_url) = args.rpc_api_url.as_ref() {
             IngestionClient::new_rpc(
                 rpc_api_url.clone(),
@@ -197,7 +214,7 @@ impl IngestionService {
 impl Default for IngestionConfig {
     fn default() -> Self {
         Self {
-            checkpoint_buffer_size: 5000,
+            checkpoint_buffer_size: DEFAULT_CHECKPOINT_BUFFER_SIZE,
             ingest_concurrency: 200,
             retry_interval_ms: 200,
         }
@@ -227,6 +244,7 @@ mod tests {
             ClientArgs {
                 remote_store_url: Some(Url::parse(&uri).unwrap()),
                 local_ingestion_path: None,
+                local_ingestion_consumer: None,
                 rpc_api_url: None,
                 rpc_username: None,
                 rpc_password: None,
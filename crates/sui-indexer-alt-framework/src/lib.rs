@@ -4,8 +4,11 @@
 use std::{collections::BTreeSet, sync::Arc};
 
 use anyhow::{ensure, Context};
+use backfill::Backfill;
 use futures::future;
-use ingestion::{client::IngestionClient, ClientArgs, IngestionConfig, IngestionService};
+use ingestion::{
+    client::IngestionClient, consumer_watermark, ClientArgs, IngestionConfig, IngestionService,
+};
 use metrics::IndexerMetrics;
 use pipeline::{
     concurrent::{self, ConcurrentConfig},
@@ -16,7 +19,7 @@ use prometheus::Registry;
 use sui_indexer_alt_framework_store_traits::{
     CommitterWatermark, Connection, Store, TransactionalStore,
 };
-use tokio::task::JoinHandle;
+use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
@@ -26,6 +29,7 @@ pub use sui_field_count::FieldCount;
 pub use sui_indexer_alt_framework_store_traits as store;
 pub use sui_types as types;
 
+mod backfill;
 #[cfg(feature = "cluster")]
 pub mod cluster;
 pub mod ingestion;
@@ -33,6 +37,8 @@ pub mod metrics;
 pub mod pipeline;
 #[cfg(feature = "postgres")]
 pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod task;
 
 /// Command-line arguments for the indexer
@@ -89,6 +95,14 @@ pub struct Indexer<S: Store> {
     /// with the same name isn't added twice.
     added_pipelines: BTreeSet<&'static str>,
 
+    /// Enabled pipelines whose watermarks decide where ingestion resumes from (all but those that
+    /// backfill).
+    resume_pipelines: Vec<&'static str>,
+
+    /// Whether the indexer records its progress as a named consumer of a local ingestion
+    /// directory.
+    local_consumer: bool,
+
     /// Cancellation token shared among all continuous tasks in the service.
     cancel: CancellationToken,
 
@@ -97,6 +111,11 @@ pub struct Indexer<S: Store> {
     /// [Self::first_checkpoint].
     first_checkpoint_from_watermark: u64,
 
+    /// Pipelines that are catching up, added using [Self::concurrent_pipeline_with_backfill]. They
+    /// are fed by their own ingestion services until they reach the first checkpoint of live
+    /// ingestion.
+    backfills: Vec<Backfill>,
+
     /// The handles for every task spawned by this indexer, used to manage graceful shutdown.
     handles: Vec<JoinHandle<()>>,
 }
@@ -129,6 +148,7 @@ impl<S: Store> Indexer<S> {
         } = indexer_args;
 
         let metrics = IndexerMetrics::new(registry);
+        let local_consumer = client_args.local_ingestion_consumer.is_some();
 
         let ingestion_service = IngestionService::new(
             client_args,
@@ -150,8 +170,11 @@ impl<S: Store> Indexer<S> {
                 Some(pipeline.into_iter().collect())
             },
             added_pipelines: BTreeSet::new(),
+            resume_pipelines: vec![],
+            local_consumer,
             cancel,
             first_checkpoint_from_watermark: u64::MAX,
+            backfills: vec![],
             handles: vec![],
         })
     }
@@ -219,6 +242,76 @@ impl<S: Store> Indexer<S> {
         Ok(())
     }
 
+    /// Adds a new concurrent pipeline that catches up on its history while it also indexes the tip
+    /// of the network, without holding back where the other pipelines in this indexer start from.
+    ///
+    /// Unlike [Self::concurrent_pipeline], the pipeline's watermark does not affect where live
+    /// ingestion starts from. If its watermark is behind the first checkpoint of live ingestion,
+    /// the checkpoints in between are fetched by a separate ingestion service, configured by
+    /// `backfill_client_args` and `backfill_ingestion_config` (e.g. to read from a remote store),
+    /// and fed to the pipeline alongside the checkpoints from live ingestion. The pipeline's
+    /// watermark catches up with the rest of the indexer once its backfill reaches the first
+    /// checkpoint it received from live ingestion. Progress is tracked by the `backfill` metrics.
+    ///
+    /// Data from live ingestion is committed while the backfill is still running, but only
+    /// becomes visible through the pipeline's watermark once the ranges meet. Until then, the
+    /// pipeline's watermark task holds on to a watermark part for each live checkpoint, so only a
+    /// bounded number of live checkpoints are accepted while the backfill is running. If the
+    /// backfill is slower than live ingestion, the pipeline then holds up live ingestion (and every
+    /// other pipeline) until its backfill completes.
+    pub async fn concurrent_pipeline_with_backfill<H>(
+        &mut self,
+        handler: H,
+        config: ConcurrentConfig,
+        backfill_client_args: ClientArgs,
+        backfill_ingestion_config: IngestionConfig,
+    ) -> Result<()>
+    where
+        H: concurrent::Handler<Store = S> + Send + Sync + 'static,
+    {
+        // The backfill fills any gap between the watermark and the first checkpoint, so this
+        // pipeline does not need to check that the first checkpoint is consistent with it.
+        let Some(watermark) = self.register_pipeline::<H>().await? else {
+            return Ok(());
+        };
+
+        let (checkpoint_tx, checkpoint_rx) =
+            mpsc::channel(backfill_ingestion_config.checkpoint_buffer_size);
+
+        let mut ingestion_service = IngestionService::new(
+            backfill_client_args,
+            backfill_ingestion_config,
+            self.metrics.clone(),
+            self.cancel.clone(),
+        )
+        .with_context(|| format!("Failed to create backfill ingestion for {}", H::NAME))?;
+
+        self.backfills.push(Backfill {
+            pipeline: H::NAME,
+            first_checkpoint: watermark
+                .as_ref()
+                .map(|w| w.checkpoint_hi_inclusive + 1)
+                .unwrap_or_default(),
+            backfill_rx: ingestion_service.subscribe().0,
+            live_rx: self.ingestion_service.subscribe().0,
+            ingestion_service,
+            checkpoint_tx,
+        });
+
+        self.handles.push(concurrent::pipeline::<H>(
+            handler,
+            watermark,
+            config,
+            self.skip_watermark,
+            self.store.clone(),
+            checkpoint_rx,
+            self.metrics.clone(),
+            self.cancel.clone(),
+        ));
+
+        Ok(())
+    }
+
     /// Checks that the first checkpoint override is consistent with the watermark for the pipeline.
     /// If the watermark does not exist, the override can be anything. If the watermark exists, the
     /// override must not leave any gap in the data: it can be in the past, or at the tip of the
@@ -245,6 +338,9 @@ impl<S: Store> Indexer<S> {
     /// `first_checkpoint`, or it is calculated based on the watermarks of all active pipelines.
     /// Ingestion will stop after consuming the configured `last_checkpoint`, if one is provided,
     /// or will continue until it tracks the tip of the network.
+    ///
+    /// Pipelines added with [Self::concurrent_pipeline_with_backfill] also start their backfills,
+    /// up to just before the first checkpoint of live ingestion.
     pub async fn run(mut self) -> Result<JoinHandle<()>> {
         if let Some(enabled_pipelines) = self.enabled_pipelines {
             ensure!(
@@ -260,10 +356,46 @@ impl<S: Store> Indexer<S> {
             .first_checkpoint
             .unwrap_or(self.first_checkpoint_from_watermark);
 
+        // Pipelines that are catching up don't contribute to the watermark-based lowerbound, so
+        // if they are the only ones, there is no way to tell where live ingestion should start.
+        ensure!(
+            self.backfills.is_empty() || first_checkpoint != u64::MAX,
+            "Pipelines that backfill need another pipeline or a first checkpoint override to \
+             decide where live ingestion starts",
+        );
+
         let last_checkpoint = self.last_checkpoint.unwrap_or(u64::MAX);
 
         info!(first_checkpoint, last_checkpoint = ?self.last_checkpoint, "Ingestion range");
 
+        // A consumer of a local ingestion directory records how far its pipelines have committed,
+        // so that the directory's writer knows which checkpoints it will not ask for again.
+        let consumer = (self.local_consumer && !self.resume_pipelines.is_empty()).then(|| {
+            let cancel = self.cancel.child_token();
+            let handle = consumer_watermark(
+                self.ingestion_service.client().clone(),
+                self.store.clone(),
+                self.resume_pipelines.clone(),
+                first_checkpoint,
+                cancel.clone(),
+            );
+
+            (handle, cancel)
+        });
+
+        for backfill in std::mem::take(&mut self.backfills) {
+            let handles = backfill
+                .run(
+                    first_checkpoint,
+                    last_checkpoint,
+                    self.metrics.clone(),
+                    self.cancel.clone(),
+                )
+                .await?;
+
+            self.handles.extend(handles);
+        }
+
         let (regulator_handle, broadcaster_handle) = self
             .ingestion_service
             .run(first_checkpoint..=last_checkpoint)
@@ -279,6 +411,13 @@ impl<S: Store> Indexer<S> {
             // want to make sure that tasks are allowed to run to completion before shutting them
             // down.
             future::join_all(self.handles).await;
+
+            // Record the consumer's final watermark once its pipelines have stopped.
+            if let Some((handle, cancel)) = consumer {
+                cancel.cancel();
+                let _ = handle.await;
+            }
+
             info!("Indexing pipeline gracefully shut down");
         }))
     }
@@ -289,6 +428,28 @@ impl<S: Store> Indexer<S> {
     /// `Ok(Some(Some(watermark)))` if the pipeline is enabled and the watermark is found.
     async fn add_pipeline<P: Processor + 'static>(
         &mut self,
+    ) -> Result<Option<Option<CommitterWatermark>>> {
+        let Some(watermark) = self.register_pipeline::<P>().await? else {
+            return Ok(None);
+        };
+
+        let expected_first_checkpoint = watermark
+            .as_ref()
+            .map(|w| w.checkpoint_hi_inclusive + 1)
+            .unwrap_or_default();
+
+        self.first_checkpoint_from_watermark =
+            expected_first_checkpoint.min(self.first_checkpoint_from_watermark);
+        self.resume_pipelines.push(P::NAME);
+
+        Ok(Some(watermark))
+    }
+
+    /// Record that the pipeline for handler `P` has been added, and fetch its watermark (as long
+    /// as it's enabled), without affecting the indexer's first checkpoint. Returns values in the
+    /// same way as [Self::add_pipeline].
+    async fn register_pipeline<P: Processor + 'static>(
+        &mut self,
     ) -> Result<Option<Option<CommitterWatermark>>> {
         ensure!(
             self.added_pipelines.insert(P::NAME),
@@ -314,14 +475,6 @@ impl<S: Store> Indexer<S> {
             .await
             .with_context(|| format!("Failed to get watermark for {}", P::NAME))?;
 
-        let expected_first_checkpoint = watermark
-            .as_ref()
-            .map(|w| w.checkpoint_hi_inclusive + 1)
-            .unwrap_or_default();
-
-        self.first_checkpoint_from_watermark =
-            expected_first_checkpoint.min(self.first_checkpoint_from_watermark);
-
         Ok(Some(watermark))
     }
 }
@@ -86,6 +86,13 @@ pub struct IndexerMetrics {
     pub total_pruner_chunks_deleted: IntCounterVec,
     pub total_pruner_rows_deleted: IntCounterVec,
 
+    // Statistics related to pipelines that are catching up, by backfilling their history from a
+    // separate ingestion service.
+    pub total_backfilled_checkpoints: IntCounterVec,
+    pub latest_backfilled_checkpoint: IntGaugeVec,
+    pub backfill_target_checkpoint: IntGaugeVec,
+    pub backfill_catching_up: IntGaugeVec,
+
     // Checkpoint lag metrics for the collector.
     pub latest_collected_checkpoint: IntGaugeVec,
     pub latest_collected_checkpoint_timestamp_lag_ms: IntGaugeVec,
@@ -364,6 +371,36 @@ impl IndexerMetrics {
                 registry,
             )
             .unwrap(),
+            total_backfilled_checkpoints: register_int_counter_vec_with_registry!(
+                "indexer_total_backfilled_checkpoints",
+                "Total number of checkpoints fed to this pipeline by its backfill",
+                &["pipeline"],
+                registry,
+            )
+            .unwrap(),
+            latest_backfilled_checkpoint: register_int_gauge_vec_with_registry!(
+                "indexer_latest_backfilled_checkpoint",
+                "Latest checkpoint sequence number fed to this pipeline by its backfill",
+                &["pipeline"],
+                registry,
+            )
+            .unwrap(),
+            backfill_target_checkpoint: register_int_gauge_vec_with_registry!(
+                "indexer_backfill_target_checkpoint",
+                "Last checkpoint this pipeline's backfill will fetch, just before the first \
+                 checkpoint fed to it by live ingestion",
+                &["pipeline"],
+                registry,
+            )
+            .unwrap(),
+            backfill_catching_up: register_int_gauge_vec_with_registry!(
+                "indexer_backfill_catching_up",
+                "Whether this pipeline is still being fed checkpoints by its backfill (1) or only \
+                 by live ingestion (0)",
+                &["pipeline"],
+                registry,
+            )
+            .unwrap(),
             latest_collected_checkpoint: register_int_gauge_vec_with_registry!(
                 "indexer_latest_collected_checkpoint",
                 "Latest checkpoint sequence number collected by this collector",
@@ -0,0 +1,91 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use sui_package_resolver::{PackageStore, Resolver};
+
+#[cfg(feature = "postgres")]
+use sui_indexer_alt_reader::{
+    package_resolver::{DbPackageStore, PackageCache},
+    pg_reader::PgReader,
+};
+
+/// A package resolver that is agnostic of where packages are loaded from.
+pub type PackageResolver = Arc<Resolver<Arc<dyn PackageStore>>>;
+
+/// Resources shared by all the workers of an [AsyncProcessor](super::AsyncProcessor), to enrich
+/// the rows they produce with information that is not in the checkpoint itself: package layouts
+/// (to decode Move values into typed rows), data that was previously indexed, and any state the
+/// handler needs (e.g. a client for off-chain metadata).
+///
+/// The context is built once, when the pipeline is set up, and is borrowed by every call to
+/// [AsyncProcessor::process](super::AsyncProcessor::process), so anything in it that caches
+/// (like the package resolver) is shared between workers.
+pub struct ProcessorContext<S = ()> {
+    package_resolver: Option<PackageResolver>,
+    #[cfg(feature = "postgres")]
+    reader: Option<PgReader>,
+    state: S,
+}
+
+impl<S> ProcessorContext<S> {
+    /// A context holding just the handler's own `state`. Use the `with_` methods to add shared
+    /// resources.
+    pub fn new(state: S) -> Self {
+        Self {
+            package_resolver: None,
+            #[cfg(feature = "postgres")]
+            reader: None,
+            state,
+        }
+    }
+
+    /// Resolve packages (and the layouts of their types) with `resolver`.
+    pub fn with_package_resolver(mut self, resolver: PackageResolver) -> Self {
+        self.package_resolver = Some(resolver);
+        self
+    }
+
+    /// Read previously indexed data through `reader`. If the context does not have a package
+    /// resolver yet, it also gets one that loads packages through `reader`.
+    #[cfg(feature = "postgres")]
+    pub fn with_reader(mut self, reader: PgReader) -> Self {
+        if self.package_resolver.is_none() {
+            let store: Arc<dyn PackageStore> = Arc::new(PackageCache::new(DbPackageStore::new(
+                Arc::new(reader.as_data_loader()),
+            )));
+            self.package_resolver = Some(Arc::new(Resolver::new(store)));
+        }
+
+        self.reader = Some(reader);
+        self
+    }
+
+    /// The shared package resolver. Fails if the context was built without one.
+    pub fn package_resolver(&self) -> anyhow::Result<&PackageResolver> {
+        self.package_resolver
+            .as_ref()
+            .ok_or_else(|| anyhow!("Processor context has no package resolver"))
+    }
+
+    /// The reader for previously indexed data. Fails if the context was built without one.
+    #[cfg(feature = "postgres")]
+    pub fn reader(&self) -> anyhow::Result<&PgReader> {
+        self.reader
+            .as_ref()
+            .ok_or_else(|| anyhow!("Processor context has no reader"))
+    }
+
+    /// The handler's own state.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+}
+
+impl<S: Default> Default for ProcessorContext<S> {
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
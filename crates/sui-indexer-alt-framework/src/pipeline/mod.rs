@@ -3,12 +3,14 @@
 
 use std::time::Duration;
 
-pub use processor::Processor;
+pub use context::{PackageResolver, ProcessorContext};
+pub use processor::{AsyncProcessor, Processor};
 use serde::{Deserialize, Serialize};
 
 use crate::store::CommitterWatermark;
 
 pub mod concurrent;
+mod context;
 mod logging;
 mod processor;
 pub mod sequential;
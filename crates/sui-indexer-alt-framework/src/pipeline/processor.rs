@@ -3,6 +3,7 @@
 
 use std::sync::Arc;
 
+use async_trait::async_trait;
 use sui_types::full_checkpoint_content::CheckpointData;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::wrappers::ReceiverStream;
@@ -15,11 +16,15 @@ use crate::{
     task::TrySpawnStreamExt,
 };
 
-use super::IndexedCheckpoint;
+use super::{IndexedCheckpoint, ProcessorContext};
 
 /// Implementors of this trait are responsible for transforming checkpoint into rows for their
 /// table. The `FANOUT` associated value controls how many concurrent workers will be used to
 /// process checkpoint information.
+///
+/// Processors that need to await on other services while processing (e.g. to resolve package
+/// layouts) should implement [AsyncProcessor] instead, which implies this trait.
+#[async_trait]
 pub trait Processor {
     /// Used to identify the pipeline in logs and metrics.
     const NAME: &'static str;
@@ -32,6 +37,65 @@ pub trait Processor {
 
     /// The processing logic for turning a checkpoint into rows of the table.
     fn process(&self, checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>>;
+
+    /// The entrypoint the framework uses to process a checkpoint. By default this defers to
+    /// [Self::process], and it is only overridden for [AsyncProcessor]s.
+    async fn process_async(
+        &self,
+        checkpoint: &Arc<CheckpointData>,
+    ) -> anyhow::Result<Vec<Self::Value>> {
+        self.process(checkpoint)
+    }
+}
+
+/// A variant of [Processor] whose processing logic is asynchronous, and has access to a
+/// [ProcessorContext] of resources shared between its workers. Implementing this trait implements
+/// [Processor] as well, so async processors can be used by any pipeline, and share the same
+/// fanout and backpressure behavior.
+#[async_trait]
+pub trait AsyncProcessor: Send + Sync + 'static {
+    /// Used to identify the pipeline in logs and metrics.
+    const NAME: &'static str;
+
+    /// How much concurrency to use when processing checkpoint data.
+    const FANOUT: usize = 10;
+
+    /// The type of value being inserted by the handler.
+    type Value: Send + Sync + 'static;
+
+    /// The handler's own state, made available through its context.
+    type State: Send + Sync + 'static;
+
+    /// The context passed to every call to [Self::process].
+    fn context(&self) -> &ProcessorContext<Self::State>;
+
+    /// The processing logic for turning a checkpoint into rows of the table.
+    async fn process(
+        &self,
+        checkpoint: &Arc<CheckpointData>,
+        ctx: &ProcessorContext<Self::State>,
+    ) -> anyhow::Result<Vec<Self::Value>>;
+}
+
+#[async_trait]
+impl<P: AsyncProcessor> Processor for P {
+    const NAME: &'static str = <P as AsyncProcessor>::NAME;
+    const FANOUT: usize = <P as AsyncProcessor>::FANOUT;
+    type Value = <P as AsyncProcessor>::Value;
+
+    fn process(&self, _checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
+        anyhow::bail!(
+            "Pipeline {} can only process checkpoints asynchronously",
+            <P as AsyncProcessor>::NAME,
+        )
+    }
+
+    async fn process_async(
+        &self,
+        checkpoint: &Arc<CheckpointData>,
+    ) -> anyhow::Result<Vec<Self::Value>> {
+        AsyncProcessor::process(self, checkpoint, self.context()).await
+    }
 }
 
 /// The processor task is responsible for taking checkpoint data and breaking it down into rows
@@ -81,7 +145,7 @@ pub(super) fn processor<P: Processor + Send + Sync + 'static>(
                         .with_label_values(&[P::NAME])
                         .start_timer();
 
-                    let values = processor.process(&checkpoint)?;
+                    let values = processor.process_async(&checkpoint).await?;
                     let elapsed = guard.stop_and_record();
 
                     let epoch = checkpoint.checkpoint_summary.epoch;
@@ -339,6 +403,62 @@ mod tests {
         let _ = handle.await;
     }
 
+    #[tokio::test]
+    async fn test_async_processor_with_context() {
+        // A processor that enriches each checkpoint with a value from its state, after awaiting.
+        struct EnrichingPipeline(ProcessorContext<u64>);
+
+        #[async_trait]
+        impl AsyncProcessor for EnrichingPipeline {
+            const NAME: &'static str = "enriching";
+            type Value = StoredData;
+            type State = u64;
+
+            fn context(&self) -> &ProcessorContext<u64> {
+                &self.0
+            }
+
+            async fn process(
+                &self,
+                checkpoint: &Arc<CheckpointData>,
+                ctx: &ProcessorContext<u64>,
+            ) -> anyhow::Result<Vec<Self::Value>> {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+
+                // This context was built without a package resolver.
+                assert!(ctx.package_resolver().is_err());
+
+                Ok(vec![StoredData {
+                    value: checkpoint.checkpoint_summary.sequence_number + ctx.state(),
+                }])
+            }
+        }
+
+        let processor = Arc::new(EnrichingPipeline(ProcessorContext::new(100)));
+        let (data_tx, data_rx) = mpsc::channel(2);
+        let (indexed_tx, mut indexed_rx) = mpsc::channel(2);
+        let metrics = IndexerMetrics::new(&Default::default());
+        let cancel = CancellationToken::new();
+
+        // The framework only ever calls the async entrypoint.
+        let checkpoint = Arc::new(TestCheckpointDataBuilder::new(1).build_checkpoint());
+        assert!(Processor::process(&*processor, &checkpoint).is_err());
+
+        let handle = super::processor(processor, data_rx, indexed_tx, metrics, cancel.clone());
+
+        data_tx.send(checkpoint).await.unwrap();
+        let indexed = indexed_rx
+            .recv()
+            .await
+            .expect("Should receive IndexedCheckpoint");
+        assert_eq!(indexed.watermark.checkpoint_hi_inclusive, 1);
+        assert_eq!(indexed.values.len(), 1);
+        assert_eq!(indexed.values[0].value, 101);
+
+        drop(data_tx);
+        let _ = handle.await;
+    }
+
     // By default, Rust's async tests run on the single-threaded runtime.
     // We need multi_thread here because our test uses std::thread::sleep which blocks the worker thread.
     // The multi-threaded runtime allows other worker threads to continue processing while one is blocked.
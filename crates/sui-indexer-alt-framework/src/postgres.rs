@@ -87,6 +87,7 @@ impl Indexer<Db> {
             ClientArgs {
                 remote_store_url: None,
                 local_ingestion_path: Some(tempdir().unwrap().keep()),
+                local_ingestion_consumer: None,
                 rpc_api_url: None,
                 rpc_username: None,
                 rpc_password: None,
@@ -0,0 +1,303 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use diesel_migrations::EmbeddedMigrations;
+use prometheus::Registry;
+use tempfile::{tempdir, TempDir};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    ingestion::{ClientArgs, IngestionConfig},
+    Indexer, IndexerArgs,
+};
+
+pub use sui_sqlite_db::*;
+
+/// An opinionated indexer implementation that uses an embedded SQLite database, in a single file
+/// on disk, as the store.
+impl Indexer<Db> {
+    /// Create a new instance of the indexer framework, storing its data in the SQLite database at
+    /// `path` (created if it does not exist). `db_args`, `indexer_args`, `client_args`, and
+    /// `ingestion_config` are as in [Indexer::new_from_pg].
+    ///
+    /// Optional `migrations` contains the SQL to run in order to bring the database schema
+    /// up-to-date for the specific instance of the indexer, generated using diesel's
+    /// `embed_migrations!` macro. These migrations must be written for SQLite, and will be run as
+    /// part of initializing the indexer if provided.
+    ///
+    /// After initialization, at least one pipeline must be added using [Self::concurrent_pipeline]
+    /// or [Self::sequential_pipeline], before the indexer is started using [Self::run].
+    pub async fn new_from_sqlite(
+        path: impl AsRef<Path>,
+        db_args: DbArgs,
+        indexer_args: IndexerArgs,
+        client_args: ClientArgs,
+        ingestion_config: IngestionConfig,
+        migrations: Option<&'static EmbeddedMigrations>,
+        registry: &Registry,
+        cancel: CancellationToken,
+    ) -> Result<Self> {
+        let store = Db::for_write(path, db_args)
+            .await
+            .context("Failed to open database")?;
+
+        // At indexer initialization, we ensure that the DB schema is up-to-date.
+        store
+            .run_migrations(migrations)
+            .await
+            .context("Failed to run pending migrations")?;
+
+        Indexer::new(
+            store,
+            indexer_args,
+            client_args,
+            ingestion_config,
+            registry,
+            cancel,
+        )
+        .await
+    }
+
+    /// Create a new SQLite database in a temporary directory and run the provided migrations in
+    /// tandem with the migrations necessary to support watermark operations on the indexer. The
+    /// indexer is then instantiated and returned along with the directory holding the database.
+    pub async fn new_sqlite_for_testing(
+        migrations: &'static EmbeddedMigrations,
+    ) -> (Indexer<Db>, TempDir) {
+        let temp_dir = tempdir().unwrap();
+        let store = Db::for_write(temp_dir.path().join("indexer.sqlite"), DbArgs::default())
+            .await
+            .unwrap();
+        store.run_migrations(Some(migrations)).await.unwrap();
+
+        let indexer = Indexer::new(
+            store,
+            IndexerArgs::default(),
+            ClientArgs {
+                remote_store_url: None,
+                local_ingestion_path: Some(tempdir().unwrap().keep()),
+                local_ingestion_consumer: None,
+                rpc_api_url: None,
+                rpc_username: None,
+                rpc_password: None,
+            },
+            IngestionConfig::default(),
+            &Registry::new(),
+            CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+        (indexer, temp_dir)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+
+    use async_trait::async_trait;
+    use std::sync::Arc;
+    use sui_indexer_alt_framework_store_traits::{CommitterWatermark, Store};
+    use sui_types::full_checkpoint_content::CheckpointData;
+
+    use super::*;
+
+    use crate::pipeline::{concurrent, sequential};
+    use crate::{
+        pipeline::Processor, store::Connection, ConcurrentConfig, FieldCount, SequentialConfig,
+    };
+
+    #[derive(FieldCount)]
+    struct V {
+        _v: u64,
+    }
+
+    struct ConcurrentPipeline;
+
+    struct SequentialPipeline;
+
+    impl Processor for ConcurrentPipeline {
+        const NAME: &'static str = "concurrent";
+        type Value = V;
+        fn process(&self, _checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
+            todo!()
+        }
+    }
+
+    #[async_trait]
+    impl concurrent::Handler for ConcurrentPipeline {
+        type Store = Db;
+
+        async fn commit<'a>(
+            _values: &[Self::Value],
+            _conn: &mut <Self::Store as Store>::Connection<'a>,
+        ) -> anyhow::Result<usize> {
+            todo!()
+        }
+    }
+
+    impl Processor for SequentialPipeline {
+        const NAME: &'static str = "sequential";
+        type Value = V;
+        fn process(&self, _checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>> {
+            todo!()
+        }
+    }
+
+    #[async_trait]
+    impl sequential::Handler for SequentialPipeline {
+        type Store = Db;
+        type Batch = ();
+
+        fn batch(_batch: &mut Self::Batch, _values: Vec<Self::Value>) {}
+
+        async fn commit<'a>(
+            _batch: &Self::Batch,
+            _conn: &mut <Self::Store as Store>::Connection<'a>,
+        ) -> anyhow::Result<usize> {
+            todo!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_pipelines() {
+        let (mut indexer, _temp_dir) = Indexer::new_sqlite_for_testing(&MIGRATIONS).await;
+        {
+            let mut conn = indexer.store().connect().await.unwrap();
+            assert!(conn
+                .set_committer_watermark(
+                    ConcurrentPipeline::NAME,
+                    CommitterWatermark::new_for_testing(10)
+                )
+                .await
+                .unwrap());
+            assert!(conn
+                .set_committer_watermark(
+                    SequentialPipeline::NAME,
+                    CommitterWatermark::new_for_testing(20)
+                )
+                .await
+                .unwrap());
+        }
+
+        indexer
+            .sequential_pipeline(SequentialPipeline, SequentialConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(indexer.first_checkpoint_from_watermark, 21);
+        indexer
+            .concurrent_pipeline(ConcurrentPipeline, ConcurrentConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(indexer.first_checkpoint_from_watermark, 11);
+    }
+
+    #[tokio::test]
+    async fn test_add_pipeline_with_backfill() {
+        let (mut indexer, temp_dir) = Indexer::new_sqlite_for_testing(&MIGRATIONS).await;
+        {
+            let mut conn = indexer.store().connect().await.unwrap();
+            assert!(conn
+                .set_committer_watermark(
+                    ConcurrentPipeline::NAME,
+                    CommitterWatermark::new_for_testing(10)
+                )
+                .await
+                .unwrap());
+            assert!(conn
+                .set_committer_watermark(
+                    SequentialPipeline::NAME,
+                    CommitterWatermark::new_for_testing(20)
+                )
+                .await
+                .unwrap());
+        }
+
+        indexer
+            .sequential_pipeline(SequentialPipeline, SequentialConfig::default())
+            .await
+            .unwrap();
+
+        // The pipeline that is catching up does not hold back live ingestion, it backfills
+        // checkpoints 11 to 20 instead.
+        indexer
+            .concurrent_pipeline_with_backfill(
+                ConcurrentPipeline,
+                ConcurrentConfig::default(),
+                ClientArgs {
+                    local_ingestion_path: Some(temp_dir.path().to_owned()),
+                    local_ingestion_consumer: None,
+                    remote_store_url: None,
+                    rpc_api_url: None,
+                    rpc_username: None,
+                    rpc_password: None,
+                },
+                IngestionConfig::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(indexer.first_checkpoint_from_watermark, 21);
+        assert_eq!(indexer.backfills.len(), 1);
+        assert_eq!(indexer.backfills[0].first_checkpoint, 11);
+        assert_eq!(
+            indexer.pipelines().collect::<Vec<_>>(),
+            vec![ConcurrentPipeline::NAME, SequentialPipeline::NAME]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backfill_needs_live_first_checkpoint() {
+        let (mut indexer, temp_dir) = Indexer::new_sqlite_for_testing(&MIGRATIONS).await;
+
+        indexer
+            .concurrent_pipeline_with_backfill(
+                ConcurrentPipeline,
+                ConcurrentConfig::default(),
+                ClientArgs {
+                    local_ingestion_path: Some(temp_dir.path().to_owned()),
+                    local_ingestion_consumer: None,
+                    remote_store_url: None,
+                    rpc_api_url: None,
+                    rpc_username: None,
+                    rpc_password: None,
+                },
+                IngestionConfig::default(),
+            )
+            .await
+            .unwrap();
+
+        // Nothing decides where live ingestion starts.
+        assert!(indexer.run().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reopen_database() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("indexer.sqlite");
+        let watermark = CommitterWatermark::new_for_testing(10);
+
+        {
+            let db = Db::for_write(&path, DbArgs::default()).await.unwrap();
+            db.run_migrations(None).await.unwrap();
+            let mut conn = db.connect().await.unwrap();
+            assert!(conn
+                .set_committer_watermark(ConcurrentPipeline::NAME, watermark)
+                .await
+                .unwrap());
+        }
+
+        // Watermarks survive the indexer restarting against the same file.
+        let db = Db::for_write(&path, DbArgs::default()).await.unwrap();
+        assert!(db.run_migrations(None).await.unwrap().is_empty());
+        let mut conn = db.connect().await.unwrap();
+        let stored = conn
+            .committer_watermark(ConcurrentPipeline::NAME)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.checkpoint_hi_inclusive, 10);
+    }
+}
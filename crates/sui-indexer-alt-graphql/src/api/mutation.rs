@@ -0,0 +1,34 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::{Context, Object};
+
+use crate::{error::RpcError, scope::Scope};
+
+use super::{
+    scalars::base64::Base64,
+    types::transaction_effects::{self, TransactionEffects},
+};
+
+#[derive(Default)]
+pub struct Mutation;
+
+/// Mutations are used to write to the Sui network.
+#[Object]
+impl Mutation {
+    /// Execute a transaction, committing its effects on chain.
+    ///
+    /// - `transactionDataBcs` is the transaction's `TransactionData`, serialized as BCS and encoded as Base64.
+    /// - `signatures` are the transaction's signatures, each one a BCS-serialized `GenericSignature` (flag || signature || public key), encoded as Base64.
+    ///
+    /// The transaction is forwarded to a fullnode for execution, and this mutation waits until it has been finalized before returning its effects. These effects are not associated with a checkpoint yet, so fields that depend on the transaction's checkpoint will be `null`.
+    async fn execute_transaction(
+        &self,
+        ctx: &Context<'_>,
+        transaction_data_bcs: Base64,
+        signatures: Vec<Base64>,
+    ) -> Result<TransactionEffects, RpcError<transaction_effects::Error>> {
+        let scope = Scope::new(ctx)?;
+        TransactionEffects::execute(ctx, scope, transaction_data_bcs, signatures).await
+    }
+}
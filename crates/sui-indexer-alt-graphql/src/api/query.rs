@@ -12,17 +12,18 @@ use crate::{
 };
 
 use super::{
-    scalars::{digest::Digest, sui_address::SuiAddress, uint53::UInt53},
+    scalars::{base64::Base64, digest::Digest, sui_address::SuiAddress, uint53::UInt53},
     types::{
         address::Address,
         checkpoint::Checkpoint,
         epoch::Epoch,
+        event::{self, Event, EventFilter},
         move_package::{self, CheckpointFilter, MovePackage, PackageKey},
         object::{self, Object, ObjectKey, VersionFilter},
         protocol_configs::ProtocolConfigs,
         service_config::ServiceConfig,
-        transaction::Transaction,
-        transaction_effects::TransactionEffects,
+        transaction::{self, filter::TransactionFilter, Transaction},
+        transaction_effects::{self, TransactionEffects},
     },
 };
 
@@ -75,6 +76,25 @@ impl Query {
         Epoch::fetch(ctx, scope, epoch_id).await
     }
 
+    /// Paginate events that are emitted in the network, optionally filtered by `filter`.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<event::CEvent>,
+        last: Option<u64>,
+        before: Option<event::CEvent>,
+        filter: Option<EventFilter>,
+    ) -> Result<Option<Connection<String, Event>>, RpcError<event::Error>> {
+        let pagination: &PaginationConfig = ctx.data()?;
+        let limits = pagination.limits("Query", "events");
+        let page = Page::from_params(limits, first, after, last, before)?;
+
+        Ok(Some(
+            Event::paginate(ctx, self.scope(ctx)?, page, filter.unwrap_or_default()).await?,
+        ))
+    }
+
     /// Fetch checkpoints by their sequence numbers.
     ///
     /// Returns a list of checkpoints that is guaranteed to be the same length as `keys`. If a checkpoint in `keys` could not be found in the store, its corresponding entry in the result will be `null`. This could be because the checkpoint does not exist yet, or because it was pruned.
@@ -338,6 +358,19 @@ impl Query {
         ServiceConfig
     }
 
+    /// Simulate executing a transaction, without committing its effects on chain.
+    ///
+    /// `transactionDataBcs` is the transaction's `TransactionData`, serialized as BCS and encoded as Base64. The transaction does not need to be signed.
+    ///
+    /// The transaction is forwarded to a fullnode, which simulates it against its latest state. The resulting effects are not associated with a checkpoint, so fields that depend on the transaction's checkpoint will be `null`.
+    async fn simulate_transaction(
+        &self,
+        ctx: &Context<'_>,
+        transaction_data_bcs: Base64,
+    ) -> Result<TransactionEffects, RpcError<transaction_effects::Error>> {
+        TransactionEffects::simulate(ctx, self.scope(ctx)?, transaction_data_bcs).await
+    }
+
     /// Fetch a transaction by its digest.
     ///
     /// Returns `null` if the transaction does not exist in the store, either because it never existed or because it was pruned.
@@ -359,6 +392,27 @@ impl Query {
     ) -> Result<Option<TransactionEffects>, RpcError> {
         TransactionEffects::fetch(ctx, self.scope(ctx)?, digest).await
     }
+
+    /// Paginate transactions in the order they were finalized, optionally filtered by `filter`.
+    ///
+    /// Cursors are based on each transaction's position in the global order of transactions, so a cursor from one page remains valid even if the checkpoint bounds in `filter` change.
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<transaction::CTransaction>,
+        last: Option<u64>,
+        before: Option<transaction::CTransaction>,
+        filter: Option<TransactionFilter>,
+    ) -> Result<Option<Connection<String, Transaction>>, RpcError> {
+        let pagination: &PaginationConfig = ctx.data()?;
+        let limits = pagination.limits("Query", "transactions");
+        let page = Page::from_params(limits, first, after, last, before)?;
+
+        Ok(Some(
+            Transaction::paginate(ctx, self.scope(ctx)?, page, filter.unwrap_or_default()).await?,
+        ))
+    }
 }
 
 impl Query {
@@ -0,0 +1,365 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
+use sui_types::{parse_sui_struct_tag, parse_sui_type_tag};
+
+use super::{impl_string_input, sui_address::SuiAddress};
+
+/// A fully-qualified type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TypeInput(pub(crate) TypeTag);
+
+/// Filter on the module a value is associated with: either a package (`0x2`), or a specific module
+/// within it (`0x2::coin`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ModuleFilter {
+    Package(AccountAddress),
+    Module(AccountAddress, String),
+}
+
+/// Filter on a Move datatype. It can be as coarse as the package (`0x2`) or module (`0x2::coin`)
+/// that defines it, match every instantiation of a type (`0x2::coin::Coin`), or one instantiation
+/// exactly (`0x2::coin::Coin<0x2::sui::SUI>`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TypeFilter {
+    Module(ModuleFilter),
+    Type(StructTag),
+}
+
+/// Filter on a Move function. It can be as coarse as the package (`0x2`) or module (`0x2::coin`)
+/// that defines it, or name the function exactly (`0x2::coin::value`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum FunctionFilter {
+    Module(ModuleFilter),
+    Function(AccountAddress, String, String),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Invalid type: {0}")]
+    BadType(String),
+
+    #[error("Invalid filter, expected: package[::module[::name[<type, ...>]]], got: {0}")]
+    BadFilter(String),
+
+    #[error("Invalid function filter, expected: package[::module[::function]], got: {0}")]
+    BadFunction(String),
+}
+
+impl_string_input!(TypeInput);
+impl_string_input!(ModuleFilter);
+impl_string_input!(TypeFilter);
+impl_string_input!(FunctionFilter);
+
+impl ModuleFilter {
+    pub(crate) fn package(&self) -> AccountAddress {
+        match self {
+            ModuleFilter::Package(p) | ModuleFilter::Module(p, _) => *p,
+        }
+    }
+
+    pub(crate) fn module(&self) -> Option<&str> {
+        match self {
+            ModuleFilter::Package(_) => None,
+            ModuleFilter::Module(_, m) => Some(m),
+        }
+    }
+
+    /// Whether this filter admits a value associated with `module` in `package`.
+    pub(crate) fn matches(&self, package: AccountAddress, module: &str) -> bool {
+        self.package() == package && self.module().is_none_or(|m| m == module)
+    }
+}
+
+impl TypeFilter {
+    pub(crate) fn package(&self) -> AccountAddress {
+        match self {
+            TypeFilter::Module(m) => m.package(),
+            TypeFilter::Type(t) => t.address,
+        }
+    }
+
+    pub(crate) fn module(&self) -> Option<&str> {
+        match self {
+            TypeFilter::Module(m) => m.module(),
+            TypeFilter::Type(t) => Some(t.module.as_str()),
+        }
+    }
+
+    pub(crate) fn name(&self) -> Option<&str> {
+        match self {
+            TypeFilter::Module(_) => None,
+            TypeFilter::Type(t) => Some(t.name.as_str()),
+        }
+    }
+
+    /// The type parameters to match, if the filter is for a specific instantiation of a type.
+    pub(crate) fn type_params(&self) -> Option<&[TypeTag]> {
+        match self {
+            TypeFilter::Type(t) if !t.type_params.is_empty() => Some(&t.type_params),
+            _ => None,
+        }
+    }
+
+    /// Whether this filter admits a value of type `tag`.
+    pub(crate) fn matches(&self, tag: &StructTag) -> bool {
+        match self {
+            TypeFilter::Module(m) => m.matches(tag.address, tag.module.as_str()),
+            TypeFilter::Type(t) => {
+                t.address == tag.address
+                    && t.module == tag.module
+                    && t.name == tag.name
+                    && (t.type_params.is_empty() || t.type_params == tag.type_params)
+            }
+        }
+    }
+}
+
+impl FunctionFilter {
+    pub(crate) fn package(&self) -> AccountAddress {
+        match self {
+            FunctionFilter::Module(m) => m.package(),
+            FunctionFilter::Function(p, _, _) => *p,
+        }
+    }
+
+    pub(crate) fn module(&self) -> Option<&str> {
+        match self {
+            FunctionFilter::Module(m) => m.module(),
+            FunctionFilter::Function(_, m, _) => Some(m),
+        }
+    }
+
+    pub(crate) fn function(&self) -> Option<&str> {
+        match self {
+            FunctionFilter::Module(_) => None,
+            FunctionFilter::Function(_, _, f) => Some(f),
+        }
+    }
+
+    /// Try to create a filter that only admits functions that both `self` and `other` admit. This
+    /// is only possible if one filter is at least as specific as the other, in which case the more
+    /// specific filter is returned.
+    pub(crate) fn intersect(self, other: Self) -> Option<Self> {
+        if self.subsumes(&other) {
+            Some(other)
+        } else if other.subsumes(&self) {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    /// Whether every function admitted by `other` is also admitted by `self`.
+    fn subsumes(&self, other: &Self) -> bool {
+        self.package() == other.package()
+            && self.module().is_none_or(|m| other.module() == Some(m))
+            && self.function().is_none_or(|f| other.function() == Some(f))
+    }
+}
+
+impl FromStr for TypeInput {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        parse_sui_type_tag(s)
+            .map(TypeInput)
+            .map_err(|_| Error::BadType(s.to_owned()))
+    }
+}
+
+impl FromStr for ModuleFilter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match TypeFilter::from_str(s)? {
+            TypeFilter::Module(m) => Ok(m),
+            TypeFilter::Type(_) => Err(Error::BadFilter(s.to_owned())),
+        }
+    }
+}
+
+impl FromStr for TypeFilter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let bad_filter = || Error::BadFilter(s.to_owned());
+
+        // Filters that mention a type's name are parsed as struct tags, with or without type
+        // parameters.
+        if s.matches("::").count() >= 2 {
+            return parse_sui_struct_tag(s)
+                .map(TypeFilter::Type)
+                .map_err(|_| bad_filter());
+        }
+
+        let (package, module) = match s.split_once("::") {
+            Some((p, m)) => (p, Some(m)),
+            None => (s, None),
+        };
+
+        let package: AccountAddress = SuiAddress::from_str(package)
+            .map_err(|_| bad_filter())?
+            .into();
+
+        let Some(module) = module else {
+            return Ok(TypeFilter::Module(ModuleFilter::Package(package)));
+        };
+
+        if !Identifier::is_valid(module) {
+            return Err(bad_filter());
+        }
+
+        Ok(TypeFilter::Module(ModuleFilter::Module(
+            package,
+            module.to_owned(),
+        )))
+    }
+}
+
+impl FromStr for FunctionFilter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let bad_function = || Error::BadFunction(s.to_owned());
+
+        let mut parts = s.splitn(3, "::");
+        let (Some(package), Some(module), Some(function)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return ModuleFilter::from_str(s)
+                .map(FunctionFilter::Module)
+                .map_err(|_| bad_function());
+        };
+
+        let package: AccountAddress = SuiAddress::from_str(package)
+            .map_err(|_| bad_function())?
+            .into();
+
+        if !Identifier::is_valid(module) || !Identifier::is_valid(function) {
+            return Err(bad_function());
+        }
+
+        Ok(FunctionFilter::Function(
+            package,
+            module.to_owned(),
+            function.to_owned(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_type_filter() {
+        let package = TypeFilter::from_str("0x2").unwrap();
+        assert_eq!(
+            package,
+            TypeFilter::Module(ModuleFilter::Package(AccountAddress::TWO))
+        );
+
+        let module = TypeFilter::from_str("0x2::coin").unwrap();
+        assert_eq!(
+            module,
+            TypeFilter::Module(ModuleFilter::Module(AccountAddress::TWO, "coin".to_owned()))
+        );
+
+        let TypeFilter::Type(name) = TypeFilter::from_str("0x2::coin::Coin").unwrap() else {
+            panic!("Expected a type filter");
+        };
+        assert_eq!(name.name.as_str(), "Coin");
+        assert!(name.type_params.is_empty());
+
+        let TypeFilter::Type(inst) =
+            TypeFilter::from_str("0x2::coin::Coin<0x2::sui::SUI>").unwrap()
+        else {
+            panic!("Expected a type filter");
+        };
+        assert_eq!(inst.type_params.len(), 1);
+    }
+
+    #[test]
+    fn test_bad_type_filter() {
+        assert!(TypeFilter::from_str("").is_err());
+        assert!(TypeFilter::from_str("2").is_err());
+        assert!(TypeFilter::from_str("0x2::").is_err());
+        assert!(TypeFilter::from_str("0x2::1coin").is_err());
+        assert!(TypeFilter::from_str("0x2::coin::Coin<").is_err());
+        assert!(ModuleFilter::from_str("0x2::coin::Coin").is_err());
+    }
+
+    #[test]
+    fn test_type_filter_matches() {
+        let coin = parse_sui_struct_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+        let balance = parse_sui_struct_tag("0x2::balance::Balance<0x2::sui::SUI>").unwrap();
+
+        let filter = |s: &str| TypeFilter::from_str(s).unwrap();
+        assert!(filter("0x2").matches(&coin));
+        assert!(filter("0x2::coin").matches(&coin));
+        assert!(!filter("0x2::coin").matches(&balance));
+        assert!(filter("0x2::coin::Coin").matches(&coin));
+        assert!(filter("0x2::coin::Coin<0x2::sui::SUI>").matches(&coin));
+        assert!(!filter("0x2::coin::Coin<0x3::sui::SUI>").matches(&coin));
+        assert!(!filter("0x3").matches(&coin));
+    }
+
+    #[test]
+    fn test_parse_function_filter() {
+        let filter = |s: &str| FunctionFilter::from_str(s).unwrap();
+
+        assert_eq!(
+            filter("0x2"),
+            FunctionFilter::Module(ModuleFilter::Package(AccountAddress::TWO))
+        );
+
+        assert_eq!(
+            filter("0x2::coin"),
+            FunctionFilter::Module(ModuleFilter::Module(AccountAddress::TWO, "coin".to_owned()))
+        );
+
+        assert_eq!(
+            filter("0x2::coin::value"),
+            FunctionFilter::Function(AccountAddress::TWO, "coin".to_owned(), "value".to_owned())
+        );
+
+        assert!(FunctionFilter::from_str("0x2::coin::").is_err());
+        assert!(FunctionFilter::from_str("0x2::coin::value::x").is_err());
+        assert!(FunctionFilter::from_str("0x2::coin::Coin<0x2::sui::SUI>").is_err());
+    }
+
+    #[test]
+    fn test_intersect_function_filter() {
+        let filter = |s: &str| FunctionFilter::from_str(s).unwrap();
+
+        assert_eq!(
+            filter("0x2").intersect(filter("0x2::coin")),
+            Some(filter("0x2::coin"))
+        );
+
+        assert_eq!(
+            filter("0x2::coin::value").intersect(filter("0x2")),
+            Some(filter("0x2::coin::value"))
+        );
+
+        assert_eq!(
+            filter("0x2::coin").intersect(filter("0x2::coin")),
+            Some(filter("0x2::coin"))
+        );
+
+        assert_eq!(filter("0x2::coin").intersect(filter("0x2::balance")), None);
+        assert_eq!(filter("0x2").intersect(filter("0x3")), None);
+        assert_eq!(
+            filter("0x2::coin::value").intersect(filter("0x2::coin::split")),
+            None
+        );
+    }
+}
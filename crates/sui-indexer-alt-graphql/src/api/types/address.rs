@@ -1,12 +1,22 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use async_graphql::{Interface, Object};
+use async_graphql::{connection::Connection, Context, Interface, Object};
 use sui_types::base_types::SuiAddress as NativeSuiAddress;
 
-use crate::{api::scalars::sui_address::SuiAddress, scope::Scope};
+use crate::{
+    api::scalars::{sui_address::SuiAddress, type_filter::TypeInput},
+    error::RpcError,
+    pagination::{Page, PaginationConfig},
+    scope::Scope,
+};
 
-use super::{move_package::MovePackage, object::Object};
+use super::{
+    balance::{Balance, CBalance},
+    coin::{CCoin, Coin},
+    move_package::MovePackage,
+    object::{self, Object},
+};
 
 /// Interface implemented by GraphQL types representing entities that are identified by an address.
 ///
@@ -33,6 +43,62 @@ impl Address {
     pub(crate) async fn address(&self) -> SuiAddress {
         AddressableImpl::from(self).address()
     }
+
+    /// The total balance of coins of type `coinType` owned by this address, as of the checkpoint being viewed.
+    async fn balance(
+        &self,
+        ctx: &Context<'_>,
+        coin_type: TypeInput,
+    ) -> Result<Option<Balance>, RpcError<object::Error>> {
+        let TypeInput(coin_type) = coin_type;
+        Ok(Some(
+            Balance::fetch(ctx, self.scope.clone(), self.address, coin_type).await?,
+        ))
+    }
+
+    /// Paginate the total balances of all the coin types owned by this address, as of the checkpoint being viewed, ordered by coin type.
+    async fn balances(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<CBalance>,
+        last: Option<u64>,
+        before: Option<CBalance>,
+    ) -> Result<Option<Connection<String, Balance>>, RpcError<object::Error>> {
+        let pagination: &PaginationConfig = ctx.data()?;
+        let limits = pagination.limits("Address", "balances");
+        let page = Page::from_params(limits, first, after, last, before)?;
+
+        Ok(Some(
+            Balance::paginate(ctx, self.scope.clone(), page, self.address).await?,
+        ))
+    }
+
+    /// Paginate the coins owned by this address, as of the checkpoint being viewed, optionally only those of type `coinType`. Coins are returned in (roughly) descending order of balance.
+    async fn coins(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<CCoin>,
+        last: Option<u64>,
+        before: Option<CCoin>,
+        coin_type: Option<TypeInput>,
+    ) -> Result<Option<Connection<String, Coin>>, RpcError<object::Error>> {
+        let pagination: &PaginationConfig = ctx.data()?;
+        let limits = pagination.limits("Address", "coins");
+        let page = Page::from_params(limits, first, after, last, before)?;
+
+        Ok(Some(
+            Coin::paginate(
+                ctx,
+                self.scope.clone(),
+                page,
+                self.address,
+                coin_type.map(|TypeInput(t)| t),
+            )
+            .await?,
+        ))
+    }
 }
 
 impl Address {
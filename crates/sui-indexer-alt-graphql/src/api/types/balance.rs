@@ -0,0 +1,156 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    Context, Object,
+};
+use diesel::{sql_types::Bytea, QueryableByName};
+use futures::future::try_join_all;
+use sui_indexer_alt_reader::pg_reader::PgReader;
+use sui_sql_macro::query;
+use sui_types::{base_types::SuiAddress as NativeSuiAddress, TypeTag};
+
+use crate::{
+    api::scalars::{big_int::BigInt, cursor::BcsCursor, uint53::UInt53},
+    error::RpcError,
+    pagination::Page,
+    scope::Scope,
+};
+
+use super::{
+    coin::{live_coins, Coin},
+    object,
+};
+
+/// The total balance of a particular coin type, held by an address.
+pub(crate) struct Balance {
+    pub(crate) coin_type: TypeTag,
+    pub(crate) coin_object_count: u64,
+    pub(crate) total_balance: u128,
+}
+
+/// Cursor for paginating balances, by the BCS serialization of their coin type.
+pub(crate) type CBalance = BcsCursor<Vec<u8>>;
+
+#[derive(QueryableByName)]
+struct StoredCoinType {
+    #[diesel(sql_type = Bytea)]
+    coin_type: Vec<u8>,
+}
+
+/// The total balance of a particular coin type, held by an address.
+#[Object]
+impl Balance {
+    /// The type of the coin's currency, e.g. `0x2::sui::SUI`.
+    async fn coin_type(&self) -> String {
+        self.coin_type.to_canonical_string(/* with_prefix */ true)
+    }
+
+    /// How many coins of this type the address holds.
+    async fn coin_object_count(&self) -> UInt53 {
+        self.coin_object_count.into()
+    }
+
+    /// The total balance across all coins of this type held by the address.
+    async fn total_balance(&self) -> BigInt {
+        self.total_balance.into()
+    }
+}
+
+impl Balance {
+    /// The balance of coins of type `coin_type` that `owner` holds as of the checkpoint being
+    /// viewed.
+    pub(crate) async fn fetch(
+        ctx: &Context<'_>,
+        scope: Scope,
+        owner: NativeSuiAddress,
+        coin_type: TypeTag,
+    ) -> Result<Balance, RpcError<object::Error>> {
+        let coins = Coin::all_of_type(ctx, scope, owner, &coin_type).await?;
+
+        let total_balance = coins
+            .iter()
+            .filter_map(|c| c.contents.as_coin_maybe())
+            .map(|c| c.value() as u128)
+            .sum();
+
+        Ok(Balance {
+            coin_type,
+            coin_object_count: coins.len() as u64,
+            total_balance,
+        })
+    }
+
+    /// Paginate through the balances of all the coin types that `owner` holds as of the checkpoint
+    /// being viewed, ordered by coin type.
+    pub(crate) async fn paginate(
+        ctx: &Context<'_>,
+        scope: Scope,
+        page: Page<CBalance>,
+        owner: NativeSuiAddress,
+    ) -> Result<Connection<String, Balance>, RpcError<object::Error>> {
+        let mut conn = Connection::new(false, false);
+
+        let pg_reader: &PgReader = ctx.data()?;
+
+        let mut query = query!(
+            "SELECT DISTINCT c.coin_type {}",
+            live_coins(&scope, owner, None)?,
+        );
+
+        if let Some(after) = page.after() {
+            query += query!(" AND {Bytea} <= c.coin_type", after.to_vec());
+        }
+
+        if let Some(before) = page.before() {
+            query += query!(" AND c.coin_type <= {Bytea}", before.to_vec());
+        }
+
+        query += if page.is_from_front() {
+            query!(" ORDER BY c.coin_type")
+        } else {
+            query!(" ORDER BY c.coin_type DESC")
+        };
+
+        query += query!(" LIMIT {BigInt}", page.limit() as i64 + 2);
+
+        let mut c = pg_reader
+            .connect()
+            .await
+            .context("Failed to connect to database")?;
+
+        let mut results: Vec<StoredCoinType> = c
+            .results(query)
+            .await
+            .context("Failed to read from database")?;
+
+        if !page.is_from_front() {
+            results.reverse();
+        }
+
+        let (prev, next, results) =
+            page.paginate_results(results, |t| BcsCursor::new(t.coin_type.clone()));
+
+        conn.has_previous_page = prev;
+        conn.has_next_page = next;
+
+        let balances = try_join_all(results.map(|(cursor, stored)| {
+            let scope = scope.clone();
+            async move {
+                let coin_type: TypeTag = bcs::from_bytes(&stored.coin_type)
+                    .context("Failed to deserialize coin type")?;
+                let balance = Balance::fetch(ctx, scope, owner, coin_type).await?;
+                Ok::<_, RpcError<object::Error>>((cursor, balance))
+            }
+        }))
+        .await?;
+
+        for (cursor, balance) in balances {
+            conn.edges.push(Edge::new(cursor.encode_cursor(), balance));
+        }
+
+        Ok(conn)
+    }
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Context as _;
-use async_graphql::{Context, Object};
+use async_graphql::{connection::Connection, Context, Object};
 use sui_indexer_alt_reader::kv_loader::KvLoader;
 use sui_types::{
     crypto::AuthorityStrongQuorumSignInfo,
@@ -15,10 +15,14 @@ use crate::{
         scalars::{date_time::DateTime, uint53::UInt53},
     },
     error::RpcError,
+    pagination::{Page, PaginationConfig},
     scope::Scope,
 };
 
-use super::epoch::Epoch;
+use super::{
+    epoch::Epoch,
+    transaction::{filter::TransactionFilter, CTransaction, Transaction},
+};
 
 pub(crate) struct Checkpoint {
     pub(crate) sequence_number: u64,
@@ -56,6 +60,32 @@ impl Checkpoint {
         Ok(Some(Query { scope }))
     }
 
+    /// Paginate the transactions in this checkpoint, in the order they were finalized, optionally filtered by `filter`.
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<CTransaction>,
+        last: Option<u64>,
+        before: Option<CTransaction>,
+        filter: Option<TransactionFilter>,
+    ) -> Result<Option<Connection<String, Transaction>>, RpcError> {
+        let pagination: &PaginationConfig = ctx.data()?;
+        let limits = pagination.limits("Checkpoint", "transactions");
+        let page = Page::from_params(limits, first, after, last, before)?;
+
+        let Some(filter) = filter.unwrap_or_default().intersect(TransactionFilter {
+            at_checkpoint: Some(self.sequence_number.into()),
+            ..TransactionFilter::default()
+        }) else {
+            return Ok(Some(Connection::new(false, false)));
+        };
+
+        Ok(Some(
+            Transaction::paginate(ctx, self.scope.clone(), page, filter).await?,
+        ))
+    }
+
     #[graphql(flatten)]
     async fn contents(&self, ctx: &Context<'_>) -> Result<CheckpointContents, RpcError> {
         CheckpointContents::fetch(ctx, self.scope.clone(), self.sequence_number).await
@@ -0,0 +1,292 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    Context, Object,
+};
+use diesel::{
+    sql_types::{BigInt, Bytea, SmallInt},
+    QueryableByName,
+};
+use fastcrypto::encoding::{Base58, Encoding};
+use futures::future::try_join_all;
+use serde::{Deserialize, Serialize};
+use sui_indexer_alt_reader::pg_reader::PgReader;
+use sui_indexer_alt_schema::objects::StoredCoinOwnerKind;
+use sui_pg_db::query::Query;
+use sui_sql_macro::query;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress as NativeSuiAddress},
+    object::Object as NativeObject,
+    TypeTag,
+};
+
+use crate::{
+    api::scalars::{
+        big_int::BigInt as GqlBigInt, cursor::BcsCursor, sui_address::SuiAddress, uint53::UInt53,
+    },
+    error::RpcError,
+    pagination::Page,
+    scope::Scope,
+};
+
+use super::object::{self, Object};
+
+pub(crate) struct Coin {
+    pub(crate) scope: Scope,
+    pub(crate) contents: Arc<NativeObject>,
+}
+
+/// Coins owned by an address are ordered by their balance bucket (an order of magnitude
+/// approximation of their balance, largest first), then by the checkpoint they were last modified
+/// in, and then by ID.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CoinCursor {
+    pub(crate) coin_balance_bucket: u64,
+    pub(crate) cp_sequence_number: u64,
+    pub(crate) object_id: Vec<u8>,
+}
+
+pub(crate) type CCoin = BcsCursor<CoinCursor>;
+
+#[derive(QueryableByName)]
+struct StoredCoinBucket {
+    #[diesel(sql_type = Bytea)]
+    object_id: Vec<u8>,
+
+    #[diesel(sql_type = BigInt)]
+    cp_sequence_number: i64,
+
+    #[diesel(sql_type = SmallInt)]
+    coin_balance_bucket: i16,
+}
+
+/// A Move object of type `0x2::coin::Coin<T>`, holding a balance of currency `T`.
+#[Object]
+impl Coin {
+    /// The coin's ID.
+    async fn address(&self) -> SuiAddress {
+        self.contents.id().into()
+    }
+
+    /// The version of the coin that this content comes from.
+    async fn version(&self) -> UInt53 {
+        self.contents.version().into()
+    }
+
+    /// 32-byte hash that identifies the coin's contents, encoded in Base58.
+    async fn digest(&self) -> String {
+        Base58::encode(self.contents.digest().inner())
+    }
+
+    /// The type of the coin's currency, e.g. `0x2::sui::SUI`, for a `0x2::coin::Coin<0x2::sui::SUI>`.
+    async fn coin_type(&self) -> Option<String> {
+        self.contents
+            .coin_type_maybe()
+            .map(|t| t.to_canonical_string(/* with_prefix */ true))
+    }
+
+    /// The balance held by this coin.
+    async fn coin_balance(&self) -> Option<GqlBigInt> {
+        self.contents.as_coin_maybe().map(|c| c.value().into())
+    }
+
+    /// The coin, as a generic object.
+    async fn as_object(&self) -> Object {
+        Object::from_contents(self.scope.clone(), self.contents.clone())
+    }
+}
+
+impl Coin {
+    /// Paginate through the coins owned by `owner` as of the checkpoint being viewed, optionally
+    /// restricting them to coins of currency `coin_type`.
+    pub(crate) async fn paginate(
+        ctx: &Context<'_>,
+        scope: Scope,
+        page: Page<CCoin>,
+        owner: NativeSuiAddress,
+        coin_type: Option<TypeTag>,
+    ) -> Result<Connection<String, Coin>, RpcError<object::Error>> {
+        let mut conn = Connection::new(false, false);
+
+        let pg_reader: &PgReader = ctx.data()?;
+
+        let mut query = query!(
+            "SELECT c.object_id, c.cp_sequence_number, c.coin_balance_bucket {}",
+            live_coins(&scope, owner, coin_type.as_ref())?,
+        );
+
+        if let Some(after) = page.after() {
+            query += query!(
+                " AND (c.coin_balance_bucket, c.cp_sequence_number, c.object_id) <= ({SmallInt}, {BigInt}, {Bytea})",
+                after.coin_balance_bucket as i16,
+                after.cp_sequence_number as i64,
+                after.object_id.clone(),
+            );
+        }
+
+        if let Some(before) = page.before() {
+            query += query!(
+                " AND (c.coin_balance_bucket, c.cp_sequence_number, c.object_id) >= ({SmallInt}, {BigInt}, {Bytea})",
+                before.coin_balance_bucket as i16,
+                before.cp_sequence_number as i64,
+                before.object_id.clone(),
+            );
+        }
+
+        // Coins with the largest balances come first.
+        query += if page.is_from_front() {
+            query!(
+                " ORDER BY c.coin_balance_bucket DESC, c.cp_sequence_number DESC, c.object_id DESC"
+            )
+        } else {
+            query!(" ORDER BY c.coin_balance_bucket, c.cp_sequence_number, c.object_id")
+        };
+
+        query += query!(" LIMIT {BigInt}", page.limit() as i64 + 2);
+
+        let mut c = pg_reader
+            .connect()
+            .await
+            .context("Failed to connect to database")?;
+
+        let mut results: Vec<StoredCoinBucket> = c
+            .results(query)
+            .await
+            .context("Failed to read from database")?;
+
+        if !page.is_from_front() {
+            results.reverse();
+        }
+
+        let (prev, next, results) = page.paginate_results(results, |b| {
+            BcsCursor::new(CoinCursor {
+                coin_balance_bucket: b.coin_balance_bucket as u64,
+                cp_sequence_number: b.cp_sequence_number as u64,
+                object_id: b.object_id.clone(),
+            })
+        });
+
+        conn.has_previous_page = prev;
+        conn.has_next_page = next;
+
+        let coins = try_join_all(results.map(|(cursor, stored)| {
+            let scope = scope.clone();
+            async move {
+                let coin = Coin::fetch(ctx, scope, &stored.object_id).await?;
+                Ok::<_, RpcError<object::Error>>(coin.map(|c| (cursor, c)))
+            }
+        }))
+        .await?;
+
+        for (cursor, coin) in coins.into_iter().flatten() {
+            conn.edges.push(Edge::new(cursor.encode_cursor(), coin));
+        }
+
+        Ok(conn)
+    }
+
+    /// Load all the coins of currency `coin_type` owned by `owner`, as of the checkpoint being
+    /// viewed.
+    pub(crate) async fn all_of_type(
+        ctx: &Context<'_>,
+        scope: Scope,
+        owner: NativeSuiAddress,
+        coin_type: &TypeTag,
+    ) -> Result<Vec<Coin>, RpcError<object::Error>> {
+        let pg_reader: &PgReader = ctx.data()?;
+
+        let query = query!(
+            "SELECT c.object_id, c.cp_sequence_number, c.coin_balance_bucket {}",
+            live_coins(&scope, owner, Some(coin_type))?,
+        );
+
+        let mut c = pg_reader
+            .connect()
+            .await
+            .context("Failed to connect to database")?;
+
+        let results: Vec<StoredCoinBucket> = c
+            .results(query)
+            .await
+            .context("Failed to read from database")?;
+
+        let coins = try_join_all(
+            results
+                .iter()
+                .map(|stored| Coin::fetch(ctx, scope.clone(), &stored.object_id)),
+        )
+        .await?;
+
+        Ok(coins.into_iter().flatten().collect())
+    }
+
+    /// Load the latest version of the coin with ID `object_id`, as of the checkpoint being viewed.
+    async fn fetch(
+        ctx: &Context<'_>,
+        scope: Scope,
+        object_id: &[u8],
+    ) -> Result<Option<Coin>, RpcError<object::Error>> {
+        let address = ObjectID::from_bytes(object_id).context("Failed to deserialize coin ID")?;
+        let cp = scope.checkpoint_viewed_at().into();
+
+        let Some(object) =
+            Object::checkpoint_bounded(ctx, scope.clone(), address.into(), cp).await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(object
+            .contents(ctx)
+            .await?
+            .map(|contents| Coin { scope, contents }))
+    }
+}
+
+/// The `FROM` and `WHERE` clauses selecting the latest row in `coin_balance_buckets` (aliased as
+/// `c`) for every coin that `owner` held as of the checkpoint being viewed, optionally restricted
+/// to coins of currency `coin_type`. Rows written after that checkpoint are ignored, so that the
+/// coin's state is consistent with the rest of the query.
+pub(crate) fn live_coins(
+    scope: &Scope,
+    owner: NativeSuiAddress,
+    coin_type: Option<&TypeTag>,
+) -> anyhow::Result<Query<'static>> {
+    let checkpoint = scope.checkpoint_viewed_at() as i64;
+
+    let mut query = query!(
+        r#"
+        FROM
+            coin_balance_buckets c
+        WHERE
+            c.owner_kind = {SmallInt}
+        AND c.owner_id = {Bytea}
+        AND c.cp_sequence_number <= {BigInt}
+        AND NOT EXISTS (
+            SELECT
+                1
+            FROM
+                coin_balance_buckets n
+            WHERE
+                n.object_id = c.object_id
+            AND c.cp_sequence_number < n.cp_sequence_number
+            AND n.cp_sequence_number <= {BigInt}
+        )
+        "#,
+        StoredCoinOwnerKind::Fastpath,
+        owner.to_vec(),
+        checkpoint,
+        checkpoint,
+    );
+
+    if let Some(coin_type) = coin_type {
+        let coin_type = bcs::to_bytes(coin_type).context("Failed to serialize coin type")?;
+        query += query!(" AND c.coin_type = {Bytea}", coin_type);
+    }
+
+    Ok(query)
+}
@@ -0,0 +1,298 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context as _};
+use async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    Context, InputObject, Object, Union,
+};
+use diesel::{sql_types::Bytea, QueryableByName};
+use futures::future::try_join_all;
+use move_core_types::annotated_value::MoveTypeLayout;
+use sui_indexer_alt_reader::{package_resolver::PackageCache, pg_reader::PgReader};
+use sui_indexer_alt_schema::objects::StoredOwnerKind;
+use sui_package_resolver::Resolver;
+use sui_sql_macro::query;
+use sui_types::{
+    base_types::ObjectID,
+    dynamic_field::{derive_dynamic_field_id, visitor as DFV, DynamicFieldInfo},
+    object::Object as NativeObject,
+    TypeTag, SUI_FRAMEWORK_ADDRESS,
+};
+
+use crate::{
+    api::scalars::{
+        base64::Base64, cursor::BcsCursor, sui_address::SuiAddress, type_filter::TypeInput,
+        uint53::UInt53,
+    },
+    error::RpcError,
+    pagination::Page,
+    scope::Scope,
+};
+
+use super::{
+    move_value::MoveValue,
+    object::{self, Object},
+};
+
+/// A dynamic field (or dynamic object field) of an object, represented by its
+/// `0x2::dynamic_field::Field<Name, Value>` object.
+pub(crate) struct DynamicField {
+    pub(crate) scope: Scope,
+    pub(crate) contents: Arc<NativeObject>,
+}
+
+/// The value of a dynamic field is a Move value, and the value of a dynamic object field is an object.
+#[derive(Union)]
+pub(crate) enum DynamicFieldValue {
+    MoveValue(MoveValue),
+    Object(Object),
+}
+
+/// Identifies a dynamic field by its name.
+#[derive(InputObject, Debug, Clone)]
+pub(crate) struct DynamicFieldName {
+    /// The type of the dynamic field's name, e.g. `u64` or `0x2::kiosk::Listing`.
+    #[graphql(name = "type")]
+    pub(crate) type_: TypeInput,
+
+    /// The Base64-encoded BCS serialization of the dynamic field's name.
+    pub(crate) bcs: Base64,
+}
+
+/// Cursor for paginating dynamic fields, by the ID of their `Field` object.
+pub(crate) type CDynamicField = BcsCursor<Vec<u8>>;
+
+#[derive(QueryableByName)]
+struct StoredFieldId {
+    #[diesel(sql_type = Bytea)]
+    object_id: Vec<u8>,
+}
+
+/// A dynamic field, or dynamic object field, owned by an object.
+///
+/// Dynamic fields are key-value pairs stored on an object. The value of a dynamic field is a Move value, while the value of a dynamic object field is an object that remains accessible by its own ID.
+#[Object]
+impl DynamicField {
+    /// The ID of the `0x2::dynamic_field::Field` object that holds this dynamic field.
+    async fn address(&self) -> SuiAddress {
+        self.contents.id().into()
+    }
+
+    /// The version of the `Field` object that this content comes from.
+    async fn version(&self) -> UInt53 {
+        self.contents.version().into()
+    }
+
+    /// The dynamic field's name, as a Move value.
+    async fn name(&self, ctx: &Context<'_>) -> Result<MoveValue, RpcError<object::Error>> {
+        let (contents, layout) = self.layout(ctx).await?;
+        let field = DFV::FieldVisitor::deserialize(contents, &layout)
+            .context("Failed to deserialize dynamic field")?;
+
+        Ok(MoveValue::new(
+            TypeTag::from(field.name_layout),
+            field.name_bytes.to_owned(),
+        ))
+    }
+
+    /// The dynamic field's value. This is a Move value for dynamic fields, and an object for dynamic object fields. Dynamic object field values are fetched as of the checkpoint being viewed.
+    async fn value(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<DynamicFieldValue>, RpcError<object::Error>> {
+        let (contents, layout) = self.layout(ctx).await?;
+        let field = DFV::FieldVisitor::deserialize(contents, &layout)
+            .context("Failed to deserialize dynamic field")?;
+
+        let value_metadata = field
+            .value_metadata()
+            .context("Failed to extract dynamic field value metadata")?;
+
+        Ok(match value_metadata {
+            DFV::ValueMetadata::DynamicField(type_) => Some(DynamicFieldValue::MoveValue(
+                MoveValue::new(type_, field.value_bytes.to_owned()),
+            )),
+
+            DFV::ValueMetadata::DynamicObjectField(id) => load_latest(ctx, self.scope.clone(), id)
+                .await?
+                .map(|contents| {
+                    DynamicFieldValue::Object(Object::from_contents(self.scope.clone(), contents))
+                }),
+        })
+    }
+
+    /// The `Field` object that holds this dynamic field, as a generic object.
+    async fn as_object(&self) -> Object {
+        Object::from_contents(self.scope.clone(), self.contents.clone())
+    }
+}
+
+impl DynamicField {
+    /// Fetch the dynamic field (or dynamic object field) of `parent` called `name`, as of the
+    /// checkpoint being viewed.
+    pub(crate) async fn by_name(
+        ctx: &Context<'_>,
+        scope: Scope,
+        parent: ObjectID,
+        name: DynamicFieldName,
+    ) -> Result<Option<Self>, RpcError<object::Error>> {
+        let TypeInput(type_) = name.type_;
+        let Base64(bcs) = name.bcs;
+
+        let df_id = derive_dynamic_field_id(parent, &type_, &bcs)
+            .context("Failed to derive dynamic field ID")?;
+
+        let wrapper: TypeTag = DynamicFieldInfo::dynamic_object_field_wrapper(type_).into();
+        let dof_id = derive_dynamic_field_id(parent, &wrapper, &bcs)
+            .context("Failed to derive dynamic object field ID")?;
+
+        let df = load_latest(ctx, scope.clone(), df_id);
+        let dof = load_latest(ctx, scope.clone(), dof_id);
+        let (df, dof) = futures::try_join!(df, dof)?;
+
+        Ok(df.or(dof).map(|contents| Self { scope, contents }))
+    }
+
+    /// Paginate through the dynamic fields owned by `parent` as of the checkpoint being viewed,
+    /// ordered by the IDs of their `Field` objects.
+    pub(crate) async fn paginate(
+        ctx: &Context<'_>,
+        scope: Scope,
+        page: Page<CDynamicField>,
+        parent: ObjectID,
+    ) -> Result<Connection<String, DynamicField>, RpcError<object::Error>> {
+        let mut conn = Connection::new(false, false);
+
+        let pg_reader: &PgReader = ctx.data()?;
+        let checkpoint = scope.checkpoint_viewed_at() as i64;
+
+        let mut query = query!(
+            r#"
+            SELECT
+                o.object_id
+            FROM
+                obj_info o
+            WHERE
+                o.owner_kind = {SmallInt}
+            AND o.owner_id = {Bytea}
+            AND o.package = {Bytea}
+            AND o.module = 'dynamic_field'
+            AND o.name = 'Field'
+            AND o.cp_sequence_number <= {BigInt}
+            AND NOT EXISTS (
+                SELECT
+                    1
+                FROM
+                    obj_info n
+                WHERE
+                    n.object_id = o.object_id
+                AND o.cp_sequence_number < n.cp_sequence_number
+                AND n.cp_sequence_number <= {BigInt}
+            )
+            "#,
+            StoredOwnerKind::Object,
+            parent.to_vec(),
+            SUI_FRAMEWORK_ADDRESS.to_vec(),
+            checkpoint,
+            checkpoint,
+        );
+
+        if let Some(after) = page.after() {
+            query += query!(" AND {Bytea} <= o.object_id", after.to_vec());
+        }
+
+        if let Some(before) = page.before() {
+            query += query!(" AND o.object_id <= {Bytea}", before.to_vec());
+        }
+
+        query += if page.is_from_front() {
+            query!(" ORDER BY o.object_id")
+        } else {
+            query!(" ORDER BY o.object_id DESC")
+        };
+
+        query += query!(" LIMIT {BigInt}", page.limit() as i64 + 2);
+
+        let mut c = pg_reader
+            .connect()
+            .await
+            .context("Failed to connect to database")?;
+
+        let mut results: Vec<StoredFieldId> = c
+            .results(query)
+            .await
+            .context("Failed to read from database")?;
+
+        if !page.is_from_front() {
+            results.reverse();
+        }
+
+        let (prev, next, results) =
+            page.paginate_results(results, |f| BcsCursor::new(f.object_id.clone()));
+
+        conn.has_previous_page = prev;
+        conn.has_next_page = next;
+
+        let fields = try_join_all(results.map(|(cursor, stored)| {
+            let scope = scope.clone();
+            async move {
+                let id = ObjectID::from_bytes(&stored.object_id)
+                    .context("Failed to deserialize dynamic field ID")?;
+
+                let field = load_latest(ctx, scope.clone(), id)
+                    .await?
+                    .map(|contents| (cursor, Self { scope, contents }));
+
+                Ok::<_, RpcError<object::Error>>(field)
+            }
+        }))
+        .await?;
+
+        for (cursor, field) in fields.into_iter().flatten() {
+            conn.edges.push(Edge::new(cursor.encode_cursor(), field));
+        }
+
+        Ok(conn)
+    }
+
+    /// The contents of the `Field` object, and the layout of its type.
+    async fn layout(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<(&[u8], MoveTypeLayout), RpcError<object::Error>> {
+        let resolver: &Arc<Resolver<PackageCache>> = ctx.data()?;
+
+        let Some(move_object) = self.contents.data.try_as_move() else {
+            return Err(anyhow!(
+                "Dynamic field at {} is not a Move object",
+                self.contents.id()
+            )
+            .into());
+        };
+
+        let type_: TypeTag = move_object.type_().clone().into();
+        let layout = resolver
+            .type_layout(type_)
+            .await
+            .context("Failed to resolve dynamic field type layout")?;
+
+        Ok((move_object.contents(), layout))
+    }
+}
+
+/// Load the latest contents of the object at `id`, as of the checkpoint being viewed.
+async fn load_latest(
+    ctx: &Context<'_>,
+    scope: Scope,
+    id: ObjectID,
+) -> Result<Option<Arc<NativeObject>>, RpcError<object::Error>> {
+    let cp = scope.checkpoint_viewed_at().into();
+    let Some(object) = Object::checkpoint_bounded(ctx, scope, id.into(), cp).await? else {
+        return Ok(None);
+    };
+
+    object.contents(ctx).await
+}
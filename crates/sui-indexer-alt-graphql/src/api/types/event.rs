@@ -0,0 +1,350 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Context as _;
+use async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    dataloader::DataLoader,
+    Context, InputObject, Object,
+};
+use diesel::{sql_types::BigInt, QueryableByName};
+use futures::future::try_join_all;
+use serde::{Deserialize, Serialize};
+use sui_indexer_alt_reader::{
+    kv_loader::{KvLoader, TransactionContents as NativeTransactionContents},
+    pg_reader::PgReader,
+    tx_digests::TxDigestKey,
+};
+use sui_sql_macro::query;
+use sui_types::{
+    base_types::SuiAddress as NativeSuiAddress, digests::TransactionDigest,
+    event::Event as NativeEvent,
+};
+
+use crate::{
+    api::scalars::{
+        base64::Base64,
+        cursor::BcsCursor,
+        date_time::DateTime,
+        sui_address::SuiAddress,
+        type_filter::{ModuleFilter, TypeFilter},
+        uint53::UInt53,
+    },
+    error::{bad_user_input, RpcError},
+    pagination::Page,
+    scope::Scope,
+};
+
+use super::{
+    address::Address,
+    move_value::MoveValue,
+    transaction::{Transaction, TransactionContents},
+};
+
+#[derive(Clone)]
+pub(crate) struct Event {
+    pub(crate) scope: Scope,
+
+    /// The digest of the transaction that emitted this event.
+    pub(crate) transaction_digest: TransactionDigest,
+
+    /// The contents of the transaction that emitted this event.
+    pub(crate) transaction: Arc<NativeTransactionContents>,
+
+    /// The position of this event among the events emitted by its transaction.
+    pub(crate) sequence_number: u64,
+
+    pub(crate) native: NativeEvent,
+}
+
+/// Filter for paginating events.
+///
+/// At most one of `emittingModule` and `eventType` can be specified.
+#[derive(InputObject, Default, Debug)]
+pub(crate) struct EventFilter {
+    /// Filter down to events from transactions sent by this address.
+    pub(crate) sender: Option<SuiAddress>,
+
+    /// Filter down to events emitted by a transaction calling into this package (e.g. `0x2`), or module (e.g. `0x2::coin`).
+    pub(crate) emitting_module: Option<ModuleFilter>,
+
+    /// Filter down to events whose type is defined in this package (e.g. `0x2`) or module (e.g. `0x2::coin`), or whose type is an instantiation of this type (e.g. `0x2::coin::CoinMetadata`) or exactly this type (e.g. `0x2::coin::CoinMetadata<0x2::sui::SUI>`).
+    pub(crate) event_type: Option<TypeFilter>,
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub(crate) enum Error {
+    #[error("Events can be filtered by emitting module or by event type, but not both")]
+    ModuleAndType,
+}
+
+/// Events are ordered by the transaction that emitted them, and then by their position within
+/// that transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct EventCursor {
+    pub(crate) tx_sequence_number: u64,
+    pub(crate) ev_sequence_number: u64,
+}
+
+pub(crate) type CEvent = BcsCursor<EventCursor>;
+
+#[derive(QueryableByName)]
+struct TxSequenceNumber {
+    #[diesel(sql_type = BigInt)]
+    tx_sequence_number: i64,
+}
+
+/// An event emitted by a transaction.
+#[Object]
+impl Event {
+    /// The position of this event among the events emitted by its transaction.
+    async fn sequence_number(&self) -> UInt53 {
+        self.sequence_number.into()
+    }
+
+    /// The address of the sender of the transaction that emitted this event.
+    async fn sender(&self) -> Address {
+        Address::with_address(self.scope.clone(), self.native.sender)
+    }
+
+    /// The module containing the function the transaction called into when it emitted this event, as `package::module`. This is not necessarily the module that defines the event's type.
+    async fn sending_module(&self) -> String {
+        format!(
+            "{}::{}",
+            self.native
+                .package_id
+                .to_canonical_string(/* with_prefix */ true),
+            self.native.transaction_module,
+        )
+    }
+
+    /// The event's type and its BCS-serialized contents.
+    async fn contents(&self) -> MoveValue {
+        MoveValue::new(
+            self.native.type_.clone().into(),
+            self.native.contents.clone(),
+        )
+    }
+
+    /// The Base64-encoded BCS serialization of this event, as an `Event`.
+    async fn event_bcs(&self) -> Result<Base64, RpcError> {
+        let bytes = bcs::to_bytes(&self.native).context("Failed to serialize event")?;
+        Ok(Base64(bytes))
+    }
+
+    /// Timestamp corresponding to the checkpoint that this event's transaction was finalized in.
+    async fn timestamp(&self) -> Result<Option<DateTime>, RpcError> {
+        let Some(timestamp_ms) = self.transaction.timestamp_ms() else {
+            return Ok(None);
+        };
+
+        Ok(Some(DateTime::from_ms(timestamp_ms as i64)?))
+    }
+
+    /// The transaction that emitted this event.
+    async fn transaction(&self) -> Transaction {
+        Transaction {
+            digest: self.transaction_digest,
+            contents: TransactionContents {
+                scope: self.scope.clone(),
+                contents: Some(self.transaction.clone()),
+            },
+        }
+    }
+}
+
+impl Event {
+    /// Paginate events that match `filter`, as of the checkpoint being viewed.
+    ///
+    /// The event indices are used to find the transactions that emitted matching events, in
+    /// order, and then the events themselves are read from those transactions, so that each page
+    /// is consistent with the transactions that were visible at that checkpoint.
+    pub(crate) async fn paginate(
+        ctx: &Context<'_>,
+        scope: Scope,
+        page: Page<CEvent>,
+        filter: EventFilter,
+    ) -> Result<Connection<String, Event>, RpcError<Error>> {
+        if filter.emitting_module.is_some() && filter.event_type.is_some() {
+            return Err(bad_user_input(Error::ModuleAndType));
+        }
+
+        let mut conn = Connection::new(false, false);
+
+        let pg_reader: &PgReader = ctx.data()?;
+        let pg_loader: &Arc<DataLoader<PgReader>> = ctx.data()?;
+        let kv_loader: &KvLoader = ctx.data()?;
+
+        let mut query = if let Some(module) = &filter.emitting_module {
+            let mut query = query!(
+                "SELECT DISTINCT tx_sequence_number FROM ev_emit_mod WHERE package = {Bytea}",
+                module.package().to_vec(),
+            );
+
+            if let Some(module) = module.module() {
+                query += query!(" AND module = {Text}", module.to_owned());
+            }
+
+            query
+        } else if let Some(type_) = &filter.event_type {
+            let mut query = query!(
+                "SELECT DISTINCT tx_sequence_number FROM ev_struct_inst WHERE package = {Bytea}",
+                type_.package().to_vec(),
+            );
+
+            if let Some(module) = type_.module() {
+                query += query!(" AND module = {Text}", module.to_owned());
+            }
+
+            if let Some(name) = type_.name() {
+                query += query!(" AND name = {Text}", name.to_owned());
+            }
+
+            if let Some(type_params) = type_.type_params() {
+                let instantiation =
+                    bcs::to_bytes(type_params).context("Failed to serialize type parameters")?;
+                query += query!(" AND instantiation = {Bytea}", instantiation);
+            }
+
+            query
+        } else {
+            query!("SELECT DISTINCT tx_sequence_number FROM ev_struct_inst WHERE TRUE")
+        };
+
+        query += query!(
+            " AND tx_sequence_number < {BigInt}",
+            scope.tx_hi(ctx).await? as i64
+        );
+
+        if let Some(sender) = filter.sender {
+            query += query!(
+                " AND sender = {Bytea}",
+                NativeSuiAddress::from(sender).to_vec(),
+            );
+        }
+
+        if let Some(after) = page.after() {
+            query += query!(
+                " AND tx_sequence_number >= {BigInt}",
+                after.tx_sequence_number as i64
+            );
+        }
+
+        if let Some(before) = page.before() {
+            query += query!(
+                " AND tx_sequence_number <= {BigInt}",
+                before.tx_sequence_number as i64
+            );
+        }
+
+        query += if page.is_from_front() {
+            query!(" ORDER BY tx_sequence_number")
+        } else {
+            query!(" ORDER BY tx_sequence_number DESC")
+        };
+
+        // Every transaction that is fetched contributes at least one event to the results, so this
+        // limit is enough to fetch a full page, and detect whether there is another page after it.
+        query += query!(" LIMIT {BigInt}", page.limit() as i64 + 2);
+
+        let mut c = pg_reader
+            .connect()
+            .await
+            .context("Failed to connect to database")?;
+
+        let mut tx_sequence_numbers: Vec<u64> = c
+            .results(query)
+            .await
+            .context("Failed to read from database")?
+            .into_iter()
+            .map(|r: TxSequenceNumber| r.tx_sequence_number as u64)
+            .collect();
+
+        if !page.is_from_front() {
+            tx_sequence_numbers.reverse();
+        }
+
+        let digests = pg_loader
+            .load_many(tx_sequence_numbers.iter().copied().map(TxDigestKey))
+            .await
+            .context("Failed to load transaction digests")?;
+
+        let transactions: HashMap<_, _> = try_join_all(digests.values().map(|stored| async {
+            let digest = TransactionDigest::try_from(stored.tx_digest.clone())
+                .context("Failed to deserialize transaction digest")?;
+
+            let contents = kv_loader
+                .load_one_transaction(digest)
+                .await
+                .context("Failed to fetch transaction contents")?
+                .map(Arc::new);
+
+            Ok::<_, RpcError<Error>>((stored.tx_sequence_number as u64, (digest, contents)))
+        }))
+        .await?
+        .into_iter()
+        .collect();
+
+        let mut results = vec![];
+        for tx_sequence_number in tx_sequence_numbers {
+            let Some((digest, Some(transaction))) = transactions.get(&tx_sequence_number) else {
+                continue;
+            };
+
+            for (ev_sequence_number, native) in transaction.events()?.into_iter().enumerate() {
+                let cursor = EventCursor {
+                    tx_sequence_number,
+                    ev_sequence_number: ev_sequence_number as u64,
+                };
+
+                if page.after().is_some_and(|a| cursor < **a)
+                    || page.before().is_some_and(|b| cursor > **b)
+                    || !filter.matches(&native)
+                {
+                    continue;
+                }
+
+                results.push((
+                    cursor,
+                    Event {
+                        scope: scope.clone(),
+                        transaction_digest: *digest,
+                        transaction: transaction.clone(),
+                        sequence_number: ev_sequence_number as u64,
+                        native,
+                    },
+                ));
+            }
+        }
+
+        let (prev, next, results) =
+            page.paginate_results(results, |(cursor, _)| BcsCursor::new(cursor.clone()));
+
+        conn.has_previous_page = prev;
+        conn.has_next_page = next;
+
+        for (cursor, (_, event)) in results {
+            conn.edges.push(Edge::new(cursor.encode_cursor(), event));
+        }
+
+        Ok(conn)
+    }
+}
+
+impl EventFilter {
+    /// Whether `event` is admitted by this filter. The event indices only identify the
+    /// transactions that emitted matching events, and this is used to pick out those events.
+    fn matches(&self, event: &NativeEvent) -> bool {
+        self.sender
+            .is_none_or(|s| NativeSuiAddress::from(s) == event.sender)
+            && self.emitting_module.as_ref().is_none_or(|m| {
+                m.matches(event.package_id.into(), event.transaction_module.as_str())
+            })
+            && self
+                .event_type
+                .as_ref()
+                .is_none_or(|t| t.matches(&event.type_))
+    }
+}
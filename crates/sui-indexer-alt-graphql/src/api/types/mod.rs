@@ -2,9 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod address;
+pub(crate) mod balance;
 pub(crate) mod checkpoint;
+pub(crate) mod coin;
+pub(crate) mod dynamic_field;
 pub(crate) mod epoch;
+pub(crate) mod event;
 pub(crate) mod move_package;
+pub(crate) mod move_value;
 pub(crate) mod object;
 pub(crate) mod object_change;
 pub(crate) mod protocol_configs;
@@ -0,0 +1,33 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::Object;
+use move_core_types::language_storage::TypeTag;
+
+use crate::api::scalars::base64::Base64;
+
+pub(crate) struct MoveValue {
+    pub(crate) type_: TypeTag,
+    pub(crate) native: Vec<u8>,
+}
+
+/// A Move value, represented by its type and its BCS serialization.
+#[Object]
+impl MoveValue {
+    /// The value's fully-qualified type, with addresses in their canonical (64 hex character) form.
+    #[graphql(name = "type")]
+    async fn type_(&self) -> String {
+        self.type_.to_canonical_string(/* with_prefix */ true)
+    }
+
+    /// The Base64-encoded BCS serialization of this value.
+    async fn bcs(&self) -> Base64 {
+        Base64::from(&self.native)
+    }
+}
+
+impl MoveValue {
+    pub(crate) fn new(type_: TypeTag, native: Vec<u8>) -> Self {
+        Self { type_, native }
+    }
+}
@@ -37,6 +37,7 @@ use crate::{
 
 use super::{
     address::{Address, AddressableImpl},
+    dynamic_field::{CDynamicField, DynamicField, DynamicFieldName},
     move_package::MovePackage,
     transaction::Transaction,
 };
@@ -185,6 +186,47 @@ impl Object {
         MovePackage::from_object(self, ctx).await
     }
 
+    /// Access a dynamic field, or dynamic object field, on this object by its name, as of the checkpoint being viewed.
+    ///
+    /// Returns `null` if no dynamic field with that name exists on this object.
+    async fn dynamic_field(
+        &self,
+        ctx: &Context<'_>,
+        name: DynamicFieldName,
+    ) -> Result<Option<DynamicField>, RpcError<Error>> {
+        DynamicField::by_name(
+            ctx,
+            self.super_.scope.clone(),
+            self.super_.address.into(),
+            name,
+        )
+        .await
+    }
+
+    /// Paginate the dynamic fields, and dynamic object fields, on this object, as of the checkpoint being viewed.
+    async fn dynamic_fields(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<CDynamicField>,
+        last: Option<u64>,
+        before: Option<CDynamicField>,
+    ) -> Result<Option<Connection<String, DynamicField>>, RpcError<Error>> {
+        let pagination: &PaginationConfig = ctx.data()?;
+        let limits = pagination.limits("Object", "dynamicFields");
+        let page = Page::from_params(limits, first, after, last, before)?;
+
+        Ok(Some(
+            DynamicField::paginate(
+                ctx,
+                self.super_.scope.clone(),
+                page,
+                self.super_.address.into(),
+            )
+            .await?,
+        ))
+    }
+
     /// Fetch the object with the same ID, at a different version, root version bound, or checkpoint.
     ///
     /// If no additional bound is provided, the latest version of this object is fetched at the latest checkpoint.
@@ -0,0 +1,373 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, ops::Range};
+
+use anyhow::Context as _;
+use async_graphql::{Context, Enum, InputObject};
+use diesel::{ExpressionMethods, QueryDsl};
+use sui_indexer_alt_reader::pg_reader::PgReader;
+use sui_indexer_alt_schema::{schema::cp_sequence_numbers, transactions::StoredKind};
+use sui_pg_db::query::Query;
+use sui_sql_macro::query;
+use sui_types::base_types::SuiAddress as NativeSuiAddress;
+
+use crate::{
+    api::scalars::{sui_address::SuiAddress, type_filter::FunctionFilter, uint53::UInt53},
+    error::RpcError,
+    intersect,
+    scope::Scope,
+};
+
+/// Filter for paginating transactions.
+///
+/// Filters are combined by intersecting their results: a transaction must satisfy every filter that is specified to be included.
+#[derive(InputObject, Default, Debug, Clone)]
+pub(crate) struct TransactionFilter {
+    /// Limit to transactions that were finalized strictly after this checkpoint.
+    pub(crate) after_checkpoint: Option<UInt53>,
+
+    /// Limit to transactions that were finalized in this checkpoint.
+    pub(crate) at_checkpoint: Option<UInt53>,
+
+    /// Limit to transactions that were finalized strictly before this checkpoint.
+    pub(crate) before_checkpoint: Option<UInt53>,
+
+    /// Limit to transactions that called into this package (e.g. `0x2`), module (e.g. `0x2::coin`), or function (e.g. `0x2::coin::value`).
+    pub(crate) function: Option<FunctionFilter>,
+
+    /// Limit to either system or programmable transactions.
+    pub(crate) kind: Option<TransactionKindInput>,
+
+    /// Limit to transactions that interacted with this address: as its sender, sponsor, or as the recipient of an object it created, mutated or transferred.
+    pub(crate) affected_address: Option<SuiAddress>,
+
+    /// Limit to transactions that created, read, modified, wrapped, unwrapped or deleted the object with this ID.
+    pub(crate) affected_object: Option<SuiAddress>,
+
+    /// Limit to transactions that were sent by this address.
+    pub(crate) sent_address: Option<SuiAddress>,
+}
+
+/// An input filter selecting for either system or programmable transactions.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TransactionKindInput {
+    /// A system transaction, such as a consensus commit prologue, or an end-of-epoch transaction.
+    SystemTx,
+
+    /// A user-submitted transaction, made up of a programmable transaction block.
+    ProgrammableTx,
+}
+
+impl TransactionFilter {
+    /// Try to create a filter whose results are the intersection of `self`'s results and `other`'s
+    /// results. This may not be possible if the resulting filter is inconsistent (guaranteed to
+    /// produce no results).
+    pub(crate) fn intersect(self, other: Self) -> Option<Self> {
+        use intersect::{by_eq, by_max, by_min, field};
+
+        let after_checkpoint = field(self.after_checkpoint, other.after_checkpoint, by_max)?;
+        let at_checkpoint = field(self.at_checkpoint, other.at_checkpoint, by_eq)?;
+        let before_checkpoint = field(self.before_checkpoint, other.before_checkpoint, by_min)?;
+
+        let cp_bounds = (
+            after_checkpoint.map(u64::from),
+            at_checkpoint.map(u64::from),
+            before_checkpoint.map(u64::from),
+        );
+
+        match cp_bounds {
+            // There are no checkpoints strictly before checkpoint 0
+            (_, _, Some(0)) => return None,
+
+            // If `before` is not at least two away from `after`, the interval is empty
+            (Some(a), _, Some(b)) if b.saturating_sub(a) <= 1 => return None,
+
+            // `at` must fall strictly within `after` and `before`
+            (Some(a), Some(c), _) if c <= a => return None,
+            (_, Some(c), Some(b)) if b <= c => return None,
+
+            _ => {}
+        }
+
+        Some(Self {
+            after_checkpoint,
+            at_checkpoint,
+            before_checkpoint,
+            function: field(self.function, other.function, FunctionFilter::intersect)?,
+            kind: field(self.kind, other.kind, by_eq)?,
+            affected_address: field(self.affected_address, other.affected_address, by_eq)?,
+            affected_object: field(self.affected_object, other.affected_object, by_eq)?,
+            sent_address: field(self.sent_address, other.sent_address, by_eq)?,
+        })
+    }
+
+    /// The range of transaction sequence numbers that the checkpoint bounds in this filter
+    /// correspond to, as of the checkpoint being viewed. Returns `None` if the range is empty.
+    ///
+    /// Bounds that refer to checkpoints whose transaction ranges have been pruned are treated as
+    /// starting at transaction 0 (everything before them has also been pruned).
+    pub(crate) async fn tx_bounds(
+        &self,
+        ctx: &Context<'_>,
+        scope: &Scope,
+    ) -> Result<Option<Range<u64>>, RpcError> {
+        use cp_sequence_numbers::dsl as c;
+
+        let tx_hi = scope.tx_hi(ctx).await?;
+        let checkpoint_viewed_at = scope.checkpoint_viewed_at();
+
+        // The checkpoints whose first transactions bound the range. Checkpoints after the one
+        // being viewed start at `tx_hi`, so there is no need to look them up.
+        let after_cp = self
+            .after_checkpoint
+            .map(|cp| u64::from(cp).saturating_add(1));
+        let at_cp = self.at_checkpoint.map(u64::from);
+        let at_next_cp = at_cp.map(|cp| cp.saturating_add(1));
+        let before_cp = self.before_checkpoint.map(u64::from);
+
+        let lookups: Vec<i64> = [after_cp, at_cp, at_next_cp, before_cp]
+            .into_iter()
+            .flatten()
+            .filter(|cp| *cp <= checkpoint_viewed_at)
+            .map(|cp| cp as i64)
+            .collect();
+
+        let tx_los: HashMap<u64, u64> = if lookups.is_empty() {
+            HashMap::new()
+        } else {
+            let pg_reader: &PgReader = ctx.data()?;
+            let mut conn = pg_reader
+                .connect()
+                .await
+                .context("Failed to connect to database")?;
+
+            let rows: Vec<(i64, i64)> = conn
+                .results(
+                    c::cp_sequence_numbers
+                        .select((c::cp_sequence_number, c::tx_lo))
+                        .filter(c::cp_sequence_number.eq_any(lookups)),
+                )
+                .await
+                .context("Failed to fetch checkpoint transaction bounds")?;
+
+            rows.into_iter()
+                .map(|(cp, tx)| (cp as u64, tx as u64))
+                .collect()
+        };
+
+        let tx_lo = |cp: u64| {
+            if cp > checkpoint_viewed_at {
+                tx_hi
+            } else {
+                tx_los.get(&cp).copied().unwrap_or(0)
+            }
+        };
+
+        let mut lo = 0;
+        let mut hi = tx_hi;
+
+        if let Some(cp) = after_cp {
+            lo = lo.max(tx_lo(cp));
+        }
+
+        if let (Some(cp), Some(next)) = (at_cp, at_next_cp) {
+            lo = lo.max(tx_lo(cp));
+            hi = hi.min(tx_lo(next));
+        }
+
+        if let Some(cp) = before_cp {
+            hi = hi.min(tx_lo(cp));
+        }
+
+        Ok((lo < hi).then_some(lo..hi))
+    }
+
+    /// A query selecting the sequence numbers of transactions in `tx_range` that match this
+    /// filter. Each filter that is backed by an index contributes a sub-query on its index, and
+    /// the results of these sub-queries are intersected. If there are no such filters, every
+    /// transaction in the range is selected.
+    pub(crate) fn query(&self, tx_range: Range<u64>) -> Query<'static> {
+        let sender = self
+            .sent_address
+            .map(|a| NativeSuiAddress::from(a).to_vec());
+        let lo = tx_range.start as i64;
+        let hi = tx_range.end as i64;
+
+        let bounded = |mut query: Query<'static>| {
+            query += query!(
+                " AND {BigInt} <= tx_sequence_number AND tx_sequence_number < {BigInt}",
+                lo,
+                hi,
+            );
+            query
+        };
+
+        let with_sender = |mut query: Query<'static>| {
+            if let Some(sender) = &sender {
+                query += query!(" AND sender = {Bytea}", sender.clone());
+            }
+            query
+        };
+
+        let mut subqueries = vec![];
+
+        if let Some(address) = self.affected_address {
+            subqueries.push(bounded(with_sender(query!(
+                "SELECT tx_sequence_number FROM tx_affected_addresses WHERE affected = {Bytea}",
+                NativeSuiAddress::from(address).to_vec(),
+            ))));
+        }
+
+        if let Some(object) = self.affected_object {
+            subqueries.push(bounded(with_sender(query!(
+                "SELECT tx_sequence_number FROM tx_affected_objects WHERE affected = {Bytea}",
+                NativeSuiAddress::from(object).to_vec(),
+            ))));
+        }
+
+        if let Some(function) = &self.function {
+            let mut query = query!(
+                "SELECT tx_sequence_number FROM tx_calls WHERE package = {Bytea}",
+                function.package().to_vec(),
+            );
+
+            if let Some(module) = function.module() {
+                query += query!(" AND module = {Text}", module.to_owned());
+            }
+
+            if let Some(name) = function.function() {
+                query += query!(" AND function = {Text}", name.to_owned());
+            }
+
+            subqueries.push(bounded(with_sender(query)));
+        }
+
+        // The sender is always one of the addresses affected by its transaction, so if none of the
+        // other indices are being used to apply the sender filter, it can be applied via
+        // `tx_affected_addresses`.
+        if let Some(sender) = sender.as_ref().filter(|_| subqueries.is_empty()) {
+            subqueries.push(bounded(query!(
+                "SELECT tx_sequence_number FROM tx_affected_addresses WHERE affected = {Bytea} AND sender = {Bytea}",
+                sender.clone(),
+                sender.clone(),
+            )));
+        }
+
+        if let Some(kind) = self.kind {
+            subqueries.push(bounded(query!(
+                "SELECT tx_sequence_number FROM tx_kinds WHERE tx_kind = {SmallInt}",
+                StoredKind::from(kind),
+            )));
+        }
+
+        let mut subqueries = subqueries.into_iter();
+        let Some(mut query) = subqueries.next() else {
+            return bounded(query!(
+                "SELECT tx_sequence_number FROM tx_digests WHERE TRUE"
+            ));
+        };
+
+        for subquery in subqueries {
+            query += query!(" INTERSECT {}", subquery);
+        }
+
+        query
+    }
+}
+
+impl From<TransactionKindInput> for StoredKind {
+    fn from(kind: TransactionKindInput) -> Self {
+        match kind {
+            TransactionKindInput::SystemTx => StoredKind::SystemTransaction,
+            TransactionKindInput::ProgrammableTx => StoredKind::ProgrammableTransaction,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn cp(after: Option<u64>, at: Option<u64>, before: Option<u64>) -> TransactionFilter {
+        TransactionFilter {
+            after_checkpoint: after.map(UInt53::from),
+            at_checkpoint: at.map(UInt53::from),
+            before_checkpoint: before.map(UInt53::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_intersect_checkpoint_bounds() {
+        let f = cp(Some(10), None, None)
+            .intersect(cp(Some(5), None, Some(20)))
+            .unwrap();
+        assert_eq!(f.after_checkpoint, Some(10.into()));
+        assert_eq!(f.before_checkpoint, Some(20.into()));
+
+        let f = cp(None, Some(15), None)
+            .intersect(cp(Some(10), None, Some(20)))
+            .unwrap();
+        assert_eq!(f.at_checkpoint, Some(15.into()));
+
+        assert!(cp(None, None, Some(0))
+            .intersect(cp(None, None, None))
+            .is_none());
+        assert!(cp(Some(10), None, None)
+            .intersect(cp(None, None, Some(11)))
+            .is_none());
+        assert!(cp(None, Some(10), None)
+            .intersect(cp(None, Some(11), None))
+            .is_none());
+        assert!(cp(None, Some(10), None)
+            .intersect(cp(Some(10), None, None))
+            .is_none());
+        assert!(cp(None, Some(10), None)
+            .intersect(cp(None, None, Some(10)))
+            .is_none());
+    }
+
+    #[test]
+    fn test_intersect_filters() {
+        let address = SuiAddress::from_str("0x42").unwrap();
+        let other = SuiAddress::from_str("0x43").unwrap();
+
+        let f = TransactionFilter {
+            sent_address: Some(address),
+            function: Some(FunctionFilter::from_str("0x2").unwrap()),
+            ..Default::default()
+        }
+        .intersect(TransactionFilter {
+            function: Some(FunctionFilter::from_str("0x2::coin").unwrap()),
+            kind: Some(TransactionKindInput::ProgrammableTx),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(f.sent_address, Some(address));
+        assert_eq!(
+            f.function,
+            Some(FunctionFilter::from_str("0x2::coin").unwrap())
+        );
+        assert_eq!(f.kind, Some(TransactionKindInput::ProgrammableTx));
+
+        let sent = |a| TransactionFilter {
+            sent_address: Some(a),
+            ..Default::default()
+        };
+
+        assert!(sent(address).intersect(sent(other)).is_none());
+
+        let kind = |k| TransactionFilter {
+            kind: Some(k),
+            ..Default::default()
+        };
+
+        assert!(kind(TransactionKindInput::SystemTx)
+            .intersect(kind(TransactionKindInput::ProgrammableTx))
+            .is_none());
+    }
+}
@@ -4,19 +4,28 @@
 use std::sync::Arc;
 
 use anyhow::Context as _;
-use async_graphql::{Context, Object};
+use async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    dataloader::DataLoader,
+    Context, Object,
+};
+use diesel::{sql_types::BigInt, QueryableByName};
 use fastcrypto::encoding::{Base58, Encoding};
-use sui_indexer_alt_reader::kv_loader::{
-    KvLoader, TransactionContents as NativeTransactionContents,
+use sui_indexer_alt_reader::{
+    kv_loader::{KvLoader, TransactionContents as NativeTransactionContents},
+    pg_reader::PgReader,
+    tx_digests::TxDigestKey,
 };
+use sui_sql_macro::query;
 use sui_types::{
     base_types::SuiAddress as NativeSuiAddress, digests::TransactionDigest,
     transaction::TransactionDataAPI,
 };
 
 use crate::{
-    api::scalars::{base64::Base64, digest::Digest},
+    api::scalars::{base64::Base64, cursor::JsonCursor, digest::Digest},
     error::RpcError,
+    pagination::Page,
     scope::Scope,
 };
 
@@ -25,6 +34,10 @@ use super::{
     transaction_effects::{EffectsContents, TransactionEffects},
 };
 
+use self::filter::TransactionFilter;
+
+pub(crate) mod filter;
+
 #[derive(Clone)]
 pub(crate) struct Transaction {
     pub(crate) digest: TransactionDigest,
@@ -37,6 +50,17 @@ pub(crate) struct TransactionContents {
     pub(crate) contents: Option<Arc<NativeTransactionContents>>,
 }
 
+/// Cursor for paginating transactions, by their position in the global order of transactions.
+/// The cursor is independent of any checkpoint bounds in the filter, so that it remains valid when
+/// those bounds change.
+pub(crate) type CTransaction = JsonCursor<u64>;
+
+#[derive(QueryableByName)]
+struct TxSequenceNumber {
+    #[diesel(sql_type = BigInt)]
+    tx_sequence_number: i64,
+}
+
 /// Description of a transaction, the unit of activity on Sui.
 #[Object]
 impl Transaction {
@@ -111,6 +135,92 @@ impl Transaction {
             contents,
         }))
     }
+
+    /// Paginate transactions that match `filter`, as of the checkpoint being viewed, in the order
+    /// they were finalized.
+    pub(crate) async fn paginate(
+        ctx: &Context<'_>,
+        scope: Scope,
+        page: Page<CTransaction>,
+        filter: TransactionFilter,
+    ) -> Result<Connection<String, Transaction>, RpcError> {
+        let mut conn = Connection::new(false, false);
+
+        let pg_reader: &PgReader = ctx.data()?;
+        let pg_loader: &Arc<DataLoader<PgReader>> = ctx.data()?;
+
+        let Some(mut tx_range) = filter.tx_bounds(ctx, &scope).await? else {
+            return Ok(conn);
+        };
+
+        // Cursor bounds are inclusive, so they can be folded into the transaction range.
+        if let Some(after) = page.after() {
+            tx_range.start = tx_range.start.max(**after);
+        }
+
+        if let Some(before) = page.before() {
+            tx_range.end = tx_range.end.min(before.saturating_add(1));
+        }
+
+        if tx_range.is_empty() {
+            return Ok(conn);
+        }
+
+        let mut query = query!(
+            "SELECT tx_sequence_number FROM ({}) t",
+            filter.query(tx_range)
+        );
+
+        query += if page.is_from_front() {
+            query!(" ORDER BY tx_sequence_number")
+        } else {
+            query!(" ORDER BY tx_sequence_number DESC")
+        };
+
+        query += query!(" LIMIT {BigInt}", page.limit() as i64 + 2);
+
+        let mut c = pg_reader
+            .connect()
+            .await
+            .context("Failed to connect to database")?;
+
+        let mut results: Vec<TxSequenceNumber> = c
+            .results(query)
+            .await
+            .context("Failed to read from database")?;
+
+        if !page.is_from_front() {
+            results.reverse();
+        }
+
+        let (prev, next, results) =
+            page.paginate_results(results, |t| JsonCursor::new(t.tx_sequence_number as u64));
+
+        conn.has_previous_page = prev;
+        conn.has_next_page = next;
+
+        let results: Vec<_> = results.collect();
+        let digests = pg_loader
+            .load_many(results.iter().map(|(cursor, _)| TxDigestKey(**cursor)))
+            .await
+            .context("Failed to load transaction digests")?;
+
+        for (cursor, _) in results {
+            let Some(stored) = digests.get(&TxDigestKey(*cursor)) else {
+                continue;
+            };
+
+            let digest = TransactionDigest::try_from(stored.tx_digest.clone())
+                .context("Failed to deserialize transaction digest")?;
+
+            conn.edges.push(Edge::new(
+                cursor.encode_cursor(),
+                Transaction::with_id(scope.clone(), digest),
+            ));
+        }
+
+        Ok(conn)
+    }
 }
 
 impl TransactionContents {
@@ -143,7 +253,10 @@ impl TransactionContents {
         };
 
         // Discard the loaded result if we are viewing it at a checkpoint before it existed.
-        if transaction.cp_sequence_number() > self.scope.checkpoint_viewed_at() {
+        if transaction
+            .cp_sequence_number()
+            .is_some_and(|cp| cp > self.scope.checkpoint_viewed_at())
+        {
             return Ok(self.clone());
         }
 
@@ -3,20 +3,31 @@
 
 use std::sync::Arc;
 
-use anyhow::Context as _;
+use anyhow::{anyhow, Context as _};
 use async_graphql::{
     connection::{Connection, Edge},
     Context, Object,
 };
-use fastcrypto::encoding::{Base58, Encoding};
+use fastcrypto::{
+    encoding::{Base58, Encoding},
+    traits::ToFromBytes,
+};
 use sui_indexer_alt_reader::kv_loader::{
     KvLoader, TransactionContents as NativeTransactionContents,
 };
-use sui_types::{digests::TransactionDigest, effects::TransactionEffectsAPI};
+use sui_rpc_api::Client as FullnodeClient;
+use sui_types::{
+    digests::TransactionDigest,
+    effects::{TransactionEffects as NativeTransactionEffects, TransactionEffectsAPI},
+    event::Event,
+    signature::GenericSignature,
+    transaction::{Transaction as NativeTransaction, TransactionData},
+};
+use tonic::Code;
 
 use crate::{
     api::scalars::{base64::Base64, cursor::JsonCursor, digest::Digest},
-    error::RpcError,
+    error::{bad_user_input, RpcError},
     pagination::{Page, PaginationConfig},
     scope::Scope,
 };
@@ -41,6 +52,18 @@ pub(crate) struct EffectsContents {
 
 type CObjectChange = JsonCursor<usize>;
 
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Invalid transaction data: {0}")]
+    BadTransaction(String),
+
+    #[error("Invalid signature at position {0}: {1}")]
+    BadSignature(usize, String),
+
+    #[error("Transaction rejected by fullnode: {0}")]
+    Rejected(String),
+}
+
 /// The results of executing a transaction.
 #[Object]
 impl TransactionEffects {
@@ -70,7 +93,7 @@ impl EffectsContents {
             return None;
         };
 
-        Checkpoint::with_sequence_number(self.scope.clone(), content.cp_sequence_number())
+        Checkpoint::with_sequence_number(self.scope.clone(), content.cp_sequence_number()?)
     }
 
     /// The Base64-encoded BCS serialization of these effects, as `TransactionEffects`.
@@ -147,6 +170,92 @@ impl TransactionEffects {
             contents,
         }))
     }
+
+    /// Execute a transaction on a fullnode, committing its effects on chain. `tx_data` is the BCS
+    /// serialization of the transaction's `TransactionData`, and `signatures` are the BCS
+    /// serializations of its signatures.
+    ///
+    /// Returns after the transaction has been finalized, with effects that are not yet associated
+    /// with a checkpoint.
+    pub(crate) async fn execute(
+        ctx: &Context<'_>,
+        scope: Scope,
+        tx_data: Base64,
+        signatures: Vec<Base64>,
+    ) -> Result<Self, RpcError<Error>> {
+        let client = fullnode_client(ctx)?;
+        let tx_data = transaction_data(tx_data)?;
+
+        let signatures = signatures
+            .into_iter()
+            .enumerate()
+            .map(|(i, Base64(bytes))| {
+                GenericSignature::from_bytes(&bytes)
+                    .map_err(|e| bad_user_input(Error::BadSignature(i, e.to_string())))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let transaction = NativeTransaction::from_generic_sig_data(tx_data.clone(), signatures);
+        let response = client
+            .execute_transaction(&transaction)
+            .await
+            .map_err(|s| from_status(s, "Failed to execute transaction"))?;
+
+        Ok(Self::executed(
+            scope,
+            tx_data,
+            transaction.tx_signatures().to_vec(),
+            response.effects,
+            response.events.map(|e| e.data),
+        ))
+    }
+
+    /// Simulate executing a transaction on a fullnode, without signatures and without committing
+    /// its effects on chain. `tx_data` is the BCS serialization of the transaction's
+    /// `TransactionData`.
+    pub(crate) async fn simulate(
+        ctx: &Context<'_>,
+        scope: Scope,
+        tx_data: Base64,
+    ) -> Result<Self, RpcError<Error>> {
+        let client = fullnode_client(ctx)?;
+        let tx_data = transaction_data(tx_data)?;
+
+        let response = client
+            .simulate_transaction(&tx_data)
+            .await
+            .map_err(|s| from_status(s, "Failed to simulate transaction"))?;
+
+        Ok(Self::executed(
+            scope,
+            tx_data,
+            vec![],
+            response.effects,
+            response.events.map(|e| e.data),
+        ))
+    }
+
+    /// Effects for a transaction that was just executed (or simulated) by a fullnode.
+    fn executed(
+        scope: Scope,
+        tx_data: TransactionData,
+        signatures: Vec<GenericSignature>,
+        effects: NativeTransactionEffects,
+        events: Option<Vec<Event>>,
+    ) -> Self {
+        Self {
+            digest: tx_data.digest(),
+            contents: EffectsContents {
+                scope,
+                contents: Some(Arc::new(NativeTransactionContents::ExecutedTransaction {
+                    effects: Box::new(effects),
+                    events,
+                    transaction_data: Box::new(tx_data),
+                    signatures,
+                })),
+            },
+        }
+    }
 }
 
 impl EffectsContents {
@@ -179,7 +288,10 @@ impl EffectsContents {
         };
 
         // Discard the loaded result if we are viewing it at a checkpoint before it existed.
-        if transaction.cp_sequence_number() > self.scope.checkpoint_viewed_at() {
+        if transaction
+            .cp_sequence_number()
+            .is_some_and(|cp| cp > self.scope.checkpoint_viewed_at())
+        {
             return Ok(self.clone());
         }
 
@@ -200,3 +312,27 @@ impl From<Transaction> for TransactionEffects {
         }
     }
 }
+
+/// The client for the fullnode that transactions are forwarded to for execution and simulation.
+fn fullnode_client<'c>(ctx: &'c Context<'_>) -> Result<&'c FullnodeClient, RpcError<Error>> {
+    let client: &Option<FullnodeClient> = ctx.data()?;
+    client
+        .as_ref()
+        .ok_or_else(|| anyhow!("Fullnode RPC is not configured").into())
+}
+
+fn transaction_data(Base64(bytes): Base64) -> Result<TransactionData, RpcError<Error>> {
+    bcs::from_bytes(&bytes).map_err(|e| bad_user_input(Error::BadTransaction(e.to_string())))
+}
+
+/// Errors that indicate a problem with the transaction itself are surfaced to the user, while all
+/// other errors are treated as internal errors.
+fn from_status(status: tonic::Status, context: &'static str) -> RpcError<Error> {
+    match status.code() {
+        Code::InvalidArgument | Code::FailedPrecondition | Code::NotFound | Code::OutOfRange => {
+            bad_user_input(Error::Rejected(status.message().to_owned()))
+        }
+
+        _ => anyhow::Error::new(status).context(context).into(),
+    }
+}
@@ -9,7 +9,7 @@ use sui_indexer_alt_reader::pg_reader::db::DbArgs;
 use sui_indexer_alt_reader::system_package_task::SystemPackageTaskArgs;
 use url::Url;
 
-use crate::RpcArgs;
+use crate::{FullnodeArgs, RpcArgs};
 
 #[derive(clap::Parser, Debug, Clone)]
 pub struct Args {
@@ -43,6 +43,9 @@ pub enum Command {
         #[command(flatten)]
         rpc_args: RpcArgs,
 
+        #[command(flatten)]
+        fullnode_args: FullnodeArgs,
+
         #[command(flatten)]
         system_package_task_args: SystemPackageTaskArgs,
 
@@ -27,3 +27,8 @@ pub(crate) fn by_max<T: Ord>(a: T, b: T) -> Option<T> {
 pub(crate) fn by_min<T: Ord>(a: T, b: T) -> Option<T> {
     Some(a.min(b))
 }
+
+/// Merge options by checking that they are equal. Two different values cannot be combined.
+pub(crate) fn by_eq<T: Eq>(a: T, b: T) -> Option<T> {
+    (a == b).then_some(a)
+}
@@ -6,8 +6,8 @@ use std::{any::Any, net::SocketAddr, sync::Arc};
 use anyhow::{self, Context};
 use api::types::{address::IAddressable, object::IObject};
 use async_graphql::{
-    extensions::ExtensionFactory, http::GraphiQLSource, EmptyMutation, EmptySubscription,
-    ObjectType, Schema, SchemaBuilder, SubscriptionType,
+    extensions::ExtensionFactory, http::GraphiQLSource, EmptySubscription, ObjectType, Schema,
+    SchemaBuilder, SubscriptionType,
 };
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::{
@@ -35,6 +35,7 @@ use sui_indexer_alt_reader::{
     pg_reader::PgReader,
 };
 use sui_package_resolver::Resolver;
+use sui_rpc_api::Client as FullnodeClient;
 use task::{
     chain_identifier,
     watermark::{WatermarkTask, WatermarksLock},
@@ -45,7 +46,7 @@ use tower_http::cors;
 use tracing::{error, info};
 use url::Url;
 
-use crate::api::query::Query;
+use crate::api::{mutation::Mutation, query::Query};
 use crate::extensions::logging::{Logging, Session};
 use crate::metrics::RpcMetrics;
 use crate::middleware::version::Version;
@@ -74,6 +75,15 @@ pub struct RpcArgs {
     pub no_ide: bool,
 }
 
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct FullnodeArgs {
+    /// gRPC URL of the fullnode that transactions are forwarded to, for execution and simulation.
+    /// If this is not provided, `Mutation.executeTransaction` and `Query.simulateTransaction`
+    /// will not work.
+    #[clap(long)]
+    pub fullnode_rpc_url: Option<Url>,
+}
+
 /// This type is responsible for the set-up and lifecycle of all services related to a GraphQL RPC
 /// service (the RPC service, online IDE, health checks etc). It is agnostic to the schema
 /// being served (which must be provided to `run`).
@@ -241,8 +251,8 @@ impl Default for RpcArgs {
 }
 
 /// The GraphQL schema this service will serve, without any extensions or context added.
-pub fn schema() -> SchemaBuilder<Query, EmptyMutation, EmptySubscription> {
-    Schema::build(Query::default(), EmptyMutation, EmptySubscription)
+pub fn schema() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
+    Schema::build(Query::default(), Mutation, EmptySubscription)
         .register_output_type::<IAddressable>()
         .register_output_type::<IObject>()
 }
@@ -257,6 +267,9 @@ pub fn schema() -> SchemaBuilder<Query, EmptyMutation, EmptySubscription> {
 /// provided, the `GOOGLE_APPLICATION_CREDENTIALS` environment variable must point to the
 /// credentials JSON file.
 ///
+/// Transaction execution and simulation are forwarded to the fullnode at
+/// `fullnode_args.fullnode_rpc_url`, over gRPC -- if it is `None`, those requests will not work.
+///
 /// `version` is the version string reported in response headers by the service as part of every
 /// request.
 ///
@@ -268,6 +281,7 @@ pub async fn start_rpc(
     db_args: DbArgs,
     bigtable_args: BigtableArgs,
     args: RpcArgs,
+    fullnode_args: FullnodeArgs,
     system_package_task_args: SystemPackageTaskArgs,
     version: &'static str,
     config: RpcConfig,
@@ -301,6 +315,12 @@ pub async fn start_rpc(
         None
     };
 
+    let fullnode_client = fullnode_args
+        .fullnode_rpc_url
+        .map(|url| FullnodeClient::new(url.as_str()))
+        .transpose()
+        .context("Failed to create fullnode gRPC client")?;
+
     let pg_loader = Arc::new(pg_reader.as_data_loader());
     let kv_loader = if let Some(reader) = bigtable_reader.as_ref() {
         KvLoader::new_with_bigtable(Arc::new(reader.as_data_loader()))
@@ -354,7 +374,8 @@ pub async fn start_rpc(
         .data(pg_reader)
         .data(pg_loader)
         .data(kv_loader)
-        .data(package_resolver);
+        .data(package_resolver)
+        .data(fullnode_client);
 
     let h_rpc = rpc.run().await?;
     let h_system_package_task = system_package_task.run();
@@ -371,7 +392,7 @@ pub async fn start_rpc(
 /// Handler for RPC requests (POST requests making GraphQL queries).
 async fn graphql(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    Extension(schema): Extension<Schema<Query, EmptyMutation, EmptySubscription>>,
+    Extension(schema): Extension<Schema<Query, Mutation, EmptySubscription>>,
     Extension(watermark): Extension<WatermarksLock>,
     TypedHeader(content_length): TypedHeader<ContentLength>,
     show_usage: Option<TypedHeader<ShowUsage>>,
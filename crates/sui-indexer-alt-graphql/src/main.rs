@@ -43,6 +43,7 @@ async fn main() -> anyhow::Result<()> {
             db_args,
             bigtable_args,
             rpc_args,
+            fullnode_args,
             system_package_task_args,
             metrics_args,
             config,
@@ -105,6 +106,7 @@ async fn main() -> anyhow::Result<()> {
                 db_args,
                 bigtable_args,
                 rpc_args,
+                fullnode_args,
                 system_package_task_args,
                 VERSION,
                 rpc_config,
@@ -3,7 +3,11 @@
 
 use std::sync::Arc;
 
+use anyhow::Context as _;
 use async_graphql::Context;
+use diesel::{ExpressionMethods, QueryDsl};
+use sui_indexer_alt_reader::pg_reader::PgReader;
+use sui_indexer_alt_schema::schema::cp_sequence_numbers;
 
 use crate::{error::RpcError, task::watermark::Watermarks};
 
@@ -41,4 +45,36 @@ impl Scope {
     pub(crate) fn checkpoint_viewed_at(&self) -> u64 {
         self.checkpoint_viewed_at
     }
+
+    /// The exclusive upperbound on the sequence numbers of transactions that are visible from the
+    /// checkpoint being viewed: the first transaction in the checkpoint after it.
+    pub(crate) async fn tx_hi<E: std::error::Error>(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<u64, RpcError<E>> {
+        use cp_sequence_numbers::dsl as c;
+
+        let watermark: &Arc<Watermarks> = ctx.data()?;
+        let high_watermark = watermark.high_watermark();
+        if self.checkpoint_viewed_at >= high_watermark.checkpoint() {
+            return Ok(high_watermark.transaction());
+        }
+
+        let pg_reader: &PgReader = ctx.data()?;
+        let mut conn = pg_reader
+            .connect()
+            .await
+            .context("Failed to connect to database")?;
+
+        let tx_lo: i64 = conn
+            .first(
+                c::cp_sequence_numbers
+                    .select(c::tx_lo)
+                    .filter(c::cp_sequence_number.eq(self.checkpoint_viewed_at as i64 + 1)),
+            )
+            .await
+            .context("Failed to fetch checkpoint transaction bounds")?;
+
+        Ok(tx_lo as u64)
+    }
 }
@@ -252,6 +252,11 @@ impl Watermark {
     pub(crate) fn checkpoint(&self) -> u64 {
         self.checkpoint as u64
     }
+
+    /// The exclusive upperbound on transaction sequence numbers covered by this watermark.
+    pub(crate) fn transaction(&self) -> u64 {
+        self.transaction as u64
+    }
 }
 
 impl WatermarkRow {
@@ -2,16 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Context as _;
-
+use diesel::{ExpressionMethods, QueryDsl};
+use futures::future;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use sui_json_rpc_types::Checkpoint;
+use sui_indexer_alt_schema::schema::cp_sequence_numbers;
+use sui_json_rpc_types::{Checkpoint, CheckpointPage};
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
 use sui_types::sui_serde::BigInt;
 
 use crate::{
     context::Context,
-    error::{invalid_params, InternalContext, RpcError},
+    error::{invalid_params, rpc_bail, InternalContext, RpcError},
 };
 
 use super::rpc_module::RpcModule;
@@ -26,6 +28,19 @@ trait CheckpointsApi {
         /// Checkpoint sequence number.
         seq: BigInt<u64>,
     ) -> RpcResult<Checkpoint>;
+
+    /// Return a paginated list of checkpoints.
+    #[method(name = "getCheckpoints")]
+    async fn get_checkpoints(
+        &self,
+        /// An optional paging cursor. If provided, the query will start from the next item after
+        /// the specified cursor. Defaults to start from the first item if not specified.
+        cursor: Option<BigInt<u64>>,
+        /// Maximum number of items returned per page.
+        limit: Option<usize>,
+        /// Query result ordering, defaulting to false (ascending order), oldest record first.
+        descending_order: bool,
+    ) -> RpcResult<CheckpointPage>;
 }
 
 pub(crate) struct Checkpoints(pub Context);
@@ -34,6 +49,9 @@ pub(crate) struct Checkpoints(pub Context);
 enum Error {
     #[error("Checkpoint {0} not found")]
     NotFound(u64),
+
+    #[error("Requested page size {requested} exceeds maximum {max}")]
+    ExceededMaxPageSize { requested: usize, max: usize },
 }
 
 #[async_trait::async_trait]
@@ -44,6 +62,20 @@ impl CheckpointsApiServer for Checkpoints {
             format!("Failed to fetch checkpoint at sequence number {seq:?}")
         })?)
     }
+
+    async fn get_checkpoints(
+        &self,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> RpcResult<CheckpointPage> {
+        let Self(ctx) = self;
+        Ok(
+            page_response(ctx, cursor.map(|c| *c), limit, descending_order)
+                .await
+                .internal_context("Failed to fetch checkpoints")?,
+        )
+    }
 }
 
 impl RpcModule for Checkpoints {
@@ -67,3 +99,76 @@ async fn response(ctx: &Context, seq: u64) -> Result<Checkpoint, RpcError<Error>
 
     Ok(Checkpoint::from((summary, contents, signature.signature)))
 }
+
+/// Load a page of checkpoints, starting after `cursor` (if provided), in the order requested. The
+/// sequence numbers of the checkpoints in the page are read from the database, and then their
+/// contents are loaded from the KV store.
+async fn page_response(
+    ctx: &Context,
+    cursor: Option<u64>,
+    limit: Option<usize>,
+    descending: bool,
+) -> Result<CheckpointPage, RpcError<Error>> {
+    use cp_sequence_numbers::dsl as c;
+
+    let config = &ctx.config().checkpoints;
+    let limit = limit.unwrap_or(config.default_page_size);
+    if limit > config.max_page_size {
+        return Err(invalid_params(Error::ExceededMaxPageSize {
+            requested: limit,
+            max: config.max_page_size,
+        }));
+    }
+
+    let mut query = c::cp_sequence_numbers
+        .select(c::cp_sequence_number)
+        .limit(limit as i64 + 1)
+        .into_boxed();
+
+    if descending {
+        query = query.order(c::cp_sequence_number.desc());
+        if let Some(cursor) = cursor {
+            query = query.filter(c::cp_sequence_number.lt(cursor as i64));
+        }
+    } else {
+        query = query.order(c::cp_sequence_number.asc());
+        if let Some(cursor) = cursor {
+            query = query.filter(c::cp_sequence_number.gt(cursor as i64));
+        }
+    }
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let mut seqs: Vec<i64> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch checkpoint sequence numbers")?;
+
+    let has_next_page = seqs.len() > limit;
+    seqs.truncate(limit);
+
+    let data = future::join_all(seqs.iter().map(|seq| response(ctx, *seq as u64)))
+        .await
+        .into_iter()
+        .zip(&seqs)
+        .map(|(r, seq)| {
+            // The checkpoint's sequence number was found in the database, so failing to find its
+            // contents is an internal error.
+            if let Err(RpcError::InvalidParams(e @ Error::NotFound(_))) = r {
+                rpc_bail!(e)
+            } else {
+                r.with_internal_context(|| format!("Failed to fetch checkpoint {seq}"))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(CheckpointPage {
+        data,
+        next_cursor: seqs.last().map(|seq| (*seq as u64).into()),
+        has_next_page,
+    })
+}
@@ -51,6 +51,18 @@ trait CoinsApi {
         limit: Option<usize>,
     ) -> RpcResult<PageResponse<Coin, String>>;
 
+    /// Return all Coin objects owned by an address, regardless of their coin type.
+    #[method(name = "getAllCoins")]
+    async fn get_all_coins(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional paging cursor
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<Coin, String>>;
+
     /// Return metadata (e.g., symbol, decimals) for a coin. Note that if the coin's metadata was
     /// wrapped in the transaction that published its marker type, or the latest version of the
     /// metadata object is wrapped or deleted, it will not be found.
@@ -132,33 +144,17 @@ impl CoinsApiServer for Coins {
         };
 
         let Self(ctx) = self;
-        let config = &ctx.config().coins;
-
-        let page: Page<Cursor> = Page::from_params::<Error>(
-            config.default_page_size,
-            config.max_page_size,
-            cursor,
-            limit,
-            None,
-        )?;
-
-        // We get all the qualified coin ids first.
-        let coin_id_page = filter_coins(ctx, owner, Some(coin_type_tag), Some(page)).await?;
-
-        let coin_futures = coin_id_page.data.iter().map(|id| coin_response(ctx, *id));
+        Ok(coins_response(ctx, owner, Some(coin_type_tag), cursor, limit).await?)
+    }
 
-        let coins = future::join_all(coin_futures)
-            .await
-            .into_iter()
-            .zip(coin_id_page.data)
-            .map(|(r, id)| r.with_internal_context(|| format!("Failed to get object {id}")))
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(PageResponse {
-            data: coins,
-            next_cursor: coin_id_page.next_cursor,
-            has_next_page: coin_id_page.has_next_page,
-        })
+    async fn get_all_coins(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<Coin, String>> {
+        let Self(ctx) = self;
+        Ok(coins_response(ctx, owner, None, cursor, limit).await?)
     }
 
     async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<Option<SuiCoinMetadata>> {
@@ -215,6 +211,44 @@ impl RpcModule for DelegationCoins {
     }
 }
 
+/// Load a page of coins owned by `owner`, optionally restricted to coins of type `coin_type_tag`,
+/// and convert them into a JSON-RPC response.
+async fn coins_response(
+    ctx: &Context,
+    owner: SuiAddress,
+    coin_type_tag: Option<TypeTag>,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<PageResponse<Coin, String>, RpcError<Error>> {
+    let config = &ctx.config().coins;
+
+    let page: Page<Cursor> = Page::from_params::<Error>(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        None,
+    )?;
+
+    // We get all the qualified coin ids first.
+    let coin_id_page = filter_coins(ctx, owner, coin_type_tag, Some(page)).await?;
+
+    let coin_futures = coin_id_page.data.iter().map(|id| coin_response(ctx, *id));
+
+    let coins = future::join_all(coin_futures)
+        .await
+        .into_iter()
+        .zip(coin_id_page.data)
+        .map(|(r, id)| r.with_internal_context(|| format!("Failed to get object {id}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PageResponse {
+        data: coins,
+        next_cursor: coin_id_page.next_cursor,
+        has_next_page: coin_id_page.has_next_page,
+    })
+}
+
 async fn filter_coins(
     ctx: &Context,
     owner: SuiAddress,
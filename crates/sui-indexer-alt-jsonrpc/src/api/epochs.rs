@@ -0,0 +1,298 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use diesel::{ExpressionMethods, QueryDsl};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sui_indexer_alt_reader::epochs::{EpochEndKey, EpochStartKey};
+use sui_indexer_alt_schema::{
+    epochs::{StoredEpochEnd, StoredEpochStart},
+    schema::{kv_epoch_starts, kv_genesis},
+};
+use sui_json_rpc_types::{EndOfEpochInfo, EpochInfo, EpochPage, ProtocolConfigResponse};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_types::{
+    digests::{ChainIdentifier, CheckpointDigest},
+    sui_serde::BigInt,
+    sui_system_state::{SuiSystemState, SuiSystemStateTrait},
+};
+
+use crate::{
+    context::Context,
+    error::{invalid_params, InternalContext, RpcError},
+};
+
+use super::rpc_module::RpcModule;
+
+#[open_rpc(namespace = "suix", tag = "Epochs API")]
+#[rpc(server, namespace = "suix")]
+trait EpochsApi {
+    /// Return a paginated list of epochs.
+    #[method(name = "getEpochs")]
+    async fn get_epochs(
+        &self,
+        /// Optional paging cursor.
+        cursor: Option<BigInt<u64>>,
+        /// Maximum number of items per page.
+        limit: Option<usize>,
+        /// Flag to return results in descending order.
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochPage>;
+}
+
+#[open_rpc(namespace = "sui", tag = "Protocol Config API")]
+#[rpc(server, namespace = "sui")]
+trait ProtocolConfigApi {
+    /// Return the protocol config table for the given version number. If the version number is
+    /// not specified, the version of the latest epoch is used.
+    #[method(name = "getProtocolConfig")]
+    async fn get_protocol_config(
+        &self,
+        /// An optional protocol version specifier. If omitted, the latest protocol config table
+        /// will be returned.
+        version: Option<BigInt<u64>>,
+    ) -> RpcResult<ProtocolConfigResponse>;
+}
+
+pub(crate) struct Epochs(pub Context);
+
+pub(crate) struct ProtocolConfigs(pub Context);
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Requested page size {requested} exceeds maximum {max}")]
+    ExceededMaxPageSize { requested: usize, max: usize },
+
+    #[error("Protocol version {0} is not supported")]
+    UnsupportedProtocolVersion(u64),
+}
+
+#[async_trait::async_trait]
+impl EpochsApiServer for Epochs {
+    async fn get_epochs(
+        &self,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochPage> {
+        let Self(ctx) = self;
+        Ok(epochs_response(
+            ctx,
+            cursor.map(|c| *c),
+            limit,
+            descending_order.unwrap_or(false),
+        )
+        .await
+        .internal_context("Failed to fetch epochs")?)
+    }
+}
+
+#[async_trait::async_trait]
+impl ProtocolConfigApiServer for ProtocolConfigs {
+    async fn get_protocol_config(
+        &self,
+        version: Option<BigInt<u64>>,
+    ) -> RpcResult<ProtocolConfigResponse> {
+        let Self(ctx) = self;
+        Ok(protocol_config_response(ctx, version.map(|v| *v))
+            .await
+            .internal_context("Failed to fetch protocol config")?)
+    }
+}
+
+impl RpcModule for Epochs {
+    fn schema(&self) -> Module {
+        EpochsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl RpcModule for ProtocolConfigs {
+    fn schema(&self) -> Module {
+        ProtocolConfigApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Load a page of epochs, starting after `cursor` (if provided), in the order requested.
+async fn epochs_response(
+    ctx: &Context,
+    cursor: Option<u64>,
+    limit: Option<usize>,
+    descending: bool,
+) -> Result<EpochPage, RpcError<Error>> {
+    use kv_epoch_starts::dsl as s;
+
+    let config = &ctx.config().epochs;
+    let limit = limit.unwrap_or(config.default_page_size);
+    if limit > config.max_page_size {
+        return Err(invalid_params(Error::ExceededMaxPageSize {
+            requested: limit,
+            max: config.max_page_size,
+        }));
+    }
+
+    let mut query = s::kv_epoch_starts.limit(limit as i64 + 1).into_boxed();
+
+    if descending {
+        query = query.order(s::epoch.desc());
+        if let Some(cursor) = cursor {
+            query = query.filter(s::epoch.lt(cursor as i64));
+        }
+    } else {
+        query = query.order(s::epoch.asc());
+        if let Some(cursor) = cursor {
+            query = query.filter(s::epoch.gt(cursor as i64));
+        }
+    }
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let mut starts: Vec<StoredEpochStart> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch epoch starts")?;
+
+    let has_next_page = starts.len() > limit;
+    starts.truncate(limit);
+
+    // Each epoch's information is completed by the end of that epoch and the previous one (to
+    // count its transactions), and the start of the next one (which records the protocol version
+    // and reference gas price the epoch ended with).
+    let epochs = starts.iter().map(|s| s.epoch as u64);
+    let end_keys = epochs
+        .clone()
+        .flat_map(|e| [e.checked_sub(1), Some(e)])
+        .flatten()
+        .map(EpochEndKey);
+    let start_keys = epochs.map(|e| EpochStartKey(e + 1));
+
+    let (ends, next_starts) = futures::join!(
+        ctx.pg_loader().load_many(end_keys),
+        ctx.pg_loader().load_many(start_keys),
+    );
+
+    let ends = ends.context("Failed to load epoch ends")?;
+    let next_starts = next_starts.context("Failed to load epoch starts")?;
+
+    let mut data = Vec::with_capacity(starts.len());
+    for start in starts {
+        let epoch = start.epoch as u64;
+        let prev_end = epoch.checked_sub(1).and_then(|e| ends.get(&EpochEndKey(e)));
+        let end = ends.get(&EpochEndKey(epoch));
+        let next_start = next_starts.get(&EpochStartKey(epoch + 1));
+        data.push(epoch_info(start, prev_end, end, next_start)?);
+    }
+
+    Ok(EpochPage {
+        next_cursor: data.last().map(|e| e.epoch.into()),
+        data,
+        has_next_page,
+    })
+}
+
+/// Combine information about the start and end of an epoch into a JSON-RPC response.
+fn epoch_info(
+    start: StoredEpochStart,
+    prev_end: Option<&StoredEpochEnd>,
+    end: Option<&StoredEpochEnd>,
+    next_start: Option<&StoredEpochStart>,
+) -> Result<EpochInfo, RpcError<Error>> {
+    let epoch = start.epoch as u64;
+
+    let system_state: SuiSystemState = bcs::from_bytes(&start.system_state)
+        .with_context(|| format!("Failed to deserialize system state for epoch {epoch}"))?;
+
+    let tx_lo = prev_end.map_or(0, |e| e.tx_hi as u64);
+    let epoch_total_transactions = end.map_or(0, |e| (e.tx_hi as u64).saturating_sub(tx_lo));
+
+    let end_of_epoch_info = match (end, next_start) {
+        (Some(end), Some(next)) => Some(EndOfEpochInfo {
+            last_checkpoint_id: (end.cp_hi as u64).saturating_sub(1),
+            epoch_end_timestamp: end.end_timestamp_ms as u64,
+            protocol_version: next.protocol_version as u64,
+            reference_gas_price: next.reference_gas_price as u64,
+            total_stake: end.total_stake.unwrap_or_default() as u64,
+            storage_fund_reinvestment: end.storage_fund_reinvestment.unwrap_or_default() as u64,
+            storage_charge: end.storage_charge.unwrap_or_default() as u64,
+            storage_rebate: end.storage_rebate.unwrap_or_default() as u64,
+            storage_fund_balance: end.storage_fund_balance.unwrap_or_default() as u64,
+            stake_subsidy_amount: end.stake_subsidy_amount.unwrap_or_default() as u64,
+            total_gas_fees: end.total_gas_fees.unwrap_or_default() as u64,
+            total_stake_rewards_distributed: end.total_stake_rewards_distributed.unwrap_or_default()
+                as u64,
+            leftover_storage_fund_inflow: end.leftover_storage_fund_inflow.unwrap_or_default()
+                as u64,
+        }),
+        _ => None,
+    };
+
+    Ok(EpochInfo {
+        epoch,
+        validators: system_state
+            .into_sui_system_state_summary()
+            .active_validators,
+        epoch_total_transactions,
+        first_checkpoint_id: start.cp_lo as u64,
+        epoch_start_timestamp: start.start_timestamp_ms as u64,
+        end_of_epoch_info,
+        reference_gas_price: Some(start.reference_gas_price as u64),
+    })
+}
+
+/// Load the protocol config for `version` (or the latest epoch's protocol version, if none is
+/// provided), for the chain this RPC is serving.
+async fn protocol_config_response(
+    ctx: &Context,
+    version: Option<u64>,
+) -> Result<ProtocolConfigResponse, RpcError<Error>> {
+    use kv_epoch_starts::dsl as s;
+    use kv_genesis::dsl as g;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let digest: Vec<u8> = conn
+        .first(g::kv_genesis.select(g::genesis_digest))
+        .await
+        .context("Failed to fetch genesis digest")?;
+
+    let digest =
+        CheckpointDigest::try_from(digest).context("Failed to deserialize genesis digest")?;
+
+    let version = if let Some(version) = version {
+        version
+    } else {
+        let latest: i64 = conn
+            .first(
+                s::kv_epoch_starts
+                    .select(s::protocol_version)
+                    .order(s::epoch.desc()),
+            )
+            .await
+            .context("Failed to fetch latest protocol version")?;
+
+        latest as u64
+    };
+
+    let chain = ChainIdentifier::from(digest).chain();
+    let config = ProtocolConfig::get_for_version_if_supported(ProtocolVersion::new(version), chain)
+        .ok_or_else(|| invalid_params(Error::UnsupportedProtocolVersion(version)))?;
+
+    Ok(config.into())
+}
@@ -0,0 +1,16 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::digests::TransactionDigest;
+
+#[derive(thiserror::Error, Debug)]
+pub(super) enum Error {
+    #[error("Transaction {0} not found")]
+    NotFound(TransactionDigest),
+
+    #[error("EventFilter::{0} is not supported")]
+    UnsupportedFilter(&'static str),
+
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+}
@@ -0,0 +1,492 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use diesel::{
+    expression::{
+        is_aggregate::{Never, No},
+        MixedAggregates, ValidGrouping,
+    },
+    pg::Pg,
+    query_builder::{BoxedSelectStatement, FromClause, QueryFragment},
+    sql_types::BigInt as SqlBigInt,
+    AppearsOnTable, Column, Expression, ExpressionMethods, QueryDsl, QuerySource,
+};
+use futures::future;
+use move_core_types::{identifier::Identifier, language_storage::StructTag};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sui_indexer_alt_reader::tx_digests::TxDigestKey;
+use sui_indexer_alt_schema::schema::{ev_emit_mod, ev_struct_inst, tx_digests};
+use sui_json_rpc_types::{Page as PageResponse, SuiEvent};
+use sui_sql_macro::sql;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    digests::TransactionDigest,
+    event::{Event, EventID},
+    sui_serde::{BigInt, SuiStructTag},
+};
+
+use crate::{
+    context::Context,
+    error::{invalid_params, RpcError},
+    paginate::{JsonCursor, Page},
+};
+
+use super::{error::Error, response::event_response};
+
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+pub(crate) enum EventFilter {
+    /// Return all events.
+    All([Box<EventFilter>; 0]),
+    /// Return events that match any of the given filters. Not supported: filters cannot be
+    /// combined when querying events.
+    Any(Vec<EventFilter>),
+    /// Return events that match both filters. Not supported: filters cannot be combined when
+    /// querying events.
+    And(Box<EventFilter>, Box<EventFilter>),
+    /// Return events that match either filter. Not supported: filters cannot be combined when
+    /// querying events.
+    Or(Box<EventFilter>, Box<EventFilter>),
+    /// Query by sender address.
+    Sender(SuiAddress),
+    /// Return events emitted by the given transaction.
+    Transaction(TransactionDigest),
+    /// Return events emitted by a transaction calling into the given package.
+    Package(ObjectID),
+    /// Return events emitted in a specified Move module. If the event is defined in Module A but
+    /// emitted in a tx with Module B, query `MoveModule` by module B returns the event.
+    MoveModule {
+        /// The Move package ID.
+        package: ObjectID,
+        /// The module name.
+        #[schemars(with = "String")]
+        #[serde_as(as = "DisplayFromStr")]
+        module: Identifier,
+    },
+    /// Return events with the given Move event struct name (struct tag). If the struct tag has no
+    /// type parameters, events of any instantiation of that type are returned.
+    MoveEventType(
+        #[schemars(with = "String")]
+        #[serde_as(as = "SuiStructTag")]
+        StructTag,
+    ),
+    /// Return events with the given Move module name where the event struct is defined. If the
+    /// event is defined in Module A but emitted in a tx with Module B, query `MoveEventModule` by
+    /// module A returns the event.
+    MoveEventModule {
+        /// The Move package ID.
+        package: ObjectID,
+        /// The module name.
+        #[schemars(with = "String")]
+        #[serde_as(as = "DisplayFromStr")]
+        module: Identifier,
+    },
+    /// Return events with a field at `path` whose value is `value`. Not supported: event contents
+    /// are not indexed.
+    MoveEventField {
+        /// The path to the field, e.g. `/name`.
+        path: String,
+        /// The value the field must have.
+        value: serde_json::Value,
+    },
+    /// Return events emitted in the [start_time, end_time) interval, in milliseconds since the
+    /// Unix epoch. Not supported: events are not indexed by time.
+    #[serde(rename_all = "camelCase")]
+    TimeRange {
+        /// The start of the interval (inclusive).
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        start_time: u64,
+        /// The end of the interval (exclusive).
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        end_time: u64,
+    },
+}
+
+/// Events are identified by the sequence number of the transaction that emitted them, and their
+/// position within that transaction.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct EventCursor {
+    tx: u64,
+    ev: u64,
+}
+
+type Cursor = JsonCursor<EventCursor>;
+type Events = PageResponse<SuiEvent, EventID>;
+
+impl EventFilter {
+    /// Whether `event` is admitted by this filter. The event indices only identify the
+    /// transactions that emitted matching events, and this is used to pick out those events.
+    fn matches(&self, event: &Event) -> bool {
+        use EventFilter as F;
+        match self {
+            F::All(_) | F::Transaction(_) => true,
+
+            F::Sender(sender) => event.sender == *sender,
+
+            F::Package(package) => event.package_id == *package,
+
+            F::MoveModule { package, module } => {
+                event.package_id == *package && event.transaction_module == *module
+            }
+
+            F::MoveEventType(tag) if tag.type_params.is_empty() => {
+                event.type_.address == tag.address
+                    && event.type_.module == tag.module
+                    && event.type_.name == tag.name
+            }
+
+            F::MoveEventType(tag) => event.type_ == *tag,
+
+            F::MoveEventModule { package, module } => {
+                ObjectID::from(event.type_.address) == *package && event.type_.module == *module
+            }
+
+            // These filters are rejected before any events are fetched.
+            F::Any(_)
+            | F::And(_, _)
+            | F::Or(_, _)
+            | F::MoveEventField { .. }
+            | F::TimeRange { .. } => false,
+        }
+    }
+}
+
+/// Fetch a page of events that satisfy the given `filter` and pagination parameters. Returns the
+/// events and the ID of the last result (if there are any results).
+///
+/// The event indices are used to find the transactions that emitted matching events, in order,
+/// and then the events themselves are read from those transactions.
+pub(super) async fn events(
+    ctx: &Context,
+    filter: &EventFilter,
+    cursor: Option<EventID>,
+    limit: Option<usize>,
+    descending_order: Option<bool>,
+) -> Result<Events, RpcError<Error>> {
+    let config = &ctx.config().events;
+    let mut page: Page<Cursor> = Page::from_params(
+        config.default_page_size,
+        config.max_page_size,
+        None,
+        limit,
+        descending_order,
+    )?;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    // Event IDs identify the transaction by its digest, but the indices order transactions by
+    // their sequence numbers.
+    if let Some(EventID {
+        tx_digest,
+        event_seq,
+    }) = cursor
+    {
+        use tx_digests::dsl as d;
+        let query = d::tx_digests
+            .select(d::tx_sequence_number)
+            .filter(d::tx_digest.eq(tx_digest.inner().to_vec()))
+            .limit(1);
+
+        let tx: Vec<i64> = conn
+            .results(query)
+            .await
+            .context("Failed to fetch cursor's transaction sequence number")?;
+
+        let Some(tx) = tx.first() else {
+            return Err(invalid_params(Error::NotFound(tx_digest)));
+        };
+
+        page.cursor = Some(JsonCursor(EventCursor {
+            tx: *tx as u64,
+            ev: event_seq,
+        }));
+    }
+
+    use EventFilter as F;
+    let tx_sequence_numbers: Vec<i64> = match filter {
+        F::Any(_) => return Err(invalid_params(Error::UnsupportedFilter("Any"))),
+        F::And(_, _) => return Err(invalid_params(Error::UnsupportedFilter("And"))),
+        F::Or(_, _) => return Err(invalid_params(Error::UnsupportedFilter("Or"))),
+        F::MoveEventField { .. } => {
+            return Err(invalid_params(Error::UnsupportedFilter("MoveEventField")))
+        }
+        F::TimeRange { .. } => return Err(invalid_params(Error::UnsupportedFilter("TimeRange"))),
+
+        F::All(_) => {
+            use ev_struct_inst::dsl as e;
+            let query = e::ev_struct_inst
+                .select(e::tx_sequence_number)
+                .distinct()
+                .into_boxed();
+
+            conn.results(paginate(
+                &page,
+                "ev_struct_inst",
+                e::tx_sequence_number,
+                query,
+            ))
+            .await
+            .context("Failed to fetch transaction sequence numbers")?
+        }
+
+        F::Sender(sender) => {
+            use ev_struct_inst::dsl as e;
+            let query = e::ev_struct_inst
+                .select(e::tx_sequence_number)
+                .filter(e::sender.eq(sender.to_vec()))
+                .distinct()
+                .into_boxed();
+
+            conn.results(paginate(
+                &page,
+                "ev_struct_inst",
+                e::tx_sequence_number,
+                query,
+            ))
+            .await
+            .context("Failed to fetch transaction sequence numbers")?
+        }
+
+        F::Transaction(digest) => {
+            use tx_digests::dsl as d;
+            let query = d::tx_digests
+                .select(d::tx_sequence_number)
+                .filter(d::tx_digest.eq(digest.inner().to_vec()))
+                .into_boxed();
+
+            conn.results(paginate(&page, "tx_digests", d::tx_sequence_number, query))
+                .await
+                .context("Failed to fetch transaction sequence numbers")?
+        }
+
+        F::Package(package) => {
+            use ev_emit_mod::dsl as e;
+            let query = e::ev_emit_mod
+                .select(e::tx_sequence_number)
+                .filter(e::package.eq(package.to_vec()))
+                .distinct()
+                .into_boxed();
+
+            conn.results(paginate(&page, "ev_emit_mod", e::tx_sequence_number, query))
+                .await
+                .context("Failed to fetch transaction sequence numbers")?
+        }
+
+        F::MoveModule { package, module } => {
+            use ev_emit_mod::dsl as e;
+            let query = e::ev_emit_mod
+                .select(e::tx_sequence_number)
+                .filter(e::package.eq(package.to_vec()))
+                .filter(e::module.eq(module.to_string()))
+                .into_boxed();
+
+            conn.results(paginate(&page, "ev_emit_mod", e::tx_sequence_number, query))
+                .await
+                .context("Failed to fetch transaction sequence numbers")?
+        }
+
+        F::MoveEventType(tag) => {
+            use ev_struct_inst::dsl as e;
+            let mut query = e::ev_struct_inst
+                .select(e::tx_sequence_number)
+                .filter(e::package.eq(tag.address.to_vec()))
+                .filter(e::module.eq(tag.module.to_string()))
+                .filter(e::name.eq(tag.name.to_string()))
+                .distinct()
+                .into_boxed();
+
+            if !tag.type_params.is_empty() {
+                let instantiation = bcs::to_bytes(&tag.type_params)
+                    .context("Failed to serialize type parameters")?;
+                query = query.filter(e::instantiation.eq(instantiation));
+            }
+
+            conn.results(paginate(
+                &page,
+                "ev_struct_inst",
+                e::tx_sequence_number,
+                query,
+            ))
+            .await
+            .context("Failed to fetch transaction sequence numbers")?
+        }
+
+        F::MoveEventModule { package, module } => {
+            use ev_struct_inst::dsl as e;
+            let query = e::ev_struct_inst
+                .select(e::tx_sequence_number)
+                .filter(e::package.eq(package.to_vec()))
+                .filter(e::module.eq(module.to_string()))
+                .distinct()
+                .into_boxed();
+
+            conn.results(paginate(
+                &page,
+                "ev_struct_inst",
+                e::tx_sequence_number,
+                query,
+            ))
+            .await
+            .context("Failed to fetch transaction sequence numbers")?
+        }
+    };
+
+    from_sequence_numbers(ctx, filter, &page, tx_sequence_numbers).await
+}
+
+/// Modify `query` to fetch the sequence numbers of transactions that emitted events that could
+/// belong on this page. Only transactions at or beyond the one pointed to by the cursor are
+/// returned (that transaction may have more events to return), in the order requested, and
+/// respecting the reader low watermark of the `pipeline` being queried.
+///
+/// Every transaction that is fetched contributes at least one event to the results (apart from
+/// the transaction pointed to by the cursor), so fetching two more transactions than the limit is
+/// enough to fill a page and detect whether there is another page after it.
+fn paginate<'q, TX, ST, QS>(
+    page: &Page<Cursor>,
+    pipeline: &'static str,
+    tx_sequence_number: TX,
+    mut query: BoxedSelectStatement<'q, ST, FromClause<QS>, Pg>,
+) -> BoxedSelectStatement<'q, ST, FromClause<QS>, Pg>
+where
+    QS: QuerySource,
+    TX: Copy + Send + Sync + 'q,
+    TX: ValidGrouping<()> + QueryFragment<Pg>,
+    TX: Column<Table = QS> + AppearsOnTable<QS>,
+    TX: ExpressionMethods + Expression<SqlType = SqlBigInt>,
+    TX::IsAggregate: MixedAggregates<Never, Output = No>,
+{
+    query = query.filter(tx_sequence_number.ge(sql!(as SqlBigInt,
+        "COALESCE(
+            (
+                SELECT
+                    MAX(tx_lo)
+                FROM
+                    watermarks w
+                INNER JOIN
+                    cp_sequence_numbers c
+                ON
+                    w.reader_lo = c.cp_sequence_number
+                WHERE
+                    w.pipeline IN ({Text}, 'tx_digests')
+            ),
+            0
+        )",
+        pipeline,
+    )));
+
+    if let Some(JsonCursor(EventCursor { tx, .. })) = page.cursor {
+        if page.descending {
+            query = query.filter(tx_sequence_number.le(tx as i64));
+        } else {
+            query = query.filter(tx_sequence_number.ge(tx as i64));
+        }
+    }
+
+    if page.descending {
+        query = query.order(tx_sequence_number.desc());
+    } else {
+        query = query.order(tx_sequence_number.asc());
+    }
+
+    query.limit(page.limit + 2)
+}
+
+/// Load the transactions with the given sequence numbers, and gather the events they emitted that
+/// match `filter` and fall within `page`, in the order requested.
+async fn from_sequence_numbers(
+    ctx: &Context,
+    filter: &EventFilter,
+    page: &Page<Cursor>,
+    tx_sequence_numbers: Vec<i64>,
+) -> Result<Events, RpcError<Error>> {
+    let digests = ctx
+        .pg_loader()
+        .load_many(tx_sequence_numbers.iter().map(|tx| TxDigestKey(*tx as u64)))
+        .await
+        .context("Failed to load transaction digests")?;
+
+    let transactions: HashMap<_, _> = future::try_join_all(digests.values().map(|stored| async {
+        let digest = TransactionDigest::try_from(stored.tx_digest.clone())
+            .context("Failed to deserialize transaction digest")?;
+
+        let contents = ctx
+            .kv_loader()
+            .load_one_transaction(digest)
+            .await
+            .context("Failed to fetch transaction contents")?
+            .with_context(|| format!("Transaction {digest} not found"))?;
+
+        Ok::<_, RpcError<Error>>((stored.tx_sequence_number, (digest, contents)))
+    }))
+    .await?
+    .into_iter()
+    .collect();
+
+    let limit = page.limit as usize;
+    let mut results = vec![];
+    'outer: for tx in tx_sequence_numbers {
+        let (digest, contents) = transactions
+            .get(&tx)
+            .with_context(|| format!("Missing transaction digest for transaction {tx}"))?;
+
+        let mut events: Vec<_> = contents.events()?.into_iter().enumerate().collect();
+        if page.descending {
+            events.reverse();
+        }
+
+        for (ev, event) in events {
+            let cursor = EventCursor {
+                tx: tx as u64,
+                ev: ev as u64,
+            };
+            let in_page = match &page.cursor {
+                None => true,
+                Some(JsonCursor(c)) if page.descending => cursor < *c,
+                Some(JsonCursor(c)) => cursor > *c,
+            };
+
+            if !in_page || !filter.matches(&event) {
+                continue;
+            }
+
+            // Fetch one more event than the limit, to determine if there is a next page.
+            if results.len() > limit {
+                break 'outer;
+            }
+
+            results.push((cursor, *digest, contents.timestamp_ms(), event));
+        }
+    }
+
+    let has_next_page = results.len() > limit;
+    results.truncate(limit);
+
+    let next_cursor = results
+        .last()
+        .map(|(cursor, digest, _, _)| EventID::from((*digest, cursor.ev)));
+
+    let data = future::try_join_all(results.into_iter().map(
+        |(cursor, digest, timestamp_ms, event)| {
+            event_response(ctx, digest, cursor.ev, timestamp_ms, event)
+        },
+    ))
+    .await?;
+
+    Ok(PageResponse {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
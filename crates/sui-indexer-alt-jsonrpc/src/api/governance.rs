@@ -138,7 +138,7 @@ impl RpcModule for DelegationGovernance {
 }
 
 /// Load data and generate response for `getReferenceGasPrice`.
-async fn rgp_response(ctx: &Context) -> Result<BigInt<u64>, RpcError> {
+pub(crate) async fn rgp_response(ctx: &Context) -> Result<BigInt<u64>, RpcError> {
     use kv_epoch_starts::dsl as e;
 
     let mut conn = ctx
@@ -4,10 +4,13 @@
 pub(crate) mod checkpoints;
 pub(crate) mod coin;
 pub(crate) mod dynamic_fields;
+pub(crate) mod epochs;
+pub(crate) mod events;
 pub(crate) mod governance;
 pub(crate) mod move_utils;
 pub(crate) mod name_service;
 pub(crate) mod objects;
 pub(crate) mod rpc_module;
+pub(crate) mod transaction_builder;
 pub(crate) mod transactions;
 pub mod write;
@@ -1,8 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
+
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use sui_json_rpc_types::SuiMoveNormalizedFunction;
+use sui_json_rpc_types::{
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
+};
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::ObjectID;
@@ -17,6 +21,31 @@ mod response;
 #[open_rpc(namespace = "sui", tag = "Move APIs")]
 #[rpc(server, namespace = "sui")]
 trait MoveApi {
+    /// Return structured representations of all modules in the given package.
+    #[method(name = "getNormalizedMoveModulesByPackage")]
+    async fn get_normalized_move_modules_by_package(
+        &self,
+        package: ObjectID,
+    ) -> RpcResult<BTreeMap<String, SuiMoveNormalizedModule>>;
+
+    /// Return a structured representation of a Move module.
+    #[method(name = "getNormalizedMoveModule")]
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<SuiMoveNormalizedModule>;
+
+    /// Return a structured representation of a Move struct.
+    #[method(name = "getNormalizedMoveStruct")]
+    async fn get_normalized_move_struct(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        struct_name: String,
+    ) -> RpcResult<SuiMoveNormalizedStruct>;
+
+    /// Return a structured representation of a Move function.
     #[method(name = "getNormalizedMoveFunction")]
     async fn get_normalized_move_function(
         &self,
@@ -30,6 +59,33 @@ pub(crate) struct MoveUtils(pub Context);
 
 #[async_trait::async_trait]
 impl MoveApiServer for MoveUtils {
+    async fn get_normalized_move_modules_by_package(
+        &self,
+        package: ObjectID,
+    ) -> RpcResult<BTreeMap<String, SuiMoveNormalizedModule>> {
+        let Self(ctx) = self;
+        Ok(response::modules(ctx, package).await?)
+    }
+
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<SuiMoveNormalizedModule> {
+        let Self(ctx) = self;
+        Ok(response::module(ctx, package, &module_name).await?)
+    }
+
+    async fn get_normalized_move_struct(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        struct_name: String,
+    ) -> RpcResult<SuiMoveNormalizedStruct> {
+        let Self(ctx) = self;
+        Ok(response::struct_(ctx, package, &module_name, &struct_name).await?)
+    }
+
     async fn get_normalized_move_function(
         &self,
         package: ObjectID,
@@ -1,13 +1,20 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::{collections::BTreeMap, sync::Arc};
+
 use anyhow::anyhow;
-use move_binary_format::file_format::{Ability, AbilitySet, Visibility};
+use move_binary_format::{
+    file_format::{Ability, AbilitySet, Visibility},
+    normalized,
+};
 use sui_json_rpc_types::{
-    SuiMoveAbility, SuiMoveAbilitySet, SuiMoveNormalizedFunction, SuiMoveNormalizedType,
-    SuiMoveVisibility,
+    SuiMoveAbility, SuiMoveAbilitySet, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+    SuiMoveNormalizedStruct, SuiMoveNormalizedType, SuiMoveVisibility,
+};
+use sui_package_resolver::{
+    FunctionDef, OpenSignature, OpenSignatureBody, Package, PackageStore, Reference,
 };
-use sui_package_resolver::{FunctionDef, OpenSignature, OpenSignatureBody, Reference};
 use sui_types::{base_types::ObjectID, Identifier};
 
 use crate::{
@@ -17,6 +24,72 @@ use crate::{
 
 use super::error::Error;
 
+/// Load all the modules in a package, and convert them into JSON-RPC responses.
+pub(super) async fn modules(
+    ctx: &Context,
+    package: ObjectID,
+) -> Result<BTreeMap<String, SuiMoveNormalizedModule>, RpcError<Error>> {
+    let package = fetch_package(ctx, package).await?;
+
+    let pool = &mut normalized::RcPool::new();
+    Ok(package
+        .modules()
+        .iter()
+        .map(|(name, module)| {
+            let normalized =
+                normalized::Module::new(pool, module.bytecode(), /* include code */ false);
+            (name.clone(), SuiMoveNormalizedModule::from(&normalized))
+        })
+        .collect())
+}
+
+/// Load information about a module, and convert it into a JSON-RPC response.
+pub(super) async fn module(
+    ctx: &Context,
+    package: ObjectID,
+    module: &str,
+) -> Result<SuiMoveNormalizedModule, RpcError<Error>> {
+    use Error as E;
+
+    if !Identifier::is_valid(module) {
+        return Err(invalid_params(E::BadIdentifier(module.to_owned())));
+    }
+
+    let package = fetch_package(ctx, package).await?;
+    let module = package
+        .module(module)
+        .map_err(|e| invalid_params(E::NotFound(e)))?;
+
+    let pool = &mut normalized::RcPool::new();
+    let normalized =
+        normalized::Module::new(pool, module.bytecode(), /* include code */ false);
+    Ok(SuiMoveNormalizedModule::from(&normalized))
+}
+
+/// Load information about a struct, and convert it into a JSON-RPC response.
+pub(super) async fn struct_(
+    ctx: &Context,
+    package: ObjectID,
+    module: &str,
+    name: &str,
+) -> Result<SuiMoveNormalizedStruct, RpcError<Error>> {
+    use sui_package_resolver::error::Error as PRE;
+    use Error as E;
+
+    if !Identifier::is_valid(name) {
+        return Err(invalid_params(E::BadIdentifier(name.to_owned())));
+    }
+
+    let mut normalized = self::module(ctx, package, module).await?;
+    normalized.structs.remove(name).ok_or_else(|| {
+        invalid_params(E::NotFound(PRE::DatatypeNotFound(
+            *package,
+            module.to_owned(),
+            name.to_owned(),
+        )))
+    })
+}
+
 /// Load information about a function, and convert it into a JSON-RPC response.
 pub(super) async fn function(
     ctx: &Context,
@@ -77,6 +150,22 @@ pub(super) async fn function(
     Ok(normalized_function(&sig))
 }
 
+/// Fetch the package at `id` from the package resolver's store. Failing to find the package, or
+/// finding an object that is not a package, is a user error.
+async fn fetch_package(ctx: &Context, id: ObjectID) -> Result<Arc<Package>, RpcError<Error>> {
+    use sui_package_resolver::error::Error as PRE;
+    use Error as E;
+
+    ctx.package_resolver()
+        .package_store()
+        .fetch(*id)
+        .await
+        .map_err(|e| match &e {
+            PRE::NotAPackage(_) | PRE::PackageNotFound(_) => invalid_params(E::NotFound(e)),
+            _ => RpcError::from(anyhow!(e).context("Failed to fetch package")),
+        })
+}
+
 fn normalized_function(sig: &FunctionDef) -> SuiMoveNormalizedFunction {
     SuiMoveNormalizedFunction {
         visibility: visibility(sig.visibility),
@@ -29,7 +29,7 @@ use crate::{
 
 /// Fetch the necessary data from the stores in `ctx` and transform it to build a response for a
 /// the latest version of an object, identified by its ID, according to the response `options`.
-pub(super) async fn live_object(
+pub(crate) async fn live_object(
     ctx: &Context,
     object_id: ObjectID,
     options: &SuiObjectDataOptions,
@@ -0,0 +1,367 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{future::Future, sync::Arc};
+
+use anyhow::Context as _;
+use fastcrypto::encoding::Base64;
+use futures::future;
+use jsonrpsee::core::RpcResult;
+use move_core_types::language_storage::StructTag;
+use sui_json::SuiJsonValue;
+use sui_json_rpc_api::{TransactionBuilderOpenRpc, TransactionBuilderServer};
+use sui_json_rpc_types::{
+    Page, RPCTransactionRequestParams, SuiObjectDataOptions, SuiObjectResponse,
+    SuiTransactionBlockBuilderMode, SuiTypeTag, TransactionBlockBytes,
+};
+use sui_open_rpc::Module;
+use sui_transaction_builder::{DataReader, TransactionBuilder as Builder};
+use sui_types::{
+    base_types::{ObjectID, ObjectInfo, SuiAddress},
+    sui_serde::BigInt,
+    transaction::TransactionData,
+};
+
+use crate::{
+    context::Context,
+    data::load_live,
+    error::{invalid_params, RpcError},
+};
+
+use super::{
+    governance::rgp_response,
+    objects::{
+        filter::{owned_objects, SuiObjectDataFilter},
+        response::live_object,
+    },
+    rpc_module::RpcModule,
+};
+
+/// Serves the `unsafe_*` transaction building methods, using the same builder as the fullnode,
+/// but reading the objects and reference gas price it needs from the RPC's stores.
+pub(crate) struct TransactionBuilder(Builder);
+
+/// Adapts the RPC's context to the interface the transaction builder uses to read data.
+struct ContextDataReader(Context);
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Failed to build transaction: {0:#}")]
+    Build(anyhow::Error),
+}
+
+impl TransactionBuilder {
+    pub(crate) fn new(ctx: Context) -> Self {
+        Self(Builder::new(Arc::new(ContextDataReader(ctx))))
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionBuilderServer for TransactionBuilder {
+    async fn transfer_object(
+        &self,
+        signer: SuiAddress,
+        object_id: ObjectID,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+        recipient: SuiAddress,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(builder.transfer_object(signer, object_id, gas, *gas_budget, recipient)).await?)
+    }
+
+    async fn transfer_sui(
+        &self,
+        signer: SuiAddress,
+        sui_object_id: ObjectID,
+        gas_budget: BigInt<u64>,
+        recipient: SuiAddress,
+        amount: Option<BigInt<u64>>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(builder.transfer_sui(
+            signer,
+            sui_object_id,
+            *gas_budget,
+            recipient,
+            amount.map(|a| *a),
+        ))
+        .await?)
+    }
+
+    async fn pay(
+        &self,
+        signer: SuiAddress,
+        input_coins: Vec<ObjectID>,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<BigInt<u64>>,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(builder.pay(
+            signer,
+            input_coins,
+            recipients,
+            amounts.into_iter().map(|a| *a).collect(),
+            gas,
+            *gas_budget,
+        ))
+        .await?)
+    }
+
+    async fn pay_sui(
+        &self,
+        signer: SuiAddress,
+        input_coins: Vec<ObjectID>,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<BigInt<u64>>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(builder.pay_sui(
+            signer,
+            input_coins,
+            recipients,
+            amounts.into_iter().map(|a| *a).collect(),
+            *gas_budget,
+        ))
+        .await?)
+    }
+
+    async fn pay_all_sui(
+        &self,
+        signer: SuiAddress,
+        input_coins: Vec<ObjectID>,
+        recipient: SuiAddress,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(builder.pay_all_sui(signer, input_coins, recipient, *gas_budget)).await?)
+    }
+
+    async fn move_call(
+        &self,
+        signer: SuiAddress,
+        package_object_id: ObjectID,
+        module: String,
+        function: String,
+        type_arguments: Vec<SuiTypeTag>,
+        arguments: Vec<SuiJsonValue>,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+        _execution_mode: Option<SuiTransactionBlockBuilderMode>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(builder.move_call(
+            signer,
+            package_object_id,
+            &module,
+            &function,
+            type_arguments,
+            arguments,
+            gas,
+            *gas_budget,
+            None,
+        ))
+        .await?)
+    }
+
+    async fn publish(
+        &self,
+        sender: SuiAddress,
+        compiled_modules: Vec<Base64>,
+        dependencies: Vec<ObjectID>,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(async {
+            let compiled_modules = compiled_modules
+                .into_iter()
+                .map(|m| m.to_vec().map_err(|e| anyhow::anyhow!(e)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            builder
+                .publish(sender, compiled_modules, dependencies, gas, *gas_budget)
+                .await
+        })
+        .await?)
+    }
+
+    async fn split_coin(
+        &self,
+        signer: SuiAddress,
+        coin_object_id: ObjectID,
+        split_amounts: Vec<BigInt<u64>>,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(builder.split_coin(
+            signer,
+            coin_object_id,
+            split_amounts.into_iter().map(|a| *a).collect(),
+            gas,
+            *gas_budget,
+        ))
+        .await?)
+    }
+
+    async fn split_coin_equal(
+        &self,
+        signer: SuiAddress,
+        coin_object_id: ObjectID,
+        split_count: BigInt<u64>,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(
+            build(builder.split_coin_equal(signer, coin_object_id, *split_count, gas, *gas_budget))
+                .await?,
+        )
+    }
+
+    async fn merge_coin(
+        &self,
+        signer: SuiAddress,
+        primary_coin: ObjectID,
+        coin_to_merge: ObjectID,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(
+            build(builder.merge_coins(signer, primary_coin, coin_to_merge, gas, *gas_budget))
+                .await?,
+        )
+    }
+
+    async fn batch_transaction(
+        &self,
+        signer: SuiAddress,
+        single_transaction_params: Vec<RPCTransactionRequestParams>,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+        _txn_builder_mode: Option<SuiTransactionBlockBuilderMode>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(
+            build(builder.batch_transaction(signer, single_transaction_params, gas, *gas_budget))
+                .await?,
+        )
+    }
+
+    async fn request_add_stake(
+        &self,
+        signer: SuiAddress,
+        coins: Vec<ObjectID>,
+        amount: Option<BigInt<u64>>,
+        validator: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(builder.request_add_stake(
+            signer,
+            coins,
+            amount.map(|a| *a),
+            validator,
+            gas,
+            *gas_budget,
+        ))
+        .await?)
+    }
+
+    async fn request_withdraw_stake(
+        &self,
+        signer: SuiAddress,
+        staked_sui: ObjectID,
+        gas: Option<ObjectID>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<TransactionBlockBytes> {
+        let Self(builder) = self;
+        Ok(build(builder.request_withdraw_stake(signer, staked_sui, gas, *gas_budget)).await?)
+    }
+}
+
+#[async_trait::async_trait]
+impl DataReader for ContextDataReader {
+    /// Fetch all the live objects of type `object_type` owned by `address`. The builder uses this
+    /// to select gas and input coins, so (like the fullnode) it is not paginated.
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        object_type: StructTag,
+    ) -> Result<Vec<ObjectInfo>, anyhow::Error> {
+        let Self(ctx) = self;
+        let filter = Some(SuiObjectDataFilter::StructType(object_type));
+
+        let mut infos = vec![];
+        let mut cursor = None;
+        loop {
+            let Page {
+                data: object_ids,
+                next_cursor,
+                has_next_page,
+            } = owned_objects(ctx, address, &filter, cursor, None)
+                .await
+                .context("Failed to fetch owned objects")?;
+
+            let objects = future::try_join_all(object_ids.into_iter().map(|id| load_live(ctx, id)))
+                .await
+                .context("Failed to load owned objects")?;
+
+            infos.extend(objects.iter().flatten().map(ObjectInfo::from_object));
+
+            if !has_next_page {
+                break;
+            }
+
+            cursor = next_cursor;
+        }
+
+        Ok(infos)
+    }
+
+    async fn get_object_with_options(
+        &self,
+        object_id: ObjectID,
+        options: SuiObjectDataOptions,
+    ) -> Result<SuiObjectResponse, anyhow::Error> {
+        let Self(ctx) = self;
+        live_object(ctx, object_id, &options)
+            .await
+            .with_context(|| format!("Failed to fetch object {object_id}"))
+    }
+
+    async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
+        let Self(ctx) = self;
+        let rgp = rgp_response(ctx)
+            .await
+            .context("Failed to fetch reference gas price")?;
+
+        Ok(*rgp)
+    }
+}
+
+impl RpcModule for TransactionBuilder {
+    fn schema(&self) -> Module {
+        TransactionBuilderOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Wait for the builder to produce transaction data, and serialize it into a response. Failures
+/// from the builder are treated as user errors, because they are overwhelmingly caused by the
+/// request (e.g. a missing or unusable input object, or an insufficient gas budget).
+async fn build(
+    data: impl Future<Output = anyhow::Result<TransactionData>>,
+) -> Result<TransactionBlockBytes, RpcError<Error>> {
+    let data = data.await.map_err(|e| invalid_params(Error::Build(e)))?;
+    Ok(TransactionBlockBytes::from_data(data).context("Failed to serialize transaction data")?)
+}
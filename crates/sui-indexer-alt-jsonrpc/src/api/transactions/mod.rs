@@ -6,7 +6,7 @@ use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use sui_json_rpc_types::{Page, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
-use sui_types::digests::TransactionDigest;
+use sui_types::{digests::TransactionDigest, sui_serde::BigInt};
 
 use self::{error::Error, filter::SuiTransactionBlockResponseQuery};
 
@@ -33,6 +33,10 @@ trait TransactionsApi {
         /// Options controlling the output format.
         options: Option<SuiTransactionBlockResponseOptions>,
     ) -> RpcResult<SuiTransactionBlockResponse>;
+
+    /// Return the total number of transactions known to the RPC.
+    #[method(name = "getTotalTransactionBlocks")]
+    async fn get_total_transaction_blocks(&self) -> RpcResult<BigInt<u64>>;
 }
 
 #[open_rpc(namespace = "suix", tag = "Query Transactions API")]
@@ -81,6 +85,14 @@ impl TransactionsApiServer for Transactions {
                 .with_internal_context(|| format!("Failed to get transaction {digest}"))?,
         )
     }
+
+    async fn get_total_transaction_blocks(&self) -> RpcResult<BigInt<u64>> {
+        let Self(ctx) = self;
+        Ok(response::total_transactions(ctx)
+            .await
+            .internal_context("Failed to get total transactions")?
+            .into())
+    }
 }
 
 #[async_trait::async_trait]
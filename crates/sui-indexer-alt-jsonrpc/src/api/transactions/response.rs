@@ -4,17 +4,20 @@
 use std::str::FromStr;
 
 use anyhow::Context as _;
+use diesel::{ExpressionMethods, QueryDsl};
 use futures::future::OptionFuture;
-use move_core_types::annotated_value::{MoveDatatypeLayout, MoveTypeLayout};
 use sui_indexer_alt_reader::{
     kv_loader::TransactionContents, objects::VersionedObjectKey,
     tx_balance_changes::TxBalanceChangeKey,
 };
-use sui_indexer_alt_schema::transactions::{BalanceChange, StoredTxBalanceChange};
+use sui_indexer_alt_schema::{
+    schema::watermarks,
+    transactions::{BalanceChange, StoredTxBalanceChange},
+};
 use sui_json_rpc_types::{
-    BalanceChange as SuiBalanceChange, ObjectChange as SuiObjectChange, SuiEvent,
-    SuiTransactionBlock, SuiTransactionBlockData, SuiTransactionBlockEffects,
-    SuiTransactionBlockEvents, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    BalanceChange as SuiBalanceChange, ObjectChange as SuiObjectChange, SuiTransactionBlock,
+    SuiTransactionBlockData, SuiTransactionBlockEffects, SuiTransactionBlockEvents,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_types::{
     base_types::{ObjectID, SequenceNumber},
@@ -29,6 +32,7 @@ use sui_types::{
 use tokio::join;
 
 use crate::{
+    api::events::response::event_response,
     context::Context,
     error::{invalid_params, rpc_bail, RpcError},
 };
@@ -69,8 +73,8 @@ pub(super) async fn transaction(
 
     let mut response = SuiTransactionBlockResponse::new(digest);
 
-    response.timestamp_ms = Some(tx.timestamp_ms());
-    response.checkpoint = Some(tx.cp_sequence_number());
+    response.timestamp_ms = tx.timestamp_ms();
+    response.checkpoint = tx.cp_sequence_number();
 
     if options.show_input {
         response.transaction = Some(input(ctx, &tx).await?);
@@ -103,6 +107,29 @@ pub(super) async fn transaction(
     Ok(response)
 }
 
+/// Fetch the total number of transactions that the RPC knows about, based on the watermark for the
+/// `tx_digests` pipeline, which is used to look up transactions by their sequence number.
+pub(super) async fn total_transactions(ctx: &Context) -> Result<u64, RpcError<Error>> {
+    use watermarks::dsl as w;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?;
+
+    let query = w::watermarks
+        .select(w::tx_hi)
+        .filter(w::pipeline.eq("tx_digests"));
+
+    let tx_hi: i64 = conn
+        .first(query)
+        .await
+        .context("Failed to fetch transaction watermark")?;
+
+    Ok(tx_hi as u64)
+}
+
 /// Extract a representation of the transaction's input data from the stored form.
 async fn input(
     ctx: &Context,
@@ -137,29 +164,7 @@ async fn events(
     let mut sui_events = Vec::with_capacity(events.len());
 
     for (ix, event) in events.into_iter().enumerate() {
-        let layout = match ctx
-            .package_resolver()
-            .type_layout(event.type_.clone().into())
-            .await
-            .with_context(|| {
-                format!(
-                    "Failed to resolve layout for {}",
-                    event.type_.to_canonical_display(/* with_prefix */ true)
-                )
-            })? {
-            MoveTypeLayout::Struct(s) => MoveDatatypeLayout::Struct(s),
-            MoveTypeLayout::Enum(e) => MoveDatatypeLayout::Enum(e),
-            _ => rpc_bail!(
-                "Event {ix} is not a struct or enum: {}",
-                event.type_.to_canonical_string(/* with_prefix */ true)
-            ),
-        };
-
-        let sui_event =
-            SuiEvent::try_from(event, digest, ix as u64, Some(tx.timestamp_ms()), layout)
-                .with_context(|| format!("Failed to convert Event {ix} into response"))?;
-
-        sui_events.push(sui_event)
+        sui_events.push(event_response(ctx, digest, ix as u64, tx.timestamp_ms(), event).await?);
     }
 
     Ok(SuiTransactionBlockEvents { data: sui_events })
@@ -16,6 +16,15 @@ pub const CLIENT_SDK_TYPE_HEADER: &str = "client-sdk-type";
 
 #[derive(Debug)]
 pub struct RpcConfig {
+    /// Configuration for checkpoint-related RPC methods.
+    pub checkpoints: CheckpointsConfig,
+
+    /// Configuration for epoch-related RPC methods.
+    pub epochs: EpochsConfig,
+
+    /// Configuration for event-related RPC methods.
+    pub events: EventsConfig,
+
     /// Configuration for object-related RPC methods.
     pub objects: ObjectsConfig,
 
@@ -42,6 +51,9 @@ pub struct RpcConfig {
 #[DefaultConfig]
 #[derive(Clone, Default, Debug)]
 pub struct RpcLayer {
+    pub checkpoints: CheckpointsLayer,
+    pub epochs: EpochsLayer,
+    pub events: EventsLayer,
     pub objects: ObjectsLayer,
     pub dynamic_fields: DynamicFieldsLayer,
     pub transactions: TransactionsLayer,
@@ -54,6 +66,66 @@ pub struct RpcLayer {
     pub extra: toml::Table,
 }
 
+#[derive(Debug, Clone)]
+pub struct CheckpointsConfig {
+    /// The default page size limit when querying checkpoints, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying checkpoints. Requesting a page larger than
+    /// this is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct CheckpointsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+#[derive(Debug, Clone)]
+pub struct EpochsConfig {
+    /// The default page size limit when querying epochs, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying epochs. Requesting a page larger than
+    /// this is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct EpochsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventsConfig {
+    /// The default page size limit when querying events, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying events. Requesting a page larger than
+    /// this is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct EventsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
 #[derive(Debug, Clone)]
 pub struct ObjectsConfig {
     /// The maximum number of keys that can be queried in a single multi-get request.
@@ -223,6 +295,9 @@ impl RpcLayer {
     /// configure.
     pub fn example() -> Self {
         Self {
+            checkpoints: CheckpointsConfig::default().into(),
+            epochs: EpochsConfig::default().into(),
+            events: EventsConfig::default().into(),
             objects: ObjectsConfig::default().into(),
             dynamic_fields: DynamicFieldsConfig::default().into(),
             transactions: TransactionsConfig::default().into(),
@@ -237,6 +312,9 @@ impl RpcLayer {
     pub fn finish(mut self) -> RpcConfig {
         check_extra("top-level", mem::take(&mut self.extra));
         RpcConfig {
+            checkpoints: self.checkpoints.finish(CheckpointsConfig::default()),
+            epochs: self.epochs.finish(EpochsConfig::default()),
+            events: self.events.finish(EventsConfig::default()),
             objects: self.objects.finish(ObjectsConfig::default()),
             dynamic_fields: self.dynamic_fields.finish(DynamicFieldsConfig::default()),
             transactions: self.transactions.finish(TransactionsConfig::default()),
@@ -248,6 +326,36 @@ impl RpcLayer {
     }
 }
 
+impl CheckpointsLayer {
+    pub fn finish(self, base: CheckpointsConfig) -> CheckpointsConfig {
+        check_extra("checkpoints", self.extra);
+        CheckpointsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl EpochsLayer {
+    pub fn finish(self, base: EpochsConfig) -> EpochsConfig {
+        check_extra("epochs", self.extra);
+        EpochsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl EventsLayer {
+    pub fn finish(self, base: EventsConfig) -> EventsConfig {
+        check_extra("events", self.extra);
+        EventsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
 impl ObjectsLayer {
     pub fn finish(self, base: ObjectsConfig) -> ObjectsConfig {
         check_extra("objects", self.extra);
@@ -360,6 +468,9 @@ impl PackageResolverLayer {
 impl Default for RpcConfig {
     fn default() -> Self {
         Self {
+            checkpoints: CheckpointsConfig::default(),
+            epochs: EpochsConfig::default(),
+            events: EventsConfig::default(),
             objects: ObjectsConfig::default(),
             dynamic_fields: DynamicFieldsConfig::default(),
             transactions: TransactionsConfig::default(),
@@ -371,6 +482,33 @@ impl Default for RpcConfig {
     }
 }
 
+impl Default for CheckpointsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl Default for EpochsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
 impl Default for ObjectsConfig {
     fn default() -> Self {
         Self {
@@ -444,6 +582,36 @@ impl Default for PackageResolverLayer {
     }
 }
 
+impl From<CheckpointsConfig> for CheckpointsLayer {
+    fn from(config: CheckpointsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<EpochsConfig> for EpochsLayer {
+    fn from(config: EpochsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<EventsConfig> for EventsLayer {
+    fn from(config: EventsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
 impl From<ObjectsConfig> for ObjectsLayer {
     fn from(config: ObjectsConfig) -> Self {
         Self {
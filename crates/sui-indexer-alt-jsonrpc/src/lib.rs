@@ -9,10 +9,13 @@ use anyhow::Context as _;
 use api::checkpoints::Checkpoints;
 use api::coin::{Coins, DelegationCoins};
 use api::dynamic_fields::DynamicFields;
+use api::epochs::{Epochs, ProtocolConfigs};
+use api::events::{Events, QueryEvents};
 use api::move_utils::MoveUtils;
 use api::name_service::NameService;
 use api::objects::{Objects, QueryObjects};
 use api::rpc_module::RpcModule;
+use api::transaction_builder::TransactionBuilder;
 use api::transactions::{QueryTransactions, Transactions};
 use api::write::Write;
 use config::RpcConfig;
@@ -236,8 +239,8 @@ pub struct NodeArgs {
 /// Otherwise these requests are served by the database. If a `bigtable_instance` is provided, the
 /// `GOOGLE_APPLICATION_CREDENTIALS` environment variable must point to the credentials JSON file.
 ///
-/// Access to writes (executing and dry-running transactions) is controlled by `node_args.fullnode_rpc_url`,
-/// which can be omitted to disable writes from this RPC.
+/// Access to writes (executing, dry-running and dev-inspecting transactions) is controlled by
+/// `node_args.fullnode_rpc_url`, which can be omitted to disable writes from this RPC.
 ///
 /// The service may spin up auxiliary services (such as the system package task) to support itself,
 /// and will clean these up on shutdown as well.
@@ -278,12 +281,17 @@ pub async fn start_rpc(
     rpc.add_module(Checkpoints(context.clone()))?;
     rpc.add_module(Coins(context.clone()))?;
     rpc.add_module(DynamicFields(context.clone()))?;
+    rpc.add_module(Epochs(context.clone()))?;
+    rpc.add_module(Events(context.clone()))?;
     rpc.add_module(Governance(context.clone()))?;
     rpc.add_module(MoveUtils(context.clone()))?;
     rpc.add_module(NameService(context.clone()))?;
     rpc.add_module(Objects(context.clone()))?;
+    rpc.add_module(ProtocolConfigs(context.clone()))?;
+    rpc.add_module(QueryEvents(context.clone()))?;
     rpc.add_module(QueryObjects(context.clone()))?;
     rpc.add_module(QueryTransactions(context.clone()))?;
+    rpc.add_module(TransactionBuilder::new(context.clone()))?;
     rpc.add_module(Transactions(context.clone()))?;
 
     if let Some(fullnode_rpc_url) = node_args.fullnode_rpc_url {
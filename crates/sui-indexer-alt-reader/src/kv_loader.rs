@@ -37,10 +37,20 @@ pub enum KvLoader {
     Pg(Arc<DataLoader<PgReader>>),
 }
 
-/// A wrapper for the contents of a transaction, either from Bigtable or Postgres.
+/// A wrapper for the contents of a transaction, either from Bigtable or Postgres, or freshly
+/// executed by a fullnode.
 pub enum TransactionContents {
     Bigtable(KVTransactionData),
     Pg(StoredTransaction),
+
+    /// A transaction that was just executed or simulated by a fullnode, and so has not been
+    /// included in a checkpoint (yet).
+    ExecutedTransaction {
+        effects: Box<TransactionEffects>,
+        events: Option<Vec<Event>>,
+        transaction_data: Box<TransactionData>,
+        signatures: Vec<GenericSignature>,
+    },
 }
 
 impl KvLoader {
@@ -155,6 +165,9 @@ impl TransactionContents {
             Self::Pg(stored) => bcs::from_bytes(&stored.raw_transaction)
                 .context("Failed to deserialize transaction data"),
             Self::Bigtable(kv) => Ok(kv.transaction.data().transaction_data().clone()),
+            Self::ExecutedTransaction {
+                transaction_data, ..
+            } => Ok(transaction_data.as_ref().clone()),
         }
     }
 
@@ -163,6 +176,9 @@ impl TransactionContents {
             Self::Pg(stored) => TransactionDigest::try_from(stored.tx_digest.clone())
                 .context("Failed to deserialize transaction digest"),
             Self::Bigtable(kv) => Ok(*kv.transaction.digest()),
+            Self::ExecutedTransaction {
+                transaction_data, ..
+            } => Ok(transaction_data.digest()),
         }
     }
 
@@ -175,6 +191,7 @@ impl TransactionContents {
                 Ok(effects.digest())
             }
             Self::Bigtable(kv) => Ok(kv.effects.digest()),
+            Self::ExecutedTransaction { effects, .. } => Ok(effects.digest()),
         }
     }
 
@@ -184,6 +201,7 @@ impl TransactionContents {
                 bcs::from_bytes(&stored.user_signatures).context("Failed to deserialize signatures")
             }
             Self::Bigtable(kv) => Ok(kv.transaction.tx_signatures().to_vec()),
+            Self::ExecutedTransaction { signatures, .. } => Ok(signatures.clone()),
         }
     }
 
@@ -193,6 +211,7 @@ impl TransactionContents {
                 bcs::from_bytes(&stored.raw_effects).context("Failed to deserialize effects")
             }
             Self::Bigtable(kv) => Ok(kv.effects.clone()),
+            Self::ExecutedTransaction { effects, .. } => Ok(effects.as_ref().clone()),
         }
     }
 
@@ -202,6 +221,7 @@ impl TransactionContents {
                 bcs::from_bytes(&stored.events).context("Failed to deserialize events")
             }
             Self::Bigtable(kv) => Ok(kv.events.clone().unwrap_or_default().data),
+            Self::ExecutedTransaction { events, .. } => Ok(events.clone().unwrap_or_default()),
         }
     }
 
@@ -210,6 +230,11 @@ impl TransactionContents {
             Self::Pg(stored) => Ok(stored.raw_transaction.clone()),
             Self::Bigtable(kv) => bcs::to_bytes(kv.transaction.data().transaction_data())
                 .context("Failed to serialize transaction"),
+            Self::ExecutedTransaction {
+                transaction_data, ..
+            } => {
+                bcs::to_bytes(transaction_data.as_ref()).context("Failed to serialize transaction")
+            }
         }
     }
 
@@ -217,20 +242,29 @@ impl TransactionContents {
         match self {
             Self::Pg(stored) => Ok(stored.raw_effects.clone()),
             Self::Bigtable(kv) => bcs::to_bytes(&kv.effects).context("Failed to serialize effects"),
+            Self::ExecutedTransaction { effects, .. } => {
+                bcs::to_bytes(effects.as_ref()).context("Failed to serialize effects")
+            }
         }
     }
 
-    pub fn timestamp_ms(&self) -> u64 {
+    /// The timestamp of the checkpoint this transaction was included in, or `None` if it has not
+    /// been included in a checkpoint.
+    pub fn timestamp_ms(&self) -> Option<u64> {
         match self {
-            Self::Pg(stored) => stored.timestamp_ms as u64,
-            Self::Bigtable(kv) => kv.timestamp,
+            Self::Pg(stored) => Some(stored.timestamp_ms as u64),
+            Self::Bigtable(kv) => Some(kv.timestamp),
+            Self::ExecutedTransaction { .. } => None,
         }
     }
 
-    pub fn cp_sequence_number(&self) -> u64 {
+    /// The checkpoint this transaction was included in, or `None` if it has not been included in
+    /// a checkpoint.
+    pub fn cp_sequence_number(&self) -> Option<u64> {
         match self {
-            Self::Pg(stored) => stored.cp_sequence_number as u64,
-            Self::Bigtable(kv) => kv.checkpoint_number,
+            Self::Pg(stored) => Some(stored.cp_sequence_number as u64),
+            Self::Bigtable(kv) => Some(kv.checkpoint_number),
+            Self::ExecutedTransaction { .. } => None,
         }
     }
 }
@@ -7,7 +7,10 @@ use std::path::PathBuf;
 use crate::benchmark::BenchmarkArgs;
 use crate::IndexerArgs;
 use clap::Subcommand;
-use sui_indexer_alt_framework::{ingestion::ClientArgs, postgres::DbArgs};
+use sui_indexer_alt_framework::{
+    ingestion::{local_writer::LocalWriterConfig, ClientArgs},
+    postgres::DbArgs,
+};
 use sui_indexer_alt_metrics::MetricsArgs;
 use url::Url;
 
@@ -75,6 +78,23 @@ pub enum Command {
         skip_migrations: bool,
     },
 
+    /// Move the checkpoints that a fullnode writes to its `data_ingestion_dir` into a local
+    /// ingestion directory, that any number of indexers can read from (using
+    /// `--local-ingestion-path`). Checkpoints are removed once every indexer that identifies itself
+    /// with `--local-ingestion-consumer` has committed them.
+    RelayCheckpoints {
+        /// The fullnode's `data_ingestion_dir`, to move checkpoints out of.
+        #[arg(long)]
+        data_ingestion_dir: PathBuf,
+
+        /// The local ingestion directory to move checkpoints into.
+        #[arg(long)]
+        local_ingestion_path: PathBuf,
+
+        #[command(flatten)]
+        writer_config: LocalWriterConfig,
+    },
+
     /// Run the benchmark. It will load ingestion data from the given path and run the pipelines.
     /// The first and last checkpoint will be determined automatically based on the ingestion data.
     /// Note that the indexer will not be bootstrapped from genesis, and hence will
@@ -56,6 +56,7 @@ pub async fn run_benchmark(
     let client_args = ClientArgs {
         remote_store_url: None,
         local_ingestion_path: Some(ingestion_path.clone()),
+        local_ingestion_consumer: None,
         rpc_api_url: None,
         rpc_username: None,
         rpc_password: None,
@@ -1,9 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::mem;
+use std::{collections::BTreeMap, mem};
 
-use anyhow::ensure;
+use anyhow::{bail, ensure};
 use sui_default_config::DefaultConfig;
 use sui_indexer_alt_framework::{
     ingestion::IngestionConfig,
@@ -14,6 +14,8 @@ use sui_indexer_alt_framework::{
     },
 };
 
+use crate::handlers::filters::Filter;
+
 /// Trait for merging configuration structs together.
 pub trait Merge: Sized {
     fn merge(self, other: Self) -> anyhow::Result<Self>;
@@ -39,6 +41,13 @@ pub struct IndexerConfig {
     /// Per-pipeline configurations.
     pub pipeline: PipelineLayer,
 
+    /// Declarative filters, each selecting events or objects to write to a table of their own,
+    /// keyed by the filter's name. All filters are served by the `filters` pipeline, which is
+    /// enabled if there is at least one filter. They share its watermark, so the set of filters
+    /// cannot change once the pipeline has started, unless the pipeline is reset. The pipeline
+    /// decodes values using the packages indexed by `kv_packages`, which must also be enabled.
+    pub filters: BTreeMap<String, FilterLayer>,
+
     #[serde(flatten)]
     pub extra: toml::Table,
 }
@@ -106,6 +115,28 @@ pub struct PrunerLayer {
     pub extra: toml::Table,
 }
 
+/// A filter selects either events (by `event-type` and/or `emit-module`) or objects (by
+/// `object-type` and/or `owner`). Types can be given with or without type parameters -- without
+/// them, all instantiations of the type match.
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct FilterLayer {
+    /// Select events whose type is this struct type, e.g. `0x2::coin::CoinEvent`.
+    pub event_type: Option<String>,
+
+    /// Select events emitted by a transaction calling into this module, e.g. `0x2::coin`.
+    pub emit_module: Option<String>,
+
+    /// Select objects of this type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`.
+    pub object_type: Option<String>,
+
+    /// Select objects owned by this address or object.
+    pub owner: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
 #[DefaultConfig]
 #[derive(Clone, Default, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -135,6 +166,9 @@ pub struct PipelineLayer {
     pub tx_digests: Option<ConcurrentLayer>,
     pub tx_kinds: Option<ConcurrentLayer>,
 
+    // Declarative filters
+    pub filters: Option<ConcurrentLayer>,
+
     #[serde(flatten)]
     pub extra: toml::Table,
 }
@@ -258,6 +292,28 @@ impl PrunerLayer {
     }
 }
 
+impl FilterLayer {
+    pub(crate) fn finish(self, name: &str) -> anyhow::Result<Filter> {
+        check_extra("filter", self.extra)?;
+        let selects_events = self.event_type.is_some() || self.emit_module.is_some();
+        let selects_objects = self.object_type.is_some() || self.owner.is_some();
+        match (selects_events, selects_objects) {
+            (true, false) => Filter::events(
+                name,
+                self.event_type.as_deref(),
+                self.emit_module.as_deref(),
+            ),
+
+            (false, true) => {
+                Filter::objects(name, self.object_type.as_deref(), self.owner.as_deref())
+            }
+
+            (true, true) => bail!("Filter {name} cannot select both events and objects"),
+            (false, false) => bail!("Filter {name} does not select anything"),
+        }
+    }
+}
+
 impl PipelineLayer {
     /// Generate an example configuration, suitable for demonstrating the fields available to
     /// configure.
@@ -284,6 +340,7 @@ impl PipelineLayer {
             tx_calls: Some(Default::default()),
             tx_digests: Some(Default::default()),
             tx_kinds: Some(Default::default()),
+            filters: Some(Default::default()),
             extra: Default::default(),
         }
     }
@@ -303,6 +360,7 @@ impl Merge for IndexerConfig {
             committer: self.committer.merge(other.committer)?,
             pruner: self.pruner.merge(other.pruner)?,
             pipeline: self.pipeline.merge(other.pipeline)?,
+            filters: self.filters.merge(other.filters)?,
             extra: Default::default(),
         })
     }
@@ -409,6 +467,21 @@ impl Merge for PipelineLayer {
             tx_calls: self.tx_calls.merge(other.tx_calls)?,
             tx_digests: self.tx_digests.merge(other.tx_digests)?,
             tx_kinds: self.tx_kinds.merge(other.tx_kinds)?,
+            filters: self.filters.merge(other.filters)?,
+            extra: Default::default(),
+        })
+    }
+}
+
+impl Merge for FilterLayer {
+    fn merge(self, other: FilterLayer) -> anyhow::Result<FilterLayer> {
+        check_extra("filter", self.extra)?;
+        check_extra("filter", other.extra)?;
+        Ok(FilterLayer {
+            event_type: other.event_type.or(self.event_type),
+            emit_module: other.emit_module.or(self.emit_module),
+            object_type: other.object_type.or(self.object_type),
+            owner: other.owner.or(self.owner),
             extra: Default::default(),
         })
     }
@@ -424,6 +497,22 @@ impl<T: Merge> Merge for Option<T> {
     }
 }
 
+/// Maps are merged key-wise: values for keys that appear in both maps are merged together.
+impl<T: Merge> Merge for BTreeMap<String, T> {
+    fn merge(mut self, other: BTreeMap<String, T>) -> anyhow::Result<BTreeMap<String, T>> {
+        for (key, value) in other {
+            let value = match self.remove(&key) {
+                Some(prev) => prev.merge(value)?,
+                None => value,
+            };
+
+            self.insert(key, value);
+        }
+
+        Ok(self)
+    }
+}
+
 impl From<IngestionConfig> for IngestionLayer {
     fn from(config: IngestionConfig) -> Self {
         Self {
@@ -758,6 +847,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_and_merge_filters() {
+        let this: IndexerConfig = toml::from_str(
+            r#"
+            [filters.coin_events]
+            event-type = "0x2::coin::CoinEvent"
+
+            [filters.my_coins]
+            object-type = "0x2::coin::Coin<0x2::sui::SUI>"
+            "#,
+        )
+        .unwrap();
+
+        let that: IndexerConfig = toml::from_str(
+            r#"
+            [filters.coin_events]
+            emit-module = "0x2::coin"
+            "#,
+        )
+        .unwrap();
+
+        let mut merged = this.merge(that).unwrap().finish().unwrap().filters;
+        assert_eq!(merged.len(), 2);
+
+        assert_matches!(
+            merged.remove("coin_events").unwrap(),
+            FilterLayer {
+                event_type: Some(_),
+                emit_module: Some(_),
+                object_type: None,
+                owner: None,
+                extra: _,
+            },
+        );
+
+        merged
+            .remove("my_coins")
+            .unwrap()
+            .finish("my_coins")
+            .unwrap();
+    }
+
+    #[test]
+    fn finish_filter_conflicting_selectors() {
+        let layer = FilterLayer {
+            event_type: Some("0x2::coin::CoinEvent".to_string()),
+            owner: Some("0x2".to_string()),
+            ..Default::default()
+        };
+
+        let err = layer.finish("mixed").unwrap_err();
+        assert!(
+            err.to_string().contains("both events and objects"),
+            "Unexpected error: {err}"
+        );
+
+        let err = FilterLayer::default().finish("empty").unwrap_err();
+        assert!(
+            err.to_string().contains("does not select anything"),
+            "Unexpected error: {err}"
+        );
+    }
+
     #[test]
     fn detect_unrecognized_fields() {
         let config: IndexerConfig = toml::from_str(
@@ -0,0 +1,932 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A generic pipeline, driven by the `[filters]` section of the indexer's config, that writes the
+//! events and objects selected by each filter into a table of its own (`filter_<name>`).
+//!
+//! Selected values are decoded into JSON using the processor context's shared package resolver.
+//! Packages published in the checkpoint being processed are read from the checkpoint itself, and
+//! all other packages from the `kv_packages` table, so the `kv_packages` pipeline needs to be
+//! running alongside this one. It is an independent pipeline, so it can fall behind: a value whose
+//! package it has not written yet is retried with a backoff until the package shows up.
+//!
+//! All filters share the `filters` pipeline's watermark, so a filter only sees the checkpoints
+//! processed while it is configured. The definition of each filter is recorded in the `filters`
+//! table, and once the pipeline has a watermark, filters cannot be added, changed, or removed and
+//! re-added, as they would silently miss the history before it. To change the set of filters,
+//! reset the pipeline: drop its watermark, the `filters` table and the filters' tables, and index
+//! all filters again from the first checkpoint.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{bail, ensure, Context, Result};
+use async_trait::async_trait;
+use diesel::{
+    sql_types::{Array, BigInt, Bool, Bytea, Nullable, Text},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+use move_core_types::{account_address::AccountAddress, language_storage::StructTag};
+use sui_indexer_alt_framework::{
+    pipeline::{concurrent::Handler, AsyncProcessor, ProcessorContext},
+    postgres::{Connection, Db},
+    types::{
+        base_types::{ObjectID, SuiAddress},
+        event::Event,
+        full_checkpoint_content::CheckpointData,
+        object::{bounded_visitor::BoundedVisitor, Object},
+        parse_sui_struct_tag, Identifier, TypeTag,
+    },
+    FieldCount,
+};
+use sui_indexer_alt_reader::pg_reader::PgReader;
+use sui_package_resolver::{error::Error as ResolverError, Package, PackageStore, Resolver};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+/// Filter names become part of table names, so they are limited to lowercase letters, digits and
+/// underscores, and to this length.
+const MAX_FILTER_NAME_LEN: usize = 48;
+
+/// The table recording the definition of each filter served by the pipeline, keyed by the
+/// filter's table. It is not prefixed like filter tables, so it cannot clash with them.
+const DEFINITIONS_TABLE: &str = "filters";
+
+/// How long to wait before looking for a package that `kv_packages` has not written yet, the
+/// first time, and at most.
+const INITIAL_PACKAGE_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_PACKAGE_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+pub(crate) struct Filters(ProcessorContext<FilterState>);
+
+pub(crate) struct FilterState {
+    filters: Vec<Filter>,
+    /// Stops waiting for missing packages when the indexer shuts down.
+    cancel: CancellationToken,
+}
+
+#[derive(Debug)]
+pub(crate) struct Filter {
+    table: Arc<str>,
+    selector: Selector,
+}
+
+#[derive(Debug)]
+enum Selector {
+    Events {
+        type_: Option<TypePattern>,
+        emit_module: Option<(ObjectID, Identifier)>,
+    },
+    Objects {
+        type_: Option<TypePattern>,
+        owner: Option<SuiAddress>,
+    },
+}
+
+/// A struct type to match against. If it was written without type parameters, it matches all
+/// instantiations of the type.
+#[derive(Debug)]
+struct TypePattern {
+    tag: StructTag,
+    any_instantiation: bool,
+}
+
+pub(crate) enum StoredFiltered {
+    Event(StoredFilteredEvent),
+    Object(StoredFilteredObject),
+}
+
+pub(crate) struct StoredFilteredEvent {
+    table: Arc<str>,
+    cp_sequence_number: i64,
+    tx_sequence_number: i64,
+    event_sequence_number: i64,
+    tx_digest: Vec<u8>,
+    sender: Vec<u8>,
+    type_: String,
+    contents: String,
+}
+
+pub(crate) struct StoredFilteredObject {
+    table: Arc<str>,
+    cp_sequence_number: i64,
+    tx_sequence_number: i64,
+    tx_digest: Vec<u8>,
+    object_id: Vec<u8>,
+    object_version: i64,
+    owner: Option<Vec<u8>>,
+    type_: String,
+    contents: String,
+}
+
+/// Packages published in the checkpoint being processed, which may not have been written to the
+/// database yet, in front of the shared resolver's store.
+struct CheckpointPackages {
+    packages: HashMap<AccountAddress, Arc<Package>>,
+    fallback: Arc<dyn PackageStore>,
+}
+
+impl Filters {
+    /// Packages are loaded through `reader`, which is also the source of the context's shared
+    /// package resolver.
+    pub(crate) fn new(filters: Vec<Filter>, reader: PgReader, cancel: CancellationToken) -> Self {
+        Self(ProcessorContext::new(FilterState { filters, cancel }).with_reader(reader))
+    }
+
+    /// Check that the configured filters are the ones the pipeline's watermark covers, record
+    /// their definitions, and create their tables if they do not exist already.
+    ///
+    /// Before the pipeline has a watermark, filters can be changed freely. After that, a filter
+    /// that is new, whose definition changed, or that was removed and re-added is rejected, as it
+    /// would be missing the history before the watermark. Removed filters' tables are kept, but
+    /// they are no longer updated.
+    pub(crate) async fn prepare(&self, conn: &mut Connection<'_>) -> Result<()> {
+        #[derive(QueryableByName)]
+        struct Definition {
+            #[diesel(sql_type = Text)]
+            table_name: String,
+            #[diesel(sql_type = Text)]
+            definition: String,
+        }
+
+        #[derive(QueryableByName)]
+        struct Started {
+            #[diesel(sql_type = Bool)]
+            started: bool,
+        }
+
+        let filters = &self.0.state().filters;
+
+        diesel::sql_query(format!(
+            "CREATE TABLE IF NOT EXISTS {DEFINITIONS_TABLE} (
+                table_name              TEXT    PRIMARY KEY,
+                definition              TEXT    NOT NULL
+            )"
+        ))
+        .execute(conn)
+        .await
+        .context("Failed to create filter definitions table")?;
+
+        let Started { started } = diesel::sql_query(
+            "SELECT EXISTS (SELECT 1 FROM watermarks WHERE pipeline = $1) AS started",
+        )
+        .bind::<Text, _>(<Self as AsyncProcessor>::NAME)
+        .get_result::<Started>(conn)
+        .await
+        .context("Failed to read filters watermark")?;
+
+        if started {
+            let recorded: HashMap<_, _> = diesel::sql_query(format!(
+                "SELECT table_name, definition FROM {DEFINITIONS_TABLE}"
+            ))
+            .load::<Definition>(conn)
+            .await
+            .context("Failed to read filter definitions")?
+            .into_iter()
+            .map(|d| (d.table_name, d.definition))
+            .collect();
+
+            for filter in filters {
+                match recorded.get(&*filter.table) {
+                    Some(definition) if *definition == filter.definition() => {}
+                    Some(definition) => bail!(
+                        "Filter {} changed from {definition:?} to {:?} after the filters pipeline \
+                         started, so its table would mix values selected by both definitions. \
+                         Reset the filters pipeline to change its filters",
+                        filter.table,
+                        filter.definition(),
+                    ),
+                    None => bail!(
+                        "Filter {} was added after the filters pipeline started, so it would be \
+                         missing the checkpoints before the pipeline's watermark. Reset the \
+                         filters pipeline to add filters",
+                        filter.table,
+                    ),
+                }
+            }
+        }
+
+        // Forget the filters that are no longer configured, so that they cannot be re-added
+        // once the pipeline has started.
+        diesel::sql_query(format!(
+            "DELETE FROM {DEFINITIONS_TABLE} WHERE table_name != ALL($1)"
+        ))
+        .bind::<Array<Text>, _>(
+            filters
+                .iter()
+                .map(|f| f.table.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .execute(conn)
+        .await
+        .context("Failed to update filter definitions")?;
+
+        for filter in filters {
+            diesel::sql_query(format!(
+                "INSERT INTO {DEFINITIONS_TABLE} (table_name, definition) VALUES ($1, $2)
+                 ON CONFLICT (table_name) DO UPDATE SET definition = EXCLUDED.definition"
+            ))
+            .bind::<Text, _>(filter.table.to_string())
+            .bind::<Text, _>(filter.definition())
+            .execute(conn)
+            .await
+            .context("Failed to update filter definitions")?;
+        }
+
+        for filter in filters {
+            for statement in filter.create_table() {
+                diesel::sql_query(statement)
+                    .execute(conn)
+                    .await
+                    .with_context(|| format!("Failed to create table {}", filter.table))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Filter {
+    /// A filter selecting events by their type and/or the module whose call emitted them.
+    pub(crate) fn events(
+        name: &str,
+        event_type: Option<&str>,
+        emit_module: Option<&str>,
+    ) -> Result<Self> {
+        ensure!(
+            event_type.is_some() || emit_module.is_some(),
+            "Filter {name} must select events by their type or emitting module"
+        );
+
+        let emit_module = emit_module
+            .map(|m| {
+                let Some((package, module)) = m.split_once("::") else {
+                    bail!("Invalid module {m}, expected <package>::<module>");
+                };
+
+                Ok((package.parse::<ObjectID>()?, Identifier::new(module)?))
+            })
+            .transpose()
+            .with_context(|| format!("Invalid emit-module for filter {name}"))?;
+
+        Ok(Self {
+            table: table_name(name)?,
+            selector: Selector::Events {
+                type_: event_type
+                    .map(TypePattern::parse)
+                    .transpose()
+                    .with_context(|| format!("Invalid event-type for filter {name}"))?,
+                emit_module,
+            },
+        })
+    }
+
+    /// A filter selecting objects by their type and/or owner.
+    pub(crate) fn objects(
+        name: &str,
+        object_type: Option<&str>,
+        owner: Option<&str>,
+    ) -> Result<Self> {
+        ensure!(
+            object_type.is_some() || owner.is_some(),
+            "Filter {name} must select objects by their type or owner"
+        );
+
+        Ok(Self {
+            table: table_name(name)?,
+            selector: Selector::Objects {
+                type_: object_type
+                    .map(TypePattern::parse)
+                    .transpose()
+                    .with_context(|| format!("Invalid object-type for filter {name}"))?,
+                owner: owner
+                    .map(|o| o.parse())
+                    .transpose()
+                    .with_context(|| format!("Invalid owner for filter {name}"))?,
+            },
+        })
+    }
+
+    fn matches_event(&self, event: &Event) -> bool {
+        let Selector::Events { type_, emit_module } = &self.selector else {
+            return false;
+        };
+
+        type_.as_ref().is_none_or(|t| t.matches(&event.type_))
+            && emit_module.as_ref().is_none_or(|(package, module)| {
+                event.package_id == *package && event.transaction_module == *module
+            })
+    }
+
+    fn matches_object(&self, object: &Object) -> bool {
+        let Selector::Objects { type_, owner } = &self.selector else {
+            return false;
+        };
+
+        let Some(tag) = object.struct_tag() else {
+            return false;
+        };
+
+        type_.as_ref().is_none_or(|t| t.matches(&tag))
+            && owner
+                .as_ref()
+                .is_none_or(|o| object.owner().get_owner_address().ok() == Some(*o))
+    }
+
+    /// A canonical description of the values the filter selects, to detect filters whose
+    /// definition changed between runs.
+    fn definition(&self) -> String {
+        fn or_any(value: Option<String>) -> String {
+            value.unwrap_or_else(|| "*".to_owned())
+        }
+
+        let type_ = |t: &Option<TypePattern>| {
+            or_any(t.as_ref().map(|t| {
+                let tag = t.tag.to_canonical_string(/* with_prefix */ true);
+                if t.any_instantiation {
+                    format!("{tag}<..>")
+                } else {
+                    tag
+                }
+            }))
+        };
+
+        match &self.selector {
+            Selector::Events {
+                type_: event_type,
+                emit_module,
+            } => format!(
+                "events type={} emit-module={}",
+                type_(event_type),
+                or_any(emit_module.as_ref().map(|(package, module)| {
+                    format!(
+                        "{}::{module}",
+                        package.to_canonical_string(/* with_prefix */ true)
+                    )
+                })),
+            ),
+
+            Selector::Objects {
+                type_: object_type,
+                owner,
+            } => format!(
+                "objects type={} owner={}",
+                type_(object_type),
+                or_any(owner.map(|o| o.to_string())),
+            ),
+        }
+    }
+
+    /// The statements that create this filter's table and its indices.
+    fn create_table(&self) -> Vec<String> {
+        let table = &self.table;
+        match self.selector {
+            Selector::Events { .. } => vec![
+                format!(
+                    "CREATE TABLE IF NOT EXISTS {table} (
+                        cp_sequence_number      BIGINT  NOT NULL,
+                        tx_sequence_number      BIGINT  NOT NULL,
+                        event_sequence_number   BIGINT  NOT NULL,
+                        tx_digest               BYTEA   NOT NULL,
+                        sender                  BYTEA   NOT NULL,
+                        type                    TEXT    NOT NULL,
+                        contents                JSONB   NOT NULL,
+                        PRIMARY KEY (tx_sequence_number, event_sequence_number)
+                    )"
+                ),
+                format!(
+                    "CREATE INDEX IF NOT EXISTS {table}_cp_sequence_number \
+                     ON {table} (cp_sequence_number)"
+                ),
+            ],
+
+            Selector::Objects { .. } => vec![
+                format!(
+                    "CREATE TABLE IF NOT EXISTS {table} (
+                        cp_sequence_number      BIGINT  NOT NULL,
+                        tx_sequence_number      BIGINT  NOT NULL,
+                        tx_digest               BYTEA   NOT NULL,
+                        object_id               BYTEA   NOT NULL,
+                        object_version          BIGINT  NOT NULL,
+                        owner                   BYTEA,
+                        type                    TEXT    NOT NULL,
+                        contents                JSONB   NOT NULL,
+                        PRIMARY KEY (object_id, object_version)
+                    )"
+                ),
+                format!(
+                    "CREATE INDEX IF NOT EXISTS {table}_cp_sequence_number \
+                     ON {table} (cp_sequence_number)"
+                ),
+            ],
+        }
+    }
+}
+
+impl TypePattern {
+    fn parse(s: &str) -> Result<Self> {
+        Ok(Self {
+            tag: parse_sui_struct_tag(s)?,
+            any_instantiation: !s.contains('<'),
+        })
+    }
+
+    fn matches(&self, tag: &StructTag) -> bool {
+        if self.any_instantiation {
+            self.tag.address == tag.address
+                && self.tag.module == tag.module
+                && self.tag.name == tag.name
+        } else {
+            self.tag == *tag
+        }
+    }
+}
+
+impl StoredFiltered {
+    fn table(&self) -> &str {
+        match self {
+            StoredFiltered::Event(e) => &e.table,
+            StoredFiltered::Object(o) => &o.table,
+        }
+    }
+}
+
+/// Rows are written with one statement per table, binding a column's worth of values at a time,
+/// so the number of bind parameters only depends on the number of columns.
+impl FieldCount for StoredFiltered {
+    const FIELD_COUNT: usize = 8;
+}
+
+#[async_trait]
+impl PackageStore for CheckpointPackages {
+    async fn fetch(&self, id: AccountAddress) -> sui_package_resolver::Result<Arc<Package>> {
+        if let Some(package) = self.packages.get(&id) {
+            return Ok(package.clone());
+        }
+
+        self.fallback.fetch(id).await
+    }
+}
+
+#[async_trait]
+impl AsyncProcessor for Filters {
+    const NAME: &'static str = "filters";
+
+    type Value = StoredFiltered;
+    type State = FilterState;
+
+    fn context(&self) -> &ProcessorContext<FilterState> {
+        &self.0
+    }
+
+    async fn process(
+        &self,
+        checkpoint: &Arc<CheckpointData>,
+        ctx: &ProcessorContext<FilterState>,
+    ) -> Result<Vec<Self::Value>> {
+        let CheckpointData {
+            transactions,
+            checkpoint_summary,
+            ..
+        } = checkpoint.as_ref();
+
+        let FilterState { filters, cancel } = ctx.state();
+
+        let mut published = HashMap::new();
+        for obj in transactions.iter().flat_map(|tx| &tx.output_objects) {
+            if obj.is_package() {
+                let package = Package::read_from_object(obj)
+                    .with_context(|| format!("Failed to read package {}", obj.id()))?;
+                published.insert(obj.id().into(), Arc::new(package));
+            }
+        }
+
+        // Most checkpoints do not publish packages, and use the shared resolver as is. The others
+        // layer their packages over its store, so that the cache behind it is still shared.
+        let shared = ctx.package_resolver()?;
+        let overlay;
+        let resolver: &Resolver<Arc<dyn PackageStore>> = if published.is_empty() {
+            shared
+        } else {
+            let store: Arc<dyn PackageStore> = Arc::new(CheckpointPackages {
+                packages: published,
+                fallback: shared.package_store().clone(),
+            });
+
+            overlay = Resolver::new(store);
+            &overlay
+        };
+
+        let cp_sequence_number = checkpoint_summary.sequence_number as i64;
+        let first_tx = checkpoint_summary.network_total_transactions as usize - transactions.len();
+
+        let mut values = vec![];
+        for (i, tx) in transactions.iter().enumerate() {
+            let tx_sequence_number = (first_tx + i) as i64;
+            let tx_digest = tx.transaction.digest().inner().to_vec();
+
+            for (j, ev) in tx.events.iter().flat_map(|evs| evs.data.iter().enumerate()) {
+                for filter in filters.iter().filter(|f| f.matches_event(ev)) {
+                    values.push(StoredFiltered::Event(StoredFilteredEvent {
+                        table: filter.table.clone(),
+                        cp_sequence_number,
+                        tx_sequence_number,
+                        event_sequence_number: j as i64,
+                        tx_digest: tx_digest.clone(),
+                        sender: ev.sender.to_vec(),
+                        type_: ev.type_.to_canonical_string(/* with_prefix */ true),
+                        contents: to_json(resolver, cancel, &ev.type_, &ev.contents)
+                            .await
+                            .with_context(|| {
+                                format!("Failed to decode event ({tx_sequence_number}, {j})")
+                            })?,
+                    }));
+                }
+            }
+
+            for obj in &tx.output_objects {
+                let (Some(tag), Some(move_obj)) = (obj.struct_tag(), obj.data.try_as_move()) else {
+                    continue;
+                };
+
+                for filter in filters.iter().filter(|f| f.matches_object(obj)) {
+                    values.push(StoredFiltered::Object(StoredFilteredObject {
+                        table: filter.table.clone(),
+                        cp_sequence_number,
+                        tx_sequence_number,
+                        tx_digest: tx_digest.clone(),
+                        object_id: obj.id().to_vec(),
+                        object_version: obj.version().value() as i64,
+                        owner: obj.owner().get_owner_address().ok().map(|a| a.to_vec()),
+                        type_: tag.to_canonical_string(/* with_prefix */ true),
+                        contents: to_json(resolver, cancel, &tag, move_obj.contents())
+                            .await
+                            .with_context(|| format!("Failed to decode object {}", obj.id()))?,
+                    }));
+                }
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+#[async_trait]
+impl Handler for Filters {
+    type Store = Db;
+
+    const MIN_EAGER_ROWS: usize = 100;
+    const MAX_PENDING_ROWS: usize = 10000;
+
+    async fn commit<'a>(values: &[Self::Value], conn: &mut Connection<'a>) -> Result<usize> {
+        let mut tables: BTreeMap<&str, Vec<&StoredFiltered>> = BTreeMap::new();
+        for value in values {
+            tables.entry(value.table()).or_default().push(value);
+        }
+
+        let mut affected = 0;
+        for (table, rows) in tables {
+            let events: Vec<_> = rows
+                .iter()
+                .filter_map(|r| match r {
+                    StoredFiltered::Event(e) => Some(e),
+                    StoredFiltered::Object(_) => None,
+                })
+                .collect();
+
+            let objects: Vec<_> = rows
+                .iter()
+                .filter_map(|r| match r {
+                    StoredFiltered::Object(o) => Some(o),
+                    StoredFiltered::Event(_) => None,
+                })
+                .collect();
+
+            if !events.is_empty() {
+                affected += diesel::sql_query(format!(
+                    "INSERT INTO {table} (
+                        cp_sequence_number,
+                        tx_sequence_number,
+                        event_sequence_number,
+                        tx_digest,
+                        sender,
+                        type,
+                        contents
+                    )
+                    SELECT cp, tx, ev, digest, sender, type, contents::JSONB
+                    FROM UNNEST($1, $2, $3, $4, $5, $6, $7)
+                        AS t(cp, tx, ev, digest, sender, type, contents)
+                    ON CONFLICT DO NOTHING"
+                ))
+                .bind::<Array<BigInt>, _>(
+                    events
+                        .iter()
+                        .map(|e| e.cp_sequence_number)
+                        .collect::<Vec<_>>(),
+                )
+                .bind::<Array<BigInt>, _>(
+                    events
+                        .iter()
+                        .map(|e| e.tx_sequence_number)
+                        .collect::<Vec<_>>(),
+                )
+                .bind::<Array<BigInt>, _>(
+                    events
+                        .iter()
+                        .map(|e| e.event_sequence_number)
+                        .collect::<Vec<_>>(),
+                )
+                .bind::<Array<Bytea>, _>(
+                    events
+                        .iter()
+                        .map(|e| e.tx_digest.clone())
+                        .collect::<Vec<_>>(),
+                )
+                .bind::<Array<Bytea>, _>(
+                    events.iter().map(|e| e.sender.clone()).collect::<Vec<_>>(),
+                )
+                .bind::<Array<Text>, _>(events.iter().map(|e| e.type_.clone()).collect::<Vec<_>>())
+                .bind::<Array<Text>, _>(
+                    events
+                        .iter()
+                        .map(|e| e.contents.clone())
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .await
+                .with_context(|| format!("Failed to write to {table}"))?;
+            }
+
+            if !objects.is_empty() {
+                affected += diesel::sql_query(format!(
+                    "INSERT INTO {table} (
+                        cp_sequence_number,
+                        tx_sequence_number,
+                        tx_digest,
+                        object_id,
+                        object_version,
+                        owner,
+                        type,
+                        contents
+                    )
+                    SELECT cp, tx, digest, id, version, owner, type, contents::JSONB
+                    FROM UNNEST($1, $2, $3, $4, $5, $6, $7, $8)
+                        AS t(cp, tx, digest, id, version, owner, type, contents)
+                    ON CONFLICT DO NOTHING"
+                ))
+                .bind::<Array<BigInt>, _>(
+                    objects
+                        .iter()
+                        .map(|o| o.cp_sequence_number)
+                        .collect::<Vec<_>>(),
+                )
+                .bind::<Array<BigInt>, _>(
+                    objects
+                        .iter()
+                        .map(|o| o.tx_sequence_number)
+                        .collect::<Vec<_>>(),
+                )
+                .bind::<Array<Bytea>, _>(
+                    objects
+                        .iter()
+                        .map(|o| o.tx_digest.clone())
+                        .collect::<Vec<_>>(),
+                )
+                .bind::<Array<Bytea>, _>(
+                    objects
+                        .iter()
+                        .map(|o| o.object_id.clone())
+                        .collect::<Vec<_>>(),
+                )
+                .bind::<Array<BigInt>, _>(
+                    objects.iter().map(|o| o.object_version).collect::<Vec<_>>(),
+                )
+                .bind::<Array<Nullable<Bytea>>, _>(
+                    objects.iter().map(|o| o.owner.clone()).collect::<Vec<_>>(),
+                )
+                .bind::<Array<Text>, _>(objects.iter().map(|o| o.type_.clone()).collect::<Vec<_>>())
+                .bind::<Array<Text>, _>(
+                    objects
+                        .iter()
+                        .map(|o| o.contents.clone())
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .await
+                .with_context(|| format!("Failed to write to {table}"))?;
+            }
+        }
+
+        Ok(affected)
+    }
+
+    /// Filter tables record the checkpoint each row came from, so they are pruned by checkpoint,
+    /// according to the `filters` pipeline's retention.
+    async fn prune<'a>(
+        &self,
+        from: u64,
+        to_exclusive: u64,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        let mut pruned = 0;
+        for filter in &self.0.state().filters {
+            pruned += diesel::sql_query(format!(
+                "DELETE FROM {} WHERE cp_sequence_number >= $1 AND cp_sequence_number < $2",
+                filter.table,
+            ))
+            .bind::<BigInt, _>(from as i64)
+            .bind::<BigInt, _>(to_exclusive as i64)
+            .execute(conn)
+            .await?;
+        }
+
+        Ok(pruned)
+    }
+}
+
+/// The name of the table that the filter called `name` writes to.
+fn table_name(name: &str) -> Result<Arc<str>> {
+    ensure!(
+        !name.is_empty()
+            && name.len() <= MAX_FILTER_NAME_LEN
+            && name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+        "Invalid filter name {name:?}. Filter names must start with a lowercase letter, contain \
+         only lowercase letters, digits, and underscores, and be at most {MAX_FILTER_NAME_LEN} \
+         characters long"
+    );
+
+    Ok(format!("filter_{name}").into())
+}
+
+/// Decode the BCS `bytes` of a value of type `tag` into JSON. If one of the packages involved has
+/// not been indexed yet, wait for the `kv_packages` pipeline to catch up, backing off between
+/// attempts, until the indexer is shut down.
+async fn to_json(
+    resolver: &Resolver<Arc<dyn PackageStore>>,
+    cancel: &CancellationToken,
+    tag: &StructTag,
+    bytes: &[u8],
+) -> Result<String> {
+    let type_ = TypeTag::Struct(Box::new(tag.clone()));
+
+    let mut interval = INITIAL_PACKAGE_RETRY_INTERVAL;
+    let layout = loop {
+        match resolver.type_layout(type_.clone()).await {
+            Ok(layout) => break layout,
+            Err(ResolverError::PackageNotFound(id)) => {
+                warn!(
+                    package = %id,
+                    retry_ms = interval.as_millis(),
+                    "Package not indexed yet, waiting for kv_packages",
+                );
+
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = cancel.cancelled() => {
+                        bail!("Cancelled while waiting for package {id}");
+                    }
+                }
+
+                interval = (interval * 2).min(MAX_PACKAGE_RETRY_INTERVAL);
+            }
+            Err(e) => return Err(e).context("Failed to resolve layout"),
+        }
+    };
+
+    let value = BoundedVisitor::deserialize_value(bytes, &layout)?;
+    Ok(serde_json::to_string(&value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_indexer_alt_framework::types::{
+        object::Owner, test_checkpoint_data_builder::TestCheckpointDataBuilder,
+    };
+
+    use super::*;
+
+    fn event(package: &str, module: &str, type_: &str) -> Event {
+        Event {
+            package_id: package.parse().unwrap(),
+            transaction_module: Identifier::new(module).unwrap(),
+            sender: SuiAddress::ZERO,
+            type_: parse_sui_struct_tag(type_).unwrap(),
+            contents: vec![],
+        }
+    }
+
+    #[test]
+    fn test_filter_names() {
+        assert_eq!(&*table_name("coin_events").unwrap(), "filter_coin_events");
+        assert!(table_name("").is_err());
+        assert!(table_name("1coins").is_err());
+        assert!(table_name("Coins").is_err());
+        assert!(table_name("coins; DROP TABLE watermarks").is_err());
+        assert!(table_name(&"a".repeat(MAX_FILTER_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_filter_needs_selector() {
+        assert!(Filter::events("e", None, None).is_err());
+        assert!(Filter::objects("o", None, None).is_err());
+        assert!(Filter::events("e", None, Some("0x2")).is_err());
+        assert!(Filter::objects("o", None, Some("not an address")).is_err());
+    }
+
+    #[test]
+    fn test_filter_definitions() {
+        let by_type = Filter::events("e", Some("0x2::coin::CoinEvent"), None).unwrap();
+        assert_eq!(
+            by_type.definition(),
+            format!(
+                "events type={}::coin::CoinEvent<..> emit-module=*",
+                ObjectID::from_single_byte(2).to_canonical_string(/* with_prefix */ true),
+            ),
+        );
+
+        // Equivalent filters have the same definition, however they are written.
+        let long_form = Filter::events(
+            "f",
+            Some("0x0000000000000000000000000000000000000000000000000000000000000002::coin::CoinEvent"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(by_type.definition(), long_form.definition());
+
+        // Selecting one instantiation, or a different selector, changes the definition.
+        let by_inst =
+            Filter::events("e", Some("0x2::coin::CoinEvent<0x2::sui::SUI>"), None).unwrap();
+        assert_ne!(by_type.definition(), by_inst.definition());
+
+        let by_module =
+            Filter::events("e", Some("0x2::coin::CoinEvent"), Some("0x2::coin")).unwrap();
+        assert_ne!(by_type.definition(), by_module.definition());
+
+        let objects = Filter::objects("e", Some("0x2::coin::CoinEvent"), None).unwrap();
+        assert_ne!(by_type.definition(), objects.definition());
+    }
+
+    #[test]
+    fn test_match_events() {
+        let coin = event("0x2", "coin", "0x2::coin::CoinEvent<0x2::sui::SUI>");
+        let other = event("0x3", "other", "0x3::other::Event");
+
+        // Without type parameters, any instantiation matches.
+        let by_type = Filter::events("e", Some("0x2::coin::CoinEvent"), None).unwrap();
+        assert!(by_type.matches_event(&coin));
+        assert!(!by_type.matches_event(&other));
+
+        let by_inst = Filter::events("e", Some("0x2::coin::CoinEvent<0x3::x::Y>"), None).unwrap();
+        assert!(!by_inst.matches_event(&coin));
+
+        let by_module = Filter::events("e", None, Some("0x3::other")).unwrap();
+        assert!(!by_module.matches_event(&coin));
+        assert!(by_module.matches_event(&other));
+
+        // Both conditions need to hold.
+        let both = Filter::events("e", Some("0x2::coin::CoinEvent"), Some("0x3::other")).unwrap();
+        assert!(!both.matches_event(&coin));
+        assert!(!both.matches_event(&other));
+
+        // Object filters never select events.
+        let objects = Filter::objects("o", Some("0x2::coin::CoinEvent"), None).unwrap();
+        assert!(!objects.matches_event(&coin));
+    }
+
+    #[test]
+    fn test_match_objects() {
+        let checkpoint = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_owned_object(0)
+            .finish_transaction()
+            .build_checkpoint();
+
+        let object = &checkpoint.transactions[0].output_objects[0];
+        let tag = object.struct_tag().unwrap();
+        let Owner::AddressOwner(owner) = object.owner() else {
+            panic!("Expected an address-owned object");
+        };
+
+        let type_ = format!("{}::{}::{}", tag.address, tag.module, tag.name);
+        let by_type = Filter::objects("o", Some(&type_), None).unwrap();
+        assert!(by_type.matches_object(object));
+
+        let owner = owner.to_string();
+        let by_owner = Filter::objects("o", None, Some(&owner)).unwrap();
+        assert!(by_owner.matches_object(object));
+
+        let other_owner = SuiAddress::random_for_testing_only().to_string();
+        let by_other_owner = Filter::objects("o", None, Some(&other_owner)).unwrap();
+        assert!(!by_other_owner.matches_object(object));
+
+        let events = Filter::events("e", Some(&type_), None).unwrap();
+        assert!(!events.matches_object(object));
+    }
+}
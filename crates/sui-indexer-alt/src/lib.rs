@@ -1,18 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Context;
+use anyhow::{ensure, Context};
 use bootstrap::bootstrap;
 use config::{IndexerConfig, PipelineLayer};
 use handlers::{
     coin_balance_buckets::CoinBalanceBuckets, cp_sequence_numbers::CpSequenceNumbers,
-    ev_emit_mod::EvEmitMod, ev_struct_inst::EvStructInst, kv_checkpoints::KvCheckpoints,
-    kv_epoch_ends::KvEpochEnds, kv_epoch_starts::KvEpochStarts, kv_feature_flags::KvFeatureFlags,
-    kv_objects::KvObjects, kv_packages::KvPackages, kv_protocol_configs::KvProtocolConfigs,
-    kv_transactions::KvTransactions, obj_info::ObjInfo, obj_versions::ObjVersions,
-    sum_displays::SumDisplays, tx_affected_addresses::TxAffectedAddresses,
-    tx_affected_objects::TxAffectedObjects, tx_balance_changes::TxBalanceChanges,
-    tx_calls::TxCalls, tx_digests::TxDigests, tx_kinds::TxKinds,
+    ev_emit_mod::EvEmitMod, ev_struct_inst::EvStructInst, filters::Filters,
+    kv_checkpoints::KvCheckpoints, kv_epoch_ends::KvEpochEnds, kv_epoch_starts::KvEpochStarts,
+    kv_feature_flags::KvFeatureFlags, kv_objects::KvObjects, kv_packages::KvPackages,
+    kv_protocol_configs::KvProtocolConfigs, kv_transactions::KvTransactions, obj_info::ObjInfo,
+    obj_versions::ObjVersions, sum_displays::SumDisplays,
+    tx_affected_addresses::TxAffectedAddresses, tx_affected_objects::TxAffectedObjects,
+    tx_balance_changes::TxBalanceChanges, tx_calls::TxCalls, tx_digests::TxDigests,
+    tx_kinds::TxKinds,
 };
 use prometheus::Registry;
 use sui_indexer_alt_framework::{
@@ -20,12 +21,13 @@ use sui_indexer_alt_framework::{
     pipeline::{
         concurrent::{ConcurrentConfig, PrunerConfig},
         sequential::SequentialConfig,
-        CommitterConfig,
+        CommitterConfig, Processor,
     },
     postgres::{Db, DbArgs},
     Indexer, IndexerArgs,
 };
 use sui_indexer_alt_metrics::db::DbConnectionStatsCollector;
+use sui_indexer_alt_reader::pg_reader::PgReader;
 use sui_indexer_alt_schema::MIGRATIONS;
 use tokio_util::sync::CancellationToken;
 use url::Url;
@@ -56,6 +58,7 @@ pub async fn setup_indexer(
         committer,
         pruner,
         pipeline,
+        filters,
         extra: _,
     } = indexer_config.finish()?;
 
@@ -81,9 +84,30 @@ pub async fn setup_indexer(
         tx_calls,
         tx_digests,
         tx_kinds,
+        filters: filters_pipeline,
         extra: _,
     } = pipeline.finish()?;
 
+    let filters = filters
+        .into_iter()
+        .map(|(name, layer)| layer.finish(&name))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // Filters decode the values they select using the packages that kv_packages writes, so they
+    // cannot run without it.
+    if !filters.is_empty() {
+        let selected = |name: &str| {
+            indexer_args.pipeline.is_empty() || indexer_args.pipeline.iter().any(|p| p == name)
+        };
+
+        ensure!(
+            !selected(<Filters as Processor>::NAME)
+                || (kv_packages.is_some() && selected(<KvPackages as Processor>::NAME)),
+            "The filters pipeline needs the kv_packages pipeline to be enabled, to decode the \
+             values it selects",
+        );
+    }
+
     let ingestion = ingestion.finish(IngestionConfig::default())?;
     let committer = committer.finish(CommitterConfig::default())?;
     let pruner = pruner.finish(PrunerConfig::default())?;
@@ -91,7 +115,7 @@ pub async fn setup_indexer(
     let retry_interval = ingestion.retry_interval();
 
     // Prepare the store for the indexer
-    let store = Db::for_write(database_url, db_args)
+    let store = Db::for_write(database_url.clone(), db_args.clone())
         .await
         .context("Failed to connect to database")?;
 
@@ -193,5 +217,27 @@ pub async fn setup_indexer(
     add_concurrent!(TxDigests, tx_digests);
     add_concurrent!(TxKinds, tx_kinds);
 
+    // Declarative filters, all served by one pipeline that is enabled if any filters are
+    // configured. Their values are decoded using packages read back from the indexer's own
+    // database, through the processor context's package resolver.
+    if !filters.is_empty() {
+        let reader = PgReader::new(
+            Some("filters_reader"),
+            Some(database_url),
+            db_args,
+            registry,
+            cancel.child_token(),
+        )
+        .await
+        .context("Failed to create reader for filters")?;
+
+        let handler = Filters::new(filters, reader, cancel.child_token());
+        handler
+            .prepare(&mut indexer.store().connect().await?)
+            .await?;
+
+        add_concurrent!(handler, Some(filters_pipeline.unwrap_or_default()));
+    }
+
     Ok(indexer)
 }
@@ -13,6 +13,7 @@ use sui_indexer_alt::args::Command;
 use sui_indexer_alt::config::IndexerConfig;
 use sui_indexer_alt::config::Merge;
 use sui_indexer_alt::setup_indexer;
+use sui_indexer_alt_framework::ingestion::local_writer::LocalIngestionWriter;
 use sui_indexer_alt_framework::postgres::reset_database;
 use sui_indexer_alt_metrics::uptime;
 use sui_indexer_alt_metrics::MetricsService;
@@ -152,6 +153,34 @@ async fn main() -> Result<()> {
             .await?;
         }
 
+        Command::RelayCheckpoints {
+            data_ingestion_dir,
+            local_ingestion_path,
+            writer_config,
+        } => {
+            let writer = LocalIngestionWriter::new(local_ingestion_path, writer_config)
+                .await
+                .context("Failed to set-up local ingestion directory")?;
+
+            let cancel = CancellationToken::new();
+            let h_ctrl_c = tokio::spawn({
+                let cancel = cancel.clone();
+                async move {
+                    if signal::ctrl_c().await.is_ok() {
+                        info!("Received Ctrl-C, shutting down...");
+                        cancel.cancel();
+                    }
+                }
+            });
+
+            writer
+                .relay(&data_ingestion_dir, cancel)
+                .await
+                .context("Failed to relay checkpoints")?;
+
+            h_ctrl_c.abort();
+        }
+
         #[cfg(feature = "benchmark")]
         Command::Benchmark {
             database_url,
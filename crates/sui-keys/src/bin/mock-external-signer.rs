@@ -0,0 +1,82 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A signer for `ExternalKeystore` that holds its keys in a regular `sui.keystore` file, for
+//! testing the external signer protocol.
+//!
+//! Usage: `mock-external-signer <KEYSTORE_PATH> [--reject]`. With `--reject`, every sign request
+//! is refused, as if the user had declined it on their device.
+
+use anyhow::{anyhow, bail};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::HashFunction;
+use std::io::{stdin, BufRead};
+use std::path::PathBuf;
+use sui_keys::external::{
+    ExternalKey, ListKeysResponse, SignResponse, SignerMethod, SignerRequest, SignerResponse,
+    PROTOCOL_VERSION,
+};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{DefaultHash, EncodeDecodeBase64, PublicKey};
+
+fn main() {
+    let response = match respond() {
+        Ok(response) => response,
+        Err(e) => serde_json::to_value(SignerResponse::<()>::Error {
+            error: e.to_string(),
+        })
+        .unwrap(),
+    };
+    println!("{response}");
+}
+
+fn respond() -> Result<serde_json::Value, anyhow::Error> {
+    let mut args = std::env::args().skip(1);
+    let path = args
+        .next()
+        .ok_or_else(|| anyhow!("Usage: mock-external-signer <KEYSTORE_PATH> [--reject]"))?;
+    let reject = args.any(|arg| arg == "--reject");
+    let keystore = FileBasedKeystore::new(&PathBuf::from(path))?;
+
+    let mut line = String::new();
+    stdin().lock().read_line(&mut line)?;
+    let SignerRequest { version, method } = serde_json::from_str(&line)?;
+    if version != PROTOCOL_VERSION {
+        bail!("Unsupported protocol version {version}");
+    }
+
+    Ok(match method {
+        SignerMethod::ListKeys => serde_json::to_value(ListKeysResponse {
+            keys: keystore
+                .addresses_with_alias()
+                .into_iter()
+                .map(|(_, alias)| ExternalKey {
+                    public_key: alias.public_key_base64.clone(),
+                    alias: Some(alias.alias.clone()),
+                })
+                .collect(),
+        })?,
+
+        SignerMethod::Sign { .. } if reject => bail!("Request rejected by user"),
+
+        SignerMethod::Sign {
+            public_key,
+            intent_message,
+        } => {
+            let public_key = PublicKey::decode_base64(&public_key)
+                .map_err(|e| anyhow!("Invalid public key: {e}"))?;
+            let intent_message = Base64::decode(&intent_message)
+                .map_err(|e| anyhow!("Invalid intent message: {e}"))?;
+
+            let mut hasher = DefaultHash::default();
+            hasher.update(&intent_message);
+            let digest = hasher.finalize().digest;
+
+            let signature = keystore.sign_hashed(&SuiAddress::from(&public_key), &digest)?;
+            serde_json::to_value(SignResponse {
+                signature: signature.encode_base64(),
+            })?
+        }
+    })
+}
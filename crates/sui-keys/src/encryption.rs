@@ -0,0 +1,247 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Passphrase encryption for `FileBasedKeystore`. An encrypted keystore file holds, instead of a
+//! list of private keys, a JSON object with the scrypt parameters used to derive an AES-256-GCM
+//! key from the passphrase, and the encrypted list of private keys.
+//!
+//! The passphrase is needed whenever the keystore is loaded. It is read from
+//! `SUI_KEYSTORE_PASSPHRASE` if set, or else it must have been provided to [provide_passphrase]
+//! (e.g. by an application that prompted for it). Otherwise, loading fails with [KeystoreLocked].
+//!
+//! Alternatively, the key derived from the passphrase can be set as `SUI_KEYSTORE_KEY` (it is
+//! printed by `sui keytool unlock`), which skips the slow key derivation and keeps the passphrase
+//! itself out of the environment. This key is not a session credential: it is the keystore's
+//! encryption key, it does not expire, and anyone holding it can decrypt the keystore until it is
+//! re-encrypted with a new passphrase (which derives a new key).
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, ensure, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+/// Environment variable holding the keystore passphrase.
+pub const PASSPHRASE_ENV: &str = "SUI_KEYSTORE_PASSPHRASE";
+/// Environment variable holding the key derived from the passphrase, as printed by
+/// `sui keytool unlock`.
+pub const KEY_ENV: &str = "SUI_KEYSTORE_KEY";
+
+/// Passphrases provided for encrypted files, by path.
+static PASSPHRASES: Mutex<BTreeMap<PathBuf, Zeroizing<String>>> = Mutex::new(BTreeMap::new());
+
+const VERSION: u8 = 1;
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+// scrypt's recommended interactive parameters: 32 MiB of memory, about 100ms to derive a key.
+const DEFAULT_LOG_N: u8 = 15;
+const DEFAULT_R: u32 = 8;
+const DEFAULT_P: u32 = 1;
+
+/// The error returned when an encrypted keystore (or wallets file) is loaded without its
+/// passphrase or key in the environment, and without a passphrase provided for it. Applications
+/// that can ask for the passphrase should pass it to [provide_passphrase] and load it again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeystoreLocked {
+    pub path: PathBuf,
+}
+
+impl fmt::Display for KeystoreLocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is encrypted, set {PASSPHRASE_ENV} or {KEY_ENV} to unlock it",
+            self.path.display()
+        )
+    }
+}
+
+impl std::error::Error for KeystoreLocked {}
+
+/// Unlock the encrypted file at `path` with `passphrase` whenever it is loaded later on in this
+/// process, unless the environment holds a passphrase or key for it.
+pub fn provide_passphrase(path: &Path, passphrase: Zeroizing<String>) {
+    PASSPHRASES
+        .lock()
+        .unwrap()
+        .insert(path.to_path_buf(), passphrase);
+}
+
+/// The on-disk format of an encrypted keystore.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedKeystore {
+    pub version: u8,
+    pub kdf: ScryptParams,
+    /// Base64 encoded AES-256-GCM nonce.
+    pub nonce: String,
+    /// Base64 encoded AES-256-GCM encryption of the keystore's JSON list of private keys.
+    pub ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "name", rename = "scrypt", rename_all = "camelCase")]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    /// Base64 encoded salt.
+    pub salt: String,
+}
+
+/// The key a keystore is encrypted with, together with the parameters it was derived with, so
+/// that the keystore can be re-encrypted when it changes without asking for the passphrase again.
+pub struct KeystoreCipher {
+    kdf: ScryptParams,
+    key: Zeroizing<[u8; KEY_LENGTH]>,
+}
+
+impl KeystoreCipher {
+    /// Derive a key from `passphrase` with a fresh salt, for encrypting a keystore.
+    pub fn new(passphrase: &str) -> Result<Self, anyhow::Error> {
+        ensure!(
+            !passphrase.is_empty(),
+            "Keystore passphrase cannot be empty"
+        );
+        let mut salt = [0u8; SALT_LENGTH];
+        thread_rng().fill_bytes(&mut salt);
+        let kdf = ScryptParams {
+            log_n: DEFAULT_LOG_N,
+            r: DEFAULT_R,
+            p: DEFAULT_P,
+            salt: Base64::encode(salt),
+        };
+        Self::derive(kdf, passphrase)
+    }
+
+    /// Derive the key for an existing keystore from `passphrase`.
+    pub fn derive(kdf: ScryptParams, passphrase: &str) -> Result<Self, anyhow::Error> {
+        let salt = Base64::decode(&kdf.salt).map_err(|e| anyhow!("Invalid keystore salt: {e}"))?;
+        let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p)
+            .map_err(|e| anyhow!("Invalid keystore scrypt parameters: {e}"))?;
+
+        let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+        scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key[..])
+            .map_err(|e| anyhow!("Cannot derive keystore key: {e}"))?;
+        Ok(Self { kdf, key })
+    }
+
+    /// Find the key for `encrypted`, from the derived key or passphrase in the environment, or the
+    /// passphrase provided for `path`. Returns the key along with the decrypted contents of the
+    /// keystore, or a [KeystoreLocked] error if there is no way to unlock it.
+    pub fn unlock(
+        path: &Path,
+        encrypted: &EncryptedKeystore,
+    ) -> Result<(Self, Zeroizing<Vec<u8>>), anyhow::Error> {
+        ensure!(
+            encrypted.version == VERSION,
+            "Unsupported keystore encryption version {} in {}",
+            encrypted.version,
+            path.display()
+        );
+
+        // A key for another keystore is not an error, there may be several of them.
+        if let Ok(key) = std::env::var(KEY_ENV) {
+            if let Ok(cipher) = Self::from_encoded_key(encrypted.kdf.clone(), &key) {
+                if let Ok(plaintext) = cipher.decrypt(encrypted) {
+                    return Ok((cipher, plaintext));
+                }
+            }
+        }
+
+        let passphrase = match std::env::var(PASSPHRASE_ENV) {
+            Ok(passphrase) => Zeroizing::new(passphrase),
+            Err(_) => match PASSPHRASES.lock().unwrap().get(path) {
+                Some(passphrase) => passphrase.clone(),
+                None => {
+                    return Err(KeystoreLocked {
+                        path: path.to_path_buf(),
+                    }
+                    .into())
+                }
+            },
+        };
+
+        let cipher = Self::derive(encrypted.kdf.clone(), &passphrase)?;
+        let plaintext = cipher
+            .decrypt(encrypted)
+            .with_context(|| format!("Cannot unlock keystore {}", path.display()))?;
+        Ok((cipher, plaintext))
+    }
+
+    /// Whether [Self::unlock] has a key or passphrase to try for `encrypted`, without checking
+    /// that a passphrase is correct (which is slow).
+    pub fn can_unlock(path: &Path, encrypted: &EncryptedKeystore) -> bool {
+        let key_unlocks = std::env::var(KEY_ENV).is_ok_and(|key| {
+            Self::from_encoded_key(encrypted.kdf.clone(), &key)
+                .and_then(|cipher| cipher.decrypt(encrypted))
+                .is_ok()
+        });
+
+        key_unlocks
+            || std::env::var_os(PASSPHRASE_ENV).is_some()
+            || PASSPHRASES.lock().unwrap().contains_key(path)
+    }
+
+    /// The derived key, Base64 encoded for `KEY_ENV`. It decrypts anything encrypted with this
+    /// cipher, for as long as the passphrase is unchanged.
+    pub fn encoded_key(&self) -> String {
+        Base64::encode(&self.key[..])
+    }
+
+    fn from_encoded_key(kdf: ScryptParams, encoded_key: &str) -> Result<Self, anyhow::Error> {
+        let bytes = Zeroizing::new(
+            Base64::decode(encoded_key).map_err(|e| anyhow!("Invalid keystore key: {e}"))?,
+        );
+        let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+        ensure!(bytes.len() == KEY_LENGTH, "Invalid keystore key length");
+        key.copy_from_slice(&bytes);
+        Ok(Self { kdf, key })
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptedKeystore, anyhow::Error> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = self
+            .aead()?
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| anyhow!("Cannot encrypt keystore"))?;
+
+        Ok(EncryptedKeystore {
+            version: VERSION,
+            kdf: self.kdf.clone(),
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(
+        &self,
+        encrypted: &EncryptedKeystore,
+    ) -> Result<Zeroizing<Vec<u8>>, anyhow::Error> {
+        let nonce =
+            Base64::decode(&encrypted.nonce).map_err(|e| anyhow!("Invalid keystore nonce: {e}"))?;
+        ensure!(nonce.len() == NONCE_LENGTH, "Invalid keystore nonce length");
+        let ciphertext = Base64::decode(&encrypted.ciphertext)
+            .map_err(|e| anyhow!("Invalid keystore ciphertext: {e}"))?;
+
+        // AES-GCM authenticates the ciphertext, so a wrong key is reported here, rather than as
+        // garbage keys later on.
+        self.aead()?
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map(Zeroizing::new)
+            .map_err(|_| anyhow!("Incorrect passphrase"))
+    }
+
+    fn aead(&self) -> Result<Aes256Gcm, anyhow::Error> {
+        Aes256Gcm::new_from_slice(&self.key[..]).map_err(|e| anyhow!("Invalid key: {e}"))
+    }
+}
@@ -0,0 +1,380 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A keystore whose private keys live in a separate signer process (a hardware wallet bridge, an
+//! HSM daemon, ...), so that they never touch `sui.keystore`. It is configured in `client.yaml`:
+//!
+//! ```yaml
+//! keystore:
+//!   External:
+//!     command: /usr/local/bin/my-signer
+//!     args: ["--device", "0"]
+//! ```
+//!
+//! For every request the signer is started with `args`, sent a single line of JSON on its stdin,
+//! and is expected to print a single JSON response to its stdout and exit successfully. Anything
+//! it writes to stderr (e.g. "confirm on device") is passed through to the user. Requests are:
+//!
+//! - `{"version": 1, "method": "list_keys"}`, answered with
+//!   `{"keys": [{"publicKey": "<base64 flag || pk>", "alias": "<optional>"}]}`.
+//! - `{"version": 1, "method": "sign", "publicKey": "...", "intentMessage": "..."}` where
+//!   `intentMessage` is the Base64 encoded BCS bytes of the `IntentMessage` to sign, answered
+//!   with `{"signature": "<base64 flag || sig || pk>"}`.
+//!
+//! A signer reports failure by responding with `{"error": "<message>"}` instead.
+//!
+//! Loading the keystore does not start the signer: it is asked for its keys the first time they
+//! are needed, and only then.
+
+use crate::keystore::{AccountKeystore, Alias};
+use crate::random_names::random_name;
+use anyhow::{anyhow, bail, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use once_cell::sync::OnceCell;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, Signature, SuiKeyPair, SuiSignature};
+
+/// Version of the signer protocol spoken by this keystore.
+pub const PROTOCOL_VERSION: u64 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExternalSignerConfig {
+    /// Path to the signer executable.
+    pub command: PathBuf,
+    /// Arguments passed to the signer on every invocation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SignerRequest {
+    pub version: u64,
+    #[serde(flatten)]
+    pub method: SignerMethod,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerMethod {
+    ListKeys,
+    #[serde(rename_all = "camelCase")]
+    Sign {
+        /// Base64 encoded `flag || pk` of the key to sign with.
+        public_key: String,
+        /// Base64 encoded BCS bytes of the `IntentMessage` to sign.
+        intent_message: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum SignerResponse<T> {
+    Error { error: String },
+    Ok(T),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListKeysResponse {
+    pub keys: Vec<ExternalKey>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalKey {
+    pub public_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SignResponse {
+    /// Base64 encoded `flag || sig || pk`.
+    pub signature: String,
+}
+
+pub struct ExternalKeystore {
+    config: ExternalSignerConfig,
+    /// The keys held by the signer, fetched on first use.
+    keys: OnceCell<ExternalKeys>,
+}
+
+#[derive(Default)]
+struct ExternalKeys {
+    keys: BTreeMap<SuiAddress, PublicKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.config.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ExternalKeystore::new(ExternalSignerConfig::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn sign_hashed(
+        &self,
+        address: &SuiAddress,
+        _msg: &[u8],
+    ) -> Result<Signature, signature::Error> {
+        Err(signature::Error::from_source(format!(
+            "Cannot sign a pre-hashed message for address [{address}]: external signers only \
+             sign intent messages"
+        )))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let keys = self
+            .signer_keys()
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        let public_key = keys.keys.get(address).ok_or_else(|| {
+            signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
+        })?;
+
+        let intent_msg = IntentMessage::new(intent, msg);
+        let bytes = bcs::to_bytes(&intent_msg).map_err(signature::Error::from_source)?;
+        let SignResponse { signature } = self
+            .request(SignerMethod::Sign {
+                public_key: public_key.encode_base64(),
+                intent_message: Base64::encode(bytes),
+            })
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+
+        let signature = Signature::decode_base64(&signature).map_err(|e| {
+            signature::Error::from_source(format!("Invalid signature from external signer: {e}"))
+        })?;
+
+        // Don't trust the signer to have signed the right message with the right key.
+        signature
+            .verify_secure(&intent_msg, *address, signature.scheme())
+            .map_err(|e| {
+                signature::Error::from_source(format!(
+                    "External signer returned an invalid signature for [{address}]: {e}"
+                ))
+            })?;
+
+        Ok(signature)
+    }
+
+    fn add_key(
+        &mut self,
+        _alias: Option<String>,
+        _keypair: SuiKeyPair,
+    ) -> Result<(), anyhow::Error> {
+        bail!(
+            "Cannot add a private key to an external signer keystore, add it to the signer instead"
+        )
+    }
+
+    fn remove_key(&mut self, _address: SuiAddress) -> Result<(), anyhow::Error> {
+        bail!("Cannot remove a key from an external signer keystore, remove it from the signer instead")
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.loaded_keys().keys.values().cloned().collect()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        if self.signer_keys()?.keys.contains_key(address) {
+            bail!("The private key for address [{address}] is held by the external signer")
+        } else {
+            bail!("Cannot find key for address: [{address}]")
+        }
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.loaded_keys().aliases.iter().collect::<Vec<_>>()
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.loaded_keys().aliases.values().collect()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        let _ = self.signer_keys();
+        match self.keys.get_mut() {
+            Some(keys) => keys.aliases.values_mut().collect(),
+            None => vec![],
+        }
+    }
+
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.signer_keys()?.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.signer_keys()?
+            .aliases
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    fn create_alias(&self, _alias: Option<String>) -> Result<String, anyhow::Error> {
+        bail!("Aliases of keys in an external signer keystore are chosen by the signer")
+    }
+
+    fn update_alias(
+        &mut self,
+        _old_alias: &str,
+        _new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        bail!("Aliases of keys in an external signer keystore are chosen by the signer")
+    }
+}
+
+impl ExternalKeystore {
+    /// A keystore backed by the signer described by `config`. The signer is not started until
+    /// its keys are first needed.
+    pub fn new(config: ExternalSignerConfig) -> Self {
+        Self {
+            config,
+            keys: OnceCell::new(),
+        }
+    }
+
+    /// Start the signer and fetch the keys it holds, if that has not happened yet.
+    pub fn connect(&self) -> Result<(), anyhow::Error> {
+        self.signer_keys().map(|_| ())
+    }
+
+    pub fn config(&self) -> &ExternalSignerConfig {
+        &self.config
+    }
+
+    /// The keys held by the signer, asking the signer for them if this is the first time they are
+    /// needed. A failed attempt is not remembered, so the next use asks again.
+    fn signer_keys(&self) -> Result<&ExternalKeys, anyhow::Error> {
+        self.keys.get_or_try_init(|| self.list_keys())
+    }
+
+    /// Like [Self::signer_keys], for the methods of [AccountKeystore] that cannot fail: a keystore
+    /// whose signer cannot be reached holds no keys.
+    fn loaded_keys(&self) -> &ExternalKeys {
+        static EMPTY: ExternalKeys = ExternalKeys {
+            keys: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+        };
+
+        self.signer_keys().unwrap_or(&EMPTY)
+    }
+
+    fn list_keys(&self) -> Result<ExternalKeys, anyhow::Error> {
+        let mut signer_keys = ExternalKeys::default();
+
+        let ListKeysResponse { keys } = self.request(SignerMethod::ListKeys)?;
+        for ExternalKey { public_key, alias } in keys {
+            let public_key = PublicKey::decode_base64(&public_key).map_err(|e| {
+                anyhow!("Invalid public key {public_key} from external signer: {e}")
+            })?;
+            let address = SuiAddress::from(&public_key);
+
+            // Signers that don't name their keys get a random alias for the session.
+            let alias = match alias {
+                Some(alias) => alias,
+                None => random_name(
+                    &signer_keys
+                        .aliases
+                        .values()
+                        .map(|a| a.alias.clone())
+                        .collect::<HashSet<_>>(),
+                ),
+            };
+
+            signer_keys.aliases.insert(
+                address,
+                Alias {
+                    alias,
+                    public_key_base64: public_key.encode_base64(),
+                },
+            );
+            signer_keys.keys.insert(address, public_key);
+        }
+
+        Ok(signer_keys)
+    }
+
+    /// Run the signer once to answer `method`.
+    fn request<T: DeserializeOwned>(&self, method: SignerMethod) -> Result<T, anyhow::Error> {
+        let command = &self.config.command;
+        let request = serde_json::to_string(&SignerRequest {
+            version: PROTOCOL_VERSION,
+            method,
+        })?;
+
+        let mut child = Command::new(command)
+            .args(&self.config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Cannot start external signer {}", command.display()))?;
+
+        // Dropping stdin after the request closes it, so the signer sees the end of its input.
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("Cannot write to external signer {}", command.display()))?;
+        writeln!(stdin, "{request}")
+            .with_context(|| format!("Cannot write to external signer {}", command.display()))?;
+        drop(stdin);
+
+        let output = child
+            .wait_with_output()
+            .with_context(|| format!("External signer {} did not finish", command.display()))?;
+        if !output.status.success() {
+            bail!(
+                "External signer {} failed with {}",
+                command.display(),
+                output.status
+            );
+        }
+
+        let response: SignerResponse<T> =
+            serde_json::from_slice(&output.stdout).with_context(|| {
+                format!(
+                    "Cannot parse response from external signer {}",
+                    command.display()
+                )
+            })?;
+
+        match response {
+            SignerResponse::Ok(response) => Ok(response),
+            SignerResponse::Error { error } => {
+                bail!("External signer {} failed: {error}", command.display())
+            }
+        }
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! HD wallets: a BIP-39 seed stored once, encrypted with a passphrase, from which keys are
+//! derived one account index at a time, for any of the schemes `key_derive` supports.
+//!
+//! The wallets of a keystore are kept next to it, in `<keystore>.wallets`. Besides the encrypted
+//! seed, each wallet records the accounts derived from it so far, with their derivation paths and
+//! public keys, which is enough to list or export them without the passphrase.
+//!
+//! Account `i` is derived at the account level of the scheme's path, as Sui Wallet does:
+//! m/44'/784'/{i}'/0'/0' for Ed25519, m/54'/784'/{i}'/0/0 for Secp256k1, and m/74'/784'/{i}'/0/0
+//! for Secp256r1.
+
+use crate::encryption::{EncryptedKeystore, KeystoreCipher};
+use crate::key_derive::{
+    derive_key_pair_from_path, parse_word_length, DERIVATION_PATH_COIN_TYPE,
+    DERVIATION_PATH_PURPOSE_ED25519, DERVIATION_PATH_PURPOSE_SECP256K1,
+    DERVIATION_PATH_PURPOSE_SECP256R1,
+};
+use crate::keystore::{AccountKeystore, Keystore};
+use anyhow::{anyhow, bail, ensure, Context};
+use bip32::DerivationPath;
+use bip39::{Language, Mnemonic, Seed};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, SignatureScheme, SuiKeyPair};
+use zeroize::Zeroizing;
+
+/// The HD wallets of a keystore, as saved in `<keystore>.wallets`.
+pub struct HdWallets {
+    path: PathBuf,
+    wallets: BTreeMap<String, HdWallet>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HdWallet {
+    /// The wallet's BIP-39 seed, encrypted with the wallet's passphrase.
+    seed: EncryptedKeystore,
+    /// The accounts derived so far, in the order they were derived.
+    #[serde(default)]
+    accounts: Vec<HdAccount>,
+}
+
+/// An account derived from an HD wallet. It only holds public information.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HdAccount {
+    pub key_scheme: SignatureScheme,
+    pub index: u32,
+    pub derivation_path: String,
+    pub address: SuiAddress,
+    pub public_key_base64: String,
+}
+
+/// The accounts of a wallet, without its seed, for watching them from elsewhere.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchOnlyWallet {
+    pub name: String,
+    pub accounts: Vec<HdAccount>,
+}
+
+impl HdWallets {
+    /// The path of the wallets file for the keystore at `keystore_path`.
+    pub fn path_for_keystore(keystore_path: &Path) -> PathBuf {
+        keystore_path.with_extension("wallets")
+    }
+
+    /// Load the wallets of `keystore`, which must be file based.
+    pub fn for_keystore(keystore: &Keystore) -> Result<Self, anyhow::Error> {
+        let path = match keystore {
+            Keystore::File(file) => file.path(),
+            _ => None,
+        }
+        .ok_or_else(|| anyhow!("HD wallets are only supported for file based keystores"))?;
+        Self::load(&Self::path_for_keystore(path))
+    }
+
+    /// Load the wallets saved at `path`, if any.
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let wallets = if path.exists() {
+            let contents = fs::read(path)
+                .with_context(|| format!("Cannot open wallets file: {}", path.display()))?;
+            serde_json::from_slice(&contents)
+                .with_context(|| format!("Cannot deserialize wallets file: {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            wallets,
+        })
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let contents = serde_json::to_string_pretty(&self.wallets)
+            .with_context(|| format!("Cannot serialize wallets file: {}", self.path.display()))?;
+        fs::write(&self.path, contents)
+            .with_context(|| format!("Cannot write wallets file: {}", self.path.display()))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn wallets(&self) -> impl Iterator<Item = (&str, &HdWallet)> {
+        self.wallets.iter().map(|(name, w)| (name.as_str(), w))
+    }
+
+    pub fn get(&self, name: &str) -> Result<&HdWallet, anyhow::Error> {
+        self.wallets
+            .get(name)
+            .ok_or_else(|| anyhow!("Cannot find HD wallet {name}"))
+    }
+
+    /// Create a wallet called `name` with a newly generated mnemonic of `word_length` words, and
+    /// save it. Returns the mnemonic phrase, which is not stored.
+    pub fn generate(
+        &mut self,
+        name: &str,
+        word_length: Option<String>,
+        passphrase: &str,
+    ) -> Result<String, anyhow::Error> {
+        let mnemonic = Mnemonic::new(parse_word_length(word_length)?, Language::English);
+        self.import(name, mnemonic.phrase(), passphrase)?;
+        Ok(mnemonic.phrase().to_string())
+    }
+
+    /// Create a wallet called `name` from an existing mnemonic phrase, and save it.
+    pub fn import(
+        &mut self,
+        name: &str,
+        phrase: &str,
+        passphrase: &str,
+    ) -> Result<(), anyhow::Error> {
+        let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_\.]*$")
+            .map_err(|_| anyhow!("Cannot build the regex needed to validate the wallet name"))?;
+        ensure!(
+            re.is_match(name),
+            "Invalid wallet name {name}. A wallet name must start with a letter and can contain \
+             only letters, digits, hyphens (-), dots (.), or underscores (_)."
+        );
+        ensure!(
+            !self.wallets.contains_key(name),
+            "HD wallet {name} already exists"
+        );
+
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+            .map_err(|e| anyhow!("Invalid mnemonic phrase: {:?}", e))?;
+        let seed = Seed::new(&mnemonic, "");
+        let seed = KeystoreCipher::new(passphrase)?.encrypt(seed.as_bytes())?;
+
+        self.wallets.insert(
+            name.to_string(),
+            HdWallet {
+                seed,
+                accounts: vec![],
+            },
+        );
+        self.save()
+    }
+
+    /// Record `account` as derived from wallet `name`, and save the wallets.
+    pub fn record_account(&mut self, name: &str, account: HdAccount) -> Result<(), anyhow::Error> {
+        let wallet = self
+            .wallets
+            .get_mut(name)
+            .ok_or_else(|| anyhow!("Cannot find HD wallet {name}"))?;
+        if !wallet.accounts.contains(&account) {
+            wallet.accounts.push(account);
+        }
+        self.save()
+    }
+
+    /// Derive the next account of wallet `name` for `key_scheme`, add its key to `keystore`
+    /// under `alias`, and record it in the wallet. The wallet is unlocked as in
+    /// [HdWallet::unlock].
+    pub fn add_next_account(
+        &mut self,
+        keystore: &mut Keystore,
+        name: &str,
+        key_scheme: SignatureScheme,
+        alias: Option<String>,
+    ) -> Result<HdAccount, anyhow::Error> {
+        let wallet = self.get(name)?;
+        let seed = wallet.unlock(&self.path)?;
+        let (account, keypair) = derive_account(&seed, key_scheme, wallet.next_index(key_scheme))?;
+
+        keystore.add_key(alias, keypair)?;
+        self.record_account(name, account.clone())?;
+        Ok(account)
+    }
+}
+
+impl HdWallet {
+    /// Decrypt the wallet's seed, with the derived key or passphrase from the environment, or a
+    /// passphrase provided for `path`, the wallets file. Fails with
+    /// [crate::encryption::KeystoreLocked] if there is neither.
+    pub fn unlock(&self, path: &Path) -> Result<Zeroizing<Vec<u8>>, anyhow::Error> {
+        KeystoreCipher::unlock(path, &self.seed).map(|(_, seed)| seed)
+    }
+
+    pub fn unlock_with_passphrase(
+        &self,
+        passphrase: &str,
+    ) -> Result<Zeroizing<Vec<u8>>, anyhow::Error> {
+        KeystoreCipher::derive(self.seed.kdf.clone(), passphrase)?.decrypt(&self.seed)
+    }
+
+    pub fn accounts(&self) -> &[HdAccount] {
+        &self.accounts
+    }
+
+    /// The index of the next account to derive for `key_scheme`: one past the highest index
+    /// derived so far.
+    pub fn next_index(&self, key_scheme: SignatureScheme) -> u32 {
+        self.accounts
+            .iter()
+            .filter(|a| a.key_scheme == key_scheme)
+            .map(|a| a.index + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn watch_only(&self, name: &str) -> WatchOnlyWallet {
+        WatchOnlyWallet {
+            name: name.to_string(),
+            accounts: self.accounts.clone(),
+        }
+    }
+}
+
+/// The derivation path of account `index` for `key_scheme`.
+pub fn account_derivation_path(
+    key_scheme: SignatureScheme,
+    index: u32,
+) -> Result<DerivationPath, anyhow::Error> {
+    let path = match key_scheme {
+        SignatureScheme::ED25519 => format!(
+            "m/{DERVIATION_PATH_PURPOSE_ED25519}'/{DERIVATION_PATH_COIN_TYPE}'/{index}'/0'/0'"
+        ),
+        SignatureScheme::Secp256k1 => format!(
+            "m/{DERVIATION_PATH_PURPOSE_SECP256K1}'/{DERIVATION_PATH_COIN_TYPE}'/{index}'/0/0"
+        ),
+        SignatureScheme::Secp256r1 => format!(
+            "m/{DERVIATION_PATH_PURPOSE_SECP256R1}'/{DERIVATION_PATH_COIN_TYPE}'/{index}'/0/0"
+        ),
+        _ => bail!("HD wallets do not support key scheme {key_scheme}"),
+    };
+    path.parse()
+        .map_err(|e| anyhow!("Invalid derivation path {path}: {e}"))
+}
+
+/// Derive account `index` for `key_scheme` from `seed`.
+pub fn derive_account(
+    seed: &[u8],
+    key_scheme: SignatureScheme,
+    index: u32,
+) -> Result<(HdAccount, SuiKeyPair), anyhow::Error> {
+    let path = account_derivation_path(key_scheme, index)?;
+    let derivation_path = path.to_string();
+    let (address, keypair) = derive_key_pair_from_path(seed, Some(path), &key_scheme)
+        .map_err(|e| anyhow!("Cannot derive account {index}: {e}"))?;
+
+    let account = HdAccount {
+        key_scheme,
+        index,
+        derivation_path,
+        address,
+        public_key_base64: keypair.public().encode_base64(),
+    };
+    Ok((account, keypair))
+}
@@ -181,7 +181,7 @@ pub fn generate_new_key(
     }
 }
 
-fn parse_word_length(s: Option<String>) -> Result<MnemonicType, anyhow::Error> {
+pub(crate) fn parse_word_length(s: Option<String>) -> Result<MnemonicType, anyhow::Error> {
     match s {
         None => Ok(MnemonicType::Words12),
         Some(s) => match s.as_str() {
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::encryption::{EncryptedKeystore, KeystoreCipher};
+use crate::external::ExternalKeystore;
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use crate::random_names::{random_name, random_names};
 use anyhow::{anyhow, bail, ensure, Context};
@@ -14,20 +16,21 @@ use std::collections::{BTreeMap, HashSet};
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::fs;
-use std::fs::File;
-use std::io::BufReader;
+use std::io::Write as _;
 use std::path::{Path, PathBuf};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
     enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
 };
+use zeroize::Zeroizing;
 
 #[derive(Serialize, Deserialize)]
 #[enum_dispatch(AccountKeystore)]
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -142,6 +145,11 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Signer Command : {:?}", external.config().command)?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -157,6 +165,17 @@ pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
     aliases: BTreeMap<SuiAddress, Alias>,
     path: Option<PathBuf>,
+    /// Set if the keystore is encrypted at rest.
+    cipher: Option<KeystoreCipher>,
+}
+
+/// The contents of a keystore file: either a plaintext list of Base64 encoded private keys, or
+/// that same list encrypted with a passphrase.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum KeystoreFile {
+    Plain(Vec<String>),
+    Encrypted(EncryptedKeystore),
 }
 
 impl Serialize for FileBasedKeystore {
@@ -308,25 +327,79 @@ impl AccountKeystore for FileBasedKeystore {
 }
 
 impl FileBasedKeystore {
+    /// Load the keystore at `path`. If it is encrypted, it is unlocked with the derived key or
+    /// passphrase from the environment, or a passphrase provided for it with
+    /// [crate::encryption::provide_passphrase], and otherwise fails with
+    /// [crate::encryption::KeystoreLocked].
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
-        let keys = if path.exists() {
-            let reader =
-                BufReader::new(File::open(path).with_context(|| {
-                    format!("Cannot open the keystore file: {}", path.display())
-                })?);
-            let kp_strings: Vec<String> = serde_json::from_reader(reader).with_context(|| {
+        Self::load(path, |encrypted| KeystoreCipher::unlock(path, encrypted))
+    }
+
+    /// Whether loading the keystore at `path` with [Self::new] would fail because it is locked, so
+    /// that an application can ask for its passphrase first.
+    pub fn is_locked(path: &Path) -> Result<bool, anyhow::Error> {
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        let contents = fs::read(path)
+            .with_context(|| format!("Cannot open the keystore file: {}", path.display()))?;
+        let file: KeystoreFile = serde_json::from_slice(&contents)
+            .with_context(|| format!("Cannot deserialize the keystore file: {}", path.display()))?;
+
+        Ok(match file {
+            KeystoreFile::Plain(_) => false,
+            KeystoreFile::Encrypted(encrypted) => !KeystoreCipher::can_unlock(path, &encrypted),
+        })
+    }
+
+    /// Load the keystore at `path`, unlocking it with `passphrase` if it is encrypted.
+    pub fn new_with_passphrase(path: &PathBuf, passphrase: &str) -> Result<Self, anyhow::Error> {
+        Self::load(path, |encrypted| {
+            let cipher = KeystoreCipher::derive(encrypted.kdf.clone(), passphrase)?;
+            let plaintext = cipher
+                .decrypt(encrypted)
+                .with_context(|| format!("Cannot unlock keystore {}", path.display()))?;
+            Ok((cipher, plaintext))
+        })
+    }
+
+    fn load(
+        path: &PathBuf,
+        unlock: impl FnOnce(
+            &EncryptedKeystore,
+        ) -> Result<(KeystoreCipher, Zeroizing<Vec<u8>>), anyhow::Error>,
+    ) -> Result<Self, anyhow::Error> {
+        let (keys, cipher) = if path.exists() {
+            let contents = fs::read(path)
+                .with_context(|| format!("Cannot open the keystore file: {}", path.display()))?;
+            let file: KeystoreFile = serde_json::from_slice(&contents).with_context(|| {
                 format!("Cannot deserialize the keystore file: {}", path.display(),)
             })?;
-            kp_strings
+
+            let (kp_strings, cipher) = match file {
+                KeystoreFile::Plain(kp_strings) => (Zeroizing::new(kp_strings), None),
+                KeystoreFile::Encrypted(encrypted) => {
+                    let (cipher, plaintext) = unlock(&encrypted)?;
+                    let kp_strings: Vec<String> =
+                        serde_json::from_slice(&plaintext).with_context(|| {
+                            format!("Cannot deserialize the keystore file: {}", path.display())
+                        })?;
+                    (Zeroizing::new(kp_strings), Some(cipher))
+                }
+            };
+
+            let keys = kp_strings
                 .iter()
                 .map(|kpstr| {
                     let key = SuiKeyPair::decode_base64(kpstr);
                     key.map(|k| (SuiAddress::from(&k.public()), k))
                 })
                 .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?
+                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?;
+            (keys, cipher)
         } else {
-            BTreeMap::new()
+            (BTreeMap::new(), None)
         };
 
         // check aliases
@@ -334,14 +407,14 @@ impl FileBasedKeystore {
         aliases_path.set_extension("aliases");
 
         let aliases = if aliases_path.exists() {
-            let reader = BufReader::new(File::open(&aliases_path).with_context(|| {
+            let contents = fs::read(&aliases_path).with_context(|| {
                 format!(
                     "Cannot open aliases file in keystore: {}",
                     aliases_path.display()
                 )
-            })?);
+            })?;
 
-            let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
+            let aliases: Vec<Alias> = serde_json::from_slice(&contents).with_context(|| {
                 format!(
                     "Cannot deserialize aliases file in keystore: {}",
                     aliases_path.display(),
@@ -387,7 +460,7 @@ impl FileBasedKeystore {
                         aliases_path.display()
                     )
                 })?;
-            fs::write(aliases_path, aliases_store)?;
+            write_atomically(&aliases_path, aliases_store.as_bytes())?;
             aliases
         };
 
@@ -395,6 +468,7 @@ impl FileBasedKeystore {
             keys,
             aliases,
             path: Some(path.to_path_buf()),
+            cipher,
         })
     }
 
@@ -415,7 +489,7 @@ impl FileBasedKeystore {
 
             let mut aliases_path = path.clone();
             aliases_path.set_extension("aliases");
-            fs::write(aliases_path, aliases_store)?
+            write_atomically(&aliases_path, aliases_store.as_bytes())?
         }
         Ok(())
     }
@@ -423,21 +497,52 @@ impl FileBasedKeystore {
     /// Keys saved as Base64 with 33 bytes `flag || privkey` ($BASE64_STR).
     /// To see Bech32 format encoding, use `sui keytool export $SUI_ADDRESS` where
     /// $SUI_ADDRESS can be found with `sui keytool list`. Or use `sui keytool convert $BASE64_STR`
+    ///
+    /// If the keystore is encrypted, the same list is saved encrypted instead.
     pub fn save_keystore(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
-            let store = serde_json::to_string_pretty(
-                &self
-                    .keys
+            let kp_strings = Zeroizing::new(
+                self.keys
                     .values()
                     .map(|k| k.encode_base64())
                     .collect::<Vec<_>>(),
-            )
+            );
+
+            let store = match &self.cipher {
+                None => serde_json::to_string_pretty(&*kp_strings),
+                Some(cipher) => {
+                    let plaintext = Zeroizing::new(serde_json::to_vec(&*kp_strings)?);
+                    let encrypted = KeystoreFile::Encrypted(cipher.encrypt(&plaintext)?);
+                    serde_json::to_string_pretty(&encrypted)
+                }
+            }
             .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
-            fs::write(path, store)?;
+            write_atomically(path, Zeroizing::new(store).as_bytes())?;
         }
         Ok(())
     }
 
+    /// Encrypt the keystore with `passphrase`, replacing any passphrase it was encrypted with
+    /// before, and save it.
+    pub fn encrypt(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        self.cipher = Some(KeystoreCipher::new(passphrase)?);
+        self.save_keystore()
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// The key this keystore is encrypted with, which unlocks it without its passphrase when set
+    /// as `KEY_ENV`. It stays valid until the keystore is encrypted with a new passphrase.
+    pub fn encoded_key(&self) -> Option<String> {
+        self.cipher.as_ref().map(|c| c.encoded_key())
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
     pub fn save(&self) -> Result<(), anyhow::Error> {
         self.save_aliases()?;
         self.save_keystore()?;
@@ -607,6 +712,25 @@ impl InMemKeystore {
     }
 }
 
+/// Replace the contents of `path` with `contents`. They are written to a temporary file in the same
+/// directory first, which is then renamed over `path`, so that a failure part way through leaves
+/// the previous contents intact, rather than a truncated file.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let mut file = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("Cannot create a temporary file in {}", dir.display()))?;
+    file.write_all(contents)
+        .and_then(|_| file.as_file().sync_all())
+        .with_context(|| format!("Cannot write to {}", file.path().display()))?;
+    file.persist(path)
+        .with_context(|| format!("Cannot write to {}", path.display()))?;
+    Ok(())
+}
+
 fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_\.]*$")
         .map_err(|_| anyhow!("Cannot build the regex needed to validate the alias naming"))?;
@@ -4,11 +4,17 @@
 use std::fs;
 use std::str::FromStr;
 
+use fastcrypto::encoding::{Base64, Encoding};
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::EncodeDecodeBase64;
 use sui_keys::key_derive::generate_new_key;
 use tempfile::TempDir;
+use zeroize::Zeroizing;
 
+use shared_crypto::intent::{Intent, PersonalMessage};
+use sui_keys::encryption::{provide_passphrase, KeystoreLocked};
+use sui_keys::external::{ExternalKeystore, ExternalSignerConfig};
+use sui_keys::hd_wallet::{derive_account, HdWallets};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
 use sui_types::{
@@ -35,6 +41,29 @@ fn alias_exists_test() {
     assert!(!aliases.contains(&"alias_does_not_exist"));
 }
 
+#[test]
+fn save_keystore_replaces_file_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    for _ in 0..2 {
+        keystore
+            .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+            .unwrap();
+    }
+
+    // Saving goes through a temporary file, which is renamed over the keystore.
+    let mut files: Vec<_> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["sui.aliases", "sui.keystore"]);
+
+    let reloaded = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert_eq!(reloaded.addresses().len(), 2);
+}
+
 #[test]
 fn create_alias_keystore_file_test() {
     let temp_dir = TempDir::new().unwrap();
@@ -307,3 +336,310 @@ fn remove_key_test() {
     let aliases_content = fs::read_to_string(&aliases_path).unwrap();
     assert!(!aliases_content.contains("test_key"));
 }
+
+fn mock_signer(temp_dir: &TempDir, args: &[&str]) -> (FileBasedKeystore, ExternalSignerConfig) {
+    let keystore_path = temp_dir.path().join("signer.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("ledger-0".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    keystore
+        .generate_and_add_new_key(SignatureScheme::Secp256k1, None, None, None)
+        .unwrap();
+
+    let config = ExternalSignerConfig {
+        command: env!("CARGO_BIN_EXE_mock-external-signer").into(),
+        args: [keystore_path.to_str().unwrap()]
+            .into_iter()
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect(),
+    };
+    (keystore, config)
+}
+
+#[test]
+fn external_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (signer_keys, config) = mock_signer(&temp_dir, &[]);
+    let keystore = Keystore::from(ExternalKeystore::new(config));
+
+    let mut addresses = keystore.addresses();
+    addresses.sort();
+    let mut expected = signer_keys.addresses();
+    expected.sort();
+    assert_eq!(addresses, expected);
+
+    let address = keystore
+        .get_address_by_alias("ledger-0".to_string())
+        .unwrap();
+    assert!(keystore.get_key(address).is_err());
+
+    let msg = PersonalMessage {
+        message: b"hello".to_vec(),
+    };
+    for address in &addresses {
+        let intent = Intent::personal_message();
+        let signature = keystore.sign_secure(address, &msg, intent.clone()).unwrap();
+        let expected = signer_keys.sign_secure(address, &msg, intent).unwrap();
+        assert_eq!(signature, expected);
+    }
+
+    let unknown = SuiAddress::random_for_testing_only();
+    assert!(keystore
+        .sign_secure(&unknown, &msg, Intent::personal_message())
+        .is_err());
+}
+
+#[test]
+fn external_keystore_is_read_only_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (_, config) = mock_signer(&temp_dir, &[]);
+    let mut keystore = Keystore::from(ExternalKeystore::new(config));
+    let address = keystore.addresses()[0];
+
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+    assert!(keystore.remove_key(address).is_err());
+    assert!(keystore.update_alias("ledger-0", Some("other")).is_err());
+    assert_eq!(keystore.addresses().len(), 2);
+}
+
+#[test]
+fn external_keystore_errors_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (_, config) = mock_signer(&temp_dir, &["--reject"]);
+    let keystore = Keystore::from(ExternalKeystore::new(config.clone()));
+    let address = keystore.addresses()[0];
+
+    let err = keystore
+        .sign_secure(&address, &"data", Intent::sui_transaction())
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Request rejected by user"),
+        "{err}"
+    );
+
+    let missing = ExternalSignerConfig {
+        command: temp_dir.path().join("no-such-signer"),
+        ..config
+    };
+    let keystore = ExternalKeystore::new(missing);
+    assert!(keystore.connect().is_err());
+    assert!(keystore.addresses().is_empty());
+}
+
+#[test]
+fn external_keystore_config_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (_, config) = mock_signer(&temp_dir, &[]);
+    let keystore = Keystore::from(ExternalKeystore::new(config.clone()));
+
+    // Only the signer configuration is persisted, the keys are fetched from the signer.
+    let json = serde_json::to_string(&keystore).unwrap();
+    assert!(json.starts_with(r#"{"External":"#), "{json}");
+    let keystore: Keystore = serde_json::from_str(&json).unwrap();
+    let Keystore::External(external) = &keystore else {
+        panic!("Expected an external keystore");
+    };
+    assert_eq!(external.config(), &config);
+    assert_eq!(keystore.addresses().len(), 2);
+
+    // Loading the configuration does not start the signer.
+    let missing = ExternalSignerConfig {
+        command: temp_dir.path().join("no-such-signer"),
+        ..config
+    };
+    let json = serde_json::to_string(&Keystore::from(ExternalKeystore::new(missing))).unwrap();
+    let keystore: Keystore = serde_json::from_str(&json).unwrap();
+    assert!(keystore
+        .get_address_by_alias("ledger-0".to_string())
+        .is_err());
+}
+
+#[test]
+fn encrypted_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    let private_key = keystore.key_pairs()[0].encode_base64();
+    assert!(!keystore.is_encrypted());
+    assert!(keystore.encoded_key().is_none());
+
+    keystore.encrypt("correct horse").unwrap();
+    assert!(keystore.is_encrypted());
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    assert!(!contents.contains(&private_key), "{contents}");
+
+    let mut keystore =
+        FileBasedKeystore::new_with_passphrase(&keystore_path, "correct horse").unwrap();
+    assert!(keystore.is_encrypted());
+    assert_eq!(keystore.key_pairs()[0].encode_base64(), private_key);
+
+    // Changes to an encrypted keystore are saved encrypted.
+    keystore
+        .generate_and_add_new_key(SignatureScheme::Secp256k1, None, None, None)
+        .unwrap();
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    for key in keystore.key_pairs() {
+        assert!(!contents.contains(&key.encode_base64()), "{contents}");
+    }
+
+    let keystore = FileBasedKeystore::new_with_passphrase(&keystore_path, "correct horse").unwrap();
+    assert_eq!(keystore.key_pairs().len(), 2);
+}
+
+#[test]
+fn locked_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    assert!(!FileBasedKeystore::is_locked(&keystore_path).unwrap());
+    keystore.encrypt("correct horse").unwrap();
+
+    // Without a passphrase, loading the keystore fails rather than prompting for one.
+    assert!(FileBasedKeystore::is_locked(&keystore_path).unwrap());
+    let err = FileBasedKeystore::new(&keystore_path).err().unwrap();
+    let locked = err.downcast_ref::<KeystoreLocked>().unwrap();
+    assert_eq!(locked.path, keystore_path);
+
+    provide_passphrase(&keystore_path, Zeroizing::new("correct horse".to_string()));
+    assert!(!FileBasedKeystore::is_locked(&keystore_path).unwrap());
+    let keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert_eq!(keystore.key_pairs().len(), 1);
+}
+
+#[test]
+fn encrypted_keystore_passphrase_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    assert!(keystore.encrypt("").is_err());
+    keystore.encrypt("old passphrase").unwrap();
+
+    let err = FileBasedKeystore::new_with_passphrase(&keystore_path, "wrong passphrase")
+        .err()
+        .unwrap();
+    assert!(
+        format!("{err:#}").contains("Incorrect passphrase"),
+        "{err:#}"
+    );
+
+    // Changing the passphrase re-encrypts the keystore, and the old passphrase stops working.
+    let mut keystore =
+        FileBasedKeystore::new_with_passphrase(&keystore_path, "old passphrase").unwrap();
+    let key = keystore.encoded_key().unwrap();
+    keystore.encrypt("new passphrase").unwrap();
+    assert_ne!(keystore.encoded_key().unwrap(), key);
+
+    assert!(FileBasedKeystore::new_with_passphrase(&keystore_path, "old passphrase").is_err());
+    let keystore =
+        FileBasedKeystore::new_with_passphrase(&keystore_path, "new passphrase").unwrap();
+    assert_eq!(keystore.key_pairs().len(), 1);
+}
+
+#[test]
+fn hd_wallet_test() {
+    let phrase = "result crisp session latin must fruit genuine question prevent start coconut brave speak student dismiss";
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let wallets_path = HdWallets::path_for_keystore(&keystore_path);
+
+    let mut wallets = HdWallets::load(&wallets_path).unwrap();
+    wallets.import("main", phrase, "correct horse").unwrap();
+    assert!(wallets.import("main", phrase, "correct horse").is_err());
+    assert!(wallets.import("1st", phrase, "correct horse").is_err());
+
+    // The seed is only stored encrypted.
+    let wallets = HdWallets::load(&wallets_path).unwrap();
+    let wallet = wallets.get("main").unwrap();
+    assert!(wallet.unlock_with_passphrase("wrong").is_err());
+    let seed = wallet.unlock_with_passphrase("correct horse").unwrap();
+    let contents = fs::read_to_string(&wallets_path).unwrap();
+    assert!(!contents.contains(&Base64::encode(&seed[..])), "{contents}");
+
+    // Account 0 is the address of the mnemonic's default path, as in Sui Wallet.
+    let (account, _) = derive_account(&seed, SignatureScheme::ED25519, 0).unwrap();
+    assert_eq!(
+        account.address,
+        SuiAddress::from_str("0x936accb491f0facaac668baaedcf4d0cfc6da1120b66f77fa6a43af718669973")
+            .unwrap()
+    );
+    assert_eq!(account.derivation_path, "m/44'/784'/0'/0'/0'");
+
+    for scheme in [
+        SignatureScheme::ED25519,
+        SignatureScheme::Secp256k1,
+        SignatureScheme::Secp256r1,
+    ] {
+        let (first, _) = derive_account(&seed, scheme, 0).unwrap();
+        let (second, keypair) = derive_account(&seed, scheme, 1).unwrap();
+        assert_ne!(first.address, second.address);
+        assert_eq!(second.address, SuiAddress::from(&keypair.public()));
+        assert_eq!(keypair.public().scheme(), scheme);
+    }
+    assert!(derive_account(&seed, SignatureScheme::BLS12381, 0).is_err());
+}
+
+#[test]
+fn hd_wallet_accounts_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let mut wallets = HdWallets::for_keystore(&keystore).unwrap();
+    assert_eq!(
+        wallets.path(),
+        HdWallets::path_for_keystore(&keystore_path).as_path()
+    );
+
+    let phrase = wallets.generate("main", None, "correct horse").unwrap();
+    assert_eq!(phrase.split_whitespace().count(), 12);
+    let seed = wallets
+        .get("main")
+        .unwrap()
+        .unlock_with_passphrase("correct horse")
+        .unwrap();
+
+    // Accounts are numbered per scheme, and recorded once.
+    for (scheme, index) in [
+        (SignatureScheme::ED25519, 0),
+        (SignatureScheme::ED25519, 1),
+        (SignatureScheme::Secp256k1, 0),
+    ] {
+        let wallet = wallets.get("main").unwrap();
+        assert_eq!(wallet.next_index(scheme), index);
+        let (account, keypair) = derive_account(&seed, scheme, index).unwrap();
+        keystore.add_key(None, keypair).unwrap();
+        wallets.record_account("main", account.clone()).unwrap();
+        wallets.record_account("main", account).unwrap();
+    }
+    assert_eq!(keystore.addresses().len(), 3);
+
+    let wallets = HdWallets::load(wallets.path()).unwrap();
+    let wallet = wallets.get("main").unwrap();
+    assert_eq!(wallet.accounts().len(), 3);
+    assert_eq!(wallet.next_index(SignatureScheme::ED25519), 2);
+    assert_eq!(wallet.next_index(SignatureScheme::Secp256r1), 0);
+
+    // The watch-only export has the accounts, but nothing secret.
+    let export = serde_json::to_string(&wallet.watch_only("main")).unwrap();
+    assert!(!export.contains("ciphertext"), "{export}");
+    for account in wallet.accounts() {
+        assert!(export.contains(&account.address.to_string()), "{export}");
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::bail;
+use clap::{Parser, ValueEnum};
+use move_analyzer::analysis::call_graph::{CallGraph as Graph, CallKind, FunId};
+use move_analyzer::symbols::get_call_graph;
+use move_cli::base;
+use move_compiler::shared::files::MappedFiles;
+use move_core_types::account_address::AccountAddress;
+use move_ir_types::location::Loc;
+use move_package::BuildConfig;
+use serde::Serialize;
+
+/// Export the call graph of a package (calls made by the package's functions, including calls
+/// through method syntax and macros) for auditing.
+#[derive(Parser)]
+#[group(id = "sui-move-call-graph")]
+pub struct CallGraph {
+    /// Format to output the call graph in.
+    #[clap(long, value_enum, default_value_t = CallGraphFormat::Dot)]
+    pub format: CallGraphFormat,
+
+    /// File to write the call graph to (instead of standard output).
+    #[clap(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Only include functions from which the given function (`<address>::<module>::<function>`,
+    /// e.g. `sui::transfer::share_object`) can be reached, along with the calls between them.
+    #[clap(long, value_name = "FUNCTION")]
+    pub reaches: Option<String>,
+
+    /// Include calls made by functions in the package's dependencies.
+    #[clap(long)]
+    pub include_deps: bool,
+
+    /// Include test and test-only functions.
+    #[clap(long)]
+    pub include_tests: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CallGraphFormat {
+    /// Graphviz DOT, with one edge per pair of calling and called functions.
+    Dot,
+    /// JSON, listing every call site.
+    Json,
+}
+
+#[derive(Serialize)]
+struct JsonCallGraph {
+    functions: Vec<JsonFunction>,
+    calls: Vec<JsonCall>,
+}
+
+#[derive(Serialize)]
+struct JsonFunction {
+    id: String,
+    address: String,
+    module: String,
+    name: String,
+    /// Absent for functions defined in modules that were not analyzed (e.g., bytecode
+    /// dependencies).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<String>,
+    entry: bool,
+    #[serde(rename = "macro")]
+    macro_: bool,
+    test: bool,
+    dependency: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<JsonLocation>,
+}
+
+#[derive(Serialize)]
+struct JsonCall {
+    caller: String,
+    callee: String,
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<JsonLocation>,
+}
+
+#[derive(Serialize)]
+struct JsonLocation {
+    file: PathBuf,
+    line: usize,
+    column: usize,
+}
+
+impl CallGraph {
+    pub fn execute(self, path: Option<&Path>, build_config: BuildConfig) -> anyhow::Result<()> {
+        let pkg_path = base::reroot_path(path)?.canonicalize()?;
+        let (graph, files) = get_call_graph(&pkg_path, build_config.implicit_dependencies)?;
+
+        let included = |fun_id: &FunId| {
+            graph.functions.get(fun_id).is_some_and(|f| {
+                (self.include_tests || !f.test) && (self.include_deps || !f.dependency)
+            })
+        };
+        let mut callers: BTreeSet<FunId> = graph
+            .functions
+            .keys()
+            .filter(|f| included(f))
+            .copied()
+            .collect();
+
+        let reaching = match &self.reaches {
+            Some(target) => {
+                // only paths starting from the included functions are of interest, and not every
+                // path through dependencies to the target
+                let reachable = reachable_from(&graph, &callers);
+                let Some(target) = find_function(&graph, target).filter(|f| reachable.contains(f))
+                else {
+                    bail!("Function '{target}' is not called in this package");
+                };
+                let mut reaching = graph.reaching(&target);
+                reaching.retain(|f| reachable.contains(f));
+                Some(reaching)
+            }
+            None => None,
+        };
+        if let Some(reaching) = &reaching {
+            // functions in dependencies are kept if they are on a path to the target, so that the
+            // path is not broken
+            callers.extend(
+                reaching
+                    .iter()
+                    .filter(|f| graph.functions.get(f).is_some_and(|f| f.dependency)),
+            );
+            callers.retain(|f| reaching.contains(f));
+        }
+
+        let calls = graph
+            .calls
+            .iter()
+            .filter(|c| callers.contains(&c.caller))
+            .filter(|c| reaching.as_ref().is_none_or(|r| r.contains(&c.callee)))
+            .collect::<Vec<_>>();
+
+        // called functions are listed even if calls they make are not
+        let mut functions = callers.clone();
+        functions.extend(calls.iter().map(|c| c.callee));
+
+        let output = match self.format {
+            CallGraphFormat::Dot => {
+                let edges = calls
+                    .iter()
+                    .map(|c| ((c.caller, c.callee), c.kind))
+                    .collect::<BTreeMap<_, _>>();
+                to_dot(&graph, &functions, &edges)
+            }
+            CallGraphFormat::Json => {
+                let json = JsonCallGraph {
+                    functions: functions
+                        .iter()
+                        .map(|f| json_function(&graph, &files, &pkg_path, f))
+                        .collect(),
+                    calls: calls
+                        .iter()
+                        .map(|c| JsonCall {
+                            caller: fun_id_string(&c.caller),
+                            callee: fun_id_string(&c.callee),
+                            kind: kind_str(c.kind),
+                            location: json_location(&files, &pkg_path, &c.loc),
+                        })
+                        .collect(),
+                };
+                serde_json::to_string_pretty(&json)?
+            }
+        };
+
+        match self.output {
+            Some(output_path) => std::fs::write(output_path, format!("{output}\n"))?,
+            None => println!("{output}"),
+        }
+        Ok(())
+    }
+}
+
+fn to_dot(
+    graph: &Graph,
+    functions: &BTreeSet<FunId>,
+    edges: &BTreeMap<(FunId, FunId), CallKind>,
+) -> String {
+    let mut dot = String::from("digraph call_graph {\n    rankdir=LR;\n    node [shape=box];\n");
+    for fun_id in functions {
+        let mut attrs = vec![format!("label=\"{}\"", fun_id_string(fun_id))];
+        match graph.functions.get(fun_id) {
+            Some(f) => {
+                if f.entry {
+                    attrs.push("style=bold".to_string());
+                }
+                if f.macro_ {
+                    attrs.push("shape=hexagon".to_string());
+                }
+                if f.dependency {
+                    attrs.push("color=gray".to_string());
+                }
+            }
+            None => attrs.push("color=gray".to_string()),
+        }
+        let _ = writeln!(
+            dot,
+            "    \"{}\" [{}];",
+            fun_id_string(fun_id),
+            attrs.join(", ")
+        );
+    }
+    for ((caller, callee), kind) in edges {
+        let attrs = if *kind == CallKind::Macro {
+            " [style=dashed]"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            dot,
+            "    \"{}\" -> \"{}\"{attrs};",
+            fun_id_string(caller),
+            fun_id_string(callee)
+        );
+    }
+    dot.push('}');
+    dot
+}
+
+fn json_function(graph: &Graph, files: &MappedFiles, pkg_path: &Path, f: &FunId) -> JsonFunction {
+    let (mod_ident, name) = f;
+    let node = graph.functions.get(f);
+    JsonFunction {
+        id: fun_id_string(f),
+        address: mod_ident
+            .address
+            .into_addr_bytes()
+            .into_inner()
+            .to_hex_literal(),
+        module: mod_ident.module.to_string(),
+        name: name.to_string(),
+        visibility: node.map(|n| n.visibility.to_string()),
+        entry: node.is_some_and(|n| n.entry),
+        macro_: node.is_some_and(|n| n.macro_),
+        test: node.is_some_and(|n| n.test),
+        dependency: node.is_none_or(|n| n.dependency),
+        location: node.and_then(|n| json_location(files, pkg_path, &n.name_loc)),
+    }
+}
+
+fn json_location(files: &MappedFiles, pkg_path: &Path, loc: &Loc) -> Option<JsonLocation> {
+    let path = files.file_name_mapping().get(&loc.file_hash())?;
+    let pos = files.start_position_opt(loc)?;
+    Some(JsonLocation {
+        file: path.strip_prefix(pkg_path).unwrap_or(path).to_path_buf(),
+        line: pos.user_line(),
+        column: pos.user_column(),
+    })
+}
+
+/// Returns all functions that can be reached from the given ones via a chain of calls (including
+/// the functions themselves).
+fn reachable_from(graph: &Graph, from: &BTreeSet<FunId>) -> BTreeSet<FunId> {
+    let mut reachable = from.clone();
+    let mut worklist = from.iter().copied().collect::<Vec<_>>();
+    while let Some(fun_id) = worklist.pop() {
+        for call in graph.outgoing(&fun_id) {
+            if reachable.insert(call.callee) {
+                worklist.push(call.callee);
+            }
+        }
+    }
+    reachable
+}
+
+/// Finds a function given as `<address>::<module>::<function>`, where the address is either a
+/// named address or a hex literal.
+fn find_function(graph: &Graph, name: &str) -> Option<FunId> {
+    let [addr, module, fun] = name.split("::").collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let numerical = AccountAddress::from_hex_literal(addr).ok();
+    graph
+        .functions
+        .keys()
+        .chain(graph.calls.iter().map(|c| &c.callee))
+        .find(|(mod_ident, fun_name)| {
+            fun_name.as_str() == fun
+                && mod_ident.module.to_string() == module
+                && (mod_ident.address.to_string() == addr
+                    || numerical
+                        .is_some_and(|a| mod_ident.address.into_addr_bytes().into_inner() == a))
+        })
+        .copied()
+}
+
+fn fun_id_string((mod_ident, name): &FunId) -> String {
+    format!("{mod_ident}::{name}")
+}
+
+fn kind_str(kind: CallKind) -> &'static str {
+    match kind {
+        CallKind::Regular => "call",
+        CallKind::Method => "method",
+        CallKind::Macro => "macro",
+    }
+}
@@ -53,6 +53,15 @@ impl Test {
         let save_disassembly = self.test.trace_execution;
         // find manifest file directory from a given path or (if missing) from current dir
         let rerooted_path = base::reroot_path(path)?;
+        if self.test.mutate {
+            let report_path = self.test.mutation_report.clone();
+            return run_move_mutation_tests(
+                &rerooted_path,
+                build_config,
+                self.test.unit_test_config(),
+                report_path.as_deref(),
+            );
+        }
         let unit_test_config = self.test.unit_test_config();
         run_move_unit_tests(
             &rerooted_path,
@@ -116,6 +125,38 @@ pub fn run_move_unit_tests(
     })
 }
 
+/// Runs mutation testing on the package (see `move_cli::base::test::run_move_mutation_tests`),
+/// optionally writing a JSON report of the results to `report_path`.
+pub fn run_move_mutation_tests(
+    path: &Path,
+    mut build_config: BuildConfig,
+    config: UnitTestingConfig,
+    report_path: Option<&Path>,
+) -> anyhow::Result<UnitTestResult> {
+    // bind the extension hook if it has not yet been done
+    Lazy::force(&SET_EXTENSION_HOOK);
+
+    build_config.implicit_dependencies = implicit_deps(latest_system_packages());
+
+    move_cli::base::test::run_move_mutation_tests(
+        path,
+        build_config,
+        UnitTestingConfig {
+            report_stacktrace_on_abort: true,
+            ..config
+        },
+        // debug output of the (many) mutant runs would only be noise, so it is silenced (including
+        // when running the tests on the original code)
+        sui_move_natives::all_natives(
+            /* silent */ true,
+            &ProtocolConfig::get_for_max_version_UNSAFE(),
+        ),
+        Some(initial_cost_schedule_for_unit_tests()),
+        report_path,
+        &mut std::io::stdout(),
+    )
+}
+
 fn new_testing_object_and_natives_cost_runtime(ext: &mut NativeContextExtensions) {
     // Use a throwaway metrics registry for testing.
     let registry = prometheus::Registry::new();
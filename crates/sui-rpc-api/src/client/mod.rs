@@ -12,12 +12,14 @@ use crate::proto::rpc::v2beta::transaction_execution_service_client::Transaction
 use crate::proto::TryFromProtoError;
 use prost_types::FieldMask;
 use sui_rpc::field::FieldMaskUtil;
+use sui_rpc::proto::sui::rpc::v2beta2;
+use sui_rpc::proto::sui::rpc::v2beta2::live_data_service_client::LiveDataServiceClient;
 use sui_types::base_types::{ObjectID, SequenceNumber};
 use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
 use sui_types::object::Object;
-use sui_types::transaction::Transaction;
+use sui_types::transaction::{Transaction, TransactionData};
 
 pub use sui_rpc::client::ResponseExt;
 
@@ -85,6 +87,14 @@ impl Client {
         TransactionExecutionServiceClient::with_interceptor(self.channel.clone(), self.auth.clone())
     }
 
+    pub fn live_data_client(
+        &self,
+    ) -> LiveDataServiceClient<
+        tonic::service::interceptor::InterceptedService<tonic::transport::Channel, AuthInterceptor>,
+    > {
+        LiveDataServiceClient::with_interceptor(self.channel.clone(), self.auth.clone())
+    }
+
     pub async fn get_latest_checkpoint(&self) -> Result<CertifiedCheckpointSummary> {
         self.get_checkpoint_internal(None).await
     }
@@ -220,6 +230,35 @@ impl Client {
         execute_transaction_response_try_from_proto(&response)
             .map_err(|e| status_from_error_with_metadata(e, metadata))
     }
+
+    /// Simulate executing `transaction` against the fullnode's latest state, without signatures
+    /// and without committing its effects.
+    pub async fn simulate_transaction(
+        &self,
+        transaction: &TransactionData,
+    ) -> Result<TransactionSimulationResponse> {
+        let request = v2beta2::SimulateTransactionRequest {
+            transaction: Some(v2beta2::Transaction {
+                bcs: Some(
+                    v2beta2::Bcs::serialize(transaction)
+                        .map_err(|e| Status::from_error(e.into()))?,
+                ),
+                ..Default::default()
+            }),
+            read_mask: FieldMask::from_paths(["transaction.effects.bcs", "transaction.events.bcs"])
+                .pipe(Some),
+            ..Default::default()
+        };
+
+        let (metadata, response, _extentions) = self
+            .live_data_client()
+            .simulate_transaction(request)
+            .await?
+            .into_parts();
+
+        simulate_transaction_response_try_from_proto(&response)
+            .map_err(|e| status_from_error_with_metadata(e, metadata))
+    }
 }
 
 #[derive(Debug)]
@@ -231,6 +270,12 @@ pub struct TransactionExecutionResponse {
     pub balance_changes: Vec<sui_sdk_types::BalanceChange>,
 }
 
+#[derive(Debug)]
+pub struct TransactionSimulationResponse {
+    pub effects: TransactionEffects,
+    pub events: Option<TransactionEvents>,
+}
+
 /// Attempts to parse `CertifiedCheckpointSummary` from a proto::Checkpoint
 fn certified_checkpoint_summary_try_from_proto(
     checkpoint: &proto::Checkpoint,
@@ -396,6 +441,33 @@ fn execute_transaction_response_try_from_proto(
     .pipe(Ok)
 }
 
+/// Attempts to parse `TransactionSimulationResponse` from the fields in `SimulateTransactionResponse`
+fn simulate_transaction_response_try_from_proto(
+    response: &v2beta2::SimulateTransactionResponse,
+) -> Result<TransactionSimulationResponse, TryFromProtoError> {
+    let executed_transaction = response
+        .transaction
+        .as_ref()
+        .ok_or_else(|| TryFromProtoError::missing("transaction"))?;
+
+    let effects = executed_transaction
+        .effects
+        .as_ref()
+        .and_then(|effects| effects.bcs.as_ref())
+        .ok_or_else(|| TryFromProtoError::missing("effects_bcs"))?
+        .deserialize()
+        .map_err(TryFromProtoError::from_error)?;
+    let events = executed_transaction
+        .events
+        .as_ref()
+        .and_then(|events| events.bcs.as_ref())
+        .map(|bcs| bcs.deserialize())
+        .transpose()
+        .map_err(TryFromProtoError::from_error)?;
+
+    Ok(TransactionSimulationResponse { effects, events })
+}
+
 fn status_from_error_with_metadata<T: Into<BoxError>>(err: T, metadata: MetadataMap) -> Status {
     let mut status = Status::from_error(err.into());
     *status.metadata_mut() = metadata;
@@ -113,7 +113,6 @@ pub fn get_checkpoint(
     Ok(checkpoint)
 }
 
-#[allow(unused)]
 pub(crate) fn checkpoint_data_to_checkpoint_proto(
     checkpoint_data: sui_types::full_checkpoint_content::CheckpointData,
     read_mask: &FieldMaskTree,
@@ -156,7 +155,7 @@ pub(crate) fn checkpoint_data_to_checkpoint_proto(
     Ok(checkpoint)
 }
 
-fn core_transaction_to_executed_transaction_proto(
+pub(crate) fn core_transaction_to_executed_transaction_proto(
     sui_types::full_checkpoint_content::CheckpointTransaction {
         transaction,
         effects,
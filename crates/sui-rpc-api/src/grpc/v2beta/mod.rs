@@ -2,4 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod ledger_service;
+mod subscription_service;
 mod transaction_execution_service;
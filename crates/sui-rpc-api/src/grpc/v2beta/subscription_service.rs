@@ -0,0 +1,230 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::pin::Pin;
+
+use crate::grpc::v2beta::ledger_service::get_checkpoint::checkpoint_data_to_checkpoint_proto;
+use crate::grpc::v2beta::ledger_service::get_checkpoint::core_transaction_to_executed_transaction_proto;
+use crate::proto::google::rpc::bad_request::FieldViolation;
+use crate::proto::rpc::v2beta::subscription_service_server::SubscriptionService;
+use crate::proto::rpc::v2beta::Checkpoint;
+use crate::proto::rpc::v2beta::Event;
+use crate::proto::rpc::v2beta::ExecutedTransaction;
+use crate::proto::rpc::v2beta::SubscribeCheckpointsRequest;
+use crate::proto::rpc::v2beta::SubscribeCheckpointsResponse;
+use crate::proto::rpc::v2beta::SubscribeEventsRequest;
+use crate::proto::rpc::v2beta::SubscribeEventsResponse;
+use crate::proto::rpc::v2beta::SubscribeTransactionsRequest;
+use crate::proto::rpc::v2beta::SubscribeTransactionsResponse;
+use crate::subscription::MoveCallFilter;
+use crate::subscription::SubscriptionFilter;
+use crate::ErrorReason;
+use crate::RpcError;
+use crate::RpcService;
+use prost_types::FieldMask;
+use sui_rpc::field::FieldMaskTree;
+use sui_rpc::field::FieldMaskUtil;
+use sui_rpc::field::MessageFields;
+use sui_rpc::merge::Merge;
+use tokio_stream::StreamExt;
+
+type ResponseStream<T> = Pin<Box<dyn tokio_stream::Stream<Item = Result<T, tonic::Status>> + Send>>;
+
+#[tonic::async_trait]
+impl SubscriptionService for RpcService {
+    /// Server streaming response type for the SubscribeCheckpoints method.
+    type SubscribeCheckpointsStream = ResponseStream<SubscribeCheckpointsResponse>;
+
+    /// Server streaming response type for the SubscribeTransactions method.
+    type SubscribeTransactionsStream = ResponseStream<SubscribeTransactionsResponse>;
+
+    /// Server streaming response type for the SubscribeEvents method.
+    type SubscribeEventsStream = ResponseStream<SubscribeEventsResponse>;
+
+    async fn subscribe_checkpoints(
+        &self,
+        request: tonic::Request<SubscribeCheckpointsRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeCheckpointsStream>, tonic::Status> {
+        let SubscribeCheckpointsRequest {
+            read_mask,
+            cursor,
+            filter,
+        } = request.into_inner();
+
+        let read_mask = validate_read_mask::<Checkpoint>(
+            read_mask,
+            SubscribeCheckpointsRequest::READ_MASK_DEFAULT,
+        )?;
+        let filter = parse_filter(filter)?;
+
+        let checkpoints = self
+            .subscription_service()?
+            .subscribe_checkpoints(self.reader.inner().clone(), cursor, filter)
+            .await?;
+
+        let response = checkpoints.map(move |checkpoint| -> Result<_, tonic::Status> {
+            let checkpoint = checkpoint?;
+            let cursor = checkpoint.checkpoint_summary.sequence_number;
+            let checkpoint = checkpoint_data_to_checkpoint_proto(
+                checkpoint.as_ref().to_owned(), // TODO optimize so checkpoint isn't cloned
+                &read_mask,
+            )?;
+
+            Ok(SubscribeCheckpointsResponse {
+                cursor: Some(cursor),
+                checkpoint: Some(checkpoint),
+            })
+        });
+
+        Ok(tonic::Response::new(Box::pin(response)))
+    }
+
+    async fn subscribe_transactions(
+        &self,
+        request: tonic::Request<SubscribeTransactionsRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeTransactionsStream>, tonic::Status> {
+        let SubscribeTransactionsRequest {
+            read_mask,
+            cursor,
+            filter,
+        } = request.into_inner();
+
+        let read_mask = validate_read_mask::<ExecutedTransaction>(
+            read_mask,
+            SubscribeTransactionsRequest::READ_MASK_DEFAULT,
+        )?;
+        let filter = parse_filter(filter)?;
+
+        let transactions = self
+            .subscription_service()?
+            .subscribe_transactions(self.reader.inner().clone(), cursor, filter)
+            .await?;
+
+        let response = transactions.map(move |transaction| -> Result<_, tonic::Status> {
+            let transaction = transaction?;
+            let cursor = transaction.checkpoint;
+            let transaction = core_transaction_to_executed_transaction_proto(
+                transaction.transaction,
+                transaction.checkpoint,
+                transaction.timestamp_ms,
+                &read_mask,
+            )?;
+
+            Ok(SubscribeTransactionsResponse {
+                cursor: Some(cursor),
+                transaction: Some(transaction),
+            })
+        });
+
+        Ok(tonic::Response::new(Box::pin(response)))
+    }
+
+    async fn subscribe_events(
+        &self,
+        request: tonic::Request<SubscribeEventsRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeEventsStream>, tonic::Status> {
+        let SubscribeEventsRequest {
+            read_mask,
+            cursor,
+            filter,
+        } = request.into_inner();
+
+        let read_mask =
+            validate_read_mask::<Event>(read_mask, SubscribeEventsRequest::READ_MASK_DEFAULT)?;
+        let filter = parse_filter(filter)?;
+
+        let events = self
+            .subscription_service()?
+            .subscribe_events(self.reader.inner().clone(), cursor, filter)
+            .await?;
+
+        let response = events.map(move |event| -> Result<_, tonic::Status> {
+            let event = event?;
+            let digest = sui_sdk_types::TransactionDigest::from(event.transaction_digest);
+            let contents = sui_sdk_types::Event::try_from(event.event).map_err(RpcError::from)?;
+
+            Ok(SubscribeEventsResponse {
+                cursor: Some(event.checkpoint),
+                transaction_digest: Some(digest.to_string()),
+                event_sequence_number: Some(event.event_sequence_number),
+                event: Some(Event::merge_from(contents, &read_mask)),
+            })
+        });
+
+        Ok(tonic::Response::new(Box::pin(response)))
+    }
+}
+
+fn validate_read_mask<M: MessageFields>(
+    read_mask: Option<FieldMask>,
+    default: &str,
+) -> Result<FieldMaskTree, RpcError> {
+    let read_mask = read_mask.unwrap_or_else(|| FieldMask::from_str(default));
+    read_mask.validate::<M>().map_err(|path| {
+        FieldViolation::new("read_mask")
+            .with_description(format!("invalid read_mask path: {path}"))
+            .with_reason(ErrorReason::FieldInvalid)
+    })?;
+    Ok(FieldMaskTree::from(read_mask))
+}
+
+/// Convert the `filter` of a subscription request into a [SubscriptionFilter]. A missing filter
+/// matches everything.
+fn parse_filter(
+    filter: Option<crate::proto::rpc::v2beta::SubscriptionFilter>,
+) -> Result<SubscriptionFilter, RpcError> {
+    let Some(filter) = filter else {
+        return Ok(SubscriptionFilter::default());
+    };
+
+    let move_call = filter
+        .move_call
+        .map(|move_call| {
+            let package = move_call
+                .package
+                .as_ref()
+                .ok_or_else(|| {
+                    FieldViolation::new("filter.move_call.package")
+                        .with_reason(ErrorReason::FieldMissing)
+                })?
+                .parse()
+                .map_err(|e| {
+                    FieldViolation::new("filter.move_call.package")
+                        .with_description(format!("invalid package: {e}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                })?;
+
+            if move_call.function.is_some() && move_call.module.is_none() {
+                return Err(FieldViolation::new("filter.move_call.module")
+                    .with_description("module is required when function is set")
+                    .with_reason(ErrorReason::FieldMissing));
+            }
+
+            Ok(MoveCallFilter {
+                package,
+                module: move_call.module,
+                function: move_call.function,
+            })
+        })
+        .transpose()?;
+
+    Ok(SubscriptionFilter {
+        sender: parse_field(filter.sender, "filter.sender")?,
+        affected_address: parse_field(filter.affected_address, "filter.affected_address")?,
+        affected_object: parse_field(filter.affected_object, "filter.affected_object")?,
+        move_call,
+        event_type: parse_field(filter.event_type, "filter.event_type")?,
+    })
+}
+
+fn parse_field<T>(value: Option<String>, field: &'static str) -> Result<Option<T>, FieldViolation>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.map(|s| s.parse()).transpose().map_err(|e| {
+        FieldViolation::new(field)
+            .with_description(format!("invalid {field}: {e}"))
+            .with_reason(ErrorReason::FieldInvalid)
+    })
+}
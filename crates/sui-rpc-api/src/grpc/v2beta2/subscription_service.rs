@@ -3,16 +3,18 @@
 
 use std::pin::Pin;
 
-use crate::subscription::SubscriptionServiceHandle;
+use crate::subscription::SubscriptionFilter;
+use crate::RpcService;
 use sui_rpc::field::FieldMaskTree;
 use sui_rpc::merge::Merge;
 use sui_rpc::proto::sui::rpc::v2beta2::subscription_service_server::SubscriptionService;
 use sui_rpc::proto::sui::rpc::v2beta2::Checkpoint;
 use sui_rpc::proto::sui::rpc::v2beta2::SubscribeCheckpointsRequest;
 use sui_rpc::proto::sui::rpc::v2beta2::SubscribeCheckpointsResponse;
+use tokio_stream::StreamExt;
 
 #[tonic::async_trait]
-impl SubscriptionService for SubscriptionServiceHandle {
+impl SubscriptionService for RpcService {
     /// Server streaming response type for the SubscribeCheckpoints method.
     type SubscribeCheckpointsStream = Pin<
         Box<
@@ -28,30 +30,32 @@ impl SubscriptionService for SubscriptionServiceHandle {
         let read_mask = request.into_inner().read_mask.unwrap_or_default();
         let read_mask = FieldMaskTree::from(read_mask);
 
-        let Some(mut receiver) = self.register_subscription().await else {
-            return Err(tonic::Status::unavailable(
-                "too many existing subscriptions",
-            ));
-        };
-
-        let response = Box::pin(async_stream::stream! {
-            while let Some(checkpoint) = receiver.recv().await {
-                let cursor = checkpoint.checkpoint_summary.sequence_number;
-
-                let checkpoint = Checkpoint::merge_from(
-                    checkpoint.as_ref().to_owned(), // TODO optimize so checkpoint isn't cloned
-                    &read_mask
-                );
-
-                let response = SubscribeCheckpointsResponse {
-                    cursor: Some(cursor),
-                    checkpoint: Some(checkpoint),
-                };
-
-                yield Ok(response);
-            }
+        // This version of the API has no cursor or filter, so subscribers always start from the
+        // next live checkpoint, and receive every transaction in it.
+        let checkpoints = self
+            .subscription_service()?
+            .subscribe_checkpoints(
+                self.reader.inner().clone(),
+                None,
+                SubscriptionFilter::default(),
+            )
+            .await?;
+
+        let response = checkpoints.map(move |checkpoint| -> Result<_, tonic::Status> {
+            let checkpoint = checkpoint?;
+            let cursor = checkpoint.checkpoint_summary.sequence_number;
+
+            let checkpoint = Checkpoint::merge_from(
+                checkpoint.as_ref().to_owned(), // TODO optimize so checkpoint isn't cloned
+                &read_mask,
+            );
+
+            Ok(SubscribeCheckpointsResponse {
+                cursor: Some(cursor),
+                checkpoint: Some(checkpoint),
+            })
         });
 
-        Ok(tonic::Response::new(response))
+        Ok(tonic::Response::new(Box::pin(response)))
     }
 }
@@ -107,6 +107,15 @@ impl RpcService {
         self.server_version.as_ref()
     }
 
+    fn subscription_service(&self) -> Result<&SubscriptionServiceHandle> {
+        self.subscription_service_handle.as_ref().ok_or_else(|| {
+            RpcError::new(
+                tonic::Code::Unimplemented,
+                "subscriptions are not enabled on this node",
+            )
+        })
+    }
+
     pub async fn into_router(self) -> axum::Router {
         let metrics = self.metrics.clone();
 
@@ -196,16 +205,24 @@ impl RpcService {
                 .add_service(reflection_v1)
                 .add_service(reflection_v1alpha);
 
-            if let Some(subscription_service_handle) = self.subscription_service_handle.clone() {
+            if self.subscription_service_handle.is_some() {
+                let subscription_service =
+                    crate::proto::rpc::v2beta::subscription_service_server::SubscriptionServiceServer::new(
+                        self.clone(),
+                    );
                 let subscription_service2 =
-sui_rpc::proto::sui::rpc::v2beta2::subscription_service_server::SubscriptionServiceServer::new(subscription_service_handle);
-                health_reporter
-                    .set_service_status(
-                        service_name(&subscription_service2),
-                        tonic_health::ServingStatus::Serving,
-                    )
-                    .await;
-                services = services.add_service(subscription_service2);
+sui_rpc::proto::sui::rpc::v2beta2::subscription_service_server::SubscriptionServiceServer::new(self.clone());
+                for service_name in [
+                    service_name(&subscription_service),
+                    service_name(&subscription_service2),
+                ] {
+                    health_reporter
+                        .set_service_status(service_name, tonic_health::ServingStatus::Serving)
+                        .await;
+                }
+                services = services
+                    .add_service(subscription_service)
+                    .add_service(subscription_service2);
             }
 
             services.add_service(health_service).into_router()
@@ -31,6 +31,7 @@ mod executed_transaction;
 mod execution_status;
 mod object;
 mod signatures;
+mod subscription;
 mod system_state;
 mod transaction;
 
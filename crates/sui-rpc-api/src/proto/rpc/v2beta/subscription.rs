@@ -0,0 +1,18 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::SubscribeCheckpointsRequest;
+use super::SubscribeEventsRequest;
+use super::SubscribeTransactionsRequest;
+
+impl SubscribeCheckpointsRequest {
+    pub const READ_MASK_DEFAULT: &str = super::GetCheckpointRequest::READ_MASK_DEFAULT;
+}
+
+impl SubscribeTransactionsRequest {
+    pub const READ_MASK_DEFAULT: &str = super::GetTransactionRequest::READ_MASK_DEFAULT;
+}
+
+impl SubscribeEventsRequest {
+    pub const READ_MASK_DEFAULT: &str = "package_id,module,sender,event_type";
+}
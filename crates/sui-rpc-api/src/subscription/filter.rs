@@ -0,0 +1,384 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::StructTag;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::event::Event;
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::object::Owner;
+use sui_types::transaction::TransactionDataAPI;
+
+/// Server-side filter applied to the contents of each checkpoint before it is sent to a
+/// subscriber. A transaction (or event) is delivered only if it matches every criterion that is
+/// set, and an empty filter matches everything.
+#[derive(Clone, Debug, Default)]
+pub struct SubscriptionFilter {
+    /// Only include transactions sent by this address.
+    pub sender: Option<SuiAddress>,
+
+    /// Only include transactions that were sent by this address, or that read or wrote an object
+    /// owned by it.
+    pub affected_address: Option<SuiAddress>,
+
+    /// Only include transactions that created, modified, or removed this object.
+    pub affected_object: Option<ObjectID>,
+
+    /// Only include transactions that call into this Move package, module or function.
+    pub move_call: Option<MoveCallFilter>,
+
+    /// Only include events of this type. If the type has no type parameters, all of its
+    /// instantiations match. When filtering transactions (or checkpoints), a transaction matches
+    /// if it emitted at least one such event.
+    pub event_type: Option<StructTag>,
+}
+
+/// Matches Move calls to functions in `package`, optionally narrowed down to a `module`, and a
+/// `function` within that module.
+#[derive(Clone, Debug)]
+pub struct MoveCallFilter {
+    pub package: ObjectID,
+    pub module: Option<String>,
+    pub function: Option<String>,
+}
+
+impl SubscriptionFilter {
+    pub fn is_empty(&self) -> bool {
+        self.sender.is_none()
+            && self.affected_address.is_none()
+            && self.affected_object.is_none()
+            && self.move_call.is_none()
+            && self.event_type.is_none()
+    }
+
+    /// A copy of `checkpoint` that only contains the transactions that match this filter.
+    pub fn filter_checkpoint(&self, checkpoint: &CheckpointData) -> CheckpointData {
+        CheckpointData {
+            checkpoint_summary: checkpoint.checkpoint_summary.clone(),
+            checkpoint_contents: checkpoint.checkpoint_contents.clone(),
+            transactions: checkpoint
+                .transactions
+                .iter()
+                .filter(|transaction| self.matches_transaction(transaction))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Whether `transaction` matches all the criteria in this filter.
+    pub fn matches_transaction(&self, transaction: &CheckpointTransaction) -> bool {
+        self.matches_transaction_criteria(transaction)
+            && self.event_type.as_ref().is_none_or(|_| {
+                transaction
+                    .events
+                    .iter()
+                    .flat_map(|events| &events.data)
+                    .any(|event| self.matches_event(event))
+            })
+    }
+
+    /// Whether `event`, emitted by `transaction`, matches all the criteria in this filter.
+    pub fn matches_transaction_event(
+        &self,
+        transaction: &CheckpointTransaction,
+        event: &Event,
+    ) -> bool {
+        self.matches_transaction_criteria(transaction) && self.matches_event(event)
+    }
+
+    /// Whether `transaction` matches the criteria in this filter that don't concern its events.
+    fn matches_transaction_criteria(&self, transaction: &CheckpointTransaction) -> bool {
+        let data = transaction.transaction.transaction_data();
+
+        self.sender.is_none_or(|sender| data.sender() == sender)
+            && self
+                .affected_address
+                .is_none_or(|address| affects_address(transaction, address))
+            && self.affected_object.is_none_or(|id| {
+                transaction
+                    .effects
+                    .object_changes()
+                    .iter()
+                    .any(|change| change.id == id)
+            })
+            && self.move_call.as_ref().is_none_or(|filter| {
+                data.move_calls()
+                    .into_iter()
+                    .any(|(package, module, function)| filter.matches(package, module, function))
+            })
+    }
+
+    fn matches_event(&self, event: &Event) -> bool {
+        let Some(filter) = &self.event_type else {
+            return true;
+        };
+
+        let type_ = &event.type_;
+        if filter.type_params.is_empty() {
+            filter.address == type_.address
+                && filter.module == type_.module
+                && filter.name == type_.name
+        } else {
+            filter == type_
+        }
+    }
+}
+
+impl MoveCallFilter {
+    fn matches(&self, package: &ObjectID, module: &str, function: &str) -> bool {
+        self.package == *package
+            && self.module.as_deref().is_none_or(|m| m == module)
+            && self.function.as_deref().is_none_or(|f| f == function)
+    }
+}
+
+/// Whether `address` sent `transaction`, or owned any of the objects it read or wrote.
+fn affects_address(transaction: &CheckpointTransaction, address: SuiAddress) -> bool {
+    if transaction.transaction.transaction_data().sender() == address {
+        return true;
+    }
+
+    transaction
+        .input_objects
+        .iter()
+        .chain(&transaction.output_objects)
+        .any(|object| match object.owner() {
+            Owner::AddressOwner(owner) | Owner::ConsensusAddressOwner { owner, .. } => {
+                *owner == address
+            }
+            Owner::ObjectOwner(_) | Owner::Shared { .. } | Owner::Immutable => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::identifier::Identifier;
+    use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
+
+    use super::*;
+
+    fn package() -> ObjectID {
+        ObjectID::from_hex_literal("0xabc").unwrap()
+    }
+
+    fn event(module: &str, type_: &str) -> Event {
+        Event::new(
+            &package(),
+            Identifier::new(module).unwrap().as_ident_str(),
+            TestCheckpointDataBuilder::derive_address(0),
+            format!("{}::{type_}", package().to_hex_literal())
+                .parse()
+                .unwrap(),
+            vec![],
+        )
+    }
+
+    /// A checkpoint with three transactions:
+    ///
+    /// 0. Sent by address 0, creating object 0, calling `coin::mint` and emitting a
+    ///    `coin::Minted<SUI>` event.
+    /// 1. Sent by address 1, creating object 1, calling `other::run` and emitting an `other::Ran`
+    ///    event.
+    /// 2. Sent by address 0, transferring object 0 to address 2.
+    fn checkpoint() -> CheckpointData {
+        TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_owned_object(0)
+            .add_move_call(package(), "coin", "mint")
+            .with_events(vec![event("coin", "coin::Minted<0x2::sui::SUI>")])
+            .finish_transaction()
+            .start_transaction(1)
+            .create_owned_object(1)
+            .add_move_call(package(), "other", "run")
+            .with_events(vec![event("other", "other::Ran")])
+            .finish_transaction()
+            .start_transaction(0)
+            .transfer_object(0, 2)
+            .finish_transaction()
+            .build_checkpoint()
+    }
+
+    /// The indices of the transactions in `checkpoint` that match `filter`.
+    fn matches(filter: &SubscriptionFilter, checkpoint: &CheckpointData) -> Vec<usize> {
+        let filtered = filter.filter_checkpoint(checkpoint);
+        assert_eq!(filtered.checkpoint_summary, checkpoint.checkpoint_summary);
+
+        checkpoint
+            .transactions
+            .iter()
+            .enumerate()
+            .filter(|(_, tx)| filter.matches_transaction(tx))
+            .map(|(i, tx)| {
+                assert!(filtered
+                    .transactions
+                    .iter()
+                    .any(|f| f.transaction.digest() == tx.transaction.digest()));
+                i
+            })
+            .collect()
+    }
+
+    fn move_call(module: Option<&str>, function: Option<&str>) -> SubscriptionFilter {
+        SubscriptionFilter {
+            move_call: Some(MoveCallFilter {
+                package: package(),
+                module: module.map(str::to_owned),
+                function: function.map(str::to_owned),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn event_type(type_: &str) -> SubscriptionFilter {
+        SubscriptionFilter {
+            event_type: Some(
+                format!("{}::{type_}", package().to_hex_literal())
+                    .parse()
+                    .unwrap(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_empty_filter() {
+        let checkpoint = checkpoint();
+        let filter = SubscriptionFilter::default();
+
+        assert!(filter.is_empty());
+        assert_eq!(matches(&filter, &checkpoint), vec![0, 1, 2]);
+        assert_eq!(filter.filter_checkpoint(&checkpoint).transactions.len(), 3);
+    }
+
+    #[test]
+    fn test_sender() {
+        let checkpoint = checkpoint();
+        let filter = SubscriptionFilter {
+            sender: Some(TestCheckpointDataBuilder::derive_address(0)),
+            ..Default::default()
+        };
+
+        assert!(!filter.is_empty());
+        assert_eq!(matches(&filter, &checkpoint), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_affected_address() {
+        let checkpoint = checkpoint();
+        let affected = |idx| SubscriptionFilter {
+            affected_address: Some(TestCheckpointDataBuilder::derive_address(idx)),
+            ..Default::default()
+        };
+
+        // The sender of a transaction is affected by it.
+        assert_eq!(matches(&affected(1), &checkpoint), vec![1]);
+
+        // So is the recipient of an object.
+        assert_eq!(matches(&affected(2), &checkpoint), vec![2]);
+
+        // Addresses that are not involved are not.
+        assert_eq!(matches(&affected(3), &checkpoint), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_affected_object() {
+        let checkpoint = checkpoint();
+        let affected = |idx| SubscriptionFilter {
+            affected_object: Some(TestCheckpointDataBuilder::derive_object_id(idx)),
+            ..Default::default()
+        };
+
+        assert_eq!(matches(&affected(0), &checkpoint), vec![0, 2]);
+        assert_eq!(matches(&affected(1), &checkpoint), vec![1]);
+        assert_eq!(matches(&affected(2), &checkpoint), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_move_call() {
+        let checkpoint = checkpoint();
+
+        assert_eq!(matches(&move_call(None, None), &checkpoint), vec![0, 1]);
+        assert_eq!(
+            matches(&move_call(Some("coin"), None), &checkpoint),
+            vec![0]
+        );
+        assert_eq!(
+            matches(&move_call(Some("coin"), Some("mint")), &checkpoint),
+            vec![0]
+        );
+        assert_eq!(
+            matches(&move_call(Some("coin"), Some("run")), &checkpoint),
+            Vec::<usize>::new()
+        );
+
+        let other_package = SubscriptionFilter {
+            move_call: Some(MoveCallFilter {
+                package: ObjectID::from_hex_literal("0xdef").unwrap(),
+                module: None,
+                function: None,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(matches(&other_package, &checkpoint), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_event_type() {
+        let checkpoint = checkpoint();
+
+        // A type without type parameters matches all its instantiations.
+        assert_eq!(matches(&event_type("coin::Minted"), &checkpoint), vec![0]);
+        assert_eq!(
+            matches(&event_type("coin::Minted<0x2::sui::SUI>"), &checkpoint),
+            vec![0]
+        );
+        assert_eq!(
+            matches(&event_type("coin::Minted<u64>"), &checkpoint),
+            Vec::<usize>::new()
+        );
+        assert_eq!(matches(&event_type("other::Ran"), &checkpoint), vec![1]);
+    }
+
+    #[test]
+    fn test_transaction_event() {
+        let checkpoint = checkpoint();
+        let [minted, ran] = [0, 1].map(|i| {
+            let tx = &checkpoint.transactions[i];
+            (tx, &tx.events.as_ref().unwrap().data[0])
+        });
+
+        let filter = event_type("coin::Minted");
+        assert!(filter.matches_transaction_event(minted.0, minted.1));
+        assert!(!filter.matches_transaction_event(ran.0, ran.1));
+
+        // The transaction criteria apply to the event's transaction.
+        let filter = SubscriptionFilter {
+            sender: Some(TestCheckpointDataBuilder::derive_address(1)),
+            ..event_type("coin::Minted")
+        };
+        assert!(!filter.matches_transaction_event(minted.0, minted.1));
+
+        // An event matches a filter without an event type if its transaction does.
+        let filter = move_call(Some("other"), None);
+        assert!(!filter.matches_transaction_event(minted.0, minted.1));
+        assert!(filter.matches_transaction_event(ran.0, ran.1));
+    }
+
+    #[test]
+    fn test_conjunction() {
+        let checkpoint = checkpoint();
+
+        let filter = SubscriptionFilter {
+            sender: Some(TestCheckpointDataBuilder::derive_address(0)),
+            ..move_call(Some("other"), None)
+        };
+        assert_eq!(matches(&filter, &checkpoint), Vec::<usize>::new());
+
+        let filter = SubscriptionFilter {
+            sender: Some(TestCheckpointDataBuilder::derive_address(0)),
+            affected_object: Some(TestCheckpointDataBuilder::derive_object_id(0)),
+            ..event_type("coin::Minted")
+        };
+        assert_eq!(matches(&filter, &checkpoint), vec![0]);
+    }
+}
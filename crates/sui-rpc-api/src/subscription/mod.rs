@@ -0,0 +1,723 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::CheckpointNotFoundError;
+use crate::metrics::SubscriptionMetrics;
+use crate::RpcError;
+use std::pin::Pin;
+use std::sync::Arc;
+use sui_types::digests::TransactionDigest;
+use sui_types::event::Event;
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::storage::RpcStateReader;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tokio_stream::{Stream, StreamExt};
+use tracing::info;
+use tracing::trace;
+
+pub use filter::{MoveCallFilter, SubscriptionFilter};
+
+mod filter;
+
+const CHECKPOINT_MAILBOX_SIZE: usize = 1024;
+const MAILBOX_SIZE: usize = 128;
+const SUBSCRIPTION_CHANNEL_SIZE: usize = 256;
+const MAX_SUBSCRIBERS: usize = 1024;
+
+/// A stream of items delivered to a subscriber. The stream ends after the first error.
+pub type SubscriptionStream<T> = Pin<Box<dyn Stream<Item = crate::Result<T>> + Send>>;
+
+/// A transaction delivered to a transaction subscription, with the checkpoint it was included in.
+#[derive(Clone, Debug)]
+pub struct SubscribedTransaction {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: u64,
+    pub transaction: CheckpointTransaction,
+}
+
+/// An event delivered to an event subscription, with the transaction that emitted it, and the
+/// checkpoint that transaction was included in.
+#[derive(Clone, Debug)]
+pub struct SubscribedEvent {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: u64,
+    pub transaction_digest: TransactionDigest,
+    pub event_sequence_number: u64,
+    pub event: Event,
+}
+
+struct SubscriptionRequest {
+    sender: oneshot::Sender<mpsc::Receiver<Arc<CheckpointData>>>,
+}
+
+#[derive(Clone)]
+pub struct SubscriptionServiceHandle {
+    sender: mpsc::Sender<SubscriptionRequest>,
+}
+
+impl SubscriptionServiceHandle {
+    async fn register_subscription(&self) -> Option<mpsc::Receiver<Arc<CheckpointData>>> {
+        let (sender, reciever) = oneshot::channel();
+        let request = SubscriptionRequest { sender };
+        self.sender.send(request).await.ok()?;
+
+        reciever.await.ok()
+    }
+
+    /// Subscribe to checkpoints, keeping only the transactions in each checkpoint that match
+    /// `filter`.
+    ///
+    /// If `start` is provided, the subscription resumes from that checkpoint: checkpoints are
+    /// served from `reader`'s checkpoint store until the subscription catches up with the live
+    /// stream. Otherwise, the subscription starts from the next live checkpoint.
+    pub async fn subscribe_checkpoints(
+        &self,
+        reader: Arc<dyn RpcStateReader>,
+        start: Option<CheckpointSequenceNumber>,
+        filter: SubscriptionFilter,
+    ) -> crate::Result<SubscriptionStream<Arc<CheckpointData>>> {
+        let checkpoints = self.resume(reader, start).await?;
+
+        if filter.is_empty() {
+            return Ok(checkpoints);
+        }
+
+        Ok(Box::pin(checkpoints.map(move |checkpoint| {
+            checkpoint.map(|c| Arc::new(filter.filter_checkpoint(&c)))
+        })))
+    }
+
+    /// Subscribe to the transactions that match `filter`, in the order they were executed. See
+    /// [Self::subscribe_checkpoints] for how `start` is interpreted.
+    pub async fn subscribe_transactions(
+        &self,
+        reader: Arc<dyn RpcStateReader>,
+        start: Option<CheckpointSequenceNumber>,
+        filter: SubscriptionFilter,
+    ) -> crate::Result<SubscriptionStream<SubscribedTransaction>> {
+        let mut checkpoints = self.resume(reader, start).await?;
+
+        Ok(Box::pin(async_stream::stream! {
+            while let Some(checkpoint) = checkpoints.next().await {
+                let checkpoint = match checkpoint {
+                    Ok(checkpoint) => checkpoint,
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                };
+
+                let summary = checkpoint.checkpoint_summary.data();
+                for transaction in &checkpoint.transactions {
+                    if filter.matches_transaction(transaction) {
+                        yield Ok(SubscribedTransaction {
+                            checkpoint: summary.sequence_number,
+                            timestamp_ms: summary.timestamp_ms,
+                            transaction: transaction.clone(),
+                        });
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Subscribe to the events that match `filter`, in the order they were emitted. See
+    /// [Self::subscribe_checkpoints] for how `start` is interpreted.
+    pub async fn subscribe_events(
+        &self,
+        reader: Arc<dyn RpcStateReader>,
+        start: Option<CheckpointSequenceNumber>,
+        filter: SubscriptionFilter,
+    ) -> crate::Result<SubscriptionStream<SubscribedEvent>> {
+        let mut checkpoints = self.resume(reader, start).await?;
+
+        Ok(Box::pin(async_stream::stream! {
+            while let Some(checkpoint) = checkpoints.next().await {
+                let checkpoint = match checkpoint {
+                    Ok(checkpoint) => checkpoint,
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                };
+
+                let summary = checkpoint.checkpoint_summary.data();
+                for transaction in &checkpoint.transactions {
+                    let Some(events) = &transaction.events else {
+                        continue;
+                    };
+
+                    for (ix, event) in events.data.iter().enumerate() {
+                        if filter.matches_transaction_event(transaction, event) {
+                            yield Ok(SubscribedEvent {
+                                checkpoint: summary.sequence_number,
+                                timestamp_ms: summary.timestamp_ms,
+                                transaction_digest: *transaction.transaction.digest(),
+                                event_sequence_number: ix as u64,
+                                event: event.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Stream every checkpoint from `start` (or from the next live checkpoint, if `start` is not
+    /// provided), in order.
+    async fn resume(
+        &self,
+        reader: Arc<dyn RpcStateReader>,
+        start: Option<CheckpointSequenceNumber>,
+    ) -> crate::Result<SubscriptionStream<Arc<CheckpointData>>> {
+        let Some(start) = start else {
+            let mut receiver = self
+                .register_subscription()
+                .await
+                .ok_or_else(too_many_subscriptions)?;
+
+            return Ok(Box::pin(async_stream::stream! {
+                let mut next = None;
+                while let Some(checkpoint) = receiver.recv().await {
+                    next = Some(checkpoint.checkpoint_summary.sequence_number + 1);
+                    yield Ok(checkpoint);
+                }
+
+                yield Err(subscription_closed(next));
+            }));
+        };
+
+        let lowest = reader.get_lowest_available_checkpoint()?;
+        if start < lowest {
+            return Err(RpcError::new(
+                tonic::Code::OutOfRange,
+                format!("checkpoint {start} has been pruned, lowest available is {lowest}"),
+            ));
+        }
+
+        Ok(Box::pin(backfill_then_live(self.clone(), reader, start)))
+    }
+}
+
+/// Serve checkpoints from `start` out of `reader`'s checkpoint store until it has been exhausted,
+/// and then switch to live checkpoints from the subscription service.
+///
+/// The store is read before registering with the service, so that the subscriber is not dropped
+/// for falling behind while it catches up. The latest checkpoint is re-read after each pass over
+/// the store, and the subscription only registers once a pass finds nothing new. Any checkpoints
+/// executed between the end of the backfill and the first live checkpoint are also read from the
+/// store, and live checkpoints that were already served from the store are skipped.
+///
+/// If the service stops delivering live checkpoints (because the subscriber fell behind, or the
+/// service shut down), the stream ends with an error saying where to resume from.
+fn backfill_then_live(
+    handle: SubscriptionServiceHandle,
+    reader: Arc<dyn RpcStateReader>,
+    start: CheckpointSequenceNumber,
+) -> impl Stream<Item = crate::Result<Arc<CheckpointData>>> + Send {
+    async_stream::try_stream! {
+        let mut next = start;
+
+        loop {
+            let latest = reader.get_latest_checkpoint_sequence_number()?;
+            if next > latest {
+                break;
+            }
+
+            while next <= latest {
+                yield Arc::new(load_checkpoint(reader.as_ref(), next)?);
+                next += 1;
+            }
+        }
+
+        let mut receiver = handle
+            .register_subscription()
+            .await
+            .ok_or_else(too_many_subscriptions)?;
+
+        while let Some(checkpoint) = receiver.recv().await {
+            let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+
+            while next < sequence_number {
+                yield Arc::new(load_checkpoint(reader.as_ref(), next)?);
+                next += 1;
+            }
+
+            if sequence_number == next {
+                yield checkpoint;
+                next += 1;
+            }
+        }
+
+        Err::<(), _>(subscription_closed(Some(next)))?;
+    }
+}
+
+/// Read the full contents of checkpoint `sequence_number` from the store.
+fn load_checkpoint(
+    reader: &dyn RpcStateReader,
+    sequence_number: CheckpointSequenceNumber,
+) -> crate::Result<CheckpointData> {
+    let summary = reader
+        .get_checkpoint_by_sequence_number(sequence_number)
+        .ok_or(CheckpointNotFoundError::sequence_number(sequence_number))?;
+
+    let contents = reader
+        .get_checkpoint_contents_by_sequence_number(sequence_number)
+        .ok_or(CheckpointNotFoundError::sequence_number(sequence_number))?;
+
+    Ok(reader.get_checkpoint_data(summary, contents)?)
+}
+
+fn too_many_subscriptions() -> RpcError {
+    RpcError::new(tonic::Code::Unavailable, "too many existing subscriptions")
+}
+
+/// The error a subscription ends with when it stops receiving live checkpoints. `next` is the
+/// checkpoint to resume from, if any checkpoints were delivered.
+fn subscription_closed(next: Option<CheckpointSequenceNumber>) -> RpcError {
+    let message = match next {
+        Some(next) => format!("subscription closed, resume from checkpoint {next}"),
+        None => "subscription closed before any checkpoints were delivered".to_owned(),
+    };
+
+    RpcError::new(tonic::Code::Unavailable, message)
+}
+
+pub struct SubscriptionService {
+    // Mailbox for recieving `CheckpointData` from the Checkpoint Executor
+    //
+    // Expectation is that checkpoints are recieved in-order
+    checkpoint_mailbox: mpsc::Receiver<CheckpointData>,
+    mailbox: mpsc::Receiver<SubscriptionRequest>,
+    subscribers: Vec<mpsc::Sender<Arc<CheckpointData>>>,
+
+    metrics: SubscriptionMetrics,
+}
+
+impl SubscriptionService {
+    pub fn build(
+        registry: &prometheus::Registry,
+    ) -> (mpsc::Sender<CheckpointData>, SubscriptionServiceHandle) {
+        let metrics = SubscriptionMetrics::new(registry);
+        let (checkpoint_sender, checkpoint_mailbox) = mpsc::channel(CHECKPOINT_MAILBOX_SIZE);
+        let (subscription_request_sender, mailbox) = mpsc::channel(MAILBOX_SIZE);
+
+        tokio::spawn(
+            Self {
+                checkpoint_mailbox,
+                mailbox,
+                subscribers: Vec::new(),
+                metrics,
+            }
+            .start(),
+        );
+
+        (
+            checkpoint_sender,
+            SubscriptionServiceHandle {
+                sender: subscription_request_sender,
+            },
+        )
+    }
+
+    async fn start(mut self) {
+        // Start main loop.
+        loop {
+            tokio::select! {
+                maybe_checkpoint = self.checkpoint_mailbox.recv() => {
+                    // Once all handles to our checkpoint_mailbox have been dropped this
+                    // will yield `None` and we can terminate the event loop
+                    if let Some(checkpoint) = maybe_checkpoint {
+                        self.handle_checkpoint(checkpoint);
+                    } else {
+                        break;
+                    }
+                },
+                maybe_message = self.mailbox.recv() => {
+                    // Once all handles to our mailbox have been dropped this
+                    // will yield `None` and we can terminate the event loop
+                    if let Some(message) = maybe_message {
+                        self.handle_message(message);
+                    } else {
+                        break;
+                    }
+                },
+            }
+        }
+
+        info!("RPC Subscription Services ended");
+    }
+
+    fn handle_checkpoint(&mut self, checkpoint: CheckpointData) {
+        // Check that we recieved checkpoints in-order
+        {
+            let last_sequence_number = self.metrics.last_recieved_checkpoint.get();
+            let sequence_number = *checkpoint.checkpoint_summary.sequence_number() as i64;
+
+            if last_sequence_number != 0 && (last_sequence_number + 1) != sequence_number {
+                panic!(
+                    "recieved checkpoint out-of-order. expected checkpoint {}, recieved {}",
+                    last_sequence_number + 1,
+                    sequence_number
+                );
+            }
+
+            // Update the metric marking the latest checkpoint we've seen
+            self.metrics.last_recieved_checkpoint.set(sequence_number);
+        }
+
+        let checkpoint = Arc::new(checkpoint);
+
+        // Try to send the latest checkpoint to all subscribers. If a subscriber's channel is full
+        // then they are likely too slow so we drop them.
+        self.subscribers.retain(|subscriber| {
+            match subscriber.try_send(Arc::clone(&checkpoint)) {
+                Ok(()) => {
+                    trace!("succesfully enqueued checkpont for subscriber");
+                    true // Retain this subscriber
+                }
+                Err(e) => {
+                    // It does not matter what the error is - channel full or closed, we drop the subscriber.
+                    trace!("unable to enqueue checkpoint for subscriber: {e}");
+                    self.metrics.inflight_subscribers.dec();
+                    false // Drop this subscriber
+                }
+            }
+        });
+    }
+
+    fn handle_message(&mut self, request: SubscriptionRequest) {
+        // Check if we've reached the limit to the number of subscribers we can have at one time.
+        if self.subscribers.len() >= MAX_SUBSCRIBERS {
+            trace!(
+                "failed to register new subscriber: hit maximum number of subscribers {}",
+                MAX_SUBSCRIBERS
+            );
+            return;
+        }
+
+        let (sender, reciever) = mpsc::channel(SUBSCRIPTION_CHANNEL_SIZE);
+        match request.sender.send(reciever) {
+            Ok(()) => {
+                trace!("succesfully registered new subscriber");
+                self.metrics.inflight_subscribers.inc();
+                self.subscribers.push(sender);
+            }
+            Err(e) => {
+                trace!("failed to register new subscriber: {e:?}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    use move_core_types::annotated_value::MoveTypeLayout;
+    use move_core_types::language_storage::StructTag;
+    use sui_types::base_types::{ObjectID, VersionNumber};
+    use sui_types::committee::{Committee, EpochId};
+    use sui_types::digests::{
+        ChainIdentifier, CheckpointContentsDigest, CheckpointDigest, TransactionDigest,
+    };
+    use sui_types::effects::{TransactionEffects, TransactionEvents};
+    use sui_types::messages_checkpoint::{
+        CheckpointContents, FullCheckpointContents, VerifiedCheckpoint,
+    };
+    use sui_types::object::Object;
+    use sui_types::storage::error::Result as StorageResult;
+    use sui_types::storage::{ObjectStore, ReadStore, RpcIndexes};
+    use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
+    use sui_types::transaction::VerifiedTransaction;
+
+    use super::*;
+
+    /// A checkpoint store that only supports the reads needed to backfill a subscription.
+    #[derive(Default)]
+    struct CheckpointStore(Mutex<BTreeMap<CheckpointSequenceNumber, CheckpointData>>);
+
+    impl CheckpointStore {
+        fn insert(&self, checkpoint: CheckpointData) {
+            let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+            self.0.lock().unwrap().insert(sequence_number, checkpoint);
+        }
+
+        fn get(&self, sequence_number: CheckpointSequenceNumber) -> Option<CheckpointData> {
+            self.0.lock().unwrap().get(&sequence_number).cloned()
+        }
+    }
+
+    impl ObjectStore for CheckpointStore {
+        fn get_object(&self, _: &ObjectID) -> Option<Object> {
+            unimplemented!()
+        }
+
+        fn get_object_by_key(&self, _: &ObjectID, _: VersionNumber) -> Option<Object> {
+            unimplemented!()
+        }
+    }
+
+    impl ReadStore for CheckpointStore {
+        fn get_committee(&self, _: EpochId) -> Option<Arc<Committee>> {
+            unimplemented!()
+        }
+
+        fn get_latest_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+            let checkpoints = self.0.lock().unwrap();
+            let (_, latest) = checkpoints.last_key_value().unwrap();
+            Ok(VerifiedCheckpoint::new_unchecked(
+                latest.checkpoint_summary.clone(),
+            ))
+        }
+
+        fn get_highest_verified_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+            unimplemented!()
+        }
+
+        fn get_highest_synced_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+            unimplemented!()
+        }
+
+        fn get_lowest_available_checkpoint(&self) -> StorageResult<CheckpointSequenceNumber> {
+            let checkpoints = self.0.lock().unwrap();
+            Ok(*checkpoints.first_key_value().unwrap().0)
+        }
+
+        fn get_checkpoint_by_digest(&self, _: &CheckpointDigest) -> Option<VerifiedCheckpoint> {
+            unimplemented!()
+        }
+
+        fn get_checkpoint_by_sequence_number(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> Option<VerifiedCheckpoint> {
+            self.get(sequence_number)
+                .map(|c| VerifiedCheckpoint::new_unchecked(c.checkpoint_summary))
+        }
+
+        fn get_checkpoint_contents_by_digest(
+            &self,
+            _: &CheckpointContentsDigest,
+        ) -> Option<CheckpointContents> {
+            unimplemented!()
+        }
+
+        fn get_checkpoint_contents_by_sequence_number(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> Option<CheckpointContents> {
+            self.get(sequence_number).map(|c| c.checkpoint_contents)
+        }
+
+        fn get_transaction(&self, _: &TransactionDigest) -> Option<Arc<VerifiedTransaction>> {
+            unimplemented!()
+        }
+
+        fn get_transaction_effects(&self, _: &TransactionDigest) -> Option<TransactionEffects> {
+            unimplemented!()
+        }
+
+        fn get_events(&self, _: &TransactionDigest) -> Option<TransactionEvents> {
+            unimplemented!()
+        }
+
+        fn get_full_checkpoint_contents(
+            &self,
+            _: Option<CheckpointSequenceNumber>,
+            _: &CheckpointContentsDigest,
+        ) -> Option<FullCheckpointContents> {
+            unimplemented!()
+        }
+
+        fn get_checkpoint_data(
+            &self,
+            checkpoint: VerifiedCheckpoint,
+            _: CheckpointContents,
+        ) -> anyhow::Result<CheckpointData> {
+            Ok(self.get(checkpoint.sequence_number).unwrap())
+        }
+    }
+
+    impl RpcStateReader for CheckpointStore {
+        fn get_lowest_available_checkpoint_objects(
+            &self,
+        ) -> StorageResult<CheckpointSequenceNumber> {
+            unimplemented!()
+        }
+
+        fn get_chain_identifier(&self) -> StorageResult<ChainIdentifier> {
+            unimplemented!()
+        }
+
+        fn indexes(&self) -> Option<&dyn RpcIndexes> {
+            None
+        }
+
+        fn get_struct_layout(&self, _: &StructTag) -> StorageResult<Option<MoveTypeLayout>> {
+            unimplemented!()
+        }
+    }
+
+    /// A checkpoint store holding checkpoints `0..=latest`, and a builder for the checkpoints
+    /// after it.
+    fn store(
+        latest: CheckpointSequenceNumber,
+    ) -> (Arc<CheckpointStore>, TestCheckpointDataBuilder) {
+        let store = Arc::new(CheckpointStore::default());
+        let mut builder = TestCheckpointDataBuilder::new(0);
+        for _ in 0..=latest {
+            store.insert(builder.build_checkpoint());
+        }
+
+        (store, builder)
+    }
+
+    /// A handle whose subscription requests are served by the test, rather than by a
+    /// [SubscriptionService].
+    fn handle() -> (
+        SubscriptionServiceHandle,
+        mpsc::Receiver<SubscriptionRequest>,
+    ) {
+        let (sender, mailbox) = mpsc::channel(1);
+        (SubscriptionServiceHandle { sender }, mailbox)
+    }
+
+    /// Collect the sequence numbers of the next `n` checkpoints from `stream`.
+    async fn take(
+        stream: &mut (impl Stream<Item = crate::Result<Arc<CheckpointData>>> + Unpin),
+        n: usize,
+    ) -> Vec<CheckpointSequenceNumber> {
+        let mut sequence_numbers = vec![];
+        for _ in 0..n {
+            let checkpoint = stream.next().await.unwrap().unwrap();
+            sequence_numbers.push(checkpoint.checkpoint_summary.sequence_number);
+        }
+        sequence_numbers
+    }
+
+    /// Live checkpoints that were already served from the store are not served again.
+    #[tokio::test]
+    async fn test_backfill_then_live_skips_duplicates() {
+        let (store, mut builder) = store(4);
+        let (handle, mut mailbox) = handle();
+
+        let mut stream = Box::pin(backfill_then_live(handle, store.clone(), 1));
+        assert_eq!(take(&mut stream, 4).await, vec![1, 2, 3, 4]);
+
+        // Serve the subscription request once the backfill is done, and deliver checkpoints that
+        // overlap with the backfill.
+        let consumer = tokio::spawn(async move { take(&mut stream, 2).await });
+        let request = mailbox.recv().await.unwrap();
+        let (live, receiver) = mpsc::channel(16);
+        request.sender.send(receiver).unwrap();
+
+        for checkpoint in [3, 4] {
+            live.send(Arc::new(store.get(checkpoint).unwrap()))
+                .await
+                .unwrap();
+        }
+
+        for _ in 0..2 {
+            let checkpoint = builder.build_checkpoint();
+            store.insert(checkpoint.clone());
+            live.send(Arc::new(checkpoint)).await.unwrap();
+        }
+
+        assert_eq!(consumer.await.unwrap(), vec![5, 6]);
+    }
+
+    /// Checkpoints executed between the end of the backfill and the first live checkpoint are
+    /// served from the store.
+    #[tokio::test]
+    async fn test_backfill_then_live_fills_gaps() {
+        let (store, mut builder) = store(2);
+        let (handle, mut mailbox) = handle();
+
+        let mut stream = Box::pin(backfill_then_live(handle, store.clone(), 0));
+        assert_eq!(take(&mut stream, 3).await, vec![0, 1, 2]);
+
+        let consumer = tokio::spawn(async move { take(&mut stream, 4).await });
+        let request = mailbox.recv().await.unwrap();
+        let (live, receiver) = mpsc::channel(16);
+        request.sender.send(receiver).unwrap();
+
+        // Checkpoints 3 and 4 are executed before the subscription is registered, so they are
+        // only available from the store.
+        for _ in 3..=4 {
+            store.insert(builder.build_checkpoint());
+        }
+
+        for _ in 5..=6 {
+            let checkpoint = builder.build_checkpoint();
+            store.insert(checkpoint.clone());
+            live.send(Arc::new(checkpoint)).await.unwrap();
+        }
+
+        assert_eq!(consumer.await.unwrap(), vec![3, 4, 5, 6]);
+    }
+
+    /// Checkpoints added to the store while backfilling are served from the store, before the
+    /// subscription registers for live checkpoints.
+    #[tokio::test]
+    async fn test_backfill_rereads_latest() {
+        let (store, mut builder) = store(2);
+        let (handle, mut mailbox) = handle();
+
+        let mut stream = Box::pin(backfill_then_live(handle, store.clone(), 0));
+        assert_eq!(take(&mut stream, 3).await, vec![0, 1, 2]);
+
+        for _ in 3..=4 {
+            store.insert(builder.build_checkpoint());
+        }
+
+        assert_eq!(take(&mut stream, 2).await, vec![3, 4]);
+        assert!(mailbox.try_recv().is_err());
+    }
+
+    /// The stream ends with an error if the live receiver is closed, so the subscriber knows to
+    /// resume.
+    #[tokio::test]
+    async fn test_live_receiver_closed() {
+        let (store, _) = store(1);
+        let (handle, mut mailbox) = handle();
+
+        let mut stream = Box::pin(backfill_then_live(handle, store.clone(), 0));
+        assert_eq!(take(&mut stream, 2).await, vec![0, 1]);
+
+        let consumer = tokio::spawn(async move { stream.next().await });
+        let request = mailbox.recv().await.unwrap();
+        let (live, receiver) = mpsc::channel(16);
+        request.sender.send(receiver).unwrap();
+        drop(live);
+
+        let Some(Err(error)) = consumer.await.unwrap() else {
+            panic!("expected an error");
+        };
+
+        let status = tonic::Status::from(error);
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert!(status.message().contains("resume from checkpoint 2"));
+    }
+
+    /// Resuming from a checkpoint that has been pruned is an error.
+    #[tokio::test]
+    async fn test_resume_from_pruned_checkpoint() {
+        let (store, _) = store(1);
+        store.0.lock().unwrap().remove(&0);
+
+        let (handle, _mailbox) = handle();
+        let Err(error) = handle
+            .subscribe_checkpoints(store, Some(0), SubscriptionFilter::default())
+            .await
+        else {
+            panic!("expected an error");
+        };
+
+        assert_eq!(tonic::Status::from(error).code(), tonic::Code::OutOfRange);
+    }
+}
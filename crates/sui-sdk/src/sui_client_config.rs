@@ -1,9 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail, ensure};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
@@ -19,6 +21,13 @@ pub struct SuiClientConfig {
     pub envs: Vec<SuiEnv>,
     pub active_env: Option<String>,
     pub active_address: Option<SuiAddress>,
+    /// Addresses that are not in the keystore, saved under a name that can be used wherever a
+    /// keystore alias is accepted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub address_book: Vec<Contact>,
+    /// Saved `sui client ptb` programs, by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, PtbTemplate>,
 }
 
 impl SuiClientConfig {
@@ -28,6 +37,8 @@ impl SuiClientConfig {
             envs: vec![],
             active_env: None,
             active_address: None,
+            address_book: vec![],
+            templates: BTreeMap::new(),
         }
     }
 
@@ -57,6 +68,149 @@ impl SuiClientConfig {
             self.envs.push(env)
         }
     }
+
+    /// The address book entry called `name` in the active environment. Entries for the active
+    /// environment take precedence over entries for all environments.
+    pub fn get_contact(&self, name: &str) -> Option<&Contact> {
+        let mut contacts = self.address_book.iter().filter(|c| c.name == name);
+        contacts
+            .clone()
+            .find(|c| c.env.is_some() && c.env == self.active_env)
+            .or_else(|| contacts.find(|c| c.env.is_none()))
+    }
+
+    /// The names in the address book that apply to the active environment, and their addresses.
+    pub fn active_contacts(&self) -> BTreeMap<&str, SuiAddress> {
+        self.address_book
+            .iter()
+            .filter_map(|c| {
+                let contact = self.get_contact(&c.name)?;
+                Some((contact.name.as_str(), contact.address))
+            })
+            .collect()
+    }
+
+    /// Add `contact` to the address book, replacing any entry with the same name and environment.
+    pub fn add_contact(&mut self, contact: Contact) -> Result<(), anyhow::Error> {
+        ensure!(
+            contact.name.starts_with(|c: char| c.is_ascii_alphabetic())
+                && contact
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            "Invalid name [{}], it must start with a letter and can contain only letters, \
+             digits, hyphens (-), or underscores (_)",
+            contact.name
+        );
+        ensure!(
+            self.keystore
+                .get_address_by_alias(contact.name.clone())
+                .is_err(),
+            "Name [{}] is already used as an alias in the keystore",
+            contact.name
+        );
+        self.remove_contact(&contact.name, contact.env.as_deref());
+        self.address_book.push(contact);
+        Ok(())
+    }
+
+    /// Remove the address book entry called `name` for `env` (or for all environments, if `env`
+    /// is `None`). Returns whether there was such an entry.
+    pub fn remove_contact(&mut self, name: &str, env: Option<&str>) -> bool {
+        let len = self.address_book.len();
+        self.address_book
+            .retain(|c| c.name != name || c.env.as_deref() != env);
+        self.address_book.len() != len
+    }
+}
+
+/// An address book entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Contact {
+    pub name: String,
+    pub address: SuiAddress,
+    /// The environment the entry is for, or `None` if it is for all environments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+}
+
+/// A saved `sui client ptb` program, whose arguments may refer to the template's parameters as
+/// `${name}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PtbTemplate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<TemplateParam>,
+    /// The arguments to `sui client ptb`.
+    pub args: Vec<String>,
+}
+
+/// A template parameter, written `name` or `name=default` on the command line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TemplateParam {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+impl PtbTemplate {
+    /// The value of every parameter, given the `name=value` arguments of a run of the template.
+    /// Parameters without an argument take their default value, and it is an error for one to
+    /// have neither, or for an argument to name a parameter the template does not have.
+    pub fn bindings(
+        &self,
+        args: Vec<(String, String)>,
+    ) -> Result<BTreeMap<String, String>, anyhow::Error> {
+        let mut bindings = BTreeMap::new();
+        for (name, value) in args {
+            ensure!(
+                self.params.iter().any(|p| p.name == name),
+                "Template has no parameter [{name}]"
+            );
+            ensure!(
+                bindings.insert(name.clone(), value).is_none(),
+                "Parameter [{name}] is given more than once"
+            );
+        }
+
+        for param in &self.params {
+            if !bindings.contains_key(&param.name) {
+                let Some(default) = &param.default else {
+                    bail!(
+                        "Missing value for parameter [{}], pass it with --arg {}=<VALUE>",
+                        param.name,
+                        param.name
+                    );
+                };
+                bindings.insert(param.name.clone(), default.clone());
+            }
+        }
+
+        Ok(bindings)
+    }
+}
+
+impl FromStr for TemplateParam {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, default) = match s.split_once('=') {
+            Some((name, default)) => (name, Some(default.to_string())),
+            None => (s, None),
+        };
+
+        ensure!(
+            name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+            "Invalid parameter name [{name}], it must start with a letter or underscore and \
+             contain only letters, digits and underscores"
+        );
+        Ok(TemplateParam {
+            name: name.to_string(),
+            default,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
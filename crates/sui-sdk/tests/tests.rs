@@ -4,8 +4,9 @@ use tempfile::TempDir;
 
 use fastcrypto::ed25519::Ed25519KeyPair;
 use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_macros::sim_test;
+use sui_sdk::sui_client_config::{Contact, PtbTemplate, SuiClientConfig, SuiEnv, TemplateParam};
 use sui_sdk::verify_personal_message_signature::verify_personal_message_signature;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{Ed25519SuiSignature, SuiKeyPair};
@@ -45,6 +46,95 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[test]
+fn address_book_test() {
+    let keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(1));
+    let alias = keystore.aliases()[0].alias.clone();
+    let mut config = SuiClientConfig::new(keystore);
+    config.add_env(SuiEnv::devnet());
+    config.add_env(SuiEnv::testnet());
+    config.active_env = Some("devnet".to_string());
+
+    let (everywhere, on_testnet) = (
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+    );
+    let contact = |name: &str, address, env: Option<&str>| Contact {
+        name: name.to_string(),
+        address,
+        env: env.map(str::to_string),
+    };
+    config
+        .add_contact(contact("treasury", everywhere, None))
+        .unwrap();
+    config
+        .add_contact(contact("treasury", on_testnet, Some("testnet")))
+        .unwrap();
+
+    // Names in the keystore, or that aren't valid aliases, can't be added.
+    assert!(config
+        .add_contact(contact(&alias, everywhere, None))
+        .is_err());
+    assert!(config
+        .add_contact(contact("0x1", everywhere, None))
+        .is_err());
+
+    assert_eq!(config.get_contact("treasury").unwrap().address, everywhere);
+    config.active_env = Some("testnet".to_string());
+    assert_eq!(config.get_contact("treasury").unwrap().address, on_testnet);
+    assert_eq!(config.active_contacts().len(), 1);
+    assert_eq!(config.active_contacts()["treasury"], on_testnet);
+
+    // Re-adding replaces the entry for the same environment.
+    let replacement = SuiAddress::random_for_testing_only();
+    config
+        .add_contact(contact("treasury", replacement, Some("testnet")))
+        .unwrap();
+    assert_eq!(config.address_book.len(), 2);
+    assert_eq!(config.get_contact("treasury").unwrap().address, replacement);
+
+    assert!(config.remove_contact("treasury", Some("testnet")));
+    assert!(!config.remove_contact("treasury", Some("testnet")));
+    assert_eq!(config.get_contact("treasury").unwrap().address, everywhere);
+}
+
+#[test]
+fn template_bindings_test() {
+    let template = PtbTemplate {
+        description: None,
+        params: vec!["amount".parse().unwrap(), "to=@treasury".parse().unwrap()],
+        args: vec!["--split-coins".to_string(), "gas".to_string()],
+    };
+    assert!("1amount"
+        .parse::<sui_sdk::sui_client_config::TemplateParam>()
+        .is_err());
+
+    let arg = |name: &str, value: &str| (name.to_string(), value.to_string());
+    let bindings = template.bindings(vec![arg("amount", "100")]).unwrap();
+    assert_eq!(bindings["amount"], "100");
+    assert_eq!(bindings["to"], "@treasury");
+
+    let bindings = template
+        .bindings(vec![arg("amount", "100"), arg("to", "@0x1")])
+        .unwrap();
+    assert_eq!(bindings["to"], "@0x1");
+
+    assert!(template.bindings(vec![]).is_err());
+    assert!(template
+        .bindings(vec![arg("amount", "1"), arg("other", "2")])
+        .is_err());
+    assert!(template
+        .bindings(vec![arg("amount", "1"), arg("amount", "2")])
+        .is_err());
+
+    // Templates round trip through serialization.
+    let json = serde_json::to_string(&template).unwrap();
+    assert_eq!(
+        serde_json::from_str::<PtbTemplate>(&json).unwrap(),
+        template
+    );
+}
+
 #[tokio::test]
 async fn test_verify_personal_message_signature() {
     let (address, sec1): (_, Ed25519KeyPair) = get_key_pair();
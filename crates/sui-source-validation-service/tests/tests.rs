@@ -11,6 +11,7 @@ use std::sync::{Arc, RwLock};
 use sui::client_commands::{
     GasDataArgs, PaymentArgs, SuiClientCommandResult, SuiClientCommands, TxProcessingArgs,
 };
+use sui::client_gas::GasBudget;
 use sui_json_rpc_types::{SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI};
 use sui_move_build::{BuildConfig, SuiPackageHooks};
 use sui_sdk::rpc_types::{
@@ -189,7 +190,7 @@ async fn run_publish(
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -225,7 +226,7 @@ async fn run_upgrade(
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS watermarks
+(
+    -- The pipeline governed by this watermark, i.e `epochs`, `checkpoints`,
+    -- `transactions`.
+    pipeline                    TEXT          PRIMARY KEY NOT NULL,
+    -- Inclusive upper epoch bound for this entity's data. Committer updates
+    -- this field.
+    epoch_hi_inclusive          BIGINT        NOT NULL,
+    -- Inclusive upper checkpoint bound for this entity's data. Committer
+    -- updates this field. All data of this entity in the checkpoint must be
+    -- persisted before advancing this watermark. The committer refers to this
+    -- on disaster recovery to resume writing.
+    checkpoint_hi_inclusive     BIGINT        NOT NULL,
+    -- Exclusive upper transaction sequence number bound for this entity's
+    -- data. Committer updates this field.
+    tx_hi                       BIGINT        NOT NULL,
+    -- Inclusive upper timestamp bound (in milliseconds). Committer updates
+    -- this field once it can guarantee that all checkpoints at or before this
+    -- timestamp have been written to the database.
+    timestamp_ms_hi_inclusive   BIGINT        NOT NULL,
+    -- Inclusive low watermark that the pruner advances. Data before this
+    -- watermark is considered pruned by a reader.
+    reader_lo                   BIGINT        NOT NULL,
+    -- The database's time (in milliseconds since the Unix epoch) when the
+    -- pruner last saw that some data needs to be dropped. SQLite has no
+    -- sub-second timestamp type, so unlike in Postgres this is an integer.
+    pruner_timestamp_ms         BIGINT        NOT NULL,
+    -- Column used by the pruner to track its true progress. Data below this
+    -- watermark can be immediately pruned.
+    pruner_hi                   BIGINT        NOT NULL
+);
@@ -0,0 +1,318 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An embedded store for the indexer framework, backed by a SQLite database in a single file on
+//! disk. It offers the same watermark and transaction semantics as `sui-pg-db`, so indexers (and
+//! their tests) can run as a single binary, without a database server.
+
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use diesel::connection::SimpleConnection;
+use diesel::migration::{Migration, MigrationSource, MigrationVersion};
+use diesel::sqlite::Sqlite;
+use diesel::{Connection as _, ConnectionError, SqliteConnection};
+use diesel_async::pooled_connection::ManagerConfig;
+use diesel_async::sync_connection_wrapper::SyncConnectionWrapper;
+use diesel_async::{
+    pooled_connection::{
+        bb8::{Pool, PooledConnection},
+        AsyncDieselConnectionManager,
+    },
+    AsyncConnection,
+};
+use futures::FutureExt;
+use tracing::info;
+
+mod model;
+
+pub use sui_field_count::FieldCount;
+pub use sui_sql_macro::sql;
+
+pub mod schema;
+pub mod store;
+
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// The asynchronous connection type used by the pool. SQLite connections are synchronous, so
+/// each one is wrapped to run its queries on a blocking thread.
+pub type SqliteAsyncConnection = SyncConnectionWrapper<SqliteConnection>;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct DbArgs {
+    /// Number of connections to keep in the pool.
+    #[arg(long, default_value_t = Self::default().db_connection_pool_size)]
+    pub db_connection_pool_size: u32,
+
+    /// Time spent waiting for a connection from the pool to become available, in milliseconds.
+    #[arg(long, default_value_t = Self::default().db_connection_timeout_ms)]
+    pub db_connection_timeout_ms: u64,
+
+    /// Time spent waiting for another connection to release its lock on the database before
+    /// giving up on a statement, in milliseconds.
+    #[arg(long, default_value_t = Self::default().db_busy_timeout_ms)]
+    pub db_busy_timeout_ms: u64,
+}
+
+#[derive(Clone)]
+pub struct Db {
+    pool: Pool<SqliteAsyncConnection>,
+    path: PathBuf,
+}
+
+/// Wrapper struct over the remote `PooledConnection` type for dealing with the `Store` trait.
+pub struct Connection<'a>(PooledConnection<'a, SqliteAsyncConnection>);
+
+impl DbArgs {
+    pub fn connection_timeout(&self) -> Duration {
+        Duration::from_millis(self.db_connection_timeout_ms)
+    }
+
+    pub fn busy_timeout(&self) -> Duration {
+        Duration::from_millis(self.db_busy_timeout_ms)
+    }
+}
+
+impl Db {
+    /// Construct a new DB connection pool for the database file at `path`, creating it if it does
+    /// not exist, that supports writes and reads. Instances of [Db] can be cloned to share access
+    /// to the same pool.
+    pub async fn for_write(path: impl AsRef<Path>, config: DbArgs) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_owned();
+        Ok(Self {
+            pool: pool(&path, config, false).await?,
+            path,
+        })
+    }
+
+    /// Construct a new DB connection pool for the database file at `path` whose connections
+    /// reject writes. Instances of [Db] can be cloned to share access to the same pool.
+    pub async fn for_read(path: impl AsRef<Path>, config: DbArgs) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_owned();
+        Ok(Self {
+            pool: pool(&path, config, true).await?,
+            path,
+        })
+    }
+
+    /// The path of the database file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Retrieves a connection from the pool. Can fail with a timeout if a connection cannot be
+    /// established before the [DbArgs::connection_timeout] has elapsed.
+    pub async fn connect(&self) -> anyhow::Result<Connection<'_>> {
+        Ok(Connection(self.pool.get().await?))
+    }
+
+    /// Statistics about the connection pool
+    pub fn state(&self) -> bb8::State {
+        self.pool.state()
+    }
+
+    /// Run migrations on the database. Use Diesel's `embed_migrations!` macro to generate the
+    /// `migrations` parameter for your indexer.
+    pub async fn run_migrations(
+        &self,
+        migrations: Option<&'static EmbeddedMigrations>,
+    ) -> anyhow::Result<Vec<MigrationVersion<'static>>> {
+        use diesel_migrations::MigrationHarness;
+
+        let merged_migrations = merge_migrations(migrations);
+        let url = database_url(&self.path)?;
+
+        info!("Running migrations ...");
+        let finished_migrations = tokio::task::spawn_blocking(move || {
+            let mut conn = SqliteConnection::establish(&url)?;
+            conn.run_pending_migrations(merged_migrations)
+                .map(|versions| versions.iter().map(MigrationVersion::as_owned).collect())
+                .map_err(|e| anyhow!("Failed to run migrations: {:?}", e))
+        })
+        .await??;
+
+        info!("Migrations complete.");
+        Ok(finished_migrations)
+    }
+}
+
+impl Default for DbArgs {
+    fn default() -> Self {
+        Self {
+            db_connection_pool_size: 10,
+            db_connection_timeout_ms: 60_000,
+            db_busy_timeout_ms: 5_000,
+        }
+    }
+}
+
+impl<'a> Deref for Connection<'a> {
+    type Target = PooledConnection<'a, SqliteAsyncConnection>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Connection<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+fn database_url(path: &Path) -> anyhow::Result<String> {
+    path.to_str()
+        .map(str::to_owned)
+        .with_context(|| format!("Database path is not valid UTF-8: {}", path.display()))
+}
+
+async fn pool(
+    path: &Path,
+    args: DbArgs,
+    read_only: bool,
+) -> anyhow::Result<Pool<SqliteAsyncConnection>> {
+    let busy_timeout = args.busy_timeout();
+
+    let mut config = ManagerConfig::default();
+    config.custom_setup = Box::new(move |url| {
+        async move {
+            let mut conn = SqliteAsyncConnection::establish(url).await?;
+
+            // Write-ahead logging lets readers proceed while a writer commits, which pipelines
+            // running concurrently against the same file rely on.
+            let mut setup = format!(
+                "PRAGMA journal_mode = WAL;
+                 PRAGMA synchronous = NORMAL;
+                 PRAGMA foreign_keys = ON;
+                 PRAGMA busy_timeout = {};",
+                busy_timeout.as_millis(),
+            );
+
+            if read_only {
+                setup.push_str("PRAGMA query_only = ON;");
+            }
+
+            conn.spawn_blocking(move |conn| conn.batch_execute(&setup))
+                .await
+                .map_err(ConnectionError::CouldntSetupConfiguration)?;
+
+            Ok(conn)
+        }
+        .boxed()
+    });
+
+    let manager = AsyncDieselConnectionManager::new_with_config(database_url(path)?, config);
+
+    Ok(Pool::builder()
+        .max_size(args.db_connection_pool_size)
+        .connection_timeout(args.connection_timeout())
+        .build(manager)
+        .await?)
+}
+
+/// Returns new migrations derived from the combination of provided migrations and migrations
+/// defined in this crate.
+pub fn merge_migrations(
+    migrations: Option<&'static EmbeddedMigrations>,
+) -> impl MigrationSource<Sqlite> + Send + Sync + 'static {
+    struct Migrations(Option<&'static EmbeddedMigrations>);
+    impl MigrationSource<Sqlite> for Migrations {
+        fn migrations(&self) -> diesel::migration::Result<Vec<Box<dyn Migration<Sqlite>>>> {
+            let mut migrations = MIGRATIONS.migrations()?;
+            if let Some(more_migrations) = self.0 {
+                migrations.extend(more_migrations.migrations()?);
+            }
+            Ok(migrations)
+        }
+    }
+
+    Migrations(migrations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::prelude::QueryableByName;
+    use diesel_async::RunQueryDsl;
+    use tempfile::TempDir;
+
+    #[derive(Debug, QueryableByName)]
+    struct CountResult {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        cnt: i64,
+    }
+
+    #[tokio::test]
+    async fn test_run_migrations() {
+        let dir = TempDir::new().unwrap();
+        let db = Db::for_write(dir.path().join("db.sqlite"), DbArgs::default())
+            .await
+            .unwrap();
+
+        let applied = db.run_migrations(None).await.unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.migrations().unwrap().len());
+
+        // Migrations that have already been applied are not run again.
+        assert!(db.run_migrations(None).await.unwrap().is_empty());
+
+        let mut conn = db.connect().await.unwrap();
+        let cnt: CountResult = diesel::sql_query(
+            "SELECT COUNT(*) AS cnt FROM sqlite_master WHERE type = 'table' AND name = 'watermarks'",
+        )
+        .get_result(&mut conn)
+        .await
+        .unwrap();
+        assert_eq!(cnt.cnt, 1);
+    }
+
+    #[tokio::test]
+    async fn test_read_only() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("db.sqlite");
+
+        let writer = Db::for_write(&path, DbArgs::default()).await.unwrap();
+        let reader = Db::for_read(&path, DbArgs::default()).await.unwrap();
+
+        {
+            // Create a table
+            let mut conn = writer.connect().await.unwrap();
+            diesel::sql_query("CREATE TABLE test_table (id INTEGER PRIMARY KEY)")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+
+        {
+            // Try an insert into it using the read-only connection, which should fail
+            let mut conn = reader.connect().await.unwrap();
+            let result = diesel::sql_query("INSERT INTO test_table (id) VALUES (1)")
+                .execute(&mut conn)
+                .await;
+            assert!(result.is_err());
+        }
+
+        {
+            // Then try to write to it using the write connection, which should succeed
+            let mut conn = writer.connect().await.unwrap();
+            diesel::sql_query("INSERT INTO test_table (id) VALUES (1)")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+
+        {
+            // Finally, try to read from it using the read-only connection, which should see the
+            // write.
+            let mut conn = reader.connect().await.unwrap();
+            let cnt: CountResult = diesel::sql_query("SELECT COUNT(*) AS cnt FROM test_table")
+                .get_result(&mut conn)
+                .await
+                .unwrap();
+            assert_eq!(cnt.cnt, 1);
+        }
+    }
+}
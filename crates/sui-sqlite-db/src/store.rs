@@ -0,0 +1,332 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use diesel::ExpressionMethods;
+use diesel::OptionalExtension;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use scoped_futures::ScopedBoxFuture;
+use sui_indexer_alt_framework_store_traits as store;
+use sui_sql_macro::sql;
+
+use crate::model::StoredWatermark;
+use crate::schema::watermarks;
+use crate::{Connection, Db};
+
+pub use sui_indexer_alt_framework_store_traits::Store;
+
+/// The database's current time, in milliseconds since the Unix epoch. SQLite has no native
+/// timestamp type, so this is derived from the Julian day number of the current time (the same
+/// expression is inlined in [store::Connection::pruner_watermark]).
+const NOW_MS: &str = "CAST((JULIANDAY('now') - 2440587.5) * 86400000 AS INTEGER)";
+
+#[async_trait]
+impl store::Connection for Connection<'_> {
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<store::CommitterWatermark>> {
+        let watermark: Option<(i64, i64, i64, i64)> = watermarks::table
+            .select((
+                watermarks::epoch_hi_inclusive,
+                watermarks::checkpoint_hi_inclusive,
+                watermarks::tx_hi,
+                watermarks::timestamp_ms_hi_inclusive,
+            ))
+            .filter(watermarks::pipeline.eq(pipeline))
+            .first(self)
+            .await
+            .optional()?;
+
+        if let Some(watermark) = watermark {
+            Ok(Some(store::CommitterWatermark {
+                epoch_hi_inclusive: watermark.0 as u64,
+                checkpoint_hi_inclusive: watermark.1 as u64,
+                tx_hi: watermark.2 as u64,
+                timestamp_ms_hi_inclusive: watermark.3 as u64,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<store::ReaderWatermark>> {
+        let watermark: Option<(i64, i64)> = watermarks::table
+            .select((watermarks::checkpoint_hi_inclusive, watermarks::reader_lo))
+            .filter(watermarks::pipeline.eq(pipeline))
+            .first(self)
+            .await
+            .optional()?;
+
+        if let Some(watermark) = watermark {
+            Ok(Some(store::ReaderWatermark {
+                checkpoint_hi_inclusive: watermark.0 as u64,
+                reader_lo: watermark.1 as u64,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<store::PrunerWatermark>> {
+        //     |---------- + delay ---------------------|
+        //                             |--- wait_for ---|
+        //     |-----------------------|----------------|
+        //     ^                       ^
+        //     pruner_timestamp        NOW()
+        let wait_for = sql!(as BigInt,
+            "{BigInt} + pruner_timestamp_ms - CAST((JULIANDAY('now') - 2440587.5) * 86400000 AS INTEGER)",
+            delay.as_millis() as i64,
+        );
+
+        let watermark: Option<(i64, i64, i64)> = watermarks::table
+            .select((wait_for, watermarks::pruner_hi, watermarks::reader_lo))
+            .filter(watermarks::pipeline.eq(pipeline))
+            .first(self)
+            .await
+            .optional()?;
+
+        if let Some(watermark) = watermark {
+            Ok(Some(store::PrunerWatermark {
+                wait_for_ms: watermark.0,
+                pruner_hi: watermark.1 as u64,
+                reader_lo: watermark.2 as u64,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn set_committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+        watermark: store::CommitterWatermark,
+    ) -> anyhow::Result<bool> {
+        let stored_watermark = StoredWatermark {
+            pipeline: pipeline.to_string(),
+            epoch_hi_inclusive: watermark.epoch_hi_inclusive as i64,
+            checkpoint_hi_inclusive: watermark.checkpoint_hi_inclusive as i64,
+            tx_hi: watermark.tx_hi as i64,
+            timestamp_ms_hi_inclusive: watermark.timestamp_ms_hi_inclusive as i64,
+            reader_lo: 0,
+            pruner_timestamp_ms: 0,
+            pruner_hi: 0,
+        };
+
+        use diesel::query_dsl::methods::FilterDsl;
+        Ok(diesel::insert_into(watermarks::table)
+            .values(&stored_watermark)
+            // There is an existing entry, so only write the new `hi` values
+            .on_conflict(watermarks::pipeline)
+            .do_update()
+            .set((
+                watermarks::epoch_hi_inclusive.eq(stored_watermark.epoch_hi_inclusive),
+                watermarks::checkpoint_hi_inclusive.eq(stored_watermark.checkpoint_hi_inclusive),
+                watermarks::tx_hi.eq(stored_watermark.tx_hi),
+                watermarks::timestamp_ms_hi_inclusive
+                    .eq(stored_watermark.timestamp_ms_hi_inclusive),
+            ))
+            .filter(
+                watermarks::checkpoint_hi_inclusive.lt(stored_watermark.checkpoint_hi_inclusive),
+            )
+            .execute(self)
+            .await?
+            > 0)
+    }
+
+    async fn set_reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+        reader_lo: u64,
+    ) -> anyhow::Result<bool> {
+        Ok(diesel::update(watermarks::table)
+            .set((
+                watermarks::reader_lo.eq(reader_lo as i64),
+                watermarks::pruner_timestamp_ms.eq(diesel::dsl::sql::<BigInt>(NOW_MS)),
+            ))
+            .filter(watermarks::pipeline.eq(pipeline))
+            .filter(watermarks::reader_lo.lt(reader_lo as i64))
+            .execute(self)
+            .await?
+            > 0)
+    }
+
+    async fn set_pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        pruner_hi: u64,
+    ) -> anyhow::Result<bool> {
+        Ok(diesel::update(watermarks::table)
+            .set(watermarks::pruner_hi.eq(pruner_hi as i64))
+            .filter(watermarks::pipeline.eq(pipeline))
+            .execute(self)
+            .await?
+            > 0)
+    }
+}
+
+#[async_trait]
+impl store::Store for Db {
+    type Connection<'c> = Connection<'c>;
+
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+        Ok(Connection(self.pool.get().await?))
+    }
+}
+
+#[async_trait]
+impl store::TransactionalStore for Db {
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>,
+    {
+        let mut conn = self.connect().await?;
+        AsyncConnection::transaction(&mut conn, |conn| f(conn)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scoped_futures::ScopedFutureExt;
+    use store::{CommitterWatermark, Connection as _, TransactionalStore};
+    use tempfile::TempDir;
+
+    use crate::DbArgs;
+
+    use super::*;
+
+    async fn temp_db() -> (Db, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let db = Db::for_write(dir.path().join("db.sqlite"), DbArgs::default())
+            .await
+            .unwrap();
+        db.run_migrations(None).await.unwrap();
+        (db, dir)
+    }
+
+    fn watermark(checkpoint: u64) -> CommitterWatermark {
+        CommitterWatermark {
+            epoch_hi_inclusive: 0,
+            checkpoint_hi_inclusive: checkpoint,
+            tx_hi: checkpoint * 10,
+            timestamp_ms_hi_inclusive: checkpoint * 1000,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_committer_watermark_only_rises() {
+        let (db, _dir) = temp_db().await;
+        let mut conn = db.connect().await.unwrap();
+
+        assert!(conn.committer_watermark("p").await.unwrap().is_none());
+        assert!(conn
+            .set_committer_watermark("p", watermark(5))
+            .await
+            .unwrap());
+        assert!(conn
+            .set_committer_watermark("p", watermark(7))
+            .await
+            .unwrap());
+
+        // Neither an older nor the same watermark replaces the stored one.
+        assert!(!conn
+            .set_committer_watermark("p", watermark(3))
+            .await
+            .unwrap());
+        assert!(!conn
+            .set_committer_watermark("p", watermark(7))
+            .await
+            .unwrap());
+
+        let stored = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(stored.checkpoint_hi_inclusive, 7);
+        assert_eq!(stored.tx_hi, 70);
+    }
+
+    #[tokio::test]
+    async fn test_reader_and_pruner_watermarks() {
+        let (db, _dir) = temp_db().await;
+        let mut conn = db.connect().await.unwrap();
+
+        // There is nothing to update until the committer has written a watermark.
+        assert!(!conn.set_reader_watermark("p", 5).await.unwrap());
+        assert!(conn
+            .set_committer_watermark("p", watermark(10))
+            .await
+            .unwrap());
+
+        assert!(conn.set_reader_watermark("p", 5).await.unwrap());
+        assert!(!conn.set_reader_watermark("p", 4).await.unwrap());
+
+        let reader = conn.reader_watermark("p").await.unwrap().unwrap();
+        assert_eq!(reader.checkpoint_hi_inclusive, 10);
+        assert_eq!(reader.reader_lo, 5);
+
+        // The reader watermark was just set, so the pruner has to wait for (almost) all of the
+        // delay.
+        let pruner = conn
+            .pruner_watermark("p", Duration::from_secs(60))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(pruner.wait_for_ms > 50_000 && pruner.wait_for_ms <= 60_000);
+        assert_eq!(pruner.pruner_hi, 0);
+        assert_eq!(pruner.reader_lo, 5);
+
+        assert!(conn.set_pruner_watermark("p", 5).await.unwrap());
+        let pruner = conn
+            .pruner_watermark("p", Duration::ZERO)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(pruner.wait_for_ms <= 0);
+        assert_eq!(pruner.pruner_hi, 5);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_rollback() {
+        let (db, _dir) = temp_db().await;
+
+        let result: anyhow::Result<()> = db
+            .transaction(|conn| {
+                async move {
+                    conn.set_committer_watermark("p", watermark(1)).await?;
+                    anyhow::bail!("abort");
+                }
+                .scope_boxed()
+            })
+            .await;
+        assert!(result.is_err());
+
+        let mut conn = db.connect().await.unwrap();
+        assert!(conn.committer_watermark("p").await.unwrap().is_none());
+
+        db.transaction(|conn| {
+            async move {
+                conn.set_committer_watermark("p", watermark(2)).await?;
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await
+        .unwrap();
+
+        let stored = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(stored.checkpoint_hi_inclusive, 2);
+    }
+}
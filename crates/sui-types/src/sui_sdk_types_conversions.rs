@@ -113,6 +113,7 @@ bcs_convert_impl!(
     PasskeyAuthenticator
 );
 bcs_convert_impl!(crate::effects::TransactionEvents, TransactionEvents);
+bcs_convert_impl!(crate::event::Event, Event);
 bcs_convert_impl!(crate::transaction::TransactionKind, TransactionKind);
 bcs_convert_impl!(crate::move_package::MovePackage, MovePackage);
 
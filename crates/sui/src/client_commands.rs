@@ -3,9 +3,19 @@
 
 use crate::{
     clever_error_rendering::render_clever_error_opt,
+    client_gas::{GasBudget, GasEstimate},
+    client_history::{
+        fetch_history, ActivityEntries, ActivityEntry, ActivityLog, HistoryFilter, HistoryPage,
+    },
     client_ptb::ptb::PTB,
+    completion::{
+        complete_envs, complete_functions, complete_gas_coins, complete_identities,
+        complete_modules, complete_object_ids, CompletionCache,
+    },
     displays::Pretty,
     key_identity::{get_identity_address, KeyIdentity},
+    keytool::unlock_with_prompts,
+    signing_bundle::{BundleStatus, SigningBundle},
     upgrade_compatibility::check_compatibility,
     verifier_meter::{AccumulatingMeter, Accumulator},
 };
@@ -21,6 +31,7 @@ use std::{
 use anyhow::{anyhow, bail, ensure, Context};
 use bip32::DerivationPath;
 use clap::*;
+use clap_complete::ArgValueCompleter;
 use colored::Colorize;
 use fastcrypto::{
     encoding::{Base64, Encoding},
@@ -50,7 +61,7 @@ use sui_json_rpc_types::{
     SuiProtocolConfigValue, SuiRawData, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
     SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
-use sui_keys::keystore::AccountKeystore;
+use sui_keys::{hd_wallet::HdWallets, keystore::AccountKeystore};
 use sui_move_build::{
     build_from_resolution_graph, check_conflicting_addresses, check_invalid_dependencies,
     check_unpublished_dependencies, gather_published_ids, implicit_deps, BuildConfig,
@@ -62,7 +73,7 @@ use sui_package_management::{
 };
 use sui_sdk::{
     apis::ReadApi,
-    sui_client_config::{SuiClientConfig, SuiEnv},
+    sui_client_config::{Contact, PtbTemplate, SuiClientConfig, SuiEnv, TemplateParam},
     wallet_context::WalletContext,
     SuiClient, SUI_COIN_TYPE, SUI_DEVNET_URL, SUI_LOCAL_NETWORK_URL, SUI_LOCAL_NETWORK_URL_0,
     SUI_TESTNET_URL,
@@ -124,11 +135,16 @@ pub enum SuiClientCommands {
         #[clap(long, short = 's')]
         sort_by_alias: bool,
     },
+    /// Manage the address book: names for addresses that are not in the keystore, which can be
+    /// used wherever an alias is accepted, including as `@name` in `sui client ptb`.
+    #[clap(name = "address-book", subcommand)]
+    AddressBook(AddressBookCommand),
     /// List the coin balance of an address
     #[clap(name = "balance")]
     Balance {
         /// Address (or its alias)
         #[arg(value_parser)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         address: Option<KeyIdentity>,
         /// Show balance for the specified coin (e.g., 0x2::sui::SUI).
         /// All coins will be shown if none is passed.
@@ -146,9 +162,11 @@ pub enum SuiClientCommands {
         package: ObjectID,
         /// The name of the module in the package
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_modules))]
         module: String,
         /// Function name in module
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_functions))]
         function: String,
         /// Type arguments to the generic function being called.
         /// All must be specified, or the call will fail.
@@ -211,12 +229,27 @@ pub enum SuiClientCommands {
         signed_tx_bytes: String,
     },
 
+    /// Show who has signed a signing bundle, and how much signature weight is still missing
+    /// before it can be executed.
+    #[clap(name = "bundle-status")]
+    BundleStatus {
+        /// Path to a signing bundle, as written by sui client commands using --serialize-bundle.
+        bundle: PathBuf,
+    },
+    /// Combine the signatures collected in a signing bundle and execute its transaction. Fails
+    /// if any signer has not yet reached their threshold.
+    ExecuteBundle {
+        /// Path to a signing bundle, as written by sui client commands using --serialize-bundle.
+        bundle: PathBuf,
+    },
+
     /// Request gas coin from faucet. By default, it will use the active address and the active network.
     #[clap[name = "faucet"]]
     Faucet {
         /// Address (or its alias)
         #[clap(long)]
         #[arg(value_parser)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         address: Option<KeyIdentity>,
         /// The url to the faucet
         #[clap(long)]
@@ -230,7 +263,38 @@ pub enum SuiClientCommands {
         /// Address (or its alias) owning the objects
         #[clap(name = "owner_address")]
         #[arg(value_parser)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
+        address: Option<KeyIdentity>,
+    },
+
+    /// List the transactions sent or received by an address, most recent first. With --local,
+    /// list the transactions submitted from this client instead, as recorded in its activity log
+    /// next to client.yaml.
+    #[clap(name = "history")]
+    History {
+        /// Address (or its alias). Defaults to the active address.
+        #[clap(long)]
+        #[arg(value_parser)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         address: Option<KeyIdentity>,
+        /// Maximum number of transactions to list (at least 1).
+        #[clap(
+            long,
+            default_value = "20",
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        limit: usize,
+        /// List the transactions after this digest, as printed at the end of the previous page.
+        #[clap(long)]
+        cursor: Option<TransactionDigest>,
+        /// Only list transactions matching the filter. Supported filters are
+        /// `move-call=<PACKAGE>[::<MODULE>[::<FUNCTION>]]`. Can be repeated, in which case
+        /// transactions have to match every filter.
+        #[clap(long = "filter", value_name = "FILTER")]
+        filters: Vec<HistoryFilter>,
+        /// List the transactions submitted from this client, from its local activity log.
+        #[clap(long, conflicts_with_all = ["address", "cursor", "filters"])]
+        local: bool,
     },
 
     /// Merge two coin objects into one coin
@@ -256,6 +320,9 @@ pub enum SuiClientCommands {
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word length can be
     /// { word12 | word15 | word18 | word21 | word24} default to word12 if not specified.
+    ///
+    /// With --from-seed, the keypair is instead derived from an HD wallet created with
+    /// `sui keytool wallet`, at the wallet's next account index for the key scheme.
     #[clap(name = "new-address")]
     NewAddress {
         key_scheme: SignatureScheme,
@@ -263,6 +330,10 @@ pub enum SuiClientCommands {
         alias: Option<String>,
         word_length: Option<String>,
         derivation_path: Option<DerivationPath>,
+        /// The name of the HD wallet to derive the keypair from. The wallet is unlocked with the
+        /// passphrase in SUI_KEYSTORE_PASSPHRASE if set, and otherwise by prompting for it.
+        #[clap(long, conflicts_with_all = ["word_length", "derivation_path"])]
+        from_seed: Option<String>,
     },
 
     /// Add new Sui environment.
@@ -295,6 +366,7 @@ pub enum SuiClientCommands {
         /// Address owning the object. If no address is provided, it will show all
         /// objects owned by `sui client active-address`.
         #[clap(name = "owner_address")]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         address: Option<KeyIdentity>,
     },
     /// Pay coins to recipients following specified amounts, with input coins.
@@ -303,11 +375,13 @@ pub enum SuiClientCommands {
     Pay {
         /// The input coins to be used for pay recipients, following the specified amounts.
         #[clap(long, num_args(1..))]
+        #[arg(add = ArgValueCompleter::new(complete_object_ids))]
         input_coins: Vec<ObjectID>,
 
         /// The recipient addresses, must be of same length as amounts.
         /// Aliases of addresses are also accepted as input.
         #[clap(long, num_args(1..))]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         recipients: Vec<KeyIdentity>,
 
         /// The amounts to be paid, following the order of recipients.
@@ -329,10 +403,12 @@ pub enum SuiClientCommands {
     PayAllSui {
         /// The input coins to be used for pay recipients, including the gas coin.
         #[clap(long, num_args(1..))]
+        #[arg(add = ArgValueCompleter::new(complete_object_ids))]
         input_coins: Vec<ObjectID>,
 
         /// The recipient address (or its alias if it's an address in the keystore).
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         recipient: KeyIdentity,
 
         #[clap(flatten)]
@@ -348,11 +424,13 @@ pub enum SuiClientCommands {
     PaySui {
         /// The input coins to be used for pay recipients, including the gas coin.
         #[clap(long, num_args(1..))]
+        #[arg(add = ArgValueCompleter::new(complete_object_ids))]
         input_coins: Vec<ObjectID>,
 
         /// The recipient addresses, must be of same length as amounts.
         /// Aliases of addresses are also accepted as input.
         #[clap(long, num_args(1..))]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         recipients: Vec<KeyIdentity>,
 
         /// The amounts to be paid, following the order of recipients.
@@ -458,13 +536,20 @@ pub enum SuiClientCommands {
         /// An address to be used as the active address for subsequent
         /// commands. It accepts also the alias of the address.
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         address: Option<KeyIdentity>,
         /// The RPC server URL (e.g., local rpc server, devnet rpc server, etc) to be
         /// used for subsequent commands.
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_envs))]
         env: Option<String>,
     },
 
+    /// Save `sui client ptb` programs as named, parameterised templates in client.yaml, and run
+    /// them.
+    #[clap(name = "template", subcommand)]
+    Template(TemplateCommand),
+
     /// Get the effects of executing the given transaction block
     #[clap(name = "tx-block")]
     TransactionBlock {
@@ -478,10 +563,12 @@ pub enum SuiClientCommands {
     Transfer {
         /// Recipient address (or its alias if it's an address in the keystore)
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         to: KeyIdentity,
 
         /// ID of the object to transfer
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_object_ids))]
         object_id: ObjectID,
 
         #[clap(flatten)]
@@ -501,10 +588,12 @@ pub enum SuiClientCommands {
     TransferSui {
         /// Recipient address (or its alias if it's an address in the keystore)
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         to: KeyIdentity,
 
         /// ID of the coin to transfer. This is also the gas object.
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_object_ids))]
         sui_coin_object_id: ObjectID,
 
         /// The amount to transfer, if not specified, the entire coin object will be transferred.
@@ -676,18 +765,86 @@ pub struct PaymentArgs {
     /// IDs of gas objects to be used for gas payment. If none are provided, coins are selected
     /// automatically to cover the gas budget.
     #[clap(long, num_args(1..))]
+    #[arg(add = ArgValueCompleter::new(complete_gas_coins))]
     pub gas: Vec<ObjectID>,
 }
 
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum AddressBookCommand {
+    /// Add a name for an address, replacing any entry with the same name for the same
+    /// environment.
+    Add {
+        /// The name must start with a letter and can contain only letters, digits, hyphens (-),
+        /// or underscores (_).
+        name: String,
+        address: SuiAddress,
+        /// Only use this entry while the given environment is active. Entries without an
+        /// environment are used for all environments.
+        #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_envs))]
+        env: Option<String>,
+    },
+    /// List the address book entries.
+    List,
+    /// Remove a name from the address book.
+    Remove {
+        name: String,
+        /// Remove the entry for this environment, rather than the one for all environments.
+        #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_envs))]
+        env: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum TemplateCommand {
+    /// List the saved templates.
+    List,
+    /// Remove a saved template.
+    Remove { name: String },
+    /// Run a saved template, with values for its parameters.
+    Run {
+        name: String,
+        /// A value for a template parameter, as `<NAME>=<VALUE>`. Can be repeated. Parameters
+        /// without a value take their default.
+        #[clap(long = "arg", value_name = "NAME=VALUE", value_parser = parse_template_arg)]
+        args: Vec<(String, String)>,
+    },
+    /// Save a `sui client ptb` program as a template. The program follows `--`, and refers to the
+    /// template's parameters as `${NAME}`, quoted so that the shell does not expand them, e.g.
+    /// `sui client template save payout --param amount --param to -- --split-coins gas
+    /// '[${amount}]' --assign coin --transfer-objects [coin.0] '${to}'`.
+    Save {
+        name: String,
+        /// A description of what the template does.
+        #[clap(long)]
+        description: Option<String>,
+        /// A template parameter, as `<NAME>` or `<NAME>=<DEFAULT>`. Can be repeated.
+        #[clap(long = "param", value_name = "NAME[=DEFAULT]")]
+        params: Vec<TemplateParam>,
+        /// Replace any existing template with the same name.
+        #[clap(long)]
+        force: bool,
+        /// The arguments to `sui client ptb`.
+        #[clap(last = true, required = true)]
+        args: Vec<String>,
+    },
+}
+
 /// Arguments related to setting gas data, apart from payment coins.
-#[derive(Args, Debug, Default)]
+#[derive(Args, Clone, Debug, Default)]
 pub struct GasDataArgs {
     /// An optional gas budget for this transaction (in MIST). If gas budget is not provided, the
     /// tool will first perform a dry run to estimate the gas cost, and then it will execute the
     /// transaction. Please note that this incurs a small cost in performance due to the additional
     /// dry run call.
-    #[arg(long)]
-    pub gas_budget: Option<u64>,
+    ///
+    /// With `auto[:<MULTIPLIER>]`, the budget is estimated the same way, scaled by the multiplier
+    /// (1 by default), and the estimate's computation, storage and rebate breakdown is printed.
+    #[arg(long, value_name = "MIST | auto[:MULTIPLIER]")]
+    pub gas_budget: Option<GasBudget>,
     /// An optional gas price for this transaction (in MIST). If gas price is not provided, the
     /// tool will use the current reference gas price from RPC.
     ///
@@ -729,6 +886,15 @@ pub struct TxProcessingArgs {
     /// `sui client execute-combined-signed-tx --signed-tx-bytes <SIGNED_TX_BYTES>`.
     #[arg(long)]
     pub serialize_signed_transaction: bool,
+    /// Instead of executing the transaction, write the unsigned transaction data to a signing
+    /// bundle at this path. Signatures are added to the bundle with `sui keytool sign-bundle`,
+    /// and it is executed with `sui client execute-bundle` once every signer has signed.
+    #[arg(long, value_name = "PATH")]
+    pub serialize_bundle: Option<PathBuf>,
+    /// Dry run the transaction to estimate its gas cost and budget, and print the estimate as
+    /// JSON, without executing the transaction.
+    #[arg(long)]
+    pub estimate_only: bool,
     /// Set the transaction sender to this address. When not specified, the sender is inferred
     /// by finding the owner of the gas payment. Note that when setting this field, the
     /// transaction will fail to execute if the sender's private key is not in the keystore;
@@ -831,6 +997,30 @@ impl SuiClientCommands {
                 };
                 SuiClientCommandResult::Addresses(output)
             }
+            SuiClientCommands::AddressBook(cmd) => {
+                match cmd {
+                    AddressBookCommand::Add { name, address, env } => {
+                        if let Some(env) = &env {
+                            ensure!(
+                                context.config.get_env(&Some(env.clone())).is_some(),
+                                "Environment config not found for [{env}]"
+                            );
+                        }
+                        context.config.add_contact(Contact { name, address, env })?;
+                        context.config.save()?;
+                    }
+                    AddressBookCommand::List => (),
+                    AddressBookCommand::Remove { name, env } => {
+                        ensure!(
+                            context.config.remove_contact(&name, env.as_deref()),
+                            "No address book entry [{name}] for {}",
+                            env.map_or("all environments".to_string(), |e| format!("[{e}]"))
+                        );
+                        context.config.save()?;
+                    }
+                }
+                SuiClientCommandResult::AddressBook(context.config.address_book.clone())
+            }
             SuiClientCommands::Balance {
                 address,
                 coin_type,
@@ -1324,6 +1514,7 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .move_call_tx_kind(package, &module, &function, type_args, args)
                     .await?;
+                CompletionCache::record_package(context.config.path(), &client, package).await;
 
                 let sender = context.infer_sender(&payment.gas).await?;
                 let gas_payment = client
@@ -1569,6 +1760,7 @@ impl SuiClientCommands {
                         break;
                     }
                 }
+                CompletionCache::record_objects(context.config.path(), address, &objects);
                 SuiClientCommandResult::Objects(objects)
             }
 
@@ -1577,6 +1769,40 @@ impl SuiClientCommands {
                 alias,
                 derivation_path,
                 word_length,
+                from_seed: Some(wallet),
+            } => {
+                ensure!(
+                    derivation_path.is_none() && word_length.is_none(),
+                    "Cannot set a derivation path or word length with --from-seed"
+                );
+                let mut wallets = HdWallets::for_keystore(&context.config.keystore)?;
+                let account = unlock_with_prompts(|| {
+                    wallets.add_next_account(
+                        &mut context.config.keystore,
+                        &wallet,
+                        key_scheme,
+                        alias.clone(),
+                    )
+                })?;
+
+                SuiClientCommandResult::NewAddress(NewAddressOutput {
+                    alias: context
+                        .config
+                        .keystore
+                        .get_alias_by_address(&account.address)?,
+                    address: account.address,
+                    key_scheme,
+                    recovery_phrase: None,
+                    derivation_path: Some(account.derivation_path),
+                })
+            }
+
+            SuiClientCommands::NewAddress {
+                key_scheme,
+                alias,
+                derivation_path,
+                word_length,
+                from_seed: None,
             } => {
                 let (address, phrase, scheme) = context.config.keystore.generate_and_add_new_key(
                     key_scheme,
@@ -1594,7 +1820,8 @@ impl SuiClientCommands {
                     alias,
                     address,
                     key_scheme: scheme,
-                    recovery_phrase: phrase,
+                    recovery_phrase: Some(phrase),
+                    derivation_path: None,
                 })
             }
 
@@ -1625,6 +1852,23 @@ impl SuiClientCommands {
                     .collect();
                 SuiClientCommandResult::Gas(coins)
             }
+            SuiClientCommands::History {
+                address,
+                limit,
+                cursor,
+                filters,
+                local,
+            } => {
+                if local {
+                    let entries = ActivityLog::for_config(context.config.path()).read(limit)?;
+                    SuiClientCommandResult::ActivityLog(entries)
+                } else {
+                    let address = get_identity_address(address, context)?;
+                    let client = context.get_client().await?;
+                    let page = fetch_history(&client, address, &filters, cursor, limit).await?;
+                    SuiClientCommandResult::History(page)
+                }
+            }
             SuiClientCommands::Faucet { address, url } => {
                 let address = get_identity_address(address, context)?;
                 let url = if let Some(url) = url {
@@ -1745,7 +1989,7 @@ impl SuiClientCommands {
                 let sender = tx_data.sender();
                 let gas_payment = tx_data.gas().to_owned();
                 let gas_data = GasDataArgs {
-                    gas_budget: Some(tx_data.gas_budget()),
+                    gas_budget: Some(GasBudget::Fixed(tx_data.gas_budget())),
                     gas_price: Some(tx_data.gas_price()),
                     gas_sponsor: Some(tx_data.gas_owner()),
                 };
@@ -1845,7 +2089,7 @@ impl SuiClientCommands {
                 }
                 let transaction = Transaction::from_generic_sig_data(data, sigs);
 
-                let response = context.execute_transaction_may_fail(transaction).await?;
+                let response = execute_and_record(context, transaction).await?;
                 SuiClientCommandResult::TransactionBlock(response)
             }
             SuiClientCommands::ExecuteCombinedSignedTx { signed_tx_bytes } => {
@@ -1856,7 +2100,16 @@ impl SuiClientCommands {
                         .map_err(|_| anyhow!("Invalid Base64 encoding"))?
                 ).map_err(|_| anyhow!("Failed to parse SenderSignedData bytes, check if it matches the output of sui client commands with --serialize-signed-transaction"))?;
                 let transaction = Envelope::<SenderSignedData, EmptySignInfo>::new(data);
-                let response = context.execute_transaction_may_fail(transaction).await?;
+                let response = execute_and_record(context, transaction).await?;
+                SuiClientCommandResult::TransactionBlock(response)
+            }
+            SuiClientCommands::BundleStatus { bundle } => {
+                let status = SigningBundle::read(&bundle)?.status()?;
+                SuiClientCommandResult::SigningBundle(bundle, status)
+            }
+            SuiClientCommands::ExecuteBundle { bundle } => {
+                let transaction = SigningBundle::read(&bundle)?.combine()?;
+                let response = execute_and_record(context, transaction).await?;
                 SuiClientCommandResult::TransactionBlock(response)
             }
             SuiClientCommands::NewEnv {
@@ -1936,6 +2189,66 @@ impl SuiClientCommands {
                 ptb.execute(context).await?;
                 SuiClientCommandResult::NoOutput
             }
+            SuiClientCommands::Template(cmd) => match cmd {
+                TemplateCommand::List => {
+                    SuiClientCommandResult::Templates(context.config.templates.clone())
+                }
+                TemplateCommand::Remove { name } => {
+                    ensure!(
+                        context.config.templates.remove(&name).is_some(),
+                        "No template named [{name}]"
+                    );
+                    context.config.save()?;
+                    SuiClientCommandResult::Templates(context.config.templates.clone())
+                }
+                TemplateCommand::Run { name, args } => {
+                    let template = context
+                        .config
+                        .templates
+                        .get(&name)
+                        .ok_or_else(|| anyhow!("No template named [{name}]"))?;
+                    let bindings = template
+                        .bindings(args)
+                        .map_err(|e| anyhow!("Cannot run template [{name}]: {e}"))?;
+                    let ptb = PTB {
+                        args: template.args.clone(),
+                    };
+                    ptb.execute_with_bindings(context, bindings).await?;
+                    SuiClientCommandResult::NoOutput
+                }
+                TemplateCommand::Save {
+                    name,
+                    description,
+                    params,
+                    force,
+                    args,
+                } => {
+                    ensure!(
+                        force || !context.config.templates.contains_key(&name),
+                        "Template [{name}] already exists, use --force to replace it"
+                    );
+                    let mut names = BTreeSet::new();
+                    for param in &params {
+                        ensure!(
+                            names.insert(&param.name),
+                            "Parameter [{}] is declared more than once",
+                            param.name
+                        );
+                    }
+
+                    let template = PtbTemplate {
+                        description,
+                        params,
+                        args,
+                    };
+                    context
+                        .config
+                        .templates
+                        .insert(name.clone(), template.clone());
+                    context.config.save()?;
+                    SuiClientCommandResult::Templates(BTreeMap::from([(name, template)]))
+                }
+            },
         };
         Ok(ret.prerender_clever_errors(context).await)
     }
@@ -2352,10 +2665,12 @@ impl Display for SuiClientCommandResult {
                     "keyScheme",
                     new_address.key_scheme.to_string().as_str(),
                 ]);
-                builder.push_record(vec![
-                    "recoveryPhrase",
-                    new_address.recovery_phrase.to_string().as_str(),
-                ]);
+                if let Some(phrase) = &new_address.recovery_phrase {
+                    builder.push_record(vec!["recoveryPhrase", phrase.as_str()]);
+                }
+                if let Some(path) = &new_address.derivation_path {
+                    builder.push_record(vec!["derivationPath", path.as_str()]);
+                }
 
                 let mut table = builder.build();
                 table.with(TableStyle::rounded());
@@ -2460,6 +2775,51 @@ impl Display for SuiClientCommandResult {
                     fastcrypto::encoding::Base64::encode(bcs::to_bytes(sender_signed_tx).unwrap())
                 )?;
             }
+            SuiClientCommandResult::SigningBundle(path, status) => {
+                writeln!(
+                    writer,
+                    "Signing bundle {} for transaction {}",
+                    path.display(),
+                    status.digest
+                )?;
+
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec!["address", "role", "weight", "threshold", "missing"]);
+                for signer in &status.signers {
+                    builder.push_record(vec![
+                        signer.address.to_string(),
+                        signer.role.clone(),
+                        signer.signed_weight.to_string(),
+                        signer.threshold.to_string(),
+                        signer.missing_weight.to_string(),
+                    ]);
+                    for member in &signer.members {
+                        let signed = if member.signed { "signed" } else { "unsigned" };
+                        builder.push_record(vec![
+                            format!("  {}", member.address),
+                            "member".to_string(),
+                            format!("{} ({signed})", member.weight),
+                            String::new(),
+                            String::new(),
+                        ]);
+                    }
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                writeln!(writer, "{table}")?;
+
+                if status.complete {
+                    writeln!(
+                        writer,
+                        "All signatures collected, run `sui client execute-bundle` to execute it."
+                    )?;
+                } else {
+                    writeln!(
+                        writer,
+                        "Add signatures with `sui keytool sign-bundle` before executing it."
+                    )?;
+                }
+            }
             SuiClientCommandResult::SyncClientState => {
                 writeln!(writer, "Client state sync complete.")?;
             }
@@ -2478,6 +2838,64 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::ActiveEnv(env) => {
                 write!(writer, "{}", env.as_deref().unwrap_or("None"))?;
             }
+            SuiClientCommandResult::ActivityLog(entries) => {
+                write!(writer, "{}", ActivityEntries(entries))?;
+            }
+            SuiClientCommandResult::History(page) => {
+                write!(writer, "{}", page)?;
+            }
+            SuiClientCommandResult::GasEstimate(estimate) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    serde_json::to_string_pretty(estimate).map_err(|_| std::fmt::Error)?
+                )?;
+            }
+            SuiClientCommandResult::AddressBook(contacts) => {
+                if contacts.is_empty() {
+                    write!(writer, "The address book is empty.")?;
+                } else {
+                    let mut builder = TableBuilder::default();
+                    builder.set_header(["name", "address", "env"]);
+                    for contact in contacts {
+                        builder.push_record([
+                            contact.name.clone(),
+                            contact.address.to_string(),
+                            contact.env.clone().unwrap_or_else(|| "(all)".to_string()),
+                        ]);
+                    }
+                    let mut table = builder.build();
+                    table.with(TableStyle::rounded());
+                    write!(writer, "{}", table)?;
+                }
+            }
+            SuiClientCommandResult::Templates(templates) => {
+                if templates.is_empty() {
+                    write!(writer, "No templates have been saved.")?;
+                } else {
+                    let mut builder = TableBuilder::default();
+                    builder.set_header(["name", "params", "description"]);
+                    for (name, template) in templates {
+                        let params = template
+                            .params
+                            .iter()
+                            .map(|p| match &p.default {
+                                Some(default) => format!("{}={default}", p.name),
+                                None => p.name.clone(),
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        builder.push_record([
+                            name.clone(),
+                            params,
+                            template.description.clone().unwrap_or_default(),
+                        ]);
+                    }
+                    let mut table = builder.build();
+                    table.with(TableStyle::rounded());
+                    write!(writer, "{}", table)?;
+                }
+            }
             SuiClientCommandResult::NewEnv(env) => {
                 writeln!(writer, "Added new Sui env [{}] to config.", env.alias)?;
             }
@@ -2693,6 +3111,8 @@ impl SuiClientCommandResult {
             }) => (),
             SuiClientCommandResult::ActiveAddress(_)
             | SuiClientCommandResult::ActiveEnv(_)
+            | SuiClientCommandResult::ActivityLog(_)
+            | SuiClientCommandResult::AddressBook(_)
             | SuiClientCommandResult::Addresses(_)
             | SuiClientCommandResult::Balance(_, _)
             | SuiClientCommandResult::ComputeTransactionDigest(_)
@@ -2701,6 +3121,8 @@ impl SuiClientCommandResult {
             | SuiClientCommandResult::DevInspect(_)
             | SuiClientCommandResult::Envs(_, _)
             | SuiClientCommandResult::Gas(_)
+            | SuiClientCommandResult::GasEstimate(_)
+            | SuiClientCommandResult::History(_)
             | SuiClientCommandResult::NewAddress(_)
             | SuiClientCommandResult::NewEnv(_)
             | SuiClientCommandResult::NoOutput
@@ -2710,8 +3132,10 @@ impl SuiClientCommandResult {
             | SuiClientCommandResult::RawObject(_)
             | SuiClientCommandResult::SerializedSignedTransaction(_)
             | SuiClientCommandResult::SerializedUnsignedTransaction(_)
+            | SuiClientCommandResult::SigningBundle(_, _)
             | SuiClientCommandResult::Switch(_)
             | SuiClientCommandResult::SyncClientState
+            | SuiClientCommandResult::Templates(_)
             | SuiClientCommandResult::VerifyBytecodeMeter { .. }
             | SuiClientCommandResult::VerifySource => (),
         }
@@ -2740,7 +3164,12 @@ pub struct NewAddressOutput {
     pub alias: String,
     pub address: SuiAddress,
     pub key_scheme: SignatureScheme,
-    pub recovery_phrase: String,
+    /// The mnemonic of a newly generated keypair. Keypairs derived from an HD wallet have none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_phrase: Option<String>,
+    /// The path a keypair derived from an HD wallet was derived at.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -2850,6 +3279,8 @@ impl ObjectsOutput {
 pub enum SuiClientCommandResult {
     ActiveAddress(Option<SuiAddress>),
     ActiveEnv(Option<String>),
+    ActivityLog(Vec<ActivityEntry>),
+    AddressBook(Vec<Contact>),
     Addresses(AddressesOutput),
     Balance(Vec<(Option<SuiCoinMetadata>, Vec<Coin>)>, bool),
     ChainIdentifier(String),
@@ -2859,6 +3290,8 @@ pub enum SuiClientCommandResult {
     DevInspect(DevInspectResults),
     Envs(Vec<SuiEnv>, Option<String>),
     Gas(Vec<GasCoin>),
+    GasEstimate(GasEstimate),
+    History(HistoryPage),
     NewAddress(NewAddressOutput),
     NewEnv(SuiEnv),
     NoOutput,
@@ -2868,8 +3301,10 @@ pub enum SuiClientCommandResult {
     RemoveAddress(RemoveAddressOutput),
     SerializedSignedTransaction(SenderSignedData),
     SerializedUnsignedTransaction(TransactionData),
+    SigningBundle(PathBuf, BundleStatus),
     Switch(SwitchResponse),
     SyncClientState,
+    Templates(BTreeMap<String, PtbTemplate>),
     TransactionBlock(SuiTransactionBlockResponse),
     VerifyBytecodeMeter {
         success: bool,
@@ -3085,6 +3520,19 @@ fn format_balance(
     format!("{whole}.{fractional}{suffix}")
 }
 
+/// Execute `transaction`, recording it in the activity log next to the client config whether or
+/// not it succeeds.
+async fn execute_and_record(
+    context: &WalletContext,
+    transaction: Transaction,
+) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
+    let digest = *transaction.digest();
+    let result = context.execute_transaction_may_fail(transaction).await;
+    let env = context.get_active_env().ok().map(|env| env.alias.clone());
+    ActivityLog::for_config(context.config.path()).record(digest, env, result.as_ref());
+    result
+}
+
 /// Helper function to reduce code duplication for executing dry run
 pub async fn execute_dry_run(
     context: &mut WalletContext,
@@ -3139,14 +3587,51 @@ pub async fn estimate_gas_budget(
     gas_payment: Vec<ObjectRef>,
     sponsor: Option<SuiAddress>,
 ) -> Result<u64, anyhow::Error> {
+    let estimate = estimate_gas(
+        context,
+        signer,
+        kind,
+        gas_price,
+        gas_payment,
+        sponsor,
+        GasBudget::DEFAULT_MULTIPLIER,
+    )
+    .await?;
+    Ok(estimate.gas_budget)
+}
+
+/// Dry run the transaction at the maximum gas budget, with the gas price, payment and sponsor it
+/// will be executed with, and estimate its budget from the cost of the dry run (see
+/// [`estimate_gas_budget`]), scaled by `multiplier`.
+pub async fn estimate_gas(
+    context: &mut WalletContext,
+    signer: SuiAddress,
+    kind: TransactionKind,
+    gas_price: u64,
+    gas_payment: Vec<ObjectRef>,
+    sponsor: Option<SuiAddress>,
+    multiplier: f64,
+) -> Result<GasEstimate, anyhow::Error> {
     let client = context.get_client().await?;
-    let dry_run =
-        execute_dry_run(context, signer, kind, None, gas_price, gas_payment, sponsor).await;
+    let max_gas_budget = max_gas_budget(&client).await?;
+    let dry_run = execute_dry_run(
+        context,
+        signer,
+        kind,
+        Some(max_gas_budget),
+        gas_price,
+        gas_payment,
+        sponsor,
+    )
+    .await;
     if let Ok(SuiClientCommandResult::DryRun(dry_run)) = dry_run {
         let rgp = client.read_api().get_reference_gas_price().await?;
-        Ok(estimate_gas_budget_from_gas_cost(
+        Ok(GasEstimate::new(
             dry_run.effects.gas_cost_summary(),
             rgp,
+            gas_price,
+            multiplier,
+            max_gas_budget,
         ))
     } else {
         bail!(
@@ -3203,6 +3688,8 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         dev_inspect,
         serialize_unsigned_transaction,
         serialize_signed_transaction,
+        serialize_bundle,
+        estimate_only,
         sender,
     } = processing;
 
@@ -3210,6 +3697,20 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         !serialize_unsigned_transaction || !serialize_signed_transaction,
         "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
     );
+    ensure!(
+        serialize_bundle.is_none() || !(serialize_unsigned_transaction || serialize_signed_transaction),
+        "Cannot specify --serialize-bundle together with --serialize-unsigned-transaction or --serialize-signed-transaction."
+    );
+    ensure!(
+        !estimate_only
+            || !(tx_digest
+                || dry_run
+                || dev_inspect
+                || serialize_unsigned_transaction
+                || serialize_signed_transaction
+                || serialize_bundle.is_some()),
+        "Cannot specify --estimate-only together with other flags that process the transaction."
+    );
 
     let gas_price = if let Some(gas_price) = gas_price {
         gas_price
@@ -3226,7 +3727,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             context,
             signer,
             tx_kind,
-            gas_budget,
+            gas_budget.and_then(|b| b.fixed()),
             gas_price,
             gas_payment,
             gas_sponsor,
@@ -3240,7 +3741,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             context,
             signer,
             tx_kind,
-            gas_budget,
+            gas_budget.and_then(|b| b.fixed()),
             gas_price,
             gas_payment.clone(),
             None,
@@ -3249,20 +3750,36 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
     }
 
     let gas_budget = match gas_budget {
-        Some(gas_budget) => gas_budget,
-        None => {
+        Some(GasBudget::Fixed(gas_budget)) if !estimate_only => gas_budget,
+        gas_budget => {
+            let multiplier = match gas_budget {
+                Some(GasBudget::Auto { multiplier }) => multiplier,
+                _ => GasBudget::DEFAULT_MULTIPLIER,
+            };
+
             debug!("Estimating gas budget");
-            let budget = estimate_gas_budget(
+            let estimate = estimate_gas(
                 context,
                 signer,
                 tx_kind.clone(),
                 gas_price,
                 gas_payment.clone(),
                 gas_sponsor,
+                multiplier,
             )
             .await?;
             debug!("Finished estimating gas budget");
-            budget
+
+            if estimate_only {
+                return Ok(SuiClientCommandResult::GasEstimate(estimate));
+            }
+
+            // The breakdown is only shown when it was asked for, and on stderr, to leave the
+            // command's output as it would be with a fixed budget.
+            if gas_budget.is_some() {
+                eprintln!("{estimate}");
+            }
+            estimate.gas_budget
         }
     };
 
@@ -3307,6 +3824,13 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         Ok(SuiClientCommandResult::SerializedUnsignedTransaction(
             tx_data,
         ))
+    } else if let Some(path) = serialize_bundle {
+        let bundle = SigningBundle::new(&tx_data)?;
+        bundle.write(&path)?;
+        Ok(SuiClientCommandResult::SigningBundle(
+            path,
+            bundle.status()?,
+        ))
     } else if tx_digest {
         Ok(SuiClientCommandResult::ComputeTransactionDigest(tx_data))
     } else {
@@ -3336,9 +3860,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         } else {
             let transaction = Transaction::new(sender_signed_data);
             debug!("Executing transaction: {:?}", transaction);
-            let mut response = context
-                .execute_transaction_may_fail(transaction.clone())
-                .await?;
+            let mut response = execute_and_record(context, transaction.clone()).await?;
             debug!("Transaction executed: {:?}", transaction);
             if let Some(effects) = response.effects.as_mut() {
                 prerender_clever_errors(effects, client.read_api()).await;
@@ -3533,3 +4055,11 @@ async fn get_replay_node(context: &mut WalletContext) -> Result<SR2::Node, anyho
         Chain::Unknown => bail!("Unsupported chain identifier for replay -- only testnet and mainnet are supported currently"),
     })
 }
+
+/// Parse an `--arg <NAME>=<VALUE>` of `sui client template run`.
+fn parse_template_arg(s: &str) -> Result<(String, String), anyhow::Error> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid argument [{s}], expected <NAME>=<VALUE>"))?;
+    Ok((name.to_string(), value.to_string()))
+}
@@ -0,0 +1,174 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas budgets for the transaction-building `sui client` commands. `--gas-budget auto` dry runs
+//! the transaction to estimate its cost before setting the budget, and `--estimate-only` stops
+//! after that estimate.
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, ensure};
+use serde::{Deserialize, Serialize};
+use sui_types::gas::GasCostSummary;
+use tabled::{builder::Builder as TableBuilder, settings::Style as TableStyle};
+
+use crate::client_commands::estimate_gas_budget_from_gas_cost;
+
+#[cfg(test)]
+#[path = "unit_tests/client_gas_tests.rs"]
+mod client_gas_tests;
+
+/// The value of `--gas-budget`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GasBudget {
+    /// A budget in MIST.
+    Fixed(u64),
+    /// `auto[:<MULTIPLIER>]`: dry run the transaction, and set its budget to the estimated cost,
+    /// scaled by `multiplier`.
+    Auto { multiplier: f64 },
+}
+
+/// The cost of a transaction, as estimated from a dry run, and the budget picked for it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GasEstimate {
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub non_refundable_storage_fee: u64,
+    pub net_gas_usage: i64,
+    pub reference_gas_price: u64,
+    pub gas_price: u64,
+    /// The larger of the computation cost and the net gas usage, plus a safety overhead.
+    pub estimated_budget: u64,
+    pub multiplier: f64,
+    /// `estimated_budget` scaled by `multiplier`, capped at the protocol's maximum budget.
+    pub gas_budget: u64,
+}
+
+impl GasBudget {
+    pub const DEFAULT_MULTIPLIER: f64 = 1.0;
+
+    pub fn auto() -> Self {
+        GasBudget::Auto {
+            multiplier: Self::DEFAULT_MULTIPLIER,
+        }
+    }
+
+    /// The budget to use as is, if one was given.
+    pub fn fixed(&self) -> Option<u64> {
+        match self {
+            GasBudget::Fixed(budget) => Some(*budget),
+            GasBudget::Auto { .. } => None,
+        }
+    }
+}
+
+impl GasEstimate {
+    pub fn new(
+        gas_cost_summary: &GasCostSummary,
+        reference_gas_price: u64,
+        gas_price: u64,
+        multiplier: f64,
+        max_gas_budget: u64,
+    ) -> Self {
+        let estimated_budget =
+            estimate_gas_budget_from_gas_cost(gas_cost_summary, reference_gas_price);
+        let gas_budget = ((estimated_budget as f64 * multiplier).ceil() as u64).min(max_gas_budget);
+
+        Self {
+            computation_cost: gas_cost_summary.computation_cost,
+            storage_cost: gas_cost_summary.storage_cost,
+            storage_rebate: gas_cost_summary.storage_rebate,
+            non_refundable_storage_fee: gas_cost_summary.non_refundable_storage_fee,
+            net_gas_usage: gas_cost_summary.net_gas_usage(),
+            reference_gas_price,
+            gas_price,
+            estimated_budget,
+            multiplier,
+            gas_budget,
+        }
+    }
+}
+
+impl From<u64> for GasBudget {
+    fn from(budget: u64) -> Self {
+        GasBudget::Fixed(budget)
+    }
+}
+
+impl FromStr for GasBudget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(rest) = s.strip_prefix("auto") else {
+            return s.parse().map(GasBudget::Fixed).map_err(|_| {
+                anyhow!(
+                    "Invalid gas budget '{s}', expected an amount in MIST, 'auto', or \
+                     'auto:<MULTIPLIER>'"
+                )
+            });
+        };
+
+        if rest.is_empty() {
+            return Ok(Self::auto());
+        }
+
+        let Some(multiplier) = rest.strip_prefix(':') else {
+            bail!("Invalid gas budget '{s}', expected 'auto' or 'auto:<MULTIPLIER>'");
+        };
+
+        let multiplier: f64 = multiplier
+            .parse()
+            .map_err(|_| anyhow!("Invalid gas budget multiplier '{multiplier}'"))?;
+        ensure!(
+            multiplier.is_finite() && multiplier >= 1.0,
+            "Gas budget multiplier must be at least 1, got {multiplier}"
+        );
+
+        Ok(GasBudget::Auto { multiplier })
+    }
+}
+
+impl Display for GasBudget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GasBudget::Fixed(budget) => write!(f, "{budget}"),
+            GasBudget::Auto { multiplier } if *multiplier == Self::DEFAULT_MULTIPLIER => {
+                write!(f, "auto")
+            }
+            GasBudget::Auto { multiplier } => write!(f, "auto:{multiplier}"),
+        }
+    }
+}
+
+impl Display for GasEstimate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut builder = TableBuilder::default();
+        builder.set_header(["gas estimate", "MIST"]);
+        for (name, value) in [
+            ("computation cost", self.computation_cost.to_string()),
+            ("storage cost", self.storage_cost.to_string()),
+            ("storage rebate", self.storage_rebate.to_string()),
+            (
+                "non-refundable storage fee",
+                self.non_refundable_storage_fee.to_string(),
+            ),
+            ("net gas usage", self.net_gas_usage.to_string()),
+            ("reference gas price", self.reference_gas_price.to_string()),
+            ("gas price", self.gas_price.to_string()),
+            ("estimated budget", self.estimated_budget.to_string()),
+            ("multiplier", self.multiplier.to_string()),
+            ("gas budget", self.gas_budget.to_string()),
+        ] {
+            builder.push_record([name.to_string(), value]);
+        }
+
+        let mut table = builder.build();
+        table.with(TableStyle::rounded());
+        writeln!(f, "{table}")
+    }
+}
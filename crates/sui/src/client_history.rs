@@ -0,0 +1,406 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transaction history for `sui client history`. The history of an address is paged from the
+//! RPC's `queryTransactionBlocks`, while the transactions submitted by this CLI are also appended
+//! to a local activity log (one JSON object per line), kept next to `client.yaml`.
+
+use std::{
+    fmt::{Display, Formatter},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, ensure, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sui_config::SUI_ACTIVITY_LOG_FILENAME;
+use sui_json_rpc_types::{
+    SuiCommand, SuiExecutionStatus, SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockKind, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_sdk::SuiClient;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    digests::TransactionDigest,
+};
+use tabled::{builder::Builder as TableBuilder, settings::Style as TableStyle};
+use tracing::warn;
+
+#[cfg(test)]
+#[path = "unit_tests/client_history_tests.rs"]
+mod client_history_tests;
+
+/// The number of transactions read from the RPC per page while scanning an address' history,
+/// independent of the number of transactions requested (the RPC's default maximum page size).
+const SCAN_PAGE_SIZE: usize = 50;
+
+/// The maximum number of pages read from the RPC to fill one page of history. Filters that match
+/// few transactions would otherwise scan an address' entire history in one call.
+const MAX_SCAN_PAGES: usize = 20;
+
+/// Filters applied to an address' history, on top of it being sent or received by the address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HistoryFilter {
+    /// `move-call=<PACKAGE>[::<MODULE>[::<FUNCTION>]]`: transactions that call into the package,
+    /// and optionally into a specific module and function in it.
+    MoveCall {
+        package: ObjectID,
+        module: Option<String>,
+        function: Option<String>,
+    },
+}
+
+/// A page of an address' transaction history.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPage {
+    pub address: SuiAddress,
+    pub transactions: Vec<HistoryEntry>,
+    /// Pass to `--cursor` to fetch the next page, if there is one.
+    pub next_cursor: Option<TransactionDigest>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub digest: TransactionDigest,
+    pub timestamp_ms: Option<u64>,
+    pub checkpoint: Option<u64>,
+    pub sender: Option<SuiAddress>,
+    pub status: Option<String>,
+    /// Net gas used, in MIST. Negative if the transaction's storage rebate exceeded its cost.
+    pub gas_used: Option<i64>,
+    /// The Move functions called by the transaction, as `package::module::function`.
+    pub move_calls: Vec<String>,
+}
+
+/// The local log of transactions submitted by this CLI.
+pub struct ActivityLog {
+    path: PathBuf,
+}
+
+/// A transaction submitted by this CLI, as recorded in the activity log.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEntry {
+    pub timestamp_ms: u64,
+    pub digest: TransactionDigest,
+    /// The command line the transaction was submitted with.
+    pub command: String,
+    /// The active environment when the transaction was submitted.
+    pub env: Option<String>,
+    pub gas_used: Option<i64>,
+    /// "success", "failure: <error>", or "error: <error>" if the transaction could not be
+    /// submitted at all.
+    pub status: String,
+}
+
+impl FromStr for HistoryFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((kind, value)) = s.split_once('=') else {
+            bail!("Invalid filter '{s}', expected <KIND>=<VALUE>, e.g. move-call=0x2::coin::split");
+        };
+
+        match kind {
+            "move-call" => {
+                let mut parts = value.split("::");
+                let package = parts.next().unwrap_or_default();
+                let package = ObjectID::from_str(package)
+                    .map_err(|e| anyhow!("Invalid package '{package}' in filter '{s}': {e}"))?;
+                let module = parts.next().map(str::to_string);
+                let function = parts.next().map(str::to_string);
+                ensure!(
+                    parts.next().is_none(),
+                    "Invalid filter '{s}', expected move-call=<PACKAGE>[::<MODULE>[::<FUNCTION>]]"
+                );
+                ensure!(
+                    module.as_ref().is_none_or(|m| !m.is_empty())
+                        && function.as_ref().is_none_or(|f| !f.is_empty()),
+                    "Invalid filter '{s}', module and function names cannot be empty"
+                );
+                Ok(HistoryFilter::MoveCall {
+                    package,
+                    module,
+                    function,
+                })
+            }
+            _ => bail!("Unknown filter '{kind}', supported filters are: move-call"),
+        }
+    }
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, response: &SuiTransactionBlockResponse) -> bool {
+        match self {
+            HistoryFilter::MoveCall {
+                package,
+                module,
+                function,
+            } => move_calls(response).any(|(call_package, call_module, call_function)| {
+                call_package == package
+                    && module.as_ref().is_none_or(|m| m == call_module)
+                    && function.as_ref().is_none_or(|f| f == call_function)
+            }),
+        }
+    }
+}
+
+/// Fetch up to `limit` transactions sent or received by `address`, most recent first, starting
+/// after `cursor`. Transactions not matching every filter are skipped, so more than one page may
+/// be read from the RPC to fill the result, up to [MAX_SCAN_PAGES]. If that is not enough, the
+/// result holds fewer than `limit` transactions, and a `next_cursor` to continue scanning from.
+pub async fn fetch_history(
+    client: &SuiClient,
+    address: SuiAddress,
+    filters: &[HistoryFilter],
+    mut cursor: Option<TransactionDigest>,
+    limit: usize,
+) -> Result<HistoryPage, anyhow::Error> {
+    // With no limit, the page could never be filled, and the whole history would be read.
+    ensure!(limit > 0, "The limit must be at least 1");
+
+    let query = SuiTransactionBlockResponseQuery::new(
+        Some(TransactionFilter::FromOrToAddress { addr: address }),
+        Some(
+            SuiTransactionBlockResponseOptions::new()
+                .with_input()
+                .with_effects(),
+        ),
+    );
+
+    let mut transactions = vec![];
+    let mut scanned = 0;
+    let next_cursor = 'pages: loop {
+        let page = client
+            .read_api()
+            .query_transaction_blocks(query.clone(), cursor, Some(SCAN_PAGE_SIZE), true)
+            .await?;
+        scanned += 1;
+
+        let len = page.data.len();
+        for (i, response) in page.data.into_iter().enumerate() {
+            if filters.iter().all(|filter| filter.matches(&response)) {
+                transactions.push(HistoryEntry::from(&response));
+            }
+            if transactions.len() == limit {
+                // The next page starts after the last transaction returned, if there is more.
+                let more = i + 1 < len || page.has_next_page;
+                break 'pages more.then_some(response.digest);
+            }
+        }
+
+        if !page.has_next_page {
+            break None;
+        }
+
+        cursor = page.next_cursor;
+        if scanned == MAX_SCAN_PAGES {
+            break cursor;
+        }
+    };
+
+    Ok(HistoryPage {
+        address,
+        transactions,
+        next_cursor,
+    })
+}
+
+impl From<&SuiTransactionBlockResponse> for HistoryEntry {
+    fn from(response: &SuiTransactionBlockResponse) -> Self {
+        let effects = response.effects.as_ref();
+        HistoryEntry {
+            digest: response.digest,
+            timestamp_ms: response.timestamp_ms,
+            checkpoint: response.checkpoint,
+            sender: response.transaction.as_ref().map(|tx| *tx.data.sender()),
+            status: effects.map(|effects| status_string(effects.status())),
+            gas_used: effects.map(|effects| effects.gas_cost_summary().net_gas_usage()),
+            move_calls: move_calls(response)
+                .map(|(package, module, function)| format!("{package}::{module}::{function}"))
+                .collect(),
+        }
+    }
+}
+
+impl ActivityLog {
+    /// The activity log kept next to the client config at `config_path`.
+    pub fn for_config(config_path: &Path) -> Self {
+        Self {
+            path: config_path.with_file_name(SUI_ACTIVITY_LOG_FILENAME),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, entry: &ActivityEntry) -> Result<(), anyhow::Error> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .with_context(|| format!("Cannot write to activity log {}", self.path.display()))
+    }
+
+    /// Record the outcome of submitting transaction `digest`. Failing to record it is logged
+    /// rather than returned, as the transaction has been submitted regardless.
+    pub fn record(
+        &self,
+        digest: TransactionDigest,
+        env: Option<String>,
+        outcome: Result<&SuiTransactionBlockResponse, &anyhow::Error>,
+    ) {
+        let (gas_used, status) = match outcome {
+            Ok(response) => match &response.effects {
+                Some(effects) => (
+                    Some(effects.gas_cost_summary().net_gas_usage()),
+                    status_string(effects.status()),
+                ),
+                None => (None, "unknown".to_string()),
+            },
+            Err(e) => (None, format!("error: {e}")),
+        };
+
+        let entry = ActivityEntry {
+            timestamp_ms: Utc::now().timestamp_millis() as u64,
+            digest,
+            command: std::env::args().collect::<Vec<_>>().join(" "),
+            env,
+            gas_used,
+            status,
+        };
+
+        if let Err(e) = self.append(&entry) {
+            warn!("Cannot record transaction {digest} in the activity log: {e}");
+        }
+    }
+
+    /// The last `limit` entries of the log, most recent first.
+    pub fn read(&self, limit: usize) -> Result<Vec<ActivityEntry>, anyhow::Error> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("Cannot read activity log {}", self.path.display()))?;
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .rev()
+            .take(limit)
+            .enumerate()
+            .map(|(i, line)| {
+                serde_json::from_str(line).with_context(|| {
+                    format!(
+                        "Cannot parse entry {} from the end of activity log {}",
+                        i + 1,
+                        self.path.display()
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+impl Display for HistoryPage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.transactions.is_empty() {
+            return writeln!(f, "No transactions found for {}.", self.address);
+        }
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["digest", "time", "direction", "status", "gas used", "calls"]);
+        for tx in &self.transactions {
+            let direction = match tx.sender {
+                Some(sender) if sender == self.address => "sent".to_string(),
+                Some(sender) => format!("from {sender}"),
+                None => "".to_string(),
+            };
+            builder.push_record([
+                tx.digest.to_string(),
+                tx.timestamp_ms.map(format_timestamp).unwrap_or_default(),
+                direction,
+                tx.status.clone().unwrap_or_default(),
+                tx.gas_used.map(|g| g.to_string()).unwrap_or_default(),
+                tx.move_calls.join("\n"),
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(TableStyle::rounded());
+        writeln!(f, "{table}")?;
+        if let Some(cursor) = &self.next_cursor {
+            writeln!(f, "More transactions are available with --cursor {cursor}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Display for the entries of the local activity log.
+pub struct ActivityEntries<'a>(pub &'a [ActivityEntry]);
+
+impl Display for ActivityEntries<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return writeln!(f, "No transactions have been submitted from this client.");
+        }
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["digest", "time", "env", "status", "gas used", "command"]);
+        for entry in self.0 {
+            builder.push_record([
+                entry.digest.to_string(),
+                format_timestamp(entry.timestamp_ms),
+                entry.env.clone().unwrap_or_default(),
+                entry.status.clone(),
+                entry.gas_used.map(|g| g.to_string()).unwrap_or_default(),
+                entry.command.clone(),
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(TableStyle::rounded());
+        writeln!(f, "{table}")
+    }
+}
+
+fn move_calls(
+    response: &SuiTransactionBlockResponse,
+) -> impl Iterator<Item = (&ObjectID, &String, &String)> {
+    let commands = match response
+        .transaction
+        .as_ref()
+        .map(|tx| tx.data.transaction())
+    {
+        Some(SuiTransactionBlockKind::ProgrammableTransaction(ptb)) => ptb.commands.as_slice(),
+        _ => &[],
+    };
+
+    commands.iter().filter_map(|command| match command {
+        SuiCommand::MoveCall(call) => Some((&call.package, &call.module, &call.function)),
+        _ => None,
+    })
+}
+
+fn status_string(status: &SuiExecutionStatus) -> String {
+    match status {
+        SuiExecutionStatus::Success => "success".to_string(),
+        SuiExecutionStatus::Failure { error } => format!("failure: {error}"),
+    }
+}
+
+fn format_timestamp(timestamp_ms: u64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(timestamp_ms as i64)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| timestamp_ms.to_string())
+}
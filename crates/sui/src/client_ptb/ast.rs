@@ -14,7 +14,7 @@ use sui_types::{
     Identifier, TypeTag,
 };
 
-use crate::{err, error, sp};
+use crate::{client_gas::GasBudget, err, error, sp};
 
 use super::error::{PTBResult, Span, Spanned};
 
@@ -40,10 +40,20 @@ pub const JSON: &str = "json";
 pub const TX_DIGEST: &str = "tx-digest";
 pub const DRY_RUN: &str = "dry-run";
 pub const DEV_INSPECT: &str = "dev-inspect";
+pub const ESTIMATE_ONLY: &str = "estimate-only";
+pub const EXPECT_BALANCE_CHANGE: &str = "expect-balance-change";
 pub const SERIALIZE_UNSIGNED: &str = "serialize-unsigned-transaction";
 pub const SERIALIZE_SIGNED: &str = "serialize-signed-transaction";
+pub const SERIALIZE_BUNDLE: &str = "serialize-bundle";
 pub const SENDER: &str = "sender";
 
+// Script directives, expanded before parsing
+pub const INCLUDE: &str = "include";
+pub const MACRO: &str = "macro";
+pub const EXPAND: &str = "expand";
+pub const FOR: &str = "for";
+pub const END: &str = "end";
+
 // Types
 pub const U8: &str = "u8";
 pub const U16: &str = "u16";
@@ -82,9 +92,17 @@ pub const COMMANDS: &[&str] = &[
     JSON,
     DRY_RUN,
     DEV_INSPECT,
+    ESTIMATE_ONLY,
+    EXPECT_BALANCE_CHANGE,
     SERIALIZE_UNSIGNED,
     SERIALIZE_SIGNED,
+    SERIALIZE_BUNDLE,
     SENDER,
+    INCLUDE,
+    MACRO,
+    EXPAND,
+    FOR,
+    END,
 ];
 
 pub fn is_keyword(s: &str) -> bool {
@@ -117,16 +135,41 @@ pub struct ProgramMetadata {
     pub summary_set: bool,
     pub serialize_unsigned_set: bool,
     pub serialize_signed_set: bool,
+    pub serialize_bundle: Option<Spanned<String>>,
     pub gas_object_ids: Option<Vec<Spanned<ObjectID>>>,
     pub json_set: bool,
     pub tx_digest_set: bool,
     pub dry_run_set: bool,
     pub dev_inspect_set: bool,
-    pub gas_budget: Option<Spanned<u64>>,
+    pub estimate_only_set: bool,
+    pub gas_budget: Option<Spanned<GasBudget>>,
     pub gas_price: Option<Spanned<u64>>,
     pub gas_sponsor: Option<Spanned<NumericalAddress>>,
     pub mvr_names: BTreeMap<String, Span>,
     pub sender: Option<Spanned<NumericalAddress>>,
+    pub balance_expectations: Vec<Spanned<BalanceExpectation>>,
+}
+
+/// An assertion on the change in an address' balance of a coin type, checked against a dry run of
+/// the PTB: `--expect-balance-change <ADDRESS> <COIN_TYPE> <COMPARISON> <AMOUNT>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceExpectation {
+    /// The address as written, which can be `@me` for the sender, an alias, an address book
+    /// contact, or an address.
+    pub owner: String,
+    pub coin_type: TypeTag,
+    pub comparison: Comparison,
+    pub amount: i128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
 }
 
 /// A parsed module access consisting of the address, module name, and function name.
@@ -390,6 +433,61 @@ fn delimited_list<T: fmt::Display>(
     Ok(())
 }
 
+impl Comparison {
+    pub const ALL: &'static [&'static str] = &["<", "<=", ">", ">=", "==", "!="];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "<" => Comparison::Lt,
+            "<=" => Comparison::Le,
+            ">" => Comparison::Gt,
+            ">=" => Comparison::Ge,
+            "==" => Comparison::Eq,
+            "!=" => Comparison::Ne,
+            _ => return None,
+        })
+    }
+
+    /// Whether `lhs <comparison> rhs` holds.
+    pub fn holds(&self, lhs: i128, rhs: i128) -> bool {
+        match self {
+            Comparison::Lt => lhs < rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Gt => lhs > rhs,
+            Comparison::Ge => lhs >= rhs,
+            Comparison::Eq => lhs == rhs,
+            Comparison::Ne => lhs != rhs,
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self {
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+        };
+        write!(f, "{op}")
+    }
+}
+
+impl fmt::Display for BalanceExpectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.owner,
+            self.coin_type.to_canonical_display(/* with_prefix */ true),
+            self.comparison,
+            self.amount
+        )
+    }
+}
+
 impl fmt::Display for ParsedPTBCommand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
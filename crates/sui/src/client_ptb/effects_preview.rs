@@ -0,0 +1,545 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A human-oriented summary of what a PTB would do, built from a dry run of it: the balance
+//! changes of every address, the objects created, mutated, deleted or wrapped, the objects that
+//! change owner, and the events emitted, decoded through the package resolver.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_trait::async_trait;
+use move_core_types::account_address::AccountAddress;
+use serde::Serialize;
+use sui_json_rpc_types::{
+    BalanceChange, DryRunTransactionBlockResponse, ObjectChange, SuiEvent, SuiExecutionStatus,
+    SuiObjectDataOptions, SuiRawData, SuiTransactionBlockEffectsAPI,
+};
+use sui_package_resolver::{
+    error::Error as ResolverError, Package, PackageStore, PackageStoreWithLruCache, Resolver,
+};
+use sui_sdk::SuiClient;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    gas::GasCostSummary,
+    object::{bounded_visitor::BoundedVisitor, Owner},
+    TypeTag,
+};
+
+use super::{ast::BalanceExpectation, builder::resolve_package, error::Span};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectsPreview {
+    pub status: SuiExecutionStatus,
+    pub gas_cost: GasCostSummary,
+    pub balance_changes: Vec<BalanceChange>,
+    pub objects: Vec<ObjectPreview>,
+    pub transfers: Vec<OwnershipTransfer>,
+    pub events: Vec<EventPreview>,
+    pub checks: Vec<BalanceCheck>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ObjectChangeKind {
+    Created,
+    Mutated,
+    Deleted,
+    Wrapped,
+    Published,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectPreview {
+    pub kind: ObjectChangeKind,
+    pub object_id: ObjectID,
+    pub object_type: String,
+    /// The owner after the transaction, for objects that still exist.
+    pub owner: Option<Owner>,
+    /// The object's fields before the transaction, decoded with its type's layout, for objects
+    /// that already existed (the dry run does not return the objects' new contents).
+    pub fields: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipTransfer {
+    pub object_id: ObjectID,
+    pub object_type: String,
+    pub from: Owner,
+    pub to: Owner,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPreview {
+    pub event_type: String,
+    pub sender: SuiAddress,
+    /// The event's fields, decoded with its type's layout, or as JSON if the layout could not be
+    /// resolved.
+    pub fields: String,
+}
+
+/// The outcome of a `--expect-balance-change` assertion.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceCheck {
+    /// The assertion, as written.
+    pub expectation: String,
+    pub owner: SuiAddress,
+    pub coin_type: String,
+    pub comparison: String,
+    pub amount: i128,
+    /// The balance change in the dry run.
+    pub actual: i128,
+    pub passed: bool,
+}
+
+/// Fetches packages for the resolver from the RPC.
+struct RpcPackageStore(SuiClient);
+
+impl EffectsPreview {
+    /// Summarize `response`, checking it against `expectations`, whose owners have been resolved
+    /// to addresses.
+    pub async fn new(
+        client: &SuiClient,
+        response: &DryRunTransactionBlockResponse,
+        expectations: &[(BalanceExpectation, SuiAddress)],
+    ) -> Result<Self, anyhow::Error> {
+        let (mut objects, mutated) = classify_object_changes(&response.object_changes);
+
+        // The transaction only ran as a dry run, so the objects' current owners and contents are
+        // their owners and contents before it.
+        let existing: Vec<_> = objects
+            .iter()
+            .filter(|object| {
+                !matches!(
+                    object.kind,
+                    ObjectChangeKind::Created | ObjectChangeKind::Published
+                )
+            })
+            .map(|object| object.object_id)
+            .collect();
+        let previous = if existing.is_empty() {
+            vec![]
+        } else {
+            client
+                .read_api()
+                .multi_get_object_with_options(
+                    existing,
+                    SuiObjectDataOptions::new().with_owner().with_bcs(),
+                )
+                .await?
+        };
+
+        let resolver = Resolver::new(PackageStoreWithLruCache::new(RpcPackageStore(
+            client.clone(),
+        )));
+        let mut previous_owners = BTreeMap::new();
+        for data in previous.into_iter().filter_map(|object| object.data) {
+            if let Some(owner) = &data.owner {
+                previous_owners.insert(data.object_id, owner.clone());
+            }
+            let Some(SuiRawData::MoveObject(object)) = &data.bcs else {
+                continue;
+            };
+            let tag = TypeTag::Struct(Box::new(object.type_.clone()));
+            let fields = decode_value(&resolver, tag, &object.bcs_bytes).await;
+            for preview in objects.iter_mut() {
+                if preview.object_id == data.object_id {
+                    preview.fields.clone_from(&fields);
+                }
+            }
+        }
+        let transfers = ownership_transfers(mutated, &previous_owners);
+
+        let mut events = vec![];
+        for event in &response.events.data {
+            events.push(EventPreview {
+                event_type: event.type_.to_canonical_string(/* with_prefix */ true),
+                sender: event.sender,
+                fields: decode_event(&resolver, event).await,
+            });
+        }
+
+        let checks = expectations
+            .iter()
+            .map(|(expectation, owner)| {
+                BalanceCheck::new(expectation, *owner, &response.balance_changes)
+            })
+            .collect();
+
+        Ok(Self {
+            status: response.effects.status().clone(),
+            gas_cost: response.effects.gas_cost_summary().clone(),
+            balance_changes: response.balance_changes.clone(),
+            objects,
+            transfers,
+            events,
+            checks,
+        })
+    }
+
+    /// The checks that did not pass.
+    pub fn failed_checks(&self) -> impl Iterator<Item = &BalanceCheck> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+}
+
+/// Sorts the object changes of a dry run into created, mutated, deleted, wrapped and published
+/// objects. Also returns the type and new owner of every object that still exists, keyed by ID, to
+/// find the objects that change owner.
+fn classify_object_changes(
+    object_changes: &[ObjectChange],
+) -> (Vec<ObjectPreview>, BTreeMap<ObjectID, (String, Owner)>) {
+    let mut objects = vec![];
+    let mut mutated = BTreeMap::new();
+    for change in object_changes {
+        let (kind, object_id, object_type, owner) = match change {
+            ObjectChange::Published { package_id, .. } => (
+                ObjectChangeKind::Published,
+                *package_id,
+                "package".to_string(),
+                Some(Owner::Immutable),
+            ),
+            ObjectChange::Created {
+                object_id,
+                object_type,
+                owner,
+                ..
+            } => (
+                ObjectChangeKind::Created,
+                *object_id,
+                object_type.to_canonical_string(/* with_prefix */ true),
+                Some(owner.clone()),
+            ),
+            ObjectChange::Mutated {
+                object_id,
+                object_type,
+                owner,
+                ..
+            }
+            | ObjectChange::Transferred {
+                object_id,
+                object_type,
+                recipient: owner,
+                ..
+            } => {
+                let object_type = object_type.to_canonical_string(/* with_prefix */ true);
+                mutated.insert(*object_id, (object_type.clone(), owner.clone()));
+                (
+                    ObjectChangeKind::Mutated,
+                    *object_id,
+                    object_type,
+                    Some(owner.clone()),
+                )
+            }
+            ObjectChange::Deleted {
+                object_id,
+                object_type,
+                ..
+            } => (
+                ObjectChangeKind::Deleted,
+                *object_id,
+                object_type.to_canonical_string(/* with_prefix */ true),
+                None,
+            ),
+            ObjectChange::Wrapped {
+                object_id,
+                object_type,
+                ..
+            } => (
+                ObjectChangeKind::Wrapped,
+                *object_id,
+                object_type.to_canonical_string(/* with_prefix */ true),
+                None,
+            ),
+        };
+
+        objects.push(ObjectPreview {
+            kind,
+            object_id,
+            object_type,
+            owner,
+            fields: None,
+        });
+    }
+    (objects, mutated)
+}
+
+/// The objects among `mutated` (with their types and new owners) whose owner before the
+/// transaction, in `previous_owners`, is different.
+fn ownership_transfers(
+    mutated: BTreeMap<ObjectID, (String, Owner)>,
+    previous_owners: &BTreeMap<ObjectID, Owner>,
+) -> Vec<OwnershipTransfer> {
+    mutated
+        .into_iter()
+        .filter_map(|(object_id, (object_type, to))| {
+            let from = previous_owners.get(&object_id)?;
+            (*from != to).then(|| OwnershipTransfer {
+                object_id,
+                object_type,
+                from: from.clone(),
+                to,
+            })
+        })
+        .collect()
+}
+
+impl BalanceCheck {
+    pub fn new(
+        expectation: &BalanceExpectation,
+        owner: SuiAddress,
+        balance_changes: &[BalanceChange],
+    ) -> Self {
+        let actual = total_balance_change(balance_changes, owner, &expectation.coin_type);
+        Self {
+            expectation: expectation.to_string(),
+            owner,
+            coin_type: expectation
+                .coin_type
+                .to_canonical_string(/* with_prefix */ true),
+            comparison: expectation.comparison.to_string(),
+            amount: expectation.amount,
+            actual,
+            passed: expectation.comparison.holds(actual, expectation.amount),
+        }
+    }
+}
+
+/// The net change in `owner`'s balance of `coin_type`, zero if there was none.
+pub fn total_balance_change(
+    balance_changes: &[BalanceChange],
+    owner: SuiAddress,
+    coin_type: &TypeTag,
+) -> i128 {
+    balance_changes
+        .iter()
+        .filter(|change| {
+            &change.coin_type == coin_type && change.owner.get_owner_address().ok() == Some(owner)
+        })
+        .map(|change| change.amount)
+        .sum()
+}
+
+/// Render the fields of `event`, decoding its BCS contents with the layout of its type, or as
+/// JSON if that fails.
+async fn decode_event<S: PackageStore>(resolver: &Resolver<S>, event: &SuiEvent) -> String {
+    let tag = TypeTag::Struct(Box::new(event.type_.clone()));
+    match decode_value(resolver, tag, event.bcs.bytes()).await {
+        Some(value) => value,
+        None => event.parsed_json.to_string(),
+    }
+}
+
+/// Render a value of type `tag` from its BCS `bytes`, if the type's layout can be resolved.
+async fn decode_value<S: PackageStore>(
+    resolver: &Resolver<S>,
+    tag: TypeTag,
+    bytes: &[u8],
+) -> Option<String> {
+    let layout = resolver.type_layout(tag).await.ok()?;
+    let value = BoundedVisitor::deserialize_value(bytes, &layout).ok()?;
+    Some(value.to_string())
+}
+
+#[async_trait]
+impl PackageStore for RpcPackageStore {
+    async fn fetch(&self, id: AccountAddress) -> sui_package_resolver::Result<Arc<Package>> {
+        let package = resolve_package(self.0.read_api(), id.into(), Span { start: 0, end: 0 })
+            .await
+            .map_err(|e| ResolverError::Store {
+                store: "RPC",
+                error: e.message,
+            })?;
+        Ok(Arc::new(Package::read_from_package(&package)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_ptb::ast::Comparison;
+    use move_core_types::language_storage::StructTag;
+    use std::collections::BTreeSet;
+    use sui_json_rpc_types::{BcsEvent, EventID};
+    use sui_types::{
+        base_types::{ObjectDigest, SequenceNumber},
+        digests::TransactionDigest,
+        gas_coin::GAS,
+        parse_sui_type_tag, Identifier,
+    };
+
+    /// A package store that has no packages, so that no type layout can be resolved.
+    struct EmptyPackageStore;
+
+    #[async_trait]
+    impl PackageStore for EmptyPackageStore {
+        async fn fetch(&self, id: AccountAddress) -> sui_package_resolver::Result<Arc<Package>> {
+            Err(ResolverError::PackageNotFound(id))
+        }
+    }
+
+    fn change(owner: SuiAddress, coin_type: &TypeTag, amount: i128) -> BalanceChange {
+        BalanceChange {
+            owner: Owner::AddressOwner(owner),
+            coin_type: coin_type.clone(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn balance_checks() {
+        let sui = GAS::type_tag();
+        let other = parse_sui_type_tag("0x3::foo::FOO").unwrap();
+        let me = SuiAddress::random_for_testing_only();
+        let them = SuiAddress::random_for_testing_only();
+
+        let changes = vec![
+            change(me, &sui, -2_000),
+            change(them, &sui, 1_000),
+            change(me, &other, 5),
+        ];
+
+        assert_eq!(total_balance_change(&changes, me, &sui), -2_000);
+        assert_eq!(total_balance_change(&changes, them, &other), 0);
+
+        for (comparison, amount, owner, passed) in [
+            (Comparison::Le, -1_000, me, true),
+            (Comparison::Lt, -2_000, me, false),
+            (Comparison::Eq, 1_000, them, true),
+            (Comparison::Ne, 0, them, true),
+            (Comparison::Ge, 1, them, true),
+            (Comparison::Gt, 1_000, them, false),
+        ] {
+            let expectation = BalanceExpectation {
+                owner: owner.to_string(),
+                coin_type: sui.clone(),
+                comparison,
+                amount,
+            };
+            let check = BalanceCheck::new(&expectation, owner, &changes);
+            assert_eq!(check.passed, passed, "{}", check.expectation);
+        }
+    }
+
+    #[test]
+    fn object_changes_and_transfers() {
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let struct_tag =
+            |name: &str| -> StructTag { format!("0x2::example::{name}").parse().unwrap() };
+        let ids: Vec<_> = (0..6).map(|_| ObjectID::random()).collect();
+        let version = SequenceNumber::from_u64(2);
+
+        let changes = vec![
+            ObjectChange::Published {
+                package_id: ids[0],
+                version,
+                digest: ObjectDigest::random(),
+                modules: vec!["example".to_string()],
+            },
+            ObjectChange::Created {
+                sender,
+                owner: Owner::AddressOwner(sender),
+                object_type: struct_tag("Created"),
+                object_id: ids[1],
+                version,
+                digest: ObjectDigest::random(),
+            },
+            ObjectChange::Mutated {
+                sender,
+                owner: Owner::AddressOwner(sender),
+                object_type: struct_tag("Kept"),
+                object_id: ids[2],
+                version,
+                previous_version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::random(),
+            },
+            ObjectChange::Transferred {
+                sender,
+                recipient: Owner::AddressOwner(recipient),
+                object_type: struct_tag("Sent"),
+                object_id: ids[3],
+                version,
+                digest: ObjectDigest::random(),
+            },
+            ObjectChange::Deleted {
+                sender,
+                object_type: struct_tag("Deleted"),
+                object_id: ids[4],
+                version,
+            },
+            ObjectChange::Wrapped {
+                sender,
+                object_type: struct_tag("Wrapped"),
+                object_id: ids[5],
+                version,
+            },
+        ];
+
+        let (objects, mutated) = classify_object_changes(&changes);
+        let kinds: Vec<_> = objects.iter().map(|o| (o.kind, o.object_id)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ObjectChangeKind::Published, ids[0]),
+                (ObjectChangeKind::Created, ids[1]),
+                (ObjectChangeKind::Mutated, ids[2]),
+                (ObjectChangeKind::Mutated, ids[3]),
+                (ObjectChangeKind::Deleted, ids[4]),
+                (ObjectChangeKind::Wrapped, ids[5]),
+            ]
+        );
+        assert_eq!(
+            objects[1].object_type,
+            struct_tag("Created").to_canonical_string(/* with_prefix */ true)
+        );
+        assert_eq!(objects[3].owner, Some(Owner::AddressOwner(recipient)));
+        assert!(objects[4].owner.is_none() && objects[5].owner.is_none());
+        assert_eq!(
+            mutated.keys().copied().collect::<BTreeSet<_>>(),
+            BTreeSet::from([ids[2], ids[3]])
+        );
+
+        // Only the object whose owner changed is transferred. Objects whose previous owner is
+        // unknown (e.g., because they could not be fetched) are not reported.
+        let previous_owners = BTreeMap::from([
+            (ids[2], Owner::AddressOwner(sender)),
+            (ids[3], Owner::AddressOwner(sender)),
+        ]);
+        let transfers = ownership_transfers(mutated.clone(), &previous_owners);
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].object_id, ids[3]);
+        assert_eq!(transfers[0].from, Owner::AddressOwner(sender));
+        assert_eq!(transfers[0].to, Owner::AddressOwner(recipient));
+
+        assert!(ownership_transfers(mutated, &BTreeMap::new()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn event_decoding_falls_back_to_json() {
+        let resolver = Resolver::new(EmptyPackageStore);
+        let parsed_json = serde_json::json!({ "value": "42" });
+        let event = SuiEvent {
+            id: EventID {
+                tx_digest: TransactionDigest::random(),
+                event_seq: 0,
+            },
+            package_id: ObjectID::random(),
+            transaction_module: Identifier::new("example").unwrap(),
+            sender: SuiAddress::random_for_testing_only(),
+            type_: "0x42::example::Event".parse().unwrap(),
+            parsed_json: parsed_json.clone(),
+            bcs: BcsEvent::new(bcs::to_bytes(&42u64).unwrap()),
+            timestamp_ms: None,
+        };
+
+        assert_eq!(
+            decode_event(&resolver, &event).await,
+            parsed_json.to_string()
+        );
+    }
+}
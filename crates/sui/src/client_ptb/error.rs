@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use miette::{miette, LabeledSpan, Severity};
+use miette::{miette, LabeledSpan, NamedSource, Severity};
 use std::fmt;
 use thiserror::Error;
 
@@ -171,6 +171,19 @@ impl<T: Clone> Clone for Spanned<T> {
 impl<T: Copy> Copy for Spanned<T> {}
 
 fn build_error_report(file_string: &str, error: PTBError) -> miette::Report {
+    build_unsourced_report(file_string, error).with_source_code(file_string.to_string())
+}
+
+/// Build an error report for an error located in the file called `name`, whose contents are
+/// `file_string`.
+pub fn build_named_error_report(name: &str, file_string: &str, error: PTBError) -> miette::Report {
+    build_unsourced_report(file_string, error)
+        .with_source_code(NamedSource::new(name, file_string.to_string()))
+}
+
+/// Build the report for `error`, with labels clamped to the bounds of `file_string`, but without
+/// attaching any source code to it.
+fn build_unsourced_report(file_string: &str, error: PTBError) -> miette::Report {
     let PTBError {
         span,
         message,
@@ -193,7 +206,6 @@ fn build_error_report(file_string: &str, error: PTBError) -> miette::Report {
             error_string
         ),
     }
-    .with_source_code(file_string.to_string())
 }
 
 pub fn build_error_reports(source_string: &str, errors: Vec<PTBError>) -> Vec<miette::Report> {
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::iter::Peekable;
+
 use crate::sp;
 
 use super::{
@@ -10,9 +12,11 @@ use super::{
 
 pub struct Lexer<'l, I: Iterator<Item = &'l str>> {
     pub buf: &'l str,
-    pub tokens: I,
+    pub tokens: Peekable<I>,
     pub offset: usize,
     pub done: Option<Spanned<Lexeme<'l>>>,
+    /// The number of following shell tokens to return verbatim, as `Word`s.
+    pub verbatim: usize,
 }
 
 impl<'l, I: Iterator<Item = &'l str>> Lexer<'l, I> {
@@ -21,9 +25,10 @@ impl<'l, I: Iterator<Item = &'l str>> Lexer<'l, I> {
 
         Some(Self {
             buf,
-            tokens,
+            tokens: tokens.peekable(),
             offset: 0,
             done: None,
+            verbatim: 0,
         })
     }
 
@@ -211,6 +216,14 @@ impl<'l, I: Iterator<Item = &'l str>> Iterator for Lexer<'l, I> {
 
         self.eat_whitespace();
 
+        if self.verbatim > 0 {
+            self.verbatim -= 1;
+            let Some(word) = self.eat_while(|_| true) else {
+                return Some(self.done(T::EarlyEof));
+            };
+            return Some(word.map(|src| Lexeme(T::Word, src)));
+        }
+
         let Some(c) = self.peek() else {
             return Some(self.done(T::Eof));
         };
@@ -319,6 +332,42 @@ impl<'l, I: Iterator<Item = &'l str>> Iterator for Lexer<'l, I> {
                         file.widen(c).map(|src| Lexeme(T::Upgrade, src))
                     }
 
+                    // `--gas-budget auto[:<multiplier>]` is taken as a whole, rather than
+                    // tokenized as an argument.
+                    sp!(_, "gas-budget")
+                        if self.peek().is_none()
+                            && self.tokens.peek().is_some_and(|t| t.starts_with("auto")) =>
+                    {
+                        let Some(budget) = self.eat_token() else {
+                            break 'command self.done(T::EarlyEof);
+                        };
+
+                        budget.widen(c).map(|src| Lexeme(T::AutoGasBudget, src))
+                    }
+
+                    // The arguments of `--expect-balance-change` include comparison operators
+                    // and negative numbers, so they are taken verbatim.
+                    sp!(_, "expect-balance-change") => {
+                        if let Some(next) = self.peek() {
+                            break 'command self.unexpected(next);
+                        }
+
+                        self.verbatim = 4;
+                        ident.widen(c).map(|src| Lexeme(T::Command, src))
+                    }
+
+                    sp!(_, "serialize-bundle") => {
+                        if let Some(next) = self.peek() {
+                            break 'command self.unexpected(next);
+                        }
+
+                        let Some(file) = self.eat_token() else {
+                            break 'command self.done(T::EarlyEof);
+                        };
+
+                        file.widen(c).map(|src| Lexeme(T::SerializeBundle, src))
+                    }
+
                     sp!(_, _) => ident.widen(c).map(|src| Lexeme(T::Command, src)),
                 }
             }
@@ -3,8 +3,10 @@
 
 pub mod ast;
 pub mod builder;
+pub mod effects_preview;
 pub mod error;
 pub mod lexer;
 pub mod parser;
 pub mod ptb;
+pub mod script;
 pub mod token;
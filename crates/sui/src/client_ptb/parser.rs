@@ -8,9 +8,10 @@ use move_core_types::parsing::{
     parser::{parse_u128, parse_u16, parse_u256, parse_u32, parse_u64, parse_u8},
     types::{ParsedFqName, ParsedModuleId, ParsedStructType, ParsedType},
 };
-use sui_types::{base_types::ObjectID, Identifier};
+use sui_types::{base_types::ObjectID, parse_sui_type_tag, Identifier};
 
 use crate::{
+    client_gas::GasBudget,
     client_ptb::{
         ast::{all_keywords, COMMANDS},
         builder::{display_did_you_mean, find_did_you_means},
@@ -19,7 +20,10 @@ use crate::{
 };
 
 use super::{
-    ast::{self as A, is_keyword, Argument, ModuleAccess, ParsedPTBCommand, ParsedProgram},
+    ast::{
+        self as A, is_keyword, Argument, BalanceExpectation, Comparison, ModuleAccess,
+        ParsedPTBCommand, ParsedProgram,
+    },
     error::{PTBError, PTBResult, Span, Spanned},
     lexer::Lexer,
     token::{Lexeme, Token},
@@ -40,15 +44,18 @@ struct ProgramParsingState {
     warn_shadows_set: bool,
     serialize_unsigned_set: bool,
     serialize_signed_set: bool,
+    serialize_bundle: Option<Spanned<String>>,
     json_set: bool,
     tx_digest_set: bool,
     dry_run_set: bool,
     dev_inspect_set: bool,
+    estimate_only_set: bool,
     gas_object_ids: Option<Vec<Spanned<ObjectID>>>,
-    gas_budget: Option<Spanned<u64>>,
+    gas_budget: Option<Spanned<GasBudget>>,
     gas_price: Option<Spanned<u64>>,
     gas_sponsor: Option<Spanned<NumericalAddress>>,
     sender: Option<Spanned<NumericalAddress>>,
+    balance_expectations: Vec<Spanned<BalanceExpectation>>,
 }
 
 macro_rules! mvr_ident {
@@ -74,15 +81,18 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 warn_shadows_set: false,
                 serialize_unsigned_set: false,
                 serialize_signed_set: false,
+                serialize_bundle: None,
                 json_set: false,
                 tx_digest_set: false,
                 dry_run_set: false,
                 dev_inspect_set: false,
+                estimate_only_set: false,
                 gas_object_ids: None,
                 gas_budget: None,
                 gas_price: None,
                 gas_sponsor: None,
                 sender: None,
+                balance_expectations: Vec::new(),
             },
         })
     }
@@ -130,6 +140,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 L(T::Command, A::TX_DIGEST) => flag!(tx_digest_set),
                 L(T::Command, A::DRY_RUN) => flag!(dry_run_set),
                 L(T::Command, A::DEV_INSPECT) => flag!(dev_inspect_set),
+                L(T::Command, A::ESTIMATE_ONLY) => flag!(estimate_only_set),
                 L(T::Command, A::PREVIEW) => flag!(preview_set),
                 L(T::Command, A::WARN_SHADOWS) => flag!(warn_shadows_set),
                 L(T::Command, A::GAS_COIN) => {
@@ -143,19 +154,12 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
 
                 L(T::Command, A::GAS_BUDGET) => {
                     let budget = try_!(self.parse_gas_denomination()).widen_span(sp);
-                    if let Some(other) = self.state.gas_budget.replace(budget) {
-                        self.state.errors.extend([
-                            err!(
-                                other.span,
-                                "Multiple gas budgets found. Gas budget first set here.",
-                            ),
-                            err!(
-                                budget.span => help: { "PTBs must have exactly one gas budget set." },
-                                "Budget set again here."
-                            ),
-                        ]);
-                        self.fast_forward_to_next_command();
-                    }
+                    self.set_gas_budget(budget.map(GasBudget::Fixed));
+                }
+
+                L(T::AutoGasBudget, src) => {
+                    let budget = try_!(src.parse::<GasBudget>().map_err(|e| err!(sp, "{e}")));
+                    self.set_gas_budget(sp.wrap(budget));
                 }
 
                 L(T::Command, A::GAS_PRICE) => {
@@ -180,6 +184,11 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     self.state.sender = Some(sender);
                 }
 
+                L(T::Command, A::EXPECT_BALANCE_CHANGE) => {
+                    let expectation = try_!(self.parse_balance_expectation()).widen_span(sp);
+                    self.state.balance_expectations.push(expectation);
+                }
+
                 L(T::Command, A::TRANSFER_OBJECTS) => command!(self.parse_transfer_objects()),
                 L(T::Command, A::SPLIT_COINS) => command!(self.parse_split_coins()),
                 L(T::Command, A::MERGE_COINS) => command!(self.parse_merge_coins()),
@@ -198,6 +207,22 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     Ok(cap.span.wrap(ParsedPTBCommand::Upgrade(src, cap)))
                 }),
 
+                L(T::SerializeBundle, path) => {
+                    let path = sp.wrap(path.to_owned());
+                    if let Some(other) = self.state.serialize_bundle.replace(path) {
+                        self.state.errors.extend([
+                            err!(
+                                other.span,
+                                "Multiple signing bundles found. Bundle first set here.",
+                            ),
+                            err!(
+                                sp => help: { "PTBs can be serialized to at most one signing bundle." },
+                                "Bundle set again here."
+                            ),
+                        ]);
+                    }
+                }
+
                 L(T::Command, s) => {
                     let possibles = find_did_you_means(s, COMMANDS.iter().copied())
                         .into_iter()
@@ -252,16 +277,19 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     summary_set: self.state.summary_set,
                     serialize_unsigned_set: self.state.serialize_unsigned_set,
                     serialize_signed_set: self.state.serialize_signed_set,
+                    serialize_bundle: self.state.serialize_bundle,
                     gas_object_ids: self.state.gas_object_ids,
                     json_set: self.state.json_set,
                     tx_digest_set: self.state.tx_digest_set,
                     dry_run_set: self.state.dry_run_set,
                     dev_inspect_set: self.state.dev_inspect_set,
+                    estimate_only_set: self.state.estimate_only_set,
                     gas_budget: self.state.gas_budget,
                     gas_price: self.state.gas_price,
                     gas_sponsor: self.state.gas_sponsor,
                     mvr_names: self.state.mvr_names_with_span,
                     sender: self.state.sender,
+                    balance_expectations: self.state.balance_expectations,
                 },
             ))
         } else {
@@ -409,6 +437,54 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
         Ok(sp.wrap(ParsedPTBCommand::MoveCall(function, ty_args, args)))
     }
 
+    /// Parse the verbatim arguments of `--expect-balance-change`:
+    /// `<ADDRESS> <COIN_TYPE> <COMPARISON> <AMOUNT>`.
+    fn parse_balance_expectation(&mut self) -> PTBResult<Spanned<BalanceExpectation>> {
+        let sp!(owner_sp, Lexeme(_, owner)) = self.expect(Token::Word)?;
+        let sp!(type_sp, Lexeme(_, coin_type)) = self.expect(Token::Word)?;
+        let sp!(cmp_sp, Lexeme(_, comparison)) = self.expect(Token::Word)?;
+        let sp!(amount_sp, Lexeme(_, amount)) = self.expect(Token::Word)?;
+
+        let coin_type = parse_sui_type_tag(coin_type)
+            .map_err(|e| err!(type_sp, "Invalid coin type '{coin_type}': {e}"))?;
+
+        let Some(comparison) = Comparison::parse(comparison) else {
+            error!(
+                cmp_sp => help: { "Valid comparisons are: {}", Comparison::ALL.join(", ") },
+                "Invalid comparison '{comparison}'"
+            );
+        };
+
+        let amount: i128 = amount
+            .replace('_', "")
+            .parse()
+            .map_err(|_| err!(amount_sp, "Invalid amount '{amount}', expected an integer"))?;
+
+        Ok(owner_sp.widen(amount_sp).wrap(BalanceExpectation {
+            owner: owner.to_owned(),
+            coin_type,
+            comparison,
+            amount,
+        }))
+    }
+
+    /// Record the PTB's gas budget, reporting an error if it has already been set.
+    fn set_gas_budget(&mut self, budget: Spanned<GasBudget>) {
+        if let Some(other) = self.state.gas_budget.replace(budget) {
+            self.state.errors.extend([
+                err!(
+                    other.span,
+                    "Multiple gas budgets found. Gas budget first set here.",
+                ),
+                err!(
+                    budget.span => help: { "PTBs must have exactly one gas budget set." },
+                    "Budget set again here."
+                ),
+            ]);
+            self.fast_forward_to_next_command();
+        }
+    }
+
     /// Parse a quantity of gas, as a numeric literal that is or can be inferred to be a u64.
     fn parse_gas_denomination(&mut self) -> PTBResult<Spanned<u64>> {
         Ok(match self.parse_argument()? {
@@ -8,10 +8,13 @@ use crate::{
     client_ptb::{
         ast::{ParsedProgram, Program},
         builder::{resolve_package, PTBBuilder},
-        error::{build_error_reports, PTBError, Span},
+        effects_preview::EffectsPreview,
+        error::{PTBError, Span},
+        script::Script,
         token::{Lexeme, Token},
     },
     displays::Pretty,
+    key_identity::{get_identity_address, KeyIdentity},
     mvr_resolver::MvrResolver,
     sp,
 };
@@ -21,7 +24,7 @@ use anyhow::{anyhow, ensure, Error};
 use clap::{arg, Args, ValueHint};
 use move_core_types::account_address::AccountAddress;
 use serde::Serialize;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, str::FromStr};
 use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::{wallet_context::WalletContext, SuiClient};
@@ -77,15 +80,23 @@ impl AddressData {
 impl PTB {
     /// Parses and executes the PTB with the sender as the current active address
     pub async fn execute(self, context: &mut WalletContext) -> Result<(), Error> {
+        self.execute_with_bindings(context, BTreeMap::new()).await
+    }
+
+    /// Like `execute`, with `bindings` for the program's `${name}` references, as set by
+    /// `sui client template run`.
+    pub async fn execute_with_bindings(
+        self,
+        context: &mut WalletContext,
+        bindings: BTreeMap<String, String>,
+    ) -> Result<(), Error> {
         if self.args.is_empty() {
             ptb_description().print_help().unwrap();
             return Ok(());
         }
-        let source_string = to_source_string(self.args.clone());
-
         // Tokenize once to detect help flags
         let tokens = self.args.iter().map(|s| s.as_str());
-        for sp!(_, lexeme) in Lexer::new(tokens.clone()).into_iter().flatten() {
+        for sp!(_, lexeme) in Lexer::new(tokens).into_iter().flatten() {
             match lexeme {
                 Lexeme(Token::Command, "help") => return Ok(ptb_description().print_long_help()?),
                 Lexeme(Token::Flag, "h") => return Ok(ptb_description().print_help()?),
@@ -94,14 +105,27 @@ impl PTB {
             }
         }
 
+        // Expand script directives (includes, macros, loops, and interpolation)
+        let script = match Script::expand_with_bindings(self.args, bindings) {
+            Err(rendered) => {
+                eprintln!("Encountered error when expanding PTB script:");
+                for e in rendered.iter() {
+                    eprintln!("{:?}", e);
+                }
+                anyhow::bail!("Could not build PTB due to previous error");
+            }
+            Ok(script) => script,
+        };
+
         // Tokenize and parse to get the program
+        let tokens = script.tokens.iter().map(|s| s.as_str());
         let (program, program_metadata) = match ProgramParser::new(tokens)
             .map_err(|e| vec![e])
             .and_then(|parser| parser.parse())
         {
             Err(errors) => {
                 let suffix = if errors.len() > 1 { "s" } else { "" };
-                let rendered = build_error_reports(&source_string, errors);
+                let rendered = script.render_errors(errors);
                 eprintln!("Encountered error{suffix} when parsing PTB:");
                 for e in rendered.iter() {
                     eprintln!("{:?}", e);
@@ -116,15 +140,28 @@ impl PTB {
             "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
         );
 
+        // The preview of the commands does not need a connection to the network. With --dry-run,
+        // it is followed by a preview of the commands' effects, once the PTB has been built and
+        // dry run.
+        let preview_effects = program_metadata.preview_set && program_metadata.dry_run_set;
+        let mut ptb_preview = None;
         if program_metadata.preview_set {
-            println!(
-                "{}",
-                PTBPreview {
-                    program: &program,
-                    program_metadata: &program_metadata
-                }
-            );
-            return Ok(());
+            let preview = PTBPreview {
+                program: &program,
+                program_metadata: &program_metadata,
+            };
+            if !program_metadata.json_set {
+                println!("{preview}");
+            } else if preview_effects {
+                ptb_preview = Some(serde_json::to_value(&preview)?);
+            } else {
+                let json = serde_json::json!({ "ptb": preview });
+                println!("{}", serde_json::to_string_pretty(&json)?);
+            }
+
+            if !preview_effects {
+                return Ok(());
+            }
         }
 
         let client = context.get_client().await?;
@@ -142,6 +179,13 @@ impl PTB {
             })
             .collect();
 
+        // Names in the address book can be used like aliases, but don't shadow them.
+        for (name, address) in context.config.active_contacts() {
+            starting_addresses
+                .entry(name.to_string())
+                .or_insert(AddressData::AccountAddress(AccountAddress::from(address)));
+        }
+
         let mvr_names = program_metadata.mvr_names.clone();
         let mvr_resolver = MvrResolver {
             names: program_metadata.mvr_names.into_keys().collect(),
@@ -165,7 +209,7 @@ impl PTB {
         if !warnings.is_empty() {
             let suffix = if warnings.len() > 1 { "s" } else { "" };
             eprintln!("Warning{suffix} produced when building PTB:");
-            let rendered = build_error_reports(&source_string, warnings);
+            let rendered = script.render_errors(warnings);
             for e in rendered.iter() {
                 eprintln!("{:?}", e);
             }
@@ -174,7 +218,7 @@ impl PTB {
             Err(errors) => {
                 let suffix = if errors.len() > 1 { "s" } else { "" };
                 eprintln!("Encountered error{suffix} when building PTB:");
-                let rendered = build_error_reports(&source_string, errors);
+                let rendered = script.render_errors(errors);
                 for e in rendered.iter() {
                     eprintln!("{:?}", e);
                 }
@@ -203,6 +247,16 @@ impl PTB {
             commands: ptb.commands,
         });
 
+        // `@me` in a balance expectation is the sender.
+        let mut expectations = vec![];
+        for sp!(_, expectation) in &program_metadata.balance_expectations {
+            let owner = match expectation.owner.trim_start_matches('@') {
+                "me" => sender,
+                owner => get_identity_address(Some(KeyIdentity::from_str(owner)?), context)?,
+            };
+            expectations.push((expectation.clone(), owner));
+        }
+
         let gas_data = GasDataArgs {
             gas_budget: program_metadata.gas_budget.map(|x| x.value),
             gas_price: program_metadata.gas_price.map(|x| x.value),
@@ -217,11 +271,63 @@ impl PTB {
             dev_inspect: program_metadata.dev_inspect_set,
             serialize_unsigned_transaction: program_metadata.serialize_unsigned_set,
             serialize_signed_transaction: program_metadata.serialize_signed_set,
+            serialize_bundle: program_metadata.serialize_bundle.map(|x| x.value.into()),
+            estimate_only: program_metadata.estimate_only_set,
             sender: program_metadata.sender.map(|x| x.value.into_inner().into()),
         };
 
         let gas_payment = client.transaction_builder().input_refs(&gas).await?;
 
+        // Balance expectations are checked against a dry run, before anything is executed.
+        if preview_effects || !expectations.is_empty() {
+            let dry_run = dry_run_or_execute_or_serialize(
+                sender,
+                tx_kind.clone(),
+                context,
+                gas_payment.clone(),
+                gas_data.clone(),
+                TxProcessingArgs {
+                    dry_run: true,
+                    sender: processing.sender,
+                    ..Default::default()
+                },
+            )
+            .await?;
+            let SuiClientCommandResult::DryRun(response) = dry_run else {
+                anyhow::bail!("Internal error, unexpected response from PTB dry run.");
+            };
+
+            let preview = EffectsPreview::new(&client, &response, &expectations).await?;
+            if preview_effects {
+                if program_metadata.json_set {
+                    let json = serde_json::json!({ "ptb": ptb_preview, "effects": preview });
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                } else {
+                    println!("{preview}");
+                }
+            }
+
+            let failed: Vec<_> = preview
+                .failed_checks()
+                .map(|check| {
+                    format!(
+                        "'{}' (the balance changed by {})",
+                        check.expectation, check.actual
+                    )
+                })
+                .collect();
+            ensure!(
+                failed.is_empty(),
+                "Balance expectation{} failed: {}",
+                if failed.len() > 1 { "s" } else { "" },
+                failed.join(", ")
+            );
+
+            if preview_effects {
+                return Ok(());
+            }
+        }
+
         let transaction_response = dry_run_or_execute_or_serialize(
             sender,
             tx_kind,
@@ -235,8 +341,10 @@ impl PTB {
         let transaction_response = match transaction_response {
             SuiClientCommandResult::ComputeTransactionDigest(_)
             | SuiClientCommandResult::DryRun(_)
+            | SuiClientCommandResult::GasEstimate(_)
             | SuiClientCommandResult::SerializedUnsignedTransaction(_)
-            | SuiClientCommandResult::SerializedSignedTransaction(_) => {
+            | SuiClientCommandResult::SerializedSignedTransaction(_)
+            | SuiClientCommandResult::SigningBundle(_, _) => {
                 println!("{transaction_response}");
                 return Ok(());
             }
@@ -359,7 +467,8 @@ pub fn ptb_description() -> clap::Command {
         .value_names(["NAME", "VALUE"]))
         .arg(arg!(
             --"dry-run"
-            "Perform a dry run of the PTB instead of executing it."
+            "Perform a dry run of the PTB instead of executing it. With --preview, preview the effects \
+            of the dry run."
         ))
         .arg(arg!(
             --"dev-inspect"
@@ -375,7 +484,13 @@ pub fn ptb_description() -> clap::Command {
             "An optional gas budget for this PTB (in MIST). If gas budget is not provided, the \
             tool will first perform a dry run to estimate the gas cost, and then it will execute \
             the transaction. Please note that this incurs a small cost in performance due to the \
-            additional dry run call."
+            additional dry run call. With `auto[:<MULTIPLIER>]`, the estimated budget is scaled \
+            by the multiplier, and the estimate's breakdown is printed."
+        ))
+        .arg(arg!(
+            --"estimate-only"
+            "Instead of executing the transaction, dry run it to estimate its gas cost and \
+            budget, and print the estimate as JSON."
         ))
         .arg(arg!(
             --"gas-price" <MIST>
@@ -466,7 +581,19 @@ pub fn ptb_description() -> clap::Command {
         ).value_hint(ValueHint::DirPath))
         .arg(arg!(
             --"preview"
-            "Instead of executing the transaction, preview its PTB commands."
+            "Instead of executing the transaction, preview its PTB commands. This does not connect to \
+            the network. With --dry-run, also preview the effects of a dry run of the PTB: the \
+            balance changes of every address, the objects it creates, mutates, deletes or wraps \
+            (with their decoded contents, for objects that already exist), the objects that change \
+            owner, and the events it emits."
+        ))
+        .arg(arg!(
+            --"expect-balance-change" <EXPECTATION>
+            "Check the change in an address' balance of a coin type against a dry run of the PTB, \
+            before executing it, and fail if it does not hold. The expectation is written as \
+            `<ADDRESS> <COIN_TYPE> <COMPARISON> <AMOUNT>`, where the address can be `@me` for the \
+            sender, and the comparison is one of <, <=, >, >=, == or !=. \
+            E.g. --expect-balance-change @me 0x2::sui::SUI <= -1000"
         ))
         .arg(arg!(
             --"tx-digest"
@@ -486,6 +613,12 @@ pub fn ptb_description() -> clap::Command {
             "Instead of executing the transaction, serialize the bcs bytes of the signed \
             transaction data using base64 encoding."
         ))
+        .arg(arg!(
+            --"serialize-bundle" <PATH>
+            "Instead of executing the transaction, write the unsigned transaction data to a \
+            signing bundle at this path, to collect signatures from its signers with \
+            `sui keytool sign-bundle`."
+        ))
         .arg(arg!(
             --"summary"
             "Show only a short summary (digest, execution status, gas cost). \
@@ -499,4 +632,50 @@ pub fn ptb_description() -> clap::Command {
             --"json"
             "Return command outputs in json format."
         ))
+        .arg(arg!(
+            --"include" <PTB_SCRIPT_PATH>
+            "Include the commands in a PTB script file, relative to the script including it."
+        ).long_help(
+            "Include the commands in a PTB script file. Scripts contain the same commands as the \
+            command line, split into words using shell quoting rules, and can include other \
+            scripts, relative to their own location.\
+            \n\nExamples:\
+            \n --include airdrop.ptb --gas-budget 1000000000"
+        ).value_hint(ValueHint::FilePath))
+        .arg(arg!(
+            --"macro" <MACRO>
+            "Define a macro with the given parameters, whose body extends until '--end'."
+        ).long_help(
+            "Define a macro with the given parameters, whose body extends until '--end'. \
+            Macros are expanded with '--expand', and references to their parameters in their \
+            body are written '${param}'.\
+            \n\nExamples:\
+            \n --macro pay amount to\
+            \n   --split-coins gas [${amount}] --assign coin\
+            \n   --transfer-objects [coin.0] ${to}\
+            \n --end\
+            \n --expand pay 1000 @0x42"
+        ).value_names(["NAME", "PARAMS"]))
+        .arg(arg!(
+            --"expand" <EXPAND>
+            "Expand a macro previously defined with '--macro', with the given arguments."
+        ).value_names(["NAME", "ARGS"]))
+        .arg(arg!(
+            --"for" <FOR>
+            "Repeat the commands up to '--end' for every value in a vector or CSV file."
+        ).long_help(
+            "Repeat the commands up to '--end' for every value in a vector literal, or every \
+            record of a CSV file. References to '${var}' (or '${var.column}' for CSV files) in \
+            the loop's body are replaced by the current value. '${NAME}' also expands to the \
+            environment variable NAME, if it is not otherwise bound.\
+            \n\nExamples:\
+            \n --for to in [@0x1, @0x2]\
+            \n   --split-coins gas [1000] --assign coin\
+            \n   --transfer-objects [coin.0] ${to}\
+            \n --end\
+            \n --for row in csv payouts.csv\
+            \n   --split-coins gas [${row.amount}] --assign coin\
+            \n   --transfer-objects [coin.0] ${row.recipient}\
+            \n --end"
+        ).value_names(["VAR", "in", "VALUES"]))
 }
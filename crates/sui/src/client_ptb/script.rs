@@ -0,0 +1,1048 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{err, error};
+
+use super::{
+    ast as A,
+    error::{build_error_reports, build_named_error_report, PTBError, PTBResult, Span},
+    ptb::to_source_string,
+};
+
+/// A PTB program after its script directives have been expanded, along with the information
+/// needed to map locations in the expanded program back to the command line or script file they
+/// originated from.
+///
+/// Scripts are made up of the same commands that can be passed to `sui client ptb` on the command
+/// line, split into words using shell quoting rules (including `#` comments), plus the following
+/// directives, which are expanded before the program is parsed:
+///
+/// - `--include <path>` splices in the contents of another script, resolved relative to the
+///   script that includes it.
+/// - `--macro <name> <param>* <command>* --end` defines a macro that can be expanded later with
+///   `--expand <name> <arg>*`, binding each argument to its corresponding parameter.
+/// - `--for <var> in [<value>, ...] <command>* --end` repeats its body once per value, binding the
+///   value to `var`, and `--for <var> in csv <path> <command>* --end` repeats its body once per
+///   record in a CSV file, binding `var.<column>` for each column in its header.
+/// - `${name}` is replaced by the value bound to `name` by an enclosing macro or loop, or by the
+///   environment variable `name`, and `$$` is replaced by a literal `$`.
+pub struct Script {
+    /// The expanded program, as a sequence of shell tokens.
+    pub tokens: Vec<String>,
+    /// For each expanded token, where it came from.
+    origins: Vec<Origin>,
+    /// The command line, followed by every script file that was included.
+    sources: Vec<Source>,
+}
+
+/// The contents of the command line or of a script file. Sources are laid out next to each other
+/// in a single global offset space, so that an offset identifies both a source and a position
+/// within it.
+struct Source {
+    /// The file name, or `None` for the command line.
+    name: Option<String>,
+    contents: String,
+    base: usize,
+}
+
+/// The location of a word in global offset space, and whether the word is a verbatim copy of that
+/// location, in which case positions within the word can be mapped precisely.
+#[derive(Clone, Copy, Debug)]
+struct Origin {
+    span: Span,
+    verbatim: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Word {
+    value: String,
+    origin: Origin,
+}
+
+#[derive(Clone)]
+struct Macro {
+    params: Vec<String>,
+    body: Vec<Word>,
+    /// Directory that relative includes in the macro's body are resolved against.
+    dir: PathBuf,
+}
+
+struct Expander {
+    sources: Vec<Source>,
+    macros: BTreeMap<String, Macro>,
+    /// Canonical paths of the scripts currently being included, to detect cycles.
+    including: Vec<PathBuf>,
+    /// Names of the macros currently being expanded, to detect recursion.
+    expanding: Vec<String>,
+    /// Values bound by enclosing macro expansions and loops, innermost last.
+    scopes: Vec<BTreeMap<String, String>>,
+    output: Vec<Word>,
+}
+
+impl Script {
+    /// Expand the script directives in `args` (the shell tokens passed on the command line),
+    /// returning the expanded program, or the rendered errors encountered while expanding it.
+    pub fn expand(args: Vec<String>) -> Result<Self, Vec<miette::Report>> {
+        Self::expand_with_bindings(args, BTreeMap::new())
+    }
+
+    /// Like `expand`, but with `bindings` in scope for `${name}` references throughout the
+    /// program, ahead of environment variables.
+    pub fn expand_with_bindings(
+        args: Vec<String>,
+        bindings: BTreeMap<String, String>,
+    ) -> Result<Self, Vec<miette::Report>> {
+        let mut expander = Expander {
+            sources: vec![],
+            macros: BTreeMap::new(),
+            including: vec![],
+            expanding: vec![],
+            scopes: vec![bindings],
+            output: vec![],
+        };
+
+        let base = expander.add_source(None, to_source_string(args.clone()));
+        let mut offset = base;
+        let words: Vec<_> = args
+            .into_iter()
+            .map(|value| {
+                let start = offset;
+                offset += value.len() + 1;
+                let span = Span {
+                    start,
+                    end: start + value.len(),
+                };
+                Word {
+                    value,
+                    origin: Origin {
+                        span,
+                        verbatim: true,
+                    },
+                }
+            })
+            .collect();
+
+        let result = expander.expand(&words, Path::new(""), true);
+        let (tokens, origins): (Vec<_>, Vec<_>) = expander
+            .output
+            .into_iter()
+            .map(|w| (w.value, w.origin))
+            .unzip();
+
+        let script = Script {
+            tokens,
+            origins,
+            sources: expander.sources,
+        };
+
+        match result {
+            Ok(()) => Ok(script),
+            Err(error) => Err(vec![script.render(error)]),
+        }
+    }
+
+    /// Render errors and warnings whose spans point into the expanded program (as produced by the
+    /// parser and builder) against the sources they originated from.
+    pub fn render_errors(&self, errors: Vec<PTBError>) -> Vec<miette::Report> {
+        errors
+            .into_iter()
+            .map(|mut error| {
+                error.span = self.locate(error.span);
+                self.render(error)
+            })
+            .collect()
+    }
+
+    /// Map a span in the expanded program to global offset space.
+    fn locate(&self, span: Span) -> Span {
+        let Some(last) = self.origins.last() else {
+            return span;
+        };
+
+        if span.start == usize::MAX {
+            let end = last.span.end;
+            return Span { start: end, end };
+        }
+
+        let (ix, start) = self.locate_offset(span.start, false);
+        let (jx, end) = self.locate_offset(span.end.max(span.start), true);
+
+        // Don't let a span straddle sources, or run backwards.
+        let source = |o| self.sources.partition_point(|s: &Source| s.base <= o);
+        if source(start) == source(end) && start <= end {
+            Span { start, end }
+        } else {
+            let end = self.origins[ix.min(jx)].span.end.max(start);
+            Span { start, end }
+        }
+    }
+
+    /// Map an offset in the expanded program to global offset space, returning the index of the
+    /// token it was found in alongside it. `is_end` indicates that the offset is the exclusive end
+    /// of a span.
+    fn locate_offset(&self, offset: usize, is_end: bool) -> (usize, usize) {
+        // Tokens are laid out in the expanded program separated by a single character.
+        let mut token_start = 0;
+        let mut ix = 0;
+        for (i, token) in self.tokens.iter().enumerate() {
+            let token_end = token_start + token.len();
+            ix = i;
+            let last = i + 1 == self.tokens.len();
+            if last || offset < token_end || (is_end && offset == token_end) {
+                break;
+            }
+            token_start = token_end + 1;
+        }
+
+        let Origin { span, verbatim } = self.origins[ix];
+        let rel = offset.saturating_sub(token_start);
+        let len = self.tokens[ix].len();
+
+        let global = if rel >= len {
+            span.end
+        } else if verbatim {
+            span.start + rel
+        } else if is_end {
+            span.end
+        } else {
+            span.start
+        };
+
+        (ix, global)
+    }
+
+    /// Render an error whose span is in global offset space.
+    fn render(&self, error: PTBError) -> miette::Report {
+        let ix = self
+            .sources
+            .partition_point(|s| s.base <= error.span.start)
+            .saturating_sub(1);
+
+        let Source {
+            name,
+            contents,
+            base,
+        } = &self.sources[ix];
+
+        let local = |o: usize| o.saturating_sub(*base).min(contents.len());
+        let error = PTBError {
+            span: Span {
+                start: local(error.span.start),
+                end: local(error.span.end),
+            },
+            ..error
+        };
+
+        match name {
+            Some(name) => build_named_error_report(name, contents, error),
+            None => build_error_reports(contents, vec![error])
+                .pop()
+                .expect("One error in, one report out"),
+        }
+    }
+}
+
+impl Expander {
+    /// Register a new source, returning its base offset.
+    fn add_source(&mut self, name: Option<String>, contents: String) -> usize {
+        let base = self
+            .sources
+            .last()
+            .map_or(0, |s| s.base + s.contents.len() + 1);
+
+        self.sources.push(Source {
+            name,
+            contents,
+            base,
+        });
+
+        base
+    }
+
+    /// Expand the directives in `words`, appending the result to the output. Relative includes are
+    /// resolved against `dir`, and macros can only be defined if `top_level` is set.
+    fn expand(&mut self, words: &[Word], dir: &Path, top_level: bool) -> PTBResult<()> {
+        let mut words = words.iter().peekable();
+        while let Some(word) = words.next() {
+            let sp = word.origin.span;
+            let Some(directive) = word.value.strip_prefix("--") else {
+                let word = self.interpolate(word)?;
+                self.output.push(word);
+                continue;
+            };
+
+            match directive {
+                A::INCLUDE => {
+                    let Some(path) = words.next() else {
+                        error!(sp, "Expected a path to include after '--{}'", A::INCLUDE);
+                    };
+
+                    let path = self.interpolate(path)?;
+                    self.include(dir, path, top_level)?;
+                }
+
+                A::MACRO => {
+                    if !top_level {
+                        error!(
+                            sp => help: { "Move this definition out of any loops or macros." },
+                            "Macros can only be defined at the top level of a script",
+                        );
+                    }
+
+                    let name = expect_name(&mut words, sp, "a macro name")?;
+                    let mut params = vec![];
+                    while let Some(param) = words.next_if(|w| !w.value.starts_with("--")) {
+                        params.push(check_name(param)?);
+                    }
+
+                    let body = collect_body(&mut words, sp, A::MACRO)?;
+                    let macro_ = Macro {
+                        params,
+                        body,
+                        dir: dir.to_owned(),
+                    };
+
+                    if self.macros.insert(name.clone(), macro_).is_some() {
+                        error!(sp, "Macro '{name}' is already defined");
+                    }
+                }
+
+                A::EXPAND => {
+                    let name = expect_name(&mut words, sp, "a macro name")?;
+                    let mut args = vec![];
+                    let mut arg_sp = sp;
+                    while let Some(arg) = words.next_if(|w| !w.value.starts_with("--")) {
+                        arg_sp = arg_sp.widen(arg.origin.span);
+                        args.push(self.interpolate(arg)?.value);
+                    }
+
+                    let Some(macro_) = self.macros.get(&name).cloned() else {
+                        error!(sp, "Unknown macro '{name}'");
+                    };
+
+                    if macro_.params.len() != args.len() {
+                        error!(
+                            arg_sp,
+                            "Macro '{name}' expects {} argument(s) but {} were given",
+                            macro_.params.len(),
+                            args.len(),
+                        );
+                    }
+
+                    if self.expanding.contains(&name) {
+                        error!(sp, "Macro '{name}' expands itself recursively");
+                    }
+
+                    let scope = macro_.params.into_iter().zip(args).collect();
+                    self.expanding.push(name);
+                    self.scopes.push(scope);
+                    let result = self.expand(&macro_.body, &macro_.dir, false);
+                    self.scopes.pop();
+                    self.expanding.pop();
+                    result?;
+                }
+
+                A::FOR => {
+                    let var = expect_name(&mut words, sp, "a loop variable")?;
+                    match words.next() {
+                        Some(w) if w.value == "in" => {}
+                        Some(w) => error!(w.origin.span, "Expected 'in' but found {:?}", w.value),
+                        None => error!(sp, "Expected 'in' after '--{} {var}'", A::FOR),
+                    }
+
+                    let scopes = match words.next() {
+                        Some(w) if w.value == "csv" => {
+                            let Some(path) = words.next() else {
+                                error!(w.origin.span, "Expected a path to a CSV file");
+                            };
+                            let path = self.interpolate(path)?;
+                            csv_bindings(&var, dir, &path)?
+                        }
+
+                        Some(w) if w.value.starts_with('[') => {
+                            let literal = self.vector_literal(w, &mut words)?;
+                            split_vector(&literal)?
+                                .into_iter()
+                                .map(|value| BTreeMap::from([(var.clone(), value)]))
+                                .collect()
+                        }
+
+                        Some(w) => error!(
+                            w.origin.span => help: {
+                                "Loops iterate over a literal vector, e.g. '[1, 2, 3]', or the \
+                                 records of a CSV file, e.g. 'csv payouts.csv'."
+                            },
+                            "Expected values to iterate over but found {:?}",
+                            w.value,
+                        ),
+
+                        None => error!(sp, "Expected values to iterate over"),
+                    };
+
+                    let body = collect_body(&mut words, sp, A::FOR)?;
+                    for scope in scopes {
+                        self.scopes.push(scope);
+                        let result = self.expand(&body, dir, false);
+                        self.scopes.pop();
+                        result?;
+                    }
+                }
+
+                A::END => error!(
+                    sp,
+                    "Found '--{}' without a matching '--{}' or '--{}'",
+                    A::END,
+                    A::MACRO,
+                    A::FOR,
+                ),
+
+                _ => {
+                    let word = self.interpolate(word)?;
+                    self.output.push(word);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Splice the script at `path` (relative to `dir`) into the output.
+    fn include(&mut self, dir: &Path, path: Word, top_level: bool) -> PTBResult<()> {
+        let sp = path.origin.span;
+        let file = dir.join(&path.value);
+
+        let canonical = file
+            .canonicalize()
+            .map_err(|e| err!(sp, "Cannot find PTB script '{}': {e}", file.display()))?;
+
+        if self.including.contains(&canonical) {
+            error!(sp, "PTB script '{}' includes itself", file.display());
+        }
+
+        let contents = fs::read_to_string(&canonical)
+            .map_err(|e| err!(sp, "Cannot read PTB script '{}': {e}", file.display()))?;
+
+        let base = self.add_source(Some(file.display().to_string()), contents.clone());
+        let words = split_words(base, &contents)?;
+        let dir = canonical.parent().unwrap_or(Path::new("")).to_owned();
+
+        self.including.push(canonical);
+        let result = self.expand(&words, &dir, top_level);
+        self.including.pop();
+        result
+    }
+
+    /// Gather the words making up a vector literal, starting with `first`, into a single word.
+    fn vector_literal<'w>(
+        &self,
+        first: &Word,
+        words: &mut std::iter::Peekable<impl Iterator<Item = &'w Word>>,
+    ) -> PTBResult<Word> {
+        let mut literal = self.interpolate(first)?;
+        literal.origin.verbatim = false;
+
+        while !is_balanced(&literal.value) {
+            let Some(next) = words.next_if(|w| !w.value.starts_with("--")) else {
+                error!(literal.origin.span, "Unterminated vector literal");
+            };
+
+            let next = self.interpolate(next)?;
+            literal.value.push(' ');
+            literal.value.push_str(&next.value);
+            literal.origin.span = literal.origin.span.widen(next.origin.span);
+        }
+
+        Ok(literal)
+    }
+
+    /// Replace occurrences of `${name}` in `word` with the value bound to `name`.
+    fn interpolate(&self, word: &Word) -> PTBResult<Word> {
+        if !word.value.contains('$') {
+            return Ok(word.clone());
+        }
+
+        let sp = word.origin.span;
+        let mut value = String::new();
+        let mut rest = word.value.as_str();
+        while let Some(ix) = rest.find('$') {
+            value.push_str(&rest[..ix]);
+            rest = &rest[ix..];
+
+            if let Some(r) = rest.strip_prefix("$$") {
+                value.push('$');
+                rest = r;
+                continue;
+            }
+
+            let Some(r) = rest.strip_prefix("${") else {
+                value.push('$');
+                rest = &rest[1..];
+                continue;
+            };
+
+            let Some(close) = r.find('}') else {
+                error!(sp, "Unterminated '${{' in {:?}", word.value);
+            };
+
+            let name = &r[..close];
+            let Some(binding) = self.lookup(name) else {
+                error!(
+                    sp => help: {
+                        "Variables are bound by macro parameters, loops, or the environment. Use \
+                         '$$' for a literal '$'."
+                    },
+                    "Unbound variable '{name}'",
+                );
+            };
+
+            value.push_str(&binding);
+            rest = &r[close + 1..];
+        }
+
+        value.push_str(rest);
+        Ok(Word {
+            value,
+            origin: Origin {
+                span: sp,
+                verbatim: false,
+            },
+        })
+    }
+
+    fn lookup(&self, name: &str) -> Option<String> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).cloned())
+            .or_else(|| std::env::var(name).ok())
+    }
+}
+
+/// Split the contents of a script into words following shell quoting rules. `base` is the global
+/// offset of `contents`.
+fn split_words(base: usize, contents: &str) -> PTBResult<Vec<Word>> {
+    let mut words = vec![];
+    let mut chars = contents.char_indices().peekable();
+
+    let unterminated = |start: usize, what: &str| {
+        let span = Span {
+            start: base + start,
+            end: base + contents.len(),
+        };
+        err!(span, "Unterminated {what}")
+    };
+
+    loop {
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+        let Some(&(start, c)) = chars.peek() else {
+            break;
+        };
+
+        // Comments run until the end of the line.
+        if c == '#' {
+            while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+            continue;
+        }
+
+        let mut value = String::new();
+        let mut quoted = false;
+        let mut end = start;
+        while let Some((ix, c)) = chars.next_if(|(_, c)| !c.is_whitespace()) {
+            end = ix + c.len_utf8();
+            match c {
+                '\'' => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            Some((ix, '\'')) => {
+                                end = ix + 1;
+                                break;
+                            }
+                            Some((_, c)) => value.push(c),
+                            None => return Err(unterminated(start, "single-quoted string")),
+                        }
+                    }
+                }
+
+                '"' => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            Some((ix, '"')) => {
+                                end = ix + 1;
+                                break;
+                            }
+                            Some((_, '\\')) => match chars.next() {
+                                Some((_, c @ ('"' | '\\' | '$' | '`'))) => value.push(c),
+                                Some((_, '\n')) => {}
+                                Some((_, c)) => {
+                                    value.push('\\');
+                                    value.push(c);
+                                }
+                                None => return Err(unterminated(start, "double-quoted string")),
+                            },
+                            Some((_, c)) => value.push(c),
+                            None => return Err(unterminated(start, "double-quoted string")),
+                        }
+                    }
+                }
+
+                '\\' => match chars.next() {
+                    Some((ix, '\n')) => end = ix + 1,
+                    Some((ix, c)) => {
+                        value.push(c);
+                        end = ix + c.len_utf8();
+                    }
+                    None => {}
+                },
+
+                c => value.push(c),
+            }
+        }
+
+        if value.is_empty() && !quoted {
+            continue;
+        }
+
+        let verbatim = value == contents[start..end];
+        words.push(Word {
+            value,
+            origin: Origin {
+                span: Span {
+                    start: base + start,
+                    end: base + end,
+                },
+                verbatim,
+            },
+        });
+    }
+
+    Ok(words)
+}
+
+/// Consume the next word, which must be a valid name.
+fn expect_name<'w>(
+    words: &mut impl Iterator<Item = &'w Word>,
+    sp: Span,
+    what: &str,
+) -> PTBResult<String> {
+    let Some(word) = words.next() else {
+        error!(sp, "Expected {what}");
+    };
+
+    check_name(word)
+}
+
+/// Check that `word` is a valid macro, parameter or loop variable name.
+fn check_name(word: &Word) -> PTBResult<String> {
+    let mut chars = word.value.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if !valid {
+        error!(
+            word.origin.span,
+            "Expected a name but found {:?}", word.value
+        );
+    }
+
+    Ok(word.value.clone())
+}
+
+/// Consume the body of a `--macro` or `--for` directive (which started at `sp`), up to and
+/// including its matching `--end`.
+fn collect_body<'w>(
+    words: &mut impl Iterator<Item = &'w Word>,
+    sp: Span,
+    directive: &str,
+) -> PTBResult<Vec<Word>> {
+    let mut body = vec![];
+    let mut depth = 0;
+    for word in words {
+        match word.value.strip_prefix("--") {
+            Some(A::MACRO | A::FOR) => depth += 1,
+            Some(A::END) if depth == 0 => return Ok(body),
+            Some(A::END) => depth -= 1,
+            _ => {}
+        }
+        body.push(word.clone());
+    }
+
+    error!(
+        sp => help: { "Every '--{directive}' must be closed by an '--{}'.", A::END },
+        "Unterminated '--{directive}'",
+    )
+}
+
+/// Bindings for each record of the CSV file at `path` (relative to `dir`), binding each column
+/// `col` to `var.col`.
+fn csv_bindings(var: &str, dir: &Path, path: &Word) -> PTBResult<Vec<BTreeMap<String, String>>> {
+    let sp = path.origin.span;
+    let file = dir.join(&path.value);
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(&file)
+        .map_err(|e| err!(sp, "Cannot read CSV file '{}': {e}", file.display()))?;
+
+    let headers = reader
+        .headers()
+        .map_err(|e| {
+            err!(
+                sp,
+                "Cannot read header of CSV file '{}': {e}",
+                file.display()
+            )
+        })?
+        .clone();
+
+    reader
+        .records()
+        .map(|record| {
+            let record =
+                record.map_err(|e| err!(sp, "Malformed CSV file '{}': {e}", file.display()))?;
+            Ok(headers
+                .iter()
+                .zip(record.iter())
+                .map(|(col, value)| (format!("{var}.{col}"), value.to_owned()))
+                .collect())
+        })
+        .collect()
+}
+
+/// Whether the brackets in `s` (outside of string literals) are balanced.
+fn is_balanced(s: &str) -> bool {
+    let mut depth = 0i64;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in s.chars() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => depth -= 1,
+            (None, _) => {}
+        }
+    }
+    depth <= 0 && quote.is_none()
+}
+
+/// Split a vector literal, `[a, b, ...]`, into its (trimmed) elements.
+fn split_vector(literal: &Word) -> PTBResult<Vec<String>> {
+    let sp = literal.origin.span;
+    let Some(inner) = literal
+        .value
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+    else {
+        error!(
+            sp,
+            "Expected a vector literal but found {:?}", literal.value
+        );
+    };
+
+    let mut elems = vec![];
+    let mut elem = String::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in inner.chars() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '(' | '<') => depth += 1,
+            (None, ']' | ')' | '>') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                elems.push(std::mem::take(&mut elem).trim().to_owned());
+                continue;
+            }
+            (None, _) => {}
+        }
+        elem.push(c);
+    }
+
+    // Allow a trailing comma.
+    let last = elem.trim();
+    if !last.is_empty() {
+        elems.push(last.to_owned());
+    }
+
+    if let Some(ix) = elems.iter().position(|e| e.is_empty()) {
+        error!(sp, "Empty element at position {ix} in vector literal");
+    }
+
+    Ok(elems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(args: &[&str]) -> Script {
+        let args = args.iter().map(|s| s.to_string()).collect();
+        match Script::expand(args) {
+            Ok(script) => script,
+            Err(errors) => panic!("{errors:?}"),
+        }
+    }
+
+    /// Expand `args`, expecting failure, and return the labels of the errors (their messages and
+    /// offsets in the source they were found in).
+    fn expand_err(args: &[&str]) -> Vec<(String, usize)> {
+        let args = args.iter().map(|s| s.to_string()).collect();
+        let Err(errors) = Script::expand(args) else {
+            panic!("Expected expansion to fail");
+        };
+
+        errors
+            .iter()
+            .flat_map(|e| e.labels().into_iter().flatten())
+            .map(|l| (l.label().unwrap_or_default().to_owned(), l.offset()))
+            .collect()
+    }
+
+    fn expand_err_msg(args: &[&str]) -> String {
+        expand_err(args)
+            .into_iter()
+            .map(|(msg, _)| msg)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn no_directives() {
+        let args = ["--split-coins", "gas", "[1, 2]", "--assign", "coins"];
+        assert_eq!(expand(&args).tokens, args);
+    }
+
+    #[test]
+    fn for_vector() {
+        let script = expand(&[
+            "--for",
+            "to",
+            "in",
+            "[@0x1, @0x2]",
+            "--transfer-objects",
+            "[gas]",
+            "${to}",
+            "--end",
+        ]);
+
+        assert_eq!(
+            script.tokens,
+            [
+                "--transfer-objects",
+                "[gas]",
+                "@0x1",
+                "--transfer-objects",
+                "[gas]",
+                "@0x2",
+            ]
+        );
+    }
+
+    #[test]
+    fn macros() {
+        let script = expand(&[
+            "--macro",
+            "pay",
+            "amount",
+            "to",
+            "--split-coins",
+            "gas",
+            "[${amount}]",
+            "--assign",
+            "coin",
+            "--transfer-objects",
+            "[coin.0]",
+            "${to}",
+            "--end",
+            "--expand",
+            "pay",
+            "100",
+            "@0x42",
+        ]);
+
+        assert_eq!(
+            script.tokens,
+            [
+                "--split-coins",
+                "gas",
+                "[100]",
+                "--assign",
+                "coin",
+                "--transfer-objects",
+                "[coin.0]",
+                "@0x42",
+            ]
+        );
+    }
+
+    #[test]
+    fn bindings() {
+        let args = [
+            "--for",
+            "to",
+            "in",
+            "[${a}, @0x2]",
+            "--transfer-objects",
+            "[${coin}]",
+            "${to}",
+            "--end",
+        ];
+        let bindings = BTreeMap::from([
+            ("a".to_string(), "@0x1".to_string()),
+            ("coin".to_string(), "gas".to_string()),
+            ("to".to_string(), "shadowed".to_string()),
+        ]);
+        let script =
+            Script::expand_with_bindings(args.iter().map(|s| s.to_string()).collect(), bindings)
+                .unwrap();
+
+        // Loop variables shadow the outer bindings.
+        assert_eq!(
+            script.tokens,
+            [
+                "--transfer-objects",
+                "[gas]",
+                "@0x1",
+                "--transfer-objects",
+                "[gas]",
+                "@0x2",
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_loops() {
+        let script = expand(&[
+            "--for", "x", "in", "[1, 2]", "--for", "y", "in", "[a,b]", "--assign", "${y}${x}",
+            "--end", "--end",
+        ]);
+
+        assert_eq!(
+            script.tokens,
+            ["--assign", "a1", "--assign", "b1", "--assign", "a2", "--assign", "b2"]
+        );
+    }
+
+    #[test]
+    fn include_and_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("payouts.csv"),
+            "to, amount\n@0x1, 10\n@0x2, 20\n",
+        )
+        .unwrap();
+
+        std::fs::write(
+            dir.path().join("payouts.ptb"),
+            "# Pay everyone in the CSV file\n\
+             --for row in csv payouts.csv\n  \
+               --split-coins gas '[${row.amount}]'\n  \
+               --assign coin\n  \
+               --transfer-objects '[coin.0]' ${row.to}\n\
+             --end\n",
+        )
+        .unwrap();
+
+        let path = dir.path().join("payouts.ptb");
+        let script = expand(&["--include", path.to_str().unwrap(), "--gas-budget", "1000"]);
+
+        assert_eq!(
+            script.tokens,
+            [
+                "--split-coins",
+                "gas",
+                "[10]",
+                "--assign",
+                "coin",
+                "--transfer-objects",
+                "[coin.0]",
+                "@0x1",
+                "--split-coins",
+                "gas",
+                "[20]",
+                "--assign",
+                "coin",
+                "--transfer-objects",
+                "[coin.0]",
+                "@0x2",
+                "--gas-budget",
+                "1000",
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_point_at_script() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.ptb");
+        std::fs::write(&path, "--assign x 1\n--assign y ${undefined_variable}\n").unwrap();
+
+        let errors = expand_err(&["--include", path.to_str().unwrap()]);
+        let offset = std::fs::read_to_string(&path)
+            .unwrap()
+            .find("${undefined_variable}")
+            .unwrap();
+
+        assert_eq!(
+            errors,
+            [("Unbound variable 'undefined_variable'".to_owned(), offset)]
+        );
+    }
+
+    #[test]
+    fn parse_errors_are_remapped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("script.ptb");
+        std::fs::write(&path, "--assign x 1\n--bogus-command\n").unwrap();
+
+        let script = expand(&["--include", path.to_str().unwrap()]);
+        let errors = crate::client_ptb::ptb::PTB::parse_ptb_commands(script.tokens.clone())
+            .expect_err("Unknown command");
+
+        let offset = std::fs::read_to_string(&path)
+            .unwrap()
+            .find("--bogus")
+            .unwrap();
+
+        let sp = script.locate(errors[0].span);
+        let base = script.sources[1].base;
+        assert_eq!(sp.start - base, offset);
+        assert_eq!(sp.end - base, offset + "--bogus-command".len());
+    }
+
+    #[test]
+    fn expansion_errors() {
+        assert!(
+            expand_err_msg(&["--for", "x", "in", "[1]", "--assign", "y"])
+                .contains("Unterminated '--for'")
+        );
+        assert!(expand_err_msg(&["--end"]).contains("without a matching"));
+        assert!(expand_err_msg(&["--expand", "missing"]).contains("Unknown macro 'missing'"));
+        assert!(expand_err_msg(&[
+            "--macro", "m", "a", "--assign", "${a}", "--end", "--expand", "m",
+        ])
+        .contains("expects 1 argument(s) but 0 were given"));
+        assert!(
+            expand_err_msg(&["--macro", "m", "--expand", "m", "--end", "--expand", "m"])
+                .contains("expands itself recursively")
+        );
+    }
+}
@@ -47,6 +47,8 @@ pub enum Token {
     Dot,
     /// /
     ForwardSlash,
+    /// A whole shell token, for commands whose arguments are taken verbatim.
+    Word,
 
     /// End of input.
     Eof,
@@ -62,6 +64,10 @@ pub enum Token {
     Publish,
     /// --upgraded \<shell-token\>
     Upgrade,
+    /// --serialize-bundle \<shell-token\>
+    SerializeBundle,
+    /// --gas-budget auto[:\<multiplier\>]
+    AutoGasBudget,
 }
 
 impl Lexeme<'_> {
@@ -78,7 +84,15 @@ impl Lexeme<'_> {
 
     /// Returns true if this lexeme signifies the end of the current command.
     pub fn is_command_end(&self) -> bool {
-        self.is_terminal() || [Token::Command, Token::Publish, Token::Upgrade].contains(&self.0)
+        self.is_terminal()
+            || [
+                Token::Command,
+                Token::Publish,
+                Token::Upgrade,
+                Token::SerializeBundle,
+                Token::AutoGasBudget,
+            ]
+            .contains(&self.0)
     }
 }
 
@@ -104,11 +118,14 @@ impl fmt::Display for Lexeme<'_> {
             T::At => write!(f, "'@'"),
             T::Dot => write!(f, "'.'"),
             T::ForwardSlash => write!(f, "'/'"),
+            T::Word => write!(f, "{:?}", self.1),
             T::Unexpected => write!(f, "input {:?}", self.1),
             T::UnfinishedString => write!(f, "unfinished string {:?}", format!("{}...", self.1)),
             T::EarlyEof | T::Eof => write!(f, "end of input"),
             T::Publish => write!(f, "command '--publish {:?}'", self.1),
             T::Upgrade => write!(f, "command '--upgrade {:?}'", self.1),
+            T::SerializeBundle => write!(f, "command '--serialize-bundle {:?}'", self.1),
+            T::AutoGasBudget => write!(f, "command '--gas-budget {}'", self.1),
         }
     }
 }
@@ -134,12 +151,15 @@ impl fmt::Display for Token {
             T::At => write!(f, "'@'"),
             T::Dot => write!(f, "'.'"),
             T::ForwardSlash => write!(f, "'/'"),
+            T::Word => write!(f, "an argument"),
             T::Eof => write!(f, "end of input"),
             T::Unexpected => write!(f, "unexpected input"),
             T::UnfinishedString => write!(f, "an unfinished string"),
             T::EarlyEof => write!(f, "unexpected end of input"),
             T::Publish => write!(f, "a '--publish' command"),
             T::Upgrade => write!(f, "an '--upgrade' command"),
+            T::SerializeBundle => write!(f, "a '--serialize-bundle' command"),
+            T::AutoGasBudget => write!(f, "a '--gas-budget' command"),
         }
     }
 }
@@ -0,0 +1,350 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dynamic shell completion for the `sui` binary. On top of the subcommands and flags, values are
+//! completed from the local client configuration: keystore aliases and addresses, address book
+//! names, environment aliases, the objects owned by the active address, and the modules and
+//! functions of the package passed to `sui client call`.
+//!
+//! Completion is enabled by sourcing the script the binary prints for a shell, e.g.
+//! `source <(COMPLETE=bash sui)` for bash, or `COMPLETE=fish sui | source` for fish.
+//!
+//! Completers run on every key press, so they only read local files: they never unlock the
+//! keystore, nor connect to the network. Owned objects come from a cache next to client.yaml,
+//! refreshed by `sui client objects`, and a package's modules are added to the same cache the
+//! first time `sui client call` calls into it.
+
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use clap_complete::CompletionCandidate;
+use fastcrypto::traits::EncodeDecodeBase64;
+use serde::{Deserialize, Serialize};
+use sui_config::{
+    sui_config_dir, SUI_CLIENT_CONFIG, SUI_COMPLETION_CACHE_FILENAME, SUI_KEYSTORE_ALIASES_FILENAME,
+};
+use sui_json_rpc_types::{SuiMoveVisibility, SuiObjectResponse};
+use sui_keys::keystore::Alias;
+use sui_sdk::{
+    sui_client_config::{Contact, SuiEnv},
+    SuiClient,
+};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    crypto::PublicKey,
+};
+use tracing::warn;
+
+#[cfg(test)]
+#[path = "unit_tests/completion_tests.rs"]
+mod completion_tests;
+
+/// Values cached for completion, next to client.yaml.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionCache {
+    /// The objects owned by each address, as of the last `sui client objects` for it.
+    #[serde(default)]
+    pub objects: BTreeMap<SuiAddress, Vec<CachedObject>>,
+    /// The functions that can be called from `sui client call`, by module, for each package.
+    #[serde(default)]
+    pub packages: BTreeMap<ObjectID, BTreeMap<String, Vec<String>>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedObject {
+    pub object_id: ObjectID,
+    pub object_type: String,
+    pub gas_coin: bool,
+}
+
+/// The parts of client.yaml that completion needs. The config is read as is rather than through
+/// `SuiClientConfig`, which would load (and possibly prompt to unlock) the keystore.
+#[derive(Deserialize, Default)]
+struct ClientSnapshot {
+    /// `File: <path>` for a file based keystore.
+    #[serde(default)]
+    keystore: Option<serde_yaml::Value>,
+    #[serde(default)]
+    envs: Vec<SuiEnv>,
+    #[serde(default)]
+    active_env: Option<String>,
+    #[serde(default)]
+    active_address: Option<SuiAddress>,
+    #[serde(default)]
+    address_book: Vec<Contact>,
+}
+
+impl CompletionCache {
+    /// The path of the cache for the client configured at `config_path`.
+    pub fn path_for_config(config_path: &Path) -> PathBuf {
+        config_path.with_file_name(SUI_COMPLETION_CACHE_FILENAME)
+    }
+
+    /// Read the cache at `path`, or an empty cache if there is none, or it cannot be read.
+    pub fn read(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|contents| serde_json::from_slice(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_vec(self)?)
+            .with_context(|| format!("Cannot write completion cache {}", path.display()))
+    }
+
+    /// Replace the objects cached for `owner` with `objects`. Failing to update the cache is
+    /// logged rather than returned, as it only affects completion.
+    pub fn record_objects(config_path: &Path, owner: SuiAddress, objects: &[SuiObjectResponse]) {
+        let path = Self::path_for_config(config_path);
+        let mut cache = Self::read(&path);
+        let objects = objects
+            .iter()
+            .filter_map(|object| object.data.as_ref())
+            .map(|data| CachedObject {
+                object_id: data.object_id,
+                object_type: data
+                    .type_
+                    .as_ref()
+                    .map(|type_| type_.to_string())
+                    .unwrap_or_default(),
+                gas_coin: data.is_gas_coin(),
+            })
+            .collect();
+        cache.objects.insert(owner, objects);
+
+        if let Err(e) = cache.write(&path) {
+            warn!("Cannot update the completion cache: {e}");
+        }
+    }
+
+    /// Cache the functions of `package` that can be called from `sui client call`, by module,
+    /// fetching them with `client` if they are not cached yet. Failing to fetch the package or to
+    /// update the cache is logged rather than returned, as it only affects completion.
+    pub async fn record_package(config_path: &Path, client: &SuiClient, package: ObjectID) {
+        let path = Self::path_for_config(config_path);
+        let mut cache = Self::read(&path);
+        if cache.packages.contains_key(&package) {
+            return;
+        }
+
+        let modules = match client
+            .read_api()
+            .get_normalized_move_modules_by_package(package)
+            .await
+        {
+            Ok(modules) => modules,
+            Err(e) => {
+                warn!("Cannot fetch package {package} for the completion cache: {e}");
+                return;
+            }
+        };
+
+        let modules = modules
+            .into_iter()
+            .map(|(name, module)| {
+                let functions = module
+                    .exposed_functions
+                    .into_iter()
+                    .filter(|(_, function)| {
+                        function.is_entry
+                            || matches!(function.visibility, SuiMoveVisibility::Public)
+                    })
+                    .map(|(name, _)| name)
+                    .collect();
+                (name, functions)
+            })
+            .collect();
+        cache.packages.insert(package, modules);
+
+        if let Err(e) = cache.write(&path) {
+            warn!("Cannot update the completion cache: {e}");
+        }
+    }
+}
+
+type Modules = BTreeMap<String, Vec<String>>;
+
+/// Completes keystore aliases and addresses, and address book names.
+pub fn complete_identities(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some((config_path, client)) = read_client() else {
+        return vec![];
+    };
+
+    let mut candidates = vec![];
+    for (alias, address) in keystore_aliases(&config_path, &client) {
+        candidates.push(CompletionCandidate::new(alias).help(Some(address.to_string().into())));
+        candidates
+            .push(CompletionCandidate::new(address.to_string()).help(Some("keystore".into())));
+    }
+    for contact in &client.address_book {
+        if contact.env.is_none() || contact.env == client.active_env {
+            candidates.push(
+                CompletionCandidate::new(&contact.name)
+                    .help(Some(contact.address.to_string().into())),
+            );
+        }
+    }
+
+    matching(candidates, current)
+}
+
+/// Completes the aliases of the configured environments.
+pub fn complete_envs(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some((_, client)) = read_client() else {
+        return vec![];
+    };
+
+    let candidates = client
+        .envs
+        .iter()
+        .map(|env| CompletionCandidate::new(&env.alias).help(Some(env.rpc.clone().into())))
+        .collect();
+    matching(candidates, current)
+}
+
+/// Completes the IDs of the objects owned by the active address.
+pub fn complete_object_ids(current: &OsStr) -> Vec<CompletionCandidate> {
+    complete_owned_objects(current, |_| true)
+}
+
+/// Completes the IDs of the gas coins owned by the active address.
+pub fn complete_gas_coins(current: &OsStr) -> Vec<CompletionCandidate> {
+    complete_owned_objects(current, |object| object.gas_coin)
+}
+
+/// Completes the modules of the package passed as `--package`.
+pub fn complete_modules(current: &OsStr) -> Vec<CompletionCandidate> {
+    let args: Vec<_> = std::env::args().collect();
+    let Some(modules) = package_modules(&args) else {
+        return vec![];
+    };
+
+    let candidates = modules.keys().map(CompletionCandidate::new).collect();
+    matching(candidates, current)
+}
+
+/// Completes the callable functions of the module passed as `--module`, in the package passed
+/// as `--package`.
+pub fn complete_functions(current: &OsStr) -> Vec<CompletionCandidate> {
+    let args: Vec<_> = std::env::args().collect();
+    let (Some(modules), Some(module)) = (package_modules(&args), flag_value(&args, "--module"))
+    else {
+        return vec![];
+    };
+
+    let candidates = modules
+        .get(module)
+        .into_iter()
+        .flatten()
+        .map(CompletionCandidate::new)
+        .collect();
+    matching(candidates, current)
+}
+
+fn complete_owned_objects(
+    current: &OsStr,
+    filter: impl Fn(&CachedObject) -> bool,
+) -> Vec<CompletionCandidate> {
+    let Some((config_path, client)) = read_client() else {
+        return vec![];
+    };
+    let Some(owner) = client.active_address else {
+        return vec![];
+    };
+
+    let cache = CompletionCache::read(&CompletionCache::path_for_config(&config_path));
+    let candidates = cache
+        .objects
+        .get(&owner)
+        .into_iter()
+        .flatten()
+        .filter(|object| filter(object))
+        .map(|object| {
+            CompletionCandidate::new(object.object_id.to_string())
+                .help(Some(object.object_type.clone().into()))
+        })
+        .collect();
+    matching(candidates, current)
+}
+
+/// The cached modules of the package passed as `--package`, if it has been called before.
+fn package_modules(args: &[String]) -> Option<Modules> {
+    let package: ObjectID = flag_value(args, "--package")?.parse().ok()?;
+    let (config_path, _) = read_client()?;
+
+    let path = CompletionCache::path_for_config(&config_path);
+    CompletionCache::read(&path).packages.remove(&package)
+}
+
+/// The value of `flag` on the command line being completed, given as `--flag value` or
+/// `--flag=value`.
+pub(crate) fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next().map(String::as_str);
+        }
+        if let Some(value) = arg
+            .strip_prefix(flag)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// The candidates that start with `current`, without duplicates.
+fn matching(candidates: Vec<CompletionCandidate>, current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    let mut seen = std::collections::BTreeSet::new();
+    candidates
+        .into_iter()
+        .filter(|candidate| {
+            let value = candidate.get_value().to_string_lossy();
+            value.starts_with(current.as_ref()) && seen.insert(value.into_owned())
+        })
+        .collect()
+}
+
+fn read_client() -> Option<(PathBuf, ClientSnapshot)> {
+    let config_path = sui_config_dir().ok()?.join(SUI_CLIENT_CONFIG);
+    let contents = fs::read_to_string(&config_path).ok()?;
+    let client = serde_yaml::from_str(&contents).ok()?;
+    Some((config_path, client))
+}
+
+/// The aliases of the keys in a file based keystore, with their addresses, read from its aliases
+/// file, which is never encrypted.
+fn keystore_aliases(config_path: &Path, client: &ClientSnapshot) -> Vec<(String, SuiAddress)> {
+    let aliases_path = match &client.keystore {
+        Some(keystore) => match keystore.get("File").and_then(|path| path.as_str()) {
+            Some(path) => Path::new(path).with_extension("aliases"),
+            None => return vec![],
+        },
+        None => config_path.with_file_name(SUI_KEYSTORE_ALIASES_FILENAME),
+    };
+
+    let Some(aliases) = fs::read(aliases_path)
+        .ok()
+        .and_then(|contents| serde_json::from_slice::<Vec<Alias>>(&contents).ok())
+    else {
+        return vec![];
+    };
+
+    aliases
+        .into_iter()
+        .filter_map(|alias| {
+            let key = PublicKey::decode_base64(&alias.public_key_base64).ok()?;
+            Some((alias.alias, SuiAddress::from(&key)))
+        })
+        .collect()
+}
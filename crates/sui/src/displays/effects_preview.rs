@@ -0,0 +1,116 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client_ptb::effects_preview::{EffectsPreview, ObjectChangeKind};
+use std::fmt::{Display, Formatter};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{style::HorizontalLine, Panel as TablePanel, Style as TableStyle},
+};
+
+impl Display for EffectsPreview {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Dry run completed, execution status: {}", self.status)?;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["owner", "coin type", "amount"]);
+        for change in &self.balance_changes {
+            let amount = if change.amount > 0 {
+                format!("+{}", change.amount)
+            } else {
+                change.amount.to_string()
+            };
+            builder.push_record([
+                change.owner.to_string(),
+                change.coin_type.to_canonical_string(/* with_prefix */ true),
+                amount,
+            ]);
+        }
+        write_table(f, builder, "Balance Changes")?;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["change", "object id", "type", "owner", "fields"]);
+        for object in &self.objects {
+            let change = match object.kind {
+                ObjectChangeKind::Created => "created",
+                ObjectChangeKind::Mutated => "mutated",
+                ObjectChangeKind::Deleted => "deleted",
+                ObjectChangeKind::Wrapped => "wrapped",
+                ObjectChangeKind::Published => "published",
+            };
+            builder.push_record([
+                change.to_string(),
+                object.object_id.to_string(),
+                object.object_type.clone(),
+                object
+                    .owner
+                    .as_ref()
+                    .map(|owner| owner.to_string())
+                    .unwrap_or_default(),
+                object.fields.clone().unwrap_or_default(),
+            ]);
+        }
+        write_table(f, builder, "Objects")?;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["object id", "type", "from", "to"]);
+        for transfer in &self.transfers {
+            builder.push_record([
+                transfer.object_id.to_string(),
+                transfer.object_type.clone(),
+                transfer.from.to_string(),
+                transfer.to.to_string(),
+            ]);
+        }
+        write_table(f, builder, "Ownership Transfers")?;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["type", "sender", "fields"]);
+        for event in &self.events {
+            builder.push_record([
+                event.event_type.clone(),
+                event.sender.to_string(),
+                event.fields.clone(),
+            ]);
+        }
+        write_table(f, builder, "Events")?;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["expectation", "actual", "result"]);
+        for check in &self.checks {
+            builder.push_record([
+                check.expectation.clone(),
+                check.actual.to_string(),
+                if check.passed { "passed" } else { "FAILED" }.to_string(),
+            ]);
+        }
+        if !self.checks.is_empty() {
+            write_table(f, builder, "Balance Expectations")?;
+        }
+
+        write!(
+            f,
+            "Gas cost: {} MIST computation, {} MIST storage, {} MIST rebate",
+            self.gas_cost.computation_cost,
+            self.gas_cost.storage_cost,
+            self.gas_cost.storage_rebate
+        )
+    }
+}
+
+/// Write the table in `builder` under `title`, or a line saying there is nothing to show if the
+/// table has no rows.
+fn write_table(f: &mut Formatter<'_>, builder: TableBuilder, title: &str) -> std::fmt::Result {
+    if builder.count_rows() == 0 {
+        return writeln!(f, "No {}.", title.to_lowercase());
+    }
+
+    let mut table = builder.build();
+    table.with(TablePanel::header(title));
+    table.with(TableStyle::rounded().horizontals([
+        HorizontalLine::new(1, TableStyle::modern().get_horizontal()),
+        HorizontalLine::new(2, TableStyle::modern().get_horizontal()),
+    ]));
+    table.with(tabled::settings::style::BorderSpanCorrection);
+    writeln!(f, "{table}")
+}
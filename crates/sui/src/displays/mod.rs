@@ -3,6 +3,7 @@
 
 mod dev_inspect;
 mod dry_run_tx_block;
+mod effects_preview;
 mod gas_cost_summary;
 mod ptb_preview;
 mod status;
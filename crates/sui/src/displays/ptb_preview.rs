@@ -8,6 +8,7 @@ use crate::{
     },
     sp,
 };
+use serde::{Serialize, Serializer};
 use std::fmt::{Display, Formatter};
 use tabled::{
     builder::Builder as TableBuilder,
@@ -86,3 +87,50 @@ impl Display for PTBPreview<'_> {
         write!(f, "{}", table)
     }
 }
+
+/// The JSON form of the preview, for `--preview --json`.
+impl Serialize for PTBPreview<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Preview {
+            commands: Vec<String>,
+            gas_budget: Option<String>,
+            gas_price: Option<String>,
+            gas_sponsor: Option<String>,
+            gas_coins: Option<Vec<String>>,
+            sender: Option<String>,
+        }
+
+        let metadata = self.program_metadata;
+        Preview {
+            commands: self
+                .program
+                .commands
+                .iter()
+                .map(|sp!(_, cmd)| cmd.to_string())
+                .collect(),
+            gas_budget: metadata.gas_budget.map(|budget| budget.value.to_string()),
+            gas_price: metadata.gas_price.map(|price| price.value.to_string()),
+            gas_sponsor: metadata.gas_sponsor.map(|sponsor| {
+                sponsor
+                    .value
+                    .into_inner()
+                    .to_canonical_string(/* with_prefix */ true)
+            }),
+            gas_coins: metadata.gas_object_ids.as_ref().map(|coins| {
+                coins
+                    .iter()
+                    .map(|coin| coin.value.to_canonical_string(/* with_prefix */ true))
+                    .collect()
+            }),
+            sender: metadata.sender.as_ref().map(|sender| {
+                sender
+                    .value
+                    .into_inner()
+                    .to_canonical_string(/* with_prefix */ true)
+            }),
+        }
+        .serialize(serializer)
+    }
+}
@@ -9,7 +9,7 @@ use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_sdk::wallet_context::WalletContext;
 use sui_types::base_types::SuiAddress;
 
-/// An address or an alias associated with a key in the wallet
+/// An address or an alias associated with a key in the wallet, or a name in the address book.
 /// This is used to distinguish between an address or an alias,
 /// enabling a user to use an alias for any command that requires an address.
 #[derive(Serialize, Clone)]
@@ -39,16 +39,28 @@ impl Display for KeyIdentity {
     }
 }
 
-/// Get the SuiAddress corresponding to this key identity.
+/// Get the SuiAddress corresponding to this key identity. Aliases are looked up in the keystore,
+/// and then in the address book for the active environment.
 /// If no string is provided, then the current active address is returned.
 pub fn get_identity_address(
     input: Option<KeyIdentity>,
     ctx: &mut WalletContext,
 ) -> Result<SuiAddress, Error> {
-    if let Some(addr) = input {
-        get_identity_address_from_keystore(addr, &ctx.config.keystore)
-    } else {
-        Ok(ctx.active_address()?)
+    match input {
+        Some(KeyIdentity::Alias(alias)) => {
+            match get_identity_address_from_keystore(
+                KeyIdentity::Alias(alias.clone()),
+                &ctx.config.keystore,
+            ) {
+                Ok(address) => Ok(address),
+                Err(e) => match ctx.config.get_contact(&alias) {
+                    Some(contact) => Ok(contact.address),
+                    None => Err(e),
+                },
+            }
+        }
+        Some(addr) => get_identity_address_from_keystore(addr, &ctx.config.keystore),
+        None => Ok(ctx.active_address()?),
     }
 }
 
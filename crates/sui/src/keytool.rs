@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use crate::completion::complete_identities;
 use crate::key_identity::{get_identity_address_from_keystore, KeyIdentity};
+use crate::signing_bundle::{BundleStatus, SigningBundle};
 use crate::zklogin_commands_util::{perform_zk_login_test_tx, read_cli_line};
 use anyhow::anyhow;
 use aws_sdk_kms::{
@@ -10,6 +12,7 @@ use aws_sdk_kms::{
 };
 use bip32::DerivationPath;
 use clap::*;
+use clap_complete::ArgValueCompleter;
 use fastcrypto::ed25519::Ed25519KeyPair;
 use fastcrypto::encoding::{Base64, Encoding, Hex};
 use fastcrypto::hash::HashFunction;
@@ -32,16 +35,22 @@ use rand::SeedableRng;
 use serde::Serialize;
 use serde_json::json;
 use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
+use std::collections::BTreeSet;
 use std::fmt::{Debug, Display, Formatter};
 use std::fs;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use sui_json_rpc_types::{SuiTransactionBlockResponseQuery, TransactionFilter};
+use sui_keys::encryption::{provide_passphrase, KeystoreLocked, KEY_ENV, PASSPHRASE_ENV};
+use sui_keys::hd_wallet::{derive_account, HdAccount, HdWallets, WatchOnlyWallet};
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
 };
-use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{
@@ -60,10 +69,14 @@ use tabled::builder::Builder;
 use tabled::settings::Rotate;
 use tabled::settings::{object::Rows, Modify, Width};
 use tracing::info;
+use zeroize::Zeroizing;
 #[cfg(test)]
 #[path = "unit_tests/keytool_tests.rs"]
 mod keytool_tests;
 
+/// Environment variable holding the passphrase for `keytool encrypt` and `change-passphrase`.
+pub const NEW_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_NEW_PASSPHRASE";
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 #[clap(rename_all = "kebab-case")]
@@ -76,6 +89,9 @@ pub enum KeyToolCommand {
         /// The alias must start with a letter and can contain only letters, digits, dots, hyphens (-), or underscores (_).
         new_alias: Option<String>,
     },
+    /// Change the passphrase of an encrypted keystore. The new passphrase is read from
+    /// SUI_KEYSTORE_NEW_PASSPHRASE if set, and otherwise prompted for.
+    ChangePassphrase,
     /// Convert private key in Hex or Base64 to new format (Bech32
     /// encoded 33 byte flag || private key starting with "suiprivkey").
     /// Hex private key format import and export are both deprecated in
@@ -102,6 +118,13 @@ pub enum KeyToolCommand {
         #[clap(long, default_value = "0")]
         cur_epoch: u64,
     },
+    /// Encrypt the private keys in sui.keystore with a passphrase. The passphrase is read from
+    /// SUI_KEYSTORE_NEW_PASSPHRASE if set, and otherwise prompted for.
+    ///
+    /// Once encrypted, the keystore is unlocked with the passphrase in SUI_KEYSTORE_PASSPHRASE,
+    /// or by prompting for it, each time it is loaded, unless its key is set in SUI_KEYSTORE_KEY
+    /// (see `sui keytool unlock`).
+    Encrypt,
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
@@ -136,6 +159,7 @@ pub enum KeyToolCommand {
     /// encoded string starting with `suiprivkey`.
     Export {
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         key_identity: KeyIdentity,
     },
     /// List all keys by its Sui address, Base64 encoded public key, key scheme name in
@@ -200,6 +224,7 @@ pub enum KeyToolCommand {
     /// default will be used.
     Sign {
         #[clap(long)]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
         address: KeyIdentity,
         #[clap(long)]
         data: String,
@@ -222,10 +247,49 @@ pub enum KeyToolCommand {
         #[clap(long)]
         base64pk: String,
     },
+    /// Add a signature to a signing bundle written by `sui client` commands with
+    /// `--serialize-bundle`, using either the key for an address (or its alias) in sui keystore
+    /// or an AWS KMS key (see `sign-kms`). The bundle is updated in place.
+    ///
+    /// To sign as a member of a multisig that is the sender or gas owner, pass the multisig's
+    /// public keys, weights and threshold. They are stored in the bundle, so later signers of the
+    /// same multisig can omit them.
+    SignBundle {
+        bundle: PathBuf,
+        #[clap(long, conflicts_with = "keyid")]
+        #[arg(add = ArgValueCompleter::new(complete_identities))]
+        address: Option<KeyIdentity>,
+        #[clap(long, requires = "base64pk")]
+        keyid: Option<String>,
+        #[clap(long, requires = "keyid")]
+        base64pk: Option<String>,
+        #[clap(long, num_args(1..), requires = "multisig_weights")]
+        multisig_pks: Vec<PublicKey>,
+        #[clap(long, num_args(1..), requires = "multisig_threshold")]
+        multisig_weights: Vec<WeightUnit>,
+        #[clap(long, requires = "multisig_pks")]
+        multisig_threshold: Option<ThresholdUnit>,
+    },
     /// This takes [enum SuiKeyPair] of Base64 encoded of 33-byte `flag || privkey`). It
     /// outputs the keypair into a file at the current directory where the address is the filename,
     /// and prints out its Sui address, Base64 encoded public key, the key scheme, and the key scheme flag.
     Unpack { keypair: String },
+    /// Print a command that exports the key of an encrypted keystore as SUI_KEYSTORE_KEY, so that
+    /// its passphrase is not asked for by every command: `eval "$(sui keytool unlock)"`.
+    ///
+    /// The exported key is the keystore's encryption key, derived from the passphrase. It does not
+    /// expire when the shell exits: anyone who obtains it can decrypt the keystore without the
+    /// passphrase, until the keystore is encrypted again with a new passphrase (`sui keytool
+    /// encrypt`). Keep it as secret as the passphrase.
+    Unlock,
+    /// Manage HD wallets: mnemonics stored once, encrypted with a passphrase, from which accounts
+    /// are derived by index, for the ed25519, secp256k1 and secp256r1 key schemes. Wallets are
+    /// saved next to the keystore, in sui.wallets.
+    ///
+    /// `sui client new-address --from-seed <WALLET>` derives the next account of a wallet into
+    /// the client's keystore, like `sui keytool wallet derive`.
+    #[clap(name = "wallet", subcommand)]
+    Wallet(HdWalletCommand),
 
     /// Given the max_epoch, generate an OAuth url, ask user to paste the redirect with id_token, call salt server, then call the prover server,
     /// create a test transaction, use the ephemeral key to sign and execute it by assembling to a serialized zkLogin signature.
@@ -297,6 +361,69 @@ pub enum KeyToolCommand {
 }
 
 // Command Output types
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum HdWalletCommand {
+    /// Create a wallet from a newly generated mnemonic, which is printed once and not stored.
+    /// Word length can be { word12 | word15 | word18 | word21 | word24 } default to word12 if
+    /// not specified. The passphrase to encrypt the wallet with is read from
+    /// SUI_KEYSTORE_NEW_PASSPHRASE if set, and otherwise prompted for.
+    Create {
+        name: String,
+        #[clap(long)]
+        word_length: Option<String>,
+    },
+    /// Create a wallet from an existing mnemonic phrase. The passphrase to encrypt the wallet
+    /// with is read from SUI_KEYSTORE_NEW_PASSPHRASE if set, and otherwise prompted for.
+    Import {
+        name: String,
+        mnemonic_phrase: String,
+    },
+    /// List the wallets, with the number of accounts derived from each.
+    List,
+    /// List the accounts derived from a wallet, with their derivation paths.
+    Accounts { name: String },
+    /// Derive the next account of a wallet for a key scheme, and add its key to the keystore.
+    /// The wallet is unlocked with the passphrase in SUI_KEYSTORE_PASSPHRASE if set, and
+    /// otherwise by prompting for it.
+    Derive {
+        name: String,
+        #[clap(long, default_value = "ed25519")]
+        key_scheme: SignatureScheme,
+        /// The alias must start with a letter and can contain only letters, digits, hyphens (-),
+        /// or underscores (_).
+        #[clap(long)]
+        alias: Option<String>,
+    },
+    /// Derive the accounts of a wallet in a range of indices, and report which of them have
+    /// on-chain activity: sent transactions, or owned objects. With --add, the accounts with
+    /// activity are added to the keystore and recorded in the wallet, which recovers the accounts
+    /// of an imported mnemonic.
+    Scan {
+        name: String,
+        #[clap(long, default_value = "ed25519")]
+        key_scheme: SignatureScheme,
+        /// The first index to scan.
+        #[clap(long, default_value = "0")]
+        start: u32,
+        /// The number of indices to scan.
+        #[clap(long, default_value = "20")]
+        count: u32,
+        /// The RPC server of the network to scan.
+        #[clap(long, value_hint = ValueHint::Url)]
+        rpc: String,
+        #[clap(long)]
+        add: bool,
+    },
+    /// Export the accounts derived from a wallet, without its seed, as a JSON watch-only list.
+    ExportWatchOnly {
+        name: String,
+        /// Write the list to this file instead of printing it.
+        #[clap(long)]
+        output: Option<PathBuf>,
+    },
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AliasUpdate {
@@ -304,6 +431,53 @@ pub struct AliasUpdate {
     new_alias: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreEncryption {
+    keystore_path: Option<PathBuf>,
+    num_keys: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreKey {
+    key: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HdWalletCreated {
+    name: String,
+    wallets_path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recovery_phrase: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HdWalletSummary {
+    name: String,
+    num_accounts: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivedAccount {
+    alias: String,
+    #[serde(flatten)]
+    account: HdAccount,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScannedAccount {
+    #[serde(flatten)]
+    account: HdAccount,
+    active: bool,
+    /// Whether the account was added to the keystore by this scan.
+    added: bool,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DecodedMultiSig {
@@ -428,6 +602,16 @@ pub struct SignData {
     sui_signature: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignBundleOutput {
+    // The sender or gas owner that this signature counts towards.
+    signer: SuiAddress,
+    // Base64 encoded `flag || signature || pubkey` added to the bundle.
+    sui_signature: String,
+    status: BundleStatus,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ZkLoginSignAndExecuteTx {
@@ -458,11 +642,19 @@ pub struct ZkLoginInsecureSignPersonalMessage {
 #[serde(untagged)]
 pub enum CommandOutput {
     Alias(AliasUpdate),
+    ChangePassphrase(KeystoreEncryption),
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeOrVerifyTx(DecodeOrVerifyTxOutput),
+    Encrypt(KeystoreEncryption),
     Error(String),
     Generate(Key),
+    HdAccounts(Vec<HdAccount>),
+    HdDerive(DerivedAccount),
+    HdScan(Vec<ScannedAccount>),
+    HdWalletCreate(HdWalletCreated),
+    HdWallets(Vec<HdWalletSummary>),
+    HdWatchOnly(WatchOnlyWallet),
     Import(Key),
     Export(ExportedKey),
     List(Vec<Key>),
@@ -474,6 +666,8 @@ pub enum CommandOutput {
     Show(Key),
     Sign(SignData),
     SignKMS(SerializedSig),
+    SignBundle(SignBundleOutput),
+    Unlock(KeystoreKey),
     ZkLoginSignAndExecuteTx(ZkLoginSignAndExecuteTx),
     ZkLoginInsecureSignPersonalMessage(ZkLoginInsecureSignPersonalMessage),
     ZkLoginSigVerify(ZkLoginSigVerifyResponse),
@@ -492,6 +686,19 @@ impl KeyToolCommand {
                     new_alias,
                 })
             }
+            KeyToolCommand::ChangePassphrase => {
+                let file = file_keystore(keystore)?;
+                if !file.is_encrypted() {
+                    return Err(anyhow!(
+                        "Keystore is not encrypted, use `sui keytool encrypt` to encrypt it"
+                    ));
+                }
+                file.encrypt(&new_passphrase()?)?;
+                CommandOutput::ChangePassphrase(KeystoreEncryption {
+                    keystore_path: file.path().map(Path::to_path_buf),
+                    num_keys: file.key_pairs().len(),
+                })
+            }
             KeyToolCommand::Convert { value } => {
                 let result = convert_private_key_to_bech32(value)?;
                 CommandOutput::Convert(result)
@@ -587,6 +794,20 @@ impl KeyToolCommand {
                     }
                 }
             }
+            KeyToolCommand::Encrypt => {
+                let file = file_keystore(keystore)?;
+                if file.is_encrypted() {
+                    return Err(anyhow!(
+                        "Keystore is already encrypted, use `sui keytool change-passphrase` to \
+                         change its passphrase"
+                    ));
+                }
+                file.encrypt(&new_passphrase()?)?;
+                CommandOutput::Encrypt(KeystoreEncryption {
+                    keystore_path: file.path().map(Path::to_path_buf),
+                    num_keys: file.key_pairs().len(),
+                })
+            }
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
@@ -877,42 +1098,69 @@ impl KeyToolCommand {
                     "Raw intent message: {:?}",
                     Base64::encode(bcs::to_bytes(&intent_msg)?)
                 );
-                let mut hasher = DefaultHash::default();
-                hasher.update(bcs::to_bytes(&intent_msg)?);
-                let digest = hasher.finalize().digest;
-                info!("Digest to sign: {:?}", Base64::encode(digest));
-
-                // Set up the KMS client in default region.
-                let config = aws_config::load_from_env().await;
-                let kms = KmsClient::new(&config);
-
-                // Sign the message, normalize the signature and then compacts it
-                // serialize_compact is loaded as bytes for Secp256k1Signature
-                let response = kms
-                    .sign()
-                    .key_id(keyid)
-                    .message_type(MessageType::Raw)
-                    .message(Blob::new(digest))
-                    .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
-                    .send()
-                    .await?;
-                let sig_bytes_der = response
-                    .signature
-                    .expect("Requires Asymmetric Key Generated in KMS");
-
-                let mut external_sig = Secp256k1Sig::from_der(sig_bytes_der.as_ref())?;
-                external_sig.normalize_s();
-                let sig_compact = external_sig.serialize_compact();
-
-                let mut serialized_sig = vec![SignatureScheme::Secp256k1.flag()];
-                serialized_sig.extend_from_slice(&sig_compact);
-                serialized_sig.extend_from_slice(pk_owner.as_ref());
+                let serialized_sig = sign_with_kms(keyid, &pk_owner, &intent_msg).await?;
                 let serialized_sig = Base64::encode(&serialized_sig);
                 CommandOutput::SignKMS(SerializedSig {
                     serialized_sig_base64: serialized_sig,
                 })
             }
 
+            KeyToolCommand::SignBundle {
+                bundle,
+                address,
+                keyid,
+                base64pk,
+                multisig_pks,
+                multisig_weights,
+                multisig_threshold,
+            } => {
+                let mut signing_bundle = SigningBundle::read(&bundle)?;
+                if let Some(threshold) = multisig_threshold {
+                    let multisig_pk =
+                        MultiSigPublicKey::new(multisig_pks, multisig_weights, threshold)?;
+                    signing_bundle.set_multisig(multisig_pk)?;
+                }
+
+                let tx_data = signing_bundle.tx_data()?;
+                let signature = match (address, keyid, base64pk) {
+                    (Some(address), None, None) => {
+                        let address = get_identity_address_from_keystore(address, keystore)?;
+                        keystore.sign_secure(&address, &tx_data, Intent::sui_transaction())?
+                    }
+                    (None, Some(keyid), Some(base64pk)) => {
+                        let pk_owner = PublicKey::decode_base64(&base64pk)
+                            .map_err(|e| anyhow!("Invalid base64 key: {:?}", e))?;
+                        let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data);
+                        let serialized_sig = sign_with_kms(keyid, &pk_owner, &intent_msg).await?;
+                        Signature::from_bytes(&serialized_sig)
+                            .map_err(|e| anyhow!("Invalid signature from KMS: {:?}", e))?
+                    }
+                    _ => {
+                        return Err(anyhow!(
+                            "Either --address, or --keyid and --base64pk, must be provided"
+                        ))
+                    }
+                };
+
+                let signer = signing_bundle.add_signature(signature.clone())?;
+                signing_bundle.write(&bundle)?;
+                CommandOutput::SignBundle(SignBundleOutput {
+                    signer,
+                    sui_signature: signature.encode_base64(),
+                    status: signing_bundle.status()?,
+                })
+            }
+
+            KeyToolCommand::Unlock => {
+                let file = file_keystore(keystore)?;
+                let key = file.encoded_key().ok_or_else(|| {
+                    anyhow!("Keystore is not encrypted, there is nothing to unlock")
+                })?;
+                CommandOutput::Unlock(KeystoreKey { key })
+            }
+
+            KeyToolCommand::Wallet(cmd) => cmd.execute(keystore).await?,
+
             KeyToolCommand::Unpack { keypair } => {
                 let keypair = SuiKeyPair::decode_base64(&keypair)
                     .map_err(|_| anyhow!("Invalid Base64 encode keypair"))?;
@@ -1302,6 +1550,131 @@ impl KeyToolCommand {
     }
 }
 
+impl HdWalletCommand {
+    pub async fn execute(self, keystore: &mut Keystore) -> Result<CommandOutput, anyhow::Error> {
+        let mut wallets = HdWallets::for_keystore(keystore)?;
+        Ok(match self {
+            HdWalletCommand::Create { name, word_length } => {
+                let phrase = wallets.generate(&name, word_length, &new_passphrase()?)?;
+                CommandOutput::HdWalletCreate(HdWalletCreated {
+                    name,
+                    wallets_path: wallets.path().to_path_buf(),
+                    recovery_phrase: Some(phrase),
+                })
+            }
+
+            HdWalletCommand::Import {
+                name,
+                mnemonic_phrase,
+            } => {
+                wallets.import(&name, &mnemonic_phrase, &new_passphrase()?)?;
+                CommandOutput::HdWalletCreate(HdWalletCreated {
+                    name,
+                    wallets_path: wallets.path().to_path_buf(),
+                    recovery_phrase: None,
+                })
+            }
+
+            HdWalletCommand::List => CommandOutput::HdWallets(
+                wallets
+                    .wallets()
+                    .map(|(name, wallet)| HdWalletSummary {
+                        name: name.to_string(),
+                        num_accounts: wallet.accounts().len(),
+                    })
+                    .collect(),
+            ),
+
+            HdWalletCommand::Accounts { name } => {
+                CommandOutput::HdAccounts(wallets.get(&name)?.accounts().to_vec())
+            }
+
+            HdWalletCommand::Derive {
+                name,
+                key_scheme,
+                alias,
+            } => {
+                let account = unlock_with_prompts(|| {
+                    wallets.add_next_account(keystore, &name, key_scheme, alias.clone())
+                })?;
+                CommandOutput::HdDerive(DerivedAccount {
+                    alias: keystore.get_alias_by_address(&account.address)?,
+                    account,
+                })
+            }
+
+            HdWalletCommand::Scan {
+                name,
+                key_scheme,
+                start,
+                count,
+                rpc,
+                add,
+            } => {
+                let end = start
+                    .checked_add(count)
+                    .ok_or_else(|| anyhow!("Index range {start} + {count} is too large"))?;
+                let seed = unlock_with_prompts(|| wallets.get(&name)?.unlock(wallets.path()))?;
+                let client = SuiClientBuilder::default().build(&rpc).await?;
+
+                let mut scanned = vec![];
+                for index in start..end {
+                    let (account, keypair) = derive_account(&seed, key_scheme, index)?;
+                    let active = has_activity(&client, account.address).await?;
+                    let added = add && active && !keystore.addresses().contains(&account.address);
+                    if added {
+                        keystore.add_key(None, keypair)?;
+                    }
+                    if add && active {
+                        wallets.record_account(&name, account.clone())?;
+                    }
+                    scanned.push(ScannedAccount {
+                        account,
+                        active,
+                        added,
+                    });
+                }
+                CommandOutput::HdScan(scanned)
+            }
+
+            HdWalletCommand::ExportWatchOnly { name, output } => {
+                let watch_only = wallets.get(&name)?.watch_only(&name);
+                if let Some(output) = output {
+                    fs::write(&output, serde_json::to_string_pretty(&watch_only)?).map_err(
+                        |e| anyhow!("Cannot write watch-only list to {}: {e}", output.display()),
+                    )?;
+                }
+                CommandOutput::HdWatchOnly(watch_only)
+            }
+        })
+    }
+}
+
+/// Whether `address` has sent any transaction, or owns any object.
+async fn has_activity(client: &SuiClient, address: SuiAddress) -> Result<bool, anyhow::Error> {
+    let sent = client
+        .read_api()
+        .query_transaction_blocks(
+            SuiTransactionBlockResponseQuery::new(
+                Some(TransactionFilter::FromAddress(address)),
+                None,
+            ),
+            None,
+            Some(1),
+            false,
+        )
+        .await?;
+    if !sent.data.is_empty() {
+        return Ok(true);
+    }
+
+    let owned = client
+        .read_api()
+        .get_owned_objects(address, None, None, Some(1))
+        .await?;
+    Ok(!owned.data.is_empty())
+}
+
 impl From<&SuiKeyPair> for Key {
     fn from(skp: &SuiKeyPair) -> Self {
         Key::from(skp.public())
@@ -1332,6 +1705,10 @@ impl Display for CommandOutput {
                     update.old_alias, update.new_alias
                 )
             }
+            // Printed as a shell command, to be evaluated by the shell.
+            CommandOutput::Unlock(key) => {
+                write!(formatter, "export {KEY_ENV}={}", key.key)
+            }
             // Sign needs to be manually built because we need to wrap the very long
             // rawTxData string and rawIntentMsg strings into multiple rows due to
             // their lengths, which we cannot do with a JsonTable
@@ -1439,6 +1816,140 @@ fn convert_private_key_to_bech32(value: String) -> Result<ConvertOutput, anyhow:
     })
 }
 
+/// The file based keystore behind `keystore`, for commands that manage its encryption.
+fn file_keystore(keystore: &mut Keystore) -> Result<&mut FileBasedKeystore, anyhow::Error> {
+    match keystore {
+        Keystore::File(file) => Ok(file),
+        _ => Err(anyhow!("Only file based keystores can be encrypted")),
+    }
+}
+
+/// Run `load`, and whenever it fails because an encrypted keystore or wallets file is locked,
+/// prompt for that file's passphrase and try again.
+pub fn unlock_with_prompts<T>(
+    mut load: impl FnMut() -> Result<T, anyhow::Error>,
+) -> Result<T, anyhow::Error> {
+    let mut prompted = BTreeSet::new();
+    loop {
+        let err = match load() {
+            Ok(loaded) => return Ok(loaded),
+            Err(err) => err,
+        };
+
+        let Some(locked) = err.chain().find_map(|e| e.downcast_ref::<KeystoreLocked>()) else {
+            return Err(err);
+        };
+
+        // Don't ask twice for the same file, the passphrase was not enough to unlock it.
+        if !prompted.insert(locked.path.clone()) {
+            return Err(err);
+        }
+
+        let path = locked.path.clone();
+        let passphrase =
+            prompt_passphrase(&format!("Enter passphrase for {}:", path.display()), false)?;
+        provide_passphrase(&path, passphrase);
+    }
+}
+
+/// Prompt for the passphrase of the keystore that the client config at `config_path` refers to,
+/// if it is encrypted and cannot otherwise be unlocked. The keystore is loaded along with the
+/// config, which cannot prompt for it.
+pub fn prompt_to_unlock_client_keystore(config_path: &Path) -> Result<(), anyhow::Error> {
+    let Some(keystore_path) = fs::read_to_string(config_path)
+        .ok()
+        .and_then(|contents| serde_yaml::from_str::<serde_yaml::Value>(&contents).ok())
+        .and_then(|config| {
+            Some(PathBuf::from(
+                config.get("keystore")?.get("File")?.as_str()?,
+            ))
+        })
+    else {
+        return Ok(());
+    };
+
+    if FileBasedKeystore::is_locked(&keystore_path)? {
+        let passphrase = prompt_passphrase(
+            &format!("Enter passphrase for {}:", keystore_path.display()),
+            false,
+        )?;
+        provide_passphrase(&keystore_path, passphrase);
+    }
+
+    Ok(())
+}
+
+/// Prompt for a passphrase on the terminal, without echoing it. With `confirm`, the passphrase
+/// has to be entered twice.
+fn prompt_passphrase(message: &str, confirm: bool) -> Result<Zeroizing<String>, anyhow::Error> {
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "Cannot prompt for the keystore passphrase, set {PASSPHRASE_ENV} or {KEY_ENV} instead"
+        ));
+    }
+
+    let mut prompt =
+        inquire::Password::new(message).with_display_mode(inquire::PasswordDisplayMode::Hidden);
+    if confirm {
+        prompt = prompt.with_custom_confirmation_message("Confirm passphrase:");
+    } else {
+        prompt = prompt.without_confirmation();
+    }
+
+    prompt
+        .prompt()
+        .map(Zeroizing::new)
+        .map_err(|e| anyhow!("Cannot read passphrase: {e}"))
+}
+
+/// The passphrase to encrypt a keystore with, from `NEW_PASSPHRASE_ENV` or prompted for.
+fn new_passphrase() -> Result<Zeroizing<String>, anyhow::Error> {
+    match std::env::var(NEW_PASSPHRASE_ENV) {
+        Ok(passphrase) => Ok(Zeroizing::new(passphrase)),
+        Err(_) => prompt_passphrase("Enter new keystore passphrase:", true),
+    }
+}
+
+/// Sign the digest of `intent_msg` with a secp256k1 key held in AWS KMS, returning the serialized
+/// `flag || signature || pubkey` bytes. Currently only supports secp256k1 keys.
+async fn sign_with_kms<T: Serialize>(
+    keyid: String,
+    pk_owner: &PublicKey,
+    intent_msg: &IntentMessage<T>,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let mut hasher = DefaultHash::default();
+    hasher.update(bcs::to_bytes(intent_msg)?);
+    let digest = hasher.finalize().digest;
+    info!("Digest to sign: {:?}", Base64::encode(digest));
+
+    // Set up the KMS client in default region.
+    let config = aws_config::load_from_env().await;
+    let kms = KmsClient::new(&config);
+
+    // Sign the message, normalize the signature and then compacts it
+    // serialize_compact is loaded as bytes for Secp256k1Signature
+    let response = kms
+        .sign()
+        .key_id(keyid)
+        .message_type(MessageType::Raw)
+        .message(Blob::new(digest))
+        .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
+        .send()
+        .await?;
+    let sig_bytes_der = response
+        .signature
+        .expect("Requires Asymmetric Key Generated in KMS");
+
+    let mut external_sig = Secp256k1Sig::from_der(sig_bytes_der.as_ref())?;
+    external_sig.normalize_s();
+    let sig_compact = external_sig.serialize_compact();
+
+    let mut serialized_sig = vec![SignatureScheme::Secp256k1.flag()];
+    serialized_sig.extend_from_slice(&sig_compact);
+    serialized_sig.extend_from_slice(pk_owner.as_ref());
+    Ok(serialized_sig)
+}
+
 fn anemo_styling(pk: &PublicKey) -> Option<String> {
     if let PublicKey::Ed25519(public_key) = pk {
         Some(anemo::PeerId(public_key.0).to_string())
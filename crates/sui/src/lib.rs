@@ -3,9 +3,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod client_commands;
+pub mod client_gas;
+pub mod client_history;
 #[macro_use]
 pub mod client_ptb;
 mod clever_error_rendering;
+pub mod completion;
 pub mod displays;
 pub mod fire_drill;
 pub mod genesis_ceremony;
@@ -13,6 +16,7 @@ pub mod genesis_inspector;
 pub mod key_identity;
 pub mod keytool;
 pub mod mvr_resolver;
+pub mod signing_bundle;
 pub mod sui_commands;
 pub mod upgrade_compatibility;
 pub mod validator_commands;
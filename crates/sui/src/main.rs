@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::*;
+use clap_complete::CompleteEnv;
 use colored::Colorize;
 use sui::client_commands::SuiClientCommands::{ReplayBatch, ReplayTransaction};
 use sui::sui_commands::SuiCommand;
@@ -30,6 +31,9 @@ async fn main() {
     #[cfg(windows)]
     colored::control::set_virtual_terminal(true).unwrap();
 
+    // Answers completion requests from the shell (`COMPLETE=<shell> sui ...`), and exits.
+    CompleteEnv::with_factory(Args::command).complete();
+
     let args = Args::parse();
     let _guard = match args.command {
         SuiCommand::KeyTool { .. } | SuiCommand::Move { .. } => {
@@ -0,0 +1,278 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A signing bundle carries an unsigned transaction between the parties that need to sign it
+//! (its sender, its gas sponsor, or the members of a multisig standing in for either), together
+//! with the signatures collected so far. It is a JSON file, so it can be handed from one offline
+//! signer to the next and executed once every required signature is present.
+
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, bail, ensure, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_types::{
+    base_types::SuiAddress,
+    crypto::{EncodeDecodeBase64, PublicKey, Signature, SuiSignature},
+    digests::TransactionDigest,
+    multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit},
+    signature::GenericSignature,
+    transaction::{Transaction, TransactionData, TransactionDataAPI},
+};
+
+#[cfg(test)]
+#[path = "unit_tests/signing_bundle_tests.rs"]
+mod signing_bundle_tests;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningBundle {
+    /// Base64 encoded BCS bytes of the unsigned `TransactionData`.
+    tx_bytes: String,
+    /// Multisig public keys for those required signers that are multisig addresses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    multisig: Vec<MultiSigPublicKey>,
+    /// Signatures collected so far, each a Base64 encoded `flag || signature || pubkey`.
+    #[serde(default)]
+    signatures: Vec<Signature>,
+}
+
+/// Who has signed a bundle, and how much is still missing before it can be executed.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleStatus {
+    pub digest: TransactionDigest,
+    pub signers: Vec<SignerStatus>,
+    /// Whether every required signer has reached their threshold.
+    pub complete: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignerStatus {
+    pub address: SuiAddress,
+    /// "sender", "sponsor" or both.
+    pub role: String,
+    pub signed_weight: ThresholdUnit,
+    pub threshold: ThresholdUnit,
+    pub missing_weight: ThresholdUnit,
+    /// Members of the multisig, if this signer is a multisig address.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<MemberStatus>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberStatus {
+    pub address: SuiAddress,
+    pub public_key: String,
+    pub weight: WeightUnit,
+    pub signed: bool,
+}
+
+impl SigningBundle {
+    /// Start a bundle for `tx_data` with no signatures.
+    pub fn new(tx_data: &TransactionData) -> anyhow::Result<Self> {
+        Ok(Self {
+            tx_bytes: Base64::encode(bcs::to_bytes(tx_data)?),
+            multisig: vec![],
+            signatures: vec![],
+        })
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read signing bundle {}", path.display()))?;
+        let bundle: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Cannot parse signing bundle {}", path.display()))?;
+        // Make sure the transaction itself is readable before anyone works with the bundle.
+        bundle.tx_data()?;
+        Ok(bundle)
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("Cannot write signing bundle {}", path.display()))
+    }
+
+    pub fn tx_data(&self) -> anyhow::Result<TransactionData> {
+        let bytes = Base64::decode(&self.tx_bytes)
+            .map_err(|e| anyhow!("Invalid Base64 encoding of bundle transaction: {e}"))?;
+        bcs::from_bytes(&bytes).map_err(|e| anyhow!("Cannot deserialize TransactionData: {e}"))
+    }
+
+    /// Declare that one of the transaction's signers is the multisig described by `multisig_pk`.
+    /// Declaring the same multisig twice is a no-op, but a different definition is an error.
+    pub fn set_multisig(&mut self, multisig_pk: MultiSigPublicKey) -> anyhow::Result<SuiAddress> {
+        let tx_data = self.tx_data()?;
+        let address = SuiAddress::from(&multisig_pk);
+        ensure!(
+            tx_data.signers().contains(&address),
+            "Multisig address {address} is neither the sender nor the gas owner of this transaction"
+        );
+
+        match self.multisig_for(address) {
+            Some(existing) if *existing == multisig_pk => (),
+            Some(_) => bail!("Bundle already has a different multisig definition for {address}"),
+            None => self.multisig.push(multisig_pk),
+        }
+
+        Ok(address)
+    }
+
+    /// Add a signature over the bundle's transaction, replacing any earlier signature from the
+    /// same key. Returns the required signer this signature counts towards.
+    pub fn add_signature(&mut self, signature: Signature) -> anyhow::Result<SuiAddress> {
+        let tx_data = self.tx_data()?;
+        let pk = public_key(&signature)?;
+        let key_address = SuiAddress::from(&pk);
+
+        let signer = if tx_data.signers().contains(&key_address) {
+            key_address
+        } else if let Some(multisig) = self
+            .multisig
+            .iter()
+            .find(|multisig| multisig.get_index(&pk).is_some())
+        {
+            SuiAddress::from(multisig)
+        } else {
+            bail!(
+                "Key for {key_address} is not a signer of this transaction, nor a member of any \
+                 of its multisig signers"
+            );
+        };
+
+        let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data);
+        signature
+            .verify_secure(&intent_msg, key_address, signature.scheme())
+            .map_err(|e| anyhow!("Signature does not verify against bundle transaction: {e}"))?;
+
+        self.signatures
+            .retain(|existing| public_key(existing).is_ok_and(|existing| existing != pk));
+        self.signatures.push(signature);
+        Ok(signer)
+    }
+
+    pub fn status(&self) -> anyhow::Result<BundleStatus> {
+        let tx_data = self.tx_data()?;
+        let sender = tx_data.sender();
+        let gas_owner = tx_data.gas_owner();
+
+        let mut signers = vec![];
+        for address in tx_data.signers() {
+            let role = match (address == sender, address == gas_owner) {
+                (true, true) => "sender, sponsor",
+                (true, false) => "sender",
+                _ => "sponsor",
+            };
+
+            let (members, threshold) = match self.multisig_for(address) {
+                Some(multisig) => {
+                    let members = multisig
+                        .pubkeys()
+                        .iter()
+                        .map(|(pk, weight)| MemberStatus {
+                            address: SuiAddress::from(pk),
+                            public_key: pk.encode_base64(),
+                            weight: *weight,
+                            signed: self.signature_for(pk).is_some(),
+                        })
+                        .collect();
+                    (members, *multisig.threshold())
+                }
+                None => (vec![], 1),
+            };
+
+            let signed_weight = if members.is_empty() {
+                self.signatures
+                    .iter()
+                    .any(|sig| public_key(sig).is_ok_and(|pk| SuiAddress::from(&pk) == address))
+                    as ThresholdUnit
+            } else {
+                members
+                    .iter()
+                    .filter(|m| m.signed)
+                    .map(|m| m.weight as ThresholdUnit)
+                    .sum()
+            };
+
+            signers.push(SignerStatus {
+                address,
+                role: role.to_string(),
+                signed_weight,
+                threshold,
+                missing_weight: threshold.saturating_sub(signed_weight),
+                members,
+            });
+        }
+
+        Ok(BundleStatus {
+            digest: tx_data.digest(),
+            complete: signers.iter().all(|s| s.missing_weight == 0),
+            signers,
+        })
+    }
+
+    /// Assemble the signed transaction, failing if any required signer has not yet met their
+    /// threshold.
+    pub fn combine(&self) -> anyhow::Result<Transaction> {
+        let tx_data = self.tx_data()?;
+
+        let mut signatures = vec![];
+        for address in tx_data.signers() {
+            let Some(multisig) = self.multisig_for(address) else {
+                let signature = self
+                    .signatures
+                    .iter()
+                    .find(|sig| public_key(sig).is_ok_and(|pk| SuiAddress::from(&pk) == address))
+                    .ok_or_else(|| anyhow!("Bundle is missing a signature from {address}"))?;
+                signatures.push(GenericSignature::Signature(signature.clone()));
+                continue;
+            };
+
+            // MultiSig::combine expects signatures in the same order as the public keys.
+            let mut weight: ThresholdUnit = 0;
+            let mut partial = vec![];
+            for (pk, pk_weight) in multisig.pubkeys() {
+                if let Some(signature) = self.signature_for(pk) {
+                    weight += *pk_weight as ThresholdUnit;
+                    partial.push(GenericSignature::Signature(signature.clone()));
+                }
+            }
+
+            let threshold = *multisig.threshold();
+            ensure!(
+                weight >= threshold,
+                "Multisig {address} has collected weight {weight} of its threshold {threshold}, \
+                 {} more is needed",
+                threshold - weight,
+            );
+
+            let combined = MultiSig::combine(partial, multisig.clone())
+                .map_err(|e| anyhow!("Cannot combine signatures for multisig {address}: {e}"))?;
+            signatures.push(GenericSignature::MultiSig(combined));
+        }
+
+        Ok(Transaction::from_generic_sig_data(tx_data, signatures))
+    }
+
+    fn multisig_for(&self, address: SuiAddress) -> Option<&MultiSigPublicKey> {
+        self.multisig
+            .iter()
+            .find(|multisig| SuiAddress::from(*multisig) == address)
+    }
+
+    fn signature_for(&self, pk: &PublicKey) -> Option<&Signature> {
+        self.signatures
+            .iter()
+            .find(|sig| public_key(sig).is_ok_and(|sig_pk| &sig_pk == pk))
+    }
+}
+
+fn public_key(signature: &Signature) -> anyhow::Result<PublicKey> {
+    GenericSignature::Signature(signature.clone())
+        .to_public_key()
+        .map_err(|e| anyhow!("Cannot read public key from signature: {e}"))
+}
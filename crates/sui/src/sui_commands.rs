@@ -6,7 +6,7 @@ use crate::client_commands::{
 };
 use crate::fire_drill::{run_fire_drill, FireDrill};
 use crate::genesis_ceremony::{run, Ceremony};
-use crate::keytool::KeyToolCommand;
+use crate::keytool::{prompt_to_unlock_client_keystore, unlock_with_prompts, KeyToolCommand};
 use crate::validator_commands::SuiValidatorCommand;
 use anyhow::{anyhow, bail, ensure, Context};
 use clap::*;
@@ -448,7 +448,9 @@ impl SuiCommand {
             } => {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
-                let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+                let mut keystore = Keystore::from(unlock_with_prompts(|| {
+                    FileBasedKeystore::new(&keystore_path)
+                })?);
                 cmd.execute(&mut keystore).await?.print(!json);
                 Ok(())
             }
@@ -463,6 +465,7 @@ impl SuiCommand {
                     .unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 if let Some(cmd) = cmd {
+                    prompt_to_unlock_client_keystore(&config_path)?;
                     let mut context = WalletContext::new(&config_path)?;
                     if let Some(env_override) = config.env {
                         context = context.with_env_override(env_override);
@@ -489,6 +492,7 @@ impl SuiCommand {
             } => {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
+                prompt_to_unlock_client_keystore(&config_path)?;
                 let mut context = WalletContext::new(&config_path)?;
                 if let Some(cmd) = cmd {
                     if let Ok(client) = context.get_client().await {
@@ -686,6 +690,7 @@ impl SuiCommand {
 
                 let config_path =
                     client_config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+                prompt_to_unlock_client_keystore(&config_path)?;
                 let mut context = WalletContext::new(&config_path)?;
                 if let Ok(client) = context.get_client().await {
                     if let Err(e) = client.check_api_version() {
@@ -1037,6 +1042,8 @@ async fn start(
                 }],
                 active_address: Some(address),
                 active_env: Some("localnet".to_string()),
+                address_book: Default::default(),
+                templates: Default::default(),
             }
             .persisted(config_dir.join(SUI_CLIENT_CONFIG).as_path())
             .save()
@@ -1436,6 +1443,8 @@ async fn prompt_if_no_config(
                 envs: vec![env],
                 active_address: Some(new_address),
                 active_env: Some(alias),
+                address_book: Default::default(),
+                templates: Default::default(),
             }
             .persisted(wallet_conf_path)
             .save()?;
@@ -1460,6 +1469,7 @@ async fn get_chain_id_and_client(
         .config
         .unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
     prompt_if_no_config(&config, false).await?;
+    prompt_to_unlock_client_keystore(&config)?;
     let mut context = WalletContext::new(&config)?;
 
     if let Some(env_override) = client_config.env {
@@ -0,0 +1,55 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use sui_types::gas::GasCostSummary;
+
+use super::{GasBudget, GasEstimate};
+
+#[test]
+fn parse_gas_budget() {
+    for (input, expected) in [
+        ("5000000", GasBudget::Fixed(5_000_000)),
+        ("auto", GasBudget::Auto { multiplier: 1.0 }),
+        ("auto:1.5", GasBudget::Auto { multiplier: 1.5 }),
+        ("auto:2", GasBudget::Auto { multiplier: 2.0 }),
+    ] {
+        let budget = GasBudget::from_str(input).unwrap();
+        assert_eq!(budget, expected, "{input}");
+        assert_eq!(GasBudget::from_str(&budget.to_string()).unwrap(), budget);
+    }
+
+    for invalid in [
+        "",
+        "-1",
+        "1.5",
+        "automatic",
+        "auto:",
+        "auto:x",
+        "auto:0.5",
+        "auto:inf",
+    ] {
+        assert!(GasBudget::from_str(invalid).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn estimate_budget() {
+    let rgp = 1_000;
+    let summary = GasCostSummary::new(1_000_000, 2_000_000, 500_000, 5_000);
+
+    // Net gas usage (2.5M) plus the safety overhead (1000 * rgp).
+    let estimate = GasEstimate::new(&summary, rgp, rgp, 1.0, u64::MAX);
+    assert_eq!(estimate.net_gas_usage, 2_500_000);
+    assert_eq!(estimate.estimated_budget, 3_500_000);
+    assert_eq!(estimate.gas_budget, 3_500_000);
+
+    let estimate = GasEstimate::new(&summary, rgp, 2 * rgp, 1.5, u64::MAX);
+    assert_eq!(estimate.gas_budget, 5_250_000);
+    assert_eq!(estimate.gas_price, 2 * rgp);
+
+    // Never more than the maximum budget.
+    let estimate = GasEstimate::new(&summary, rgp, rgp, 10.0, 10_000_000);
+    assert_eq!(estimate.gas_budget, 10_000_000);
+}
@@ -0,0 +1,140 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use sui_json_rpc_types::{
+    SuiCommand, SuiGasData, SuiProgrammableMoveCall, SuiProgrammableTransactionBlock,
+    SuiTransactionBlock, SuiTransactionBlockData, SuiTransactionBlockDataV1,
+    SuiTransactionBlockKind, SuiTransactionBlockResponse,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use tempfile::TempDir;
+
+use super::{ActivityEntry, ActivityLog, HistoryEntry, HistoryFilter};
+
+fn move_call_tx(sender: SuiAddress, calls: &[(&str, &str, &str)]) -> SuiTransactionBlockResponse {
+    let commands = calls
+        .iter()
+        .map(|(package, module, function)| {
+            SuiCommand::MoveCall(Box::new(SuiProgrammableMoveCall {
+                package: ObjectID::from_str(package).unwrap(),
+                module: module.to_string(),
+                function: function.to_string(),
+                type_arguments: vec![],
+                arguments: vec![],
+            }))
+        })
+        .collect();
+
+    let mut response = SuiTransactionBlockResponse::new(TransactionDigest::random());
+    response.transaction = Some(SuiTransactionBlock {
+        data: SuiTransactionBlockData::V1(SuiTransactionBlockDataV1 {
+            transaction: SuiTransactionBlockKind::ProgrammableTransaction(
+                SuiProgrammableTransactionBlock {
+                    inputs: vec![],
+                    commands,
+                },
+            ),
+            sender,
+            gas_data: SuiGasData {
+                payment: vec![],
+                owner: sender,
+                price: 1000,
+                budget: 1_000_000,
+            },
+        }),
+        tx_signatures: vec![],
+    });
+    response
+}
+
+#[test]
+fn parse_filters() {
+    assert_eq!(
+        HistoryFilter::from_str("move-call=0x2::coin::split").unwrap(),
+        HistoryFilter::MoveCall {
+            package: ObjectID::from_single_byte(2),
+            module: Some("coin".to_string()),
+            function: Some("split".to_string()),
+        }
+    );
+    assert_eq!(
+        HistoryFilter::from_str("move-call=0x2").unwrap(),
+        HistoryFilter::MoveCall {
+            package: ObjectID::from_single_byte(2),
+            module: None,
+            function: None,
+        }
+    );
+
+    for invalid in [
+        "move-call",
+        "move-call=coin::split",
+        "move-call=0x2::coin::split::extra",
+        "move-call=0x2::::split",
+        "sender=0x2",
+    ] {
+        assert!(HistoryFilter::from_str(invalid).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn filter_move_calls() {
+    let sender = SuiAddress::random_for_testing_only();
+    let tx = move_call_tx(
+        sender,
+        &[("0x2", "coin", "split"), ("0x3", "sui_system", "stake")],
+    );
+
+    for (filter, matches) in [
+        ("move-call=0x2", true),
+        ("move-call=0x2::coin", true),
+        ("move-call=0x2::coin::split", true),
+        ("move-call=0x3::sui_system::stake", true),
+        ("move-call=0x2::coin::join", false),
+        ("move-call=0x2::sui_system", false),
+        ("move-call=0x4", false),
+    ] {
+        let filter = HistoryFilter::from_str(filter).unwrap();
+        assert_eq!(filter.matches(&tx), matches, "{filter:?}");
+    }
+
+    // Transactions without their input never match.
+    let bare = SuiTransactionBlockResponse::new(TransactionDigest::random());
+    let filter = HistoryFilter::from_str("move-call=0x2").unwrap();
+    assert!(!filter.matches(&bare));
+
+    let entry = HistoryEntry::from(&tx);
+    assert_eq!(entry.sender, Some(sender));
+    assert_eq!(entry.move_calls.len(), 2);
+    assert!(entry.move_calls[0].ends_with("::coin::split"));
+}
+
+#[test]
+fn activity_log() {
+    let dir = TempDir::new().unwrap();
+    let log = ActivityLog::for_config(&dir.path().join("client.yaml"));
+    assert_eq!(log.path().parent(), Some(dir.path()));
+    assert!(log.read(10).unwrap().is_empty());
+
+    let entries: Vec<_> = (0..3)
+        .map(|i| ActivityEntry {
+            timestamp_ms: 1_700_000_000_000 + i,
+            digest: TransactionDigest::random(),
+            command: format!("sui client call --function f{i}"),
+            env: Some("devnet".to_string()),
+            gas_used: Some(1_000 * i as i64),
+            status: "success".to_string(),
+        })
+        .collect();
+    for entry in &entries {
+        log.append(entry).unwrap();
+    }
+
+    // Most recent first, and limited.
+    let read = log.read(2).unwrap();
+    assert_eq!(read, vec![entries[2].clone(), entries[1].clone()]);
+    assert_eq!(log.read(10).unwrap().len(), 3);
+}
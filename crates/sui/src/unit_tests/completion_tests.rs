@@ -0,0 +1,117 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, ffi::OsStr};
+
+use clap_complete::CompletionCandidate;
+use fastcrypto::traits::EncodeDecodeBase64;
+use sui_keys::keystore::Alias;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    crypto::{get_key_pair, AccountKeyPair, SuiKeyPair},
+};
+use tempfile::TempDir;
+
+use super::{
+    flag_value, keystore_aliases, matching, CachedObject, ClientSnapshot, CompletionCache,
+};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+fn values(candidates: &[CompletionCandidate]) -> Vec<String> {
+    candidates
+        .iter()
+        .map(|c| c.get_value().to_string_lossy().into_owned())
+        .collect()
+}
+
+#[test]
+fn flag_values() {
+    let line = args("sui client call --package 0x2 --module=coin --function");
+    assert_eq!(flag_value(&line, "--package"), Some("0x2"));
+    assert_eq!(flag_value(&line, "--module"), Some("coin"));
+    assert_eq!(flag_value(&line, "--function"), None);
+    assert_eq!(flag_value(&line, "--gas"), None);
+
+    // A flag's prefix is not the flag.
+    let line = args("sui client call --packages 0x2");
+    assert_eq!(flag_value(&line, "--package"), None);
+}
+
+#[test]
+fn matching_candidates() {
+    let candidates = ["alice", "alan", "bob", "alice"]
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect();
+    assert_eq!(
+        values(&matching(candidates, OsStr::new("al"))),
+        vec!["alice", "alan"]
+    );
+}
+
+#[test]
+fn keystore_alias_addresses() {
+    let dir = TempDir::new().unwrap();
+    let (_, key): (_, AccountKeyPair) = get_key_pair();
+    let public_key = SuiKeyPair::Ed25519(key).public();
+    let address = SuiAddress::from(&public_key);
+    let aliases = vec![Alias {
+        alias: "alice".to_string(),
+        public_key_base64: public_key.encode_base64(),
+    }];
+
+    let keystore = dir.path().join("sui.keystore");
+    std::fs::write(
+        keystore.with_extension("aliases"),
+        serde_json::to_vec(&aliases).unwrap(),
+    )
+    .unwrap();
+
+    let client: ClientSnapshot = serde_yaml::from_str(&format!(
+        "keystore:\n  File: {}\nenvs: []\n",
+        keystore.display()
+    ))
+    .unwrap();
+    let config_path = dir.path().join("client.yaml");
+    assert_eq!(
+        keystore_aliases(&config_path, &client),
+        vec![("alice".to_string(), address)]
+    );
+
+    // Keystores that are not files have no aliases file to read.
+    let client: ClientSnapshot =
+        serde_yaml::from_str("keystore:\n  External:\n    command: signer\nenvs: []\n").unwrap();
+    assert!(keystore_aliases(&config_path, &client).is_empty());
+}
+
+#[test]
+fn cache_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let path = CompletionCache::path_for_config(&dir.path().join("client.yaml"));
+    assert_eq!(path.parent(), Some(dir.path()));
+    assert_eq!(CompletionCache::read(&path), CompletionCache::default());
+
+    let owner = SuiAddress::random_for_testing_only();
+    let mut cache = CompletionCache::default();
+    cache.objects.insert(
+        owner,
+        vec![CachedObject {
+            object_id: ObjectID::random(),
+            object_type: "0x2::coin::Coin<0x2::sui::SUI>".to_string(),
+            gas_coin: true,
+        }],
+    );
+    cache.packages.insert(
+        ObjectID::from_single_byte(2),
+        BTreeMap::from([("coin".to_string(), vec!["split".to_string()])]),
+    );
+    cache.write(&path).unwrap();
+    assert_eq!(CompletionCache::read(&path), cache);
+
+    // A corrupted cache reads as empty rather than breaking completion.
+    std::fs::write(&path, "not json").unwrap();
+    assert_eq!(CompletionCache::read(&path), CompletionCache::default());
+}
@@ -0,0 +1,171 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_keys::keystore::{AccountKeystore, InMemKeystore, Keystore};
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::multisig::MultiSigPublicKey;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::signature::{GenericSignature, VerifyParams};
+use sui_types::signature_verification::VerifiedDigestCache;
+use sui_types::transaction::{
+    TransactionData, TransactionDataAPI, TransactionKind, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+};
+use tempfile::TempDir;
+
+use super::SigningBundle;
+
+fn transfer(sender: SuiAddress, sponsor: SuiAddress) -> TransactionData {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder
+        .pay_sui(vec![SuiAddress::random_for_testing_only()], vec![10000])
+        .unwrap();
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    TransactionData::new_with_gas_coins_allow_sponsor(
+        TransactionKind::ProgrammableTransaction(builder.finish()),
+        sender,
+        vec![gas],
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        1,
+        sponsor,
+    )
+}
+
+#[test]
+fn sponsored_transaction() -> anyhow::Result<()> {
+    let keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(2));
+    let addresses = keystore.addresses();
+    let (sender, sponsor) = (addresses[0], addresses[1]);
+
+    let tx_data = transfer(sender, sponsor);
+    let mut bundle = SigningBundle::new(&tx_data)?;
+
+    let status = bundle.status()?;
+    assert_eq!(status.digest, tx_data.digest());
+    assert!(!status.complete);
+    assert_eq!(status.signers.len(), 2);
+    assert_eq!(status.signers[0].role, "sender");
+    assert_eq!(status.signers[1].role, "sponsor");
+    assert!(status.signers.iter().all(|s| s.missing_weight == 1));
+
+    let sig = keystore.sign_secure(&sender, &tx_data, Intent::sui_transaction())?;
+    assert_eq!(bundle.add_signature(sig)?, sender);
+    assert!(bundle.combine().is_err());
+
+    // The bundle survives a round trip through a file between signers.
+    let dir = TempDir::new()?;
+    let path = dir.path().join("bundle.json");
+    bundle.write(&path)?;
+    let mut bundle = SigningBundle::read(&path)?;
+
+    let sig = keystore.sign_secure(&sponsor, &tx_data, Intent::sui_transaction())?;
+    assert_eq!(bundle.add_signature(sig)?, sponsor);
+    assert!(bundle.status()?.complete);
+
+    let tx = bundle.combine()?;
+    assert_eq!(tx.data().tx_signatures().len(), 2);
+    assert_eq!(tx.data().intent_message().value, tx_data);
+    Ok(())
+}
+
+#[test]
+fn multisig_sender() -> anyhow::Result<()> {
+    let keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(3));
+    let members = keystore.addresses();
+    let pks = members
+        .iter()
+        .map(|a| keystore.get_key(a).map(|k| k.public()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let multisig_pk = MultiSigPublicKey::new(pks, vec![1, 1, 1], 2)?;
+    let multisig = SuiAddress::from(&multisig_pk);
+    let tx_data = transfer(multisig, multisig);
+
+    let mut bundle = SigningBundle::new(&tx_data)?;
+    assert_eq!(bundle.set_multisig(multisig_pk.clone())?, multisig);
+
+    // Signatures can arrive in any order, and re-signing replaces the earlier signature.
+    for member in [members[2], members[2], members[0]] {
+        let sig = keystore.sign_secure(&member, &tx_data, Intent::sui_transaction())?;
+        assert_eq!(bundle.add_signature(sig)?, multisig);
+    }
+
+    let status = bundle.status()?;
+    let signer = &status.signers[0];
+    assert_eq!(signer.role, "sender, sponsor");
+    assert_eq!(signer.signed_weight, 2);
+    assert_eq!(signer.missing_weight, 0);
+    assert_eq!(
+        signer.members.iter().map(|m| m.signed).collect::<Vec<_>>(),
+        vec![true, false, true],
+    );
+
+    let tx = bundle.combine()?;
+    let [sig @ GenericSignature::MultiSig(_)] = tx.data().tx_signatures() else {
+        panic!("Expected a single multisig signature");
+    };
+    sig.verify_authenticator(
+        &IntentMessage::new(Intent::sui_transaction(), tx_data),
+        multisig,
+        0,
+        &VerifyParams::default(),
+        Arc::new(VerifiedDigestCache::new_empty()),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn multisig_below_threshold() -> anyhow::Result<()> {
+    let keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(2));
+    let members = keystore.addresses();
+    let pks = members
+        .iter()
+        .map(|a| keystore.get_key(a).map(|k| k.public()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let multisig_pk = MultiSigPublicKey::new(pks.clone(), vec![1, 2], 3)?;
+    let multisig = SuiAddress::from(&multisig_pk);
+    let tx_data = transfer(multisig, multisig);
+
+    let mut bundle = SigningBundle::new(&tx_data)?;
+    bundle.set_multisig(multisig_pk)?;
+    let sig = keystore.sign_secure(&members[1], &tx_data, Intent::sui_transaction())?;
+    bundle.add_signature(sig)?;
+
+    assert_eq!(bundle.status()?.signers[0].missing_weight, 1);
+    let err = bundle.combine().unwrap_err().to_string();
+    assert!(err.contains("1 more is needed"), "{err}");
+
+    // A different definition for the same signer can't be swapped in.
+    let other = MultiSigPublicKey::new(pks, vec![1, 2], 2)?;
+    assert!(bundle.set_multisig(other).is_err());
+    Ok(())
+}
+
+#[test]
+fn rejects_unrelated_signatures() -> anyhow::Result<()> {
+    let keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(2));
+    let addresses = keystore.addresses();
+    let (sender, stranger) = (addresses[0], addresses[1]);
+
+    let tx_data = transfer(sender, sender);
+    let mut bundle = SigningBundle::new(&tx_data)?;
+
+    // Not a signer of this transaction.
+    let sig = keystore.sign_secure(&stranger, &tx_data, Intent::sui_transaction())?;
+    assert!(bundle.add_signature(sig).is_err());
+
+    // The right signer, but over a different transaction.
+    let other = transfer(sender, sender);
+    let sig = keystore.sign_secure(&sender, &other, Intent::sui_transaction())?;
+    assert!(bundle.add_signature(sig).is_err());
+
+    assert!(!bundle.status()?.complete);
+    Ok(())
+}
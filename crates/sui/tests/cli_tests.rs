@@ -16,6 +16,7 @@ use fastcrypto::encoding::{Base64, Encoding};
 use move_package::{lock_file::schema::ManagedPackage, BuildConfig as MoveBuildConfig};
 use serde_json::json;
 use sui::client_commands::{GasDataArgs, PaymentArgs, TxProcessingArgs};
+use sui::client_gas::{GasBudget, GasEstimate};
 use sui::client_ptb::ptb::PTB;
 use sui::key_identity::{get_identity_address, KeyIdentity};
 use sui::sui_commands::IndexerArgs;
@@ -172,7 +173,7 @@ impl TreeShakingTest {
                 gas: vec![self.gas_obj_id],
             },
             gas_data: GasDataArgs {
-                gas_budget: Some(self.rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+                gas_budget: Some(GasBudget::Fixed(self.rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
                 ..Default::default()
             },
             processing: TxProcessingArgs::default(),
@@ -223,7 +224,7 @@ async fn publish_package(
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -518,7 +519,7 @@ async fn test_ptb_publish_and_complex_arg_resolution() -> Result<(), anyhow::Err
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -552,7 +553,7 @@ async fn test_ptb_publish_and_complex_arg_resolution() -> Result<(), anyhow::Err
         args: vec![],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -759,7 +760,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
             gas: vec![object_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -815,7 +816,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -902,7 +903,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -943,7 +944,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -971,7 +972,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -996,7 +997,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS)),
             gas_price: Some(1),
             ..Default::default()
         },
@@ -1029,7 +1030,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1051,7 +1052,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS)),
             gas_price: Some(12345),
             ..Default::default()
         },
@@ -1113,7 +1114,7 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1191,7 +1192,7 @@ async fn test_package_management_on_publish_command() -> Result<(), anyhow::Erro
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1269,7 +1270,7 @@ async fn test_delete_shared_object() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1308,7 +1309,7 @@ async fn test_delete_shared_object() -> Result<(), anyhow::Error> {
         args: vec![],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1332,7 +1333,7 @@ async fn test_delete_shared_object() -> Result<(), anyhow::Error> {
         args: vec![SuiJsonValue::from_str(&shared_id.to_string()).unwrap()],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1389,7 +1390,7 @@ async fn test_receive_argument() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1428,7 +1429,7 @@ async fn test_receive_argument() -> Result<(), anyhow::Error> {
         args: vec![],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1472,7 +1473,7 @@ async fn test_receive_argument() -> Result<(), anyhow::Error> {
         ],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1529,7 +1530,7 @@ async fn test_receive_argument_by_immut_ref() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1568,7 +1569,7 @@ async fn test_receive_argument_by_immut_ref() -> Result<(), anyhow::Error> {
         args: vec![],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1612,7 +1613,7 @@ async fn test_receive_argument_by_immut_ref() -> Result<(), anyhow::Error> {
         ],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1669,7 +1670,7 @@ async fn test_receive_argument_by_mut_ref() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1708,7 +1709,7 @@ async fn test_receive_argument_by_mut_ref() -> Result<(), anyhow::Error> {
         args: vec![],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1752,7 +1753,7 @@ async fn test_receive_argument_by_mut_ref() -> Result<(), anyhow::Error> {
         ],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1811,7 +1812,7 @@ async fn test_package_publish_command_with_unpublished_dependency_succeeds(
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1888,7 +1889,7 @@ async fn test_package_publish_command_with_unpublished_dependency_fails(
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1939,7 +1940,7 @@ async fn test_package_publish_command_non_zero_unpublished_dep_fails() -> Result
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -1999,7 +2000,7 @@ async fn test_package_publish_command_failure_invalid() -> Result<(), anyhow::Er
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2046,7 +2047,7 @@ async fn test_package_publish_nonexistent_dependency() -> Result<(), anyhow::Err
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2094,7 +2095,7 @@ async fn test_package_publish_test_flag() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2154,7 +2155,7 @@ async fn test_package_publish_empty() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2216,7 +2217,7 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2295,7 +2296,7 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2368,7 +2369,7 @@ async fn test_package_management_on_upgrade_command() -> Result<(), anyhow::Erro
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2430,7 +2431,7 @@ async fn test_package_management_on_upgrade_command() -> Result<(), anyhow::Erro
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2518,7 +2519,7 @@ async fn test_package_management_on_upgrade_command_conflict() -> Result<(), any
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2594,7 +2595,7 @@ async fn test_package_management_on_upgrade_command_conflict() -> Result<(), any
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2650,7 +2651,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2761,7 +2762,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
         object_id: obj_id,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -2883,6 +2884,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
         alias: None,
         derivation_path: None,
         word_length: None,
+        from_seed: None,
     }
     .execute(context)
     .await?;
@@ -2936,6 +2938,7 @@ async fn test_new_address_command_by_flag() -> Result<(), anyhow::Error> {
         alias: None,
         derivation_path: None,
         word_length: None,
+        from_seed: None,
     }
     .execute(context)
     .await?;
@@ -3105,7 +3108,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         coin_to_merge,
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -3163,7 +3166,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         coin_to_merge,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -3231,7 +3234,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
         count: None,
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -3302,7 +3305,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
         count: Some(3),
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -3375,7 +3378,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
         count: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -3486,7 +3489,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
         sui_coin_object_id: coin,
         amount: Some(1),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -3502,7 +3505,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
         sui_coin_object_id: coin,
         amount: Some(1),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -3519,7 +3522,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
         sui_coin_object_id: coin,
         amount: Some(1),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -3856,7 +3859,7 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
             gas: vec![object_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -3875,7 +3878,7 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
         sui_coin_object_id: object_to_send,
         amount: Some(1),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -3895,7 +3898,7 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
         amounts: vec![1],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -3923,7 +3926,7 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
             gas: vec![gas_coin_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -3942,7 +3945,7 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
         recipients: vec![KeyIdentity::Address(SuiAddress::random_for_testing_only())],
         amounts: vec![1],
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -3960,7 +3963,7 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
         input_coins: vec![object_id],
         recipient: KeyIdentity::Address(SuiAddress::random_for_testing_only()),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -4042,7 +4045,7 @@ async fn test_pay() -> Result<(), anyhow::Error> {
             gas: vec![object_id1],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4061,7 +4064,7 @@ async fn test_pay() -> Result<(), anyhow::Error> {
         amounts: amounts.into(),
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4146,7 +4149,7 @@ async fn test_pay_sui() -> Result<(), anyhow::Error> {
         recipients: vec![recipient1.clone(), recipient2.clone()],
         amounts: amounts.into(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4227,7 +4230,7 @@ async fn test_pay_all_sui() -> Result<(), anyhow::Error> {
         input_coins: vec![object_id1, object_id2],
         recipient: recipient1.clone(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4279,7 +4282,7 @@ async fn test_transfer() -> Result<(), anyhow::Error> {
             gas: vec![object_id1],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4295,7 +4298,7 @@ async fn test_transfer() -> Result<(), anyhow::Error> {
         object_id: object_id1,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4347,7 +4350,7 @@ async fn test_transfer_sui() -> Result<(), anyhow::Error> {
         sui_coin_object_id: object_id1,
         amount: Some(amount),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4392,7 +4395,7 @@ async fn test_transfer_sui() -> Result<(), anyhow::Error> {
         sui_coin_object_id: object_id1,
         amount: None,
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4448,7 +4451,7 @@ async fn test_transfer_gas_smash() -> Result<(), anyhow::Error> {
             gas: vec![object_id0, object_id1],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4466,7 +4469,7 @@ async fn test_transfer_gas_smash() -> Result<(), anyhow::Error> {
             gas: vec![object_id0, object_id1],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4520,7 +4523,7 @@ async fn test_transfer_sponsored() -> Result<(), anyhow::Error> {
         sui_coin_object_id: o[1],
         amount: None,
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4540,7 +4543,7 @@ async fn test_transfer_sponsored() -> Result<(), anyhow::Error> {
         object_id: o[1],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             gas_sponsor: Some(a0),
             ..Default::default()
         },
@@ -4577,7 +4580,7 @@ async fn test_transfer_serialized_data() -> Result<(), anyhow::Error> {
         object_id: o[0],
         payment: PaymentArgs { gas: vec![o[1]] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -4655,7 +4658,7 @@ async fn test_transfer_serialized_kind() -> Result<(), anyhow::Error> {
         tx_bytes,
         payment: PaymentArgs { gas: vec![o[1]] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4730,6 +4733,59 @@ async fn test_gas_estimation() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_gas_budget_auto() -> Result<(), anyhow::Error> {
+    let (mut test_cluster, _, rgp, objects, _, addresses) = test_cluster_helper().await;
+    let context = &mut test_cluster.wallet;
+
+    // Only estimate the transfer's cost.
+    let estimate = SuiClientCommands::TransferSui {
+        to: KeyIdentity::Address(addresses[0]),
+        sui_coin_object_id: objects[0],
+        amount: Some(1000),
+        gas_data: GasDataArgs {
+            gas_budget: Some(GasBudget::Auto { multiplier: 2.0 }),
+            ..Default::default()
+        },
+        processing: TxProcessingArgs {
+            estimate_only: true,
+            ..Default::default()
+        },
+    }
+    .execute(context)
+    .await?;
+
+    let SuiClientCommandResult::GasEstimate(estimate) = estimate else {
+        panic!("Expected GasEstimate result");
+    };
+    assert_eq!(estimate.reference_gas_price, rgp);
+    assert_eq!(estimate.gas_budget, 2 * estimate.estimated_budget);
+    let json = serde_json::to_value(&estimate)?;
+    assert_eq!(serde_json::from_value::<GasEstimate>(json)?, estimate);
+
+    // Execute it with the estimated budget.
+    let transfer = SuiClientCommands::TransferSui {
+        to: KeyIdentity::Address(addresses[0]),
+        sui_coin_object_id: objects[0],
+        amount: Some(1000),
+        gas_data: GasDataArgs {
+            gas_budget: Some(GasBudget::Auto { multiplier: 2.0 }),
+            ..Default::default()
+        },
+        processing: TxProcessingArgs::default(),
+    }
+    .execute(context)
+    .await?;
+
+    let SuiClientCommandResult::TransactionBlock(response) = transfer else {
+        panic!("Expected TransactionBlock result");
+    };
+    assert!(response.status_ok().unwrap());
+    let tx = response.transaction.as_ref().unwrap();
+    assert_eq!(tx.data.gas_data().budget, estimate.gas_budget);
+    Ok(())
+}
+
 #[sim_test]
 async fn test_custom_sender() -> Result<(), anyhow::Error> {
     let (mut cluster, client, rgp, o, _, a) = test_cluster_helper().await;
@@ -4761,7 +4817,7 @@ async fn test_custom_sender() -> Result<(), anyhow::Error> {
         tx_bytes,
         payment: PaymentArgs { gas: vec![o[1]] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4803,7 +4859,7 @@ async fn test_custom_sender() -> Result<(), anyhow::Error> {
         object_id: o[0],
         payment: PaymentArgs { gas: vec![o[1]] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs {
@@ -4824,7 +4880,7 @@ async fn test_custom_sender() -> Result<(), anyhow::Error> {
         tx_bytes,
         payment: PaymentArgs { gas: vec![o[1]] },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4880,7 +4936,7 @@ async fn test_clever_errors() -> Result<(), anyhow::Error> {
             gas: vec![gas_obj_id],
         },
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4922,7 +4978,7 @@ async fn test_clever_errors() -> Result<(), anyhow::Error> {
         args: vec![],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4940,7 +4996,7 @@ async fn test_clever_errors() -> Result<(), anyhow::Error> {
         args: vec![],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4958,7 +5014,7 @@ async fn test_clever_errors() -> Result<(), anyhow::Error> {
         args: vec![],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
@@ -4976,7 +5032,7 @@ async fn test_clever_errors() -> Result<(), anyhow::Error> {
         args: vec![],
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
-            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+            gas_budget: Some(GasBudget::Fixed(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH)),
             ..Default::default()
         },
         processing: TxProcessingArgs::default(),
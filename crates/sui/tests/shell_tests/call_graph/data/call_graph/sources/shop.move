@@ -0,0 +1,53 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module call_graph::shop;
+
+use sui::coin::Coin;
+use sui::sui::SUI;
+
+public struct Shop has key {
+    id: UID,
+    price: u64,
+}
+
+public struct Item has key, store {
+    id: UID,
+}
+
+fun init(ctx: &mut TxContext) {
+    share(Shop { id: object::new(ctx), price: 10 });
+}
+
+fun share(shop: Shop) {
+    transfer::share_object(shop);
+}
+
+macro fun check_payment($shop: &Shop, $payment: &Coin<SUI>) {
+    let payment = $payment;
+    assert!(payment.value() == price($shop));
+}
+
+public fun buy(shop: &Shop, payment: Coin<SUI>, ctx: &mut TxContext): Item {
+    check_payment!(shop, &payment);
+    transfer::public_transfer(payment, @0xA);
+    mint(ctx)
+}
+
+entry fun buy_and_keep(shop: &Shop, payment: Coin<SUI>, ctx: &mut TxContext) {
+    let item = shop.buy(payment, ctx);
+    transfer::public_transfer(item, ctx.sender());
+}
+
+fun mint(ctx: &mut TxContext): Item {
+    Item { id: object::new(ctx) }
+}
+
+public fun price(shop: &Shop): u64 {
+    shop.price
+}
+
+#[test_only]
+public fun init_for_testing(ctx: &mut TxContext) {
+    init(ctx)
+}
@@ -0,0 +1,8 @@
+# Copyright (c) Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+
+# Calls made by the package's functions, as a Graphviz DOT graph
+sui move call-graph -p data/call_graph 2> /dev/null
+echo
+# Including test-only functions
+sui move call-graph -p data/call_graph --include-tests 2> /dev/null
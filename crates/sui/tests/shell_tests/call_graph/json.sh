@@ -0,0 +1,6 @@
+# Copyright (c) Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+
+# Every call site on a path to the shop's price, as JSON (restricted to the package's own functions
+# so that the output does not depend on the framework's source locations)
+sui move call-graph -p data/call_graph --reaches call_graph::shop::price --format json 2> /dev/null
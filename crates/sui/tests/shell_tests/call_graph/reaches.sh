@@ -0,0 +1,12 @@
+# Copyright (c) Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+
+# Only the functions from which an object can be shared
+sui move call-graph -p data/call_graph --reaches sui::transfer::share_object 2> /dev/null
+echo
+# Functions can also be identified by their numerical address
+sui move call-graph -p data/call_graph --reaches 0x2::transfer::public_transfer 2> /dev/null
+echo
+# Functions that are not called by the package are rejected
+sui move call-graph -p data/call_graph --reaches sui::transfer::freeze_object > /dev/null 2>&1 \
+    || echo "rejected with exit code $?"
@@ -1306,6 +1306,8 @@ impl TestClusterBuilder {
             envs: Default::default(),
             active_address,
             active_env: Default::default(),
+            address_book: Default::default(),
+            templates: Default::default(),
         }
         .save(wallet_path)?;
 
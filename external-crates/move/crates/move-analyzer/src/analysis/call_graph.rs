@@ -0,0 +1,244 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains code for computing a call graph of a package from its typed AST. The
+//! call graph records a call site for every (regular, method or macro) call, attributing it to
+//! the function whose source code contains it. In particular, as macros are expanded at their
+//! call sites in the typed AST, calls located in a macro's body are attributed to the macro
+//! itself (rather than to the function calling the macro), and calls located in lambdas passed
+//! to a macro are attributed to the function the lambda is defined in.
+
+use crate::compiler_info::CompilerInfo;
+
+use move_command_line_common::files::FileHash;
+use move_compiler::{
+    diagnostics::warning_filters::WarningFilters,
+    expansion::ast::{ModuleIdent, ModuleIdent_, Visibility},
+    parser::ast::{FunctionName, TargetKind},
+    shared::{Identifier, known_attributes::AttributeKind_, unique_map::UniqueMap},
+    typing::{
+        ast::{self as T, ModuleDefinition},
+        visitor::TypingVisitorContext,
+    },
+    unit_test::filter_test_members::UNIT_TEST_POISON_FUN_NAME,
+};
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Identifies a function by its defining module and its name
+pub type FunId = (ModuleIdent_, Symbol);
+
+/// Information about a function in the call graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunNode {
+    /// Location of the function's name
+    pub name_loc: Loc,
+    /// Location of the whole function definition
+    pub loc: Loc,
+    /// Declared visibility
+    pub visibility: Visibility,
+    /// Is it an entry function?
+    pub entry: bool,
+    /// Is it a macro function?
+    pub macro_: bool,
+    /// Is it a test or a test-only function?
+    pub test: bool,
+    /// Is it defined in one of the dependencies rather than in the root package?
+    pub dependency: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CallKind {
+    /// A call of the form `module::name(...)` or `name(...)`
+    Regular,
+    /// A call using method syntax, as in `receiver.name(...)`
+    Method,
+    /// A macro call, as in `name!(...)` or `receiver.name!(...)`
+    Macro,
+}
+
+/// A single call site
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CallEdge {
+    pub caller: FunId,
+    pub callee: FunId,
+    /// Location of the callee's name (or of the method name) at the call site
+    pub loc: Loc,
+    pub kind: CallKind,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    /// Functions defined in the analyzed modules
+    pub functions: BTreeMap<FunId, FunNode>,
+    /// Call sites in the analyzed modules (callees may be defined outside of these modules)
+    pub calls: BTreeSet<CallEdge>,
+}
+
+/// Data used while traversing function bodies
+struct CallGraphVisitor<'a> {
+    graph: &'a mut CallGraph,
+    compiler_info: &'a CompilerInfo,
+    /// Locations of macro definitions, used to find which macro a call located outside of the
+    /// currently visited function's body belongs to
+    macro_locs: BTreeMap<FileHash, Vec<(FunId, Loc)>>,
+    /// Function whose body is currently visited, along with its location
+    current_fun: Option<(FunId, Loc)>,
+}
+
+impl CallGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a call graph for functions in the given (sets of) typed modules. Modules should be
+    /// passed together (rather than added to the graph one by one) so that calls located in
+    /// macros defined in one set of modules and used in another are attributed correctly.
+    pub fn build(
+        modules: &[&UniqueMap<ModuleIdent, ModuleDefinition>],
+        compiler_info: &CompilerInfo,
+    ) -> Self {
+        let mut graph = CallGraph::new();
+        let mut macro_locs: BTreeMap<FileHash, Vec<(FunId, Loc)>> = BTreeMap::new();
+        for (mident, mdef) in modules.iter().flat_map(|m| m.key_cloned_iter()) {
+            let dependency = !matches!(
+                mdef.target_kind,
+                TargetKind::Source {
+                    is_root_package: true
+                }
+            );
+            let mod_test = mdef.attributes.is_test_or_test_only();
+            for (fname, fdef) in mdef.functions.key_cloned_iter() {
+                let fun_id = (mident.value, fname.value());
+                if fdef.macro_.is_some() {
+                    macro_locs
+                        .entry(fdef.loc.file_hash())
+                        .or_default()
+                        .push((fun_id, fdef.loc));
+                }
+                graph.functions.insert(
+                    fun_id,
+                    FunNode {
+                        name_loc: fname.loc(),
+                        loc: fdef.loc,
+                        visibility: fdef.visibility,
+                        entry: fdef.entry.is_some(),
+                        macro_: fdef.macro_.is_some(),
+                        // the poison function is generated by the compiler in test mode only
+                        test: mod_test
+                            || fname.value() == UNIT_TEST_POISON_FUN_NAME
+                            || fdef.attributes.is_test_or_test_only()
+                            || fdef.attributes.contains_key_(&AttributeKind_::Test)
+                            || fdef.attributes.contains_key_(&AttributeKind_::RandTest),
+                        dependency,
+                    },
+                );
+            }
+        }
+
+        let mut visitor = CallGraphVisitor {
+            graph: &mut graph,
+            compiler_info,
+            macro_locs,
+            current_fun: None,
+        };
+        for (mident, mdef) in modules.iter().flat_map(|m| m.key_cloned_iter()) {
+            visitor.visit_module(mident, mdef);
+        }
+        graph
+    }
+
+    /// Call sites located in the given function
+    pub fn outgoing(&self, caller: &FunId) -> impl Iterator<Item = &CallEdge> {
+        self.calls.iter().filter(move |c| c.caller == *caller)
+    }
+
+    /// Call sites calling the given function
+    pub fn incoming(&self, callee: &FunId) -> impl Iterator<Item = &CallEdge> {
+        self.calls.iter().filter(move |c| c.callee == *callee)
+    }
+
+    /// Returns all functions from which the given function can be reached via a chain of calls
+    /// (including the function itself).
+    pub fn reaching(&self, target: &FunId) -> BTreeSet<FunId> {
+        let mut callers: BTreeMap<FunId, BTreeSet<FunId>> = BTreeMap::new();
+        for c in &self.calls {
+            callers.entry(c.callee).or_default().insert(c.caller);
+        }
+        let mut reaching = BTreeSet::from([*target]);
+        let mut worklist = vec![*target];
+        while let Some(fun_id) = worklist.pop() {
+            for caller in callers.get(&fun_id).into_iter().flatten() {
+                if reaching.insert(*caller) {
+                    worklist.push(*caller);
+                }
+            }
+        }
+        reaching
+    }
+}
+
+impl CallGraphVisitor<'_> {
+    /// Returns the function whose source code contains a call site at a given location.
+    fn caller(&self, call_loc: &Loc) -> Option<FunId> {
+        let (fun_id, fun_loc) = self.current_fun?;
+        if fun_loc.contains(call_loc) {
+            return Some(fun_id);
+        }
+        let macro_fun = self
+            .macro_locs
+            .get(&call_loc.file_hash())
+            .and_then(|locs| locs.iter().find(|(_, loc)| loc.contains(call_loc)))
+            .map(|(macro_id, _)| *macro_id);
+        // if the macro's definition is not available, the best we can do is to attribute the call
+        // to the function where the macro is expanded
+        Some(macro_fun.unwrap_or(fun_id))
+    }
+
+    fn add_call(&mut self, callee: FunId, loc: Loc, kind: CallKind) {
+        let Some(caller) = self.caller(&loc) else {
+            return;
+        };
+        self.graph.calls.insert(CallEdge {
+            caller,
+            callee,
+            loc,
+            kind,
+        });
+    }
+}
+
+impl TypingVisitorContext for CallGraphVisitor<'_> {
+    // Nothing to do -- we're not producing errors.
+    fn push_warning_filter_scope(&mut self, _filter: WarningFilters) {}
+
+    // Nothing to do -- we're not producing errors.
+    fn pop_warning_filter_scope(&mut self) {}
+
+    fn visit_function_custom(
+        &mut self,
+        module: ModuleIdent,
+        function_name: FunctionName,
+        fdef: &T::Function,
+    ) -> bool {
+        self.current_fun = Some(((module.value, function_name.value()), fdef.loc));
+        // macro bodies are only type checked (and visited) at their call sites
+        matches!(fdef.body.value, T::FunctionBody_::Macro)
+    }
+
+    fn visit_exp_custom(&mut self, exp: &T::Exp) -> bool {
+        if let Some(info) = self.compiler_info.macro_info.get(&exp.exp.loc) {
+            let loc = info.method_name.map_or(info.name.loc(), |n| n.loc);
+            self.add_call((info.module.value, info.name.value()), loc, CallKind::Macro);
+        } else if let T::UnannotatedExp_::ModuleCall(call) = &exp.exp.value {
+            let (loc, kind) = match call.method_name {
+                Some(method_name) => (method_name.loc, CallKind::Method),
+                None => (call.name.loc(), CallKind::Regular),
+            };
+            self.add_call((call.module.value, call.name.value()), loc, kind);
+        }
+        // keep traversing to find calls nested in this expression
+        false
+    }
+}
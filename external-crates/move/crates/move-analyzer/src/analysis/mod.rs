@@ -26,6 +26,7 @@ use move_compiler::{
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
 
+pub mod call_graph;
 pub mod parsing_analysis;
 pub mod typing_analysis;
 
@@ -6,8 +6,10 @@ use anyhow::Result;
 use crossbeam::channel::{bounded, select};
 use lsp_server::{Connection, Message, Notification, Request, Response};
 use lsp_types::{
-    CodeActionKind, CodeActionOptions, CodeActionProviderCapability, CompletionOptions, Diagnostic,
-    HoverProviderCapability, InlayHintOptions, InlayHintServerCapabilities, OneOf, SaveOptions,
+    CallHierarchyServerCapability, CodeActionKind, CodeActionOptions, CodeActionProviderCapability,
+    CompletionOptions, Diagnostic, HoverProviderCapability, InlayHintOptions,
+    InlayHintServerCapabilities, OneOf, RenameOptions, SaveOptions, SemanticTokensFullOptions,
+    SemanticTokensOptions, SemanticTokensServerCapabilities, SignatureHelpOptions,
     TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
     TypeDefinitionProviderCapability, WorkDoneProgressOptions, notification::Notification as _,
     request::Request as _,
@@ -21,19 +23,20 @@ use std::{
 };
 
 use crate::{
-    code_action,
+    call_hierarchy, code_action,
     completions::on_completion_request,
     context::Context,
-    inlay_hints,
+    inlay_hints, rename, semantic_tokens, signature_help,
     symbols::{
         self,
         compilation::PrecomputedPkgInfo,
         requests::{
             on_document_symbol_request, on_go_to_def_request, on_go_to_type_def_request,
-            on_hover_request, on_references_request,
+            on_hover_request, on_references_request, on_workspace_symbol_request,
         },
         runner::SymbolicatorRunner,
     },
+    type_hierarchy,
     vfs::on_text_document_sync_notification,
 };
 use url::Url;
@@ -66,7 +69,7 @@ pub fn run(implicit_deps: Dependencies) {
         .initialize_start()
         .expect("could not start connection initialization");
 
-    let capabilities = serde_json::to_value(lsp_types::ServerCapabilities {
+    let mut capabilities = serde_json::to_value(lsp_types::ServerCapabilities {
         // The server receives notifications from the client as users open, close,
         // and modify documents.
         text_document_sync: Some(TextDocumentSyncCapability::Options(
@@ -111,6 +114,33 @@ pub fn run(implicit_deps: Dependencies) {
         type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
         references_provider: Some(OneOf::Left(true)),
         document_symbol_provider: Some(OneOf::Left(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
+        // Signature help is displayed when a call's argument list is opened, and updated as
+        // subsequent arguments are being typed.
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                work_done_progress_options: WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+                legend: semantic_tokens::legend(),
+                range: None,
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+            },
+        )),
         inlay_hint_provider: Some(OneOf::Right(InlayHintServerCapabilities::Options(
             InlayHintOptions {
                 work_done_progress_options: WorkDoneProgressOptions {
@@ -129,6 +159,9 @@ pub fn run(implicit_deps: Dependencies) {
         ..Default::default()
     })
     .expect("could not serialize server capabilities");
+    // lsp-types has no field for the type hierarchy capability (added in LSP 3.17), so it is
+    // added to the serialized capabilities directly
+    capabilities["typeHierarchyProvider"] = serde_json::Value::Bool(true);
 
     let (diag_sender, diag_receiver) = bounded::<Result<BTreeMap<PathBuf, Vec<Diagnostic>>>>(0);
     let initialize_params: lsp_types::InitializeParams =
@@ -352,6 +385,39 @@ fn on_request(
         lsp_types::request::DocumentSymbolRequest::METHOD => {
             on_document_symbol_request(context, request);
         }
+        lsp_types::request::WorkspaceSymbolRequest::METHOD => {
+            on_workspace_symbol_request(context, request);
+        }
+        lsp_types::request::PrepareRenameRequest::METHOD => {
+            rename::on_prepare_rename_request(context, request);
+        }
+        lsp_types::request::Rename::METHOD => {
+            rename::on_rename_request(context, request);
+        }
+        lsp_types::request::SignatureHelpRequest::METHOD => {
+            signature_help::on_signature_help_request(context, request, ide_files_root.clone());
+        }
+        lsp_types::request::SemanticTokensFullRequest::METHOD => {
+            semantic_tokens::on_semantic_tokens_full_request(context, request);
+        }
+        lsp_types::request::CallHierarchyPrepare::METHOD => {
+            call_hierarchy::on_prepare_call_hierarchy_request(context, request);
+        }
+        lsp_types::request::CallHierarchyIncomingCalls::METHOD => {
+            call_hierarchy::on_incoming_calls_request(context, request);
+        }
+        lsp_types::request::CallHierarchyOutgoingCalls::METHOD => {
+            call_hierarchy::on_outgoing_calls_request(context, request);
+        }
+        lsp_types::request::TypeHierarchyPrepare::METHOD => {
+            type_hierarchy::on_prepare_type_hierarchy_request(context, request);
+        }
+        lsp_types::request::TypeHierarchySupertypes::METHOD => {
+            type_hierarchy::on_supertypes_request(context, request);
+        }
+        lsp_types::request::TypeHierarchySubtypes::METHOD => {
+            type_hierarchy::on_subtypes_request(context, request);
+        }
         lsp_types::request::InlayHintRequest::METHOD => {
             inlay_hints::on_inlay_hint_request(context, request);
         }
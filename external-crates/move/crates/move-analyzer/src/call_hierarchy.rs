@@ -0,0 +1,219 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains code responsible for handling call hierarchy requests, which allow the
+//! IDE to display the functions calling a given function (incoming calls) and the functions called
+//! by it (outgoing calls). Both are computed from the call graph built for each package during
+//! symbolication (see `analysis::call_graph`).
+
+use crate::{
+    analysis::call_graph::FunId,
+    context::Context,
+    symbols::{
+        Symbols, def_info::DefInfo, ide_strings::mod_ident_to_ide_string,
+        runner::SymbolicatorRunner,
+    },
+};
+
+use lsp_server::{Message, Request, Response};
+use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
+    Position, SymbolKind,
+};
+use move_ir_types::location::Loc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+/// Handles prepare call hierarchy request of the language server
+pub fn on_prepare_call_hierarchy_request(context: &Context, request: &Request) {
+    let symbols_map = &context.symbols.lock().unwrap();
+    let parameters = serde_json::from_value::<CallHierarchyPrepareParams>(request.params.clone())
+        .expect("could not deserialize prepare call hierarchy request");
+
+    let fpath = parameters
+        .text_document_position_params
+        .text_document
+        .uri
+        .to_file_path()
+        .unwrap();
+    let pos = parameters.text_document_position_params.position;
+    let items = prepare_call_hierarchy(symbols_map, &fpath, pos);
+
+    let response = Response::new_ok(request.id.clone(), items);
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send prepare call hierarchy response: {:?}", err);
+    }
+}
+
+/// Handles incoming calls request of the language server
+pub fn on_incoming_calls_request(context: &Context, request: &Request) {
+    let symbols_map = &context.symbols.lock().unwrap();
+    let parameters =
+        serde_json::from_value::<CallHierarchyIncomingCallsParams>(request.params.clone())
+            .expect("could not deserialize incoming calls request");
+
+    let calls = incoming_calls(symbols_map, &parameters.item);
+
+    let response = Response::new_ok(request.id.clone(), calls);
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send incoming calls response: {:?}", err);
+    }
+}
+
+/// Handles outgoing calls request of the language server
+pub fn on_outgoing_calls_request(context: &Context, request: &Request) {
+    let symbols_map = &context.symbols.lock().unwrap();
+    let parameters =
+        serde_json::from_value::<CallHierarchyOutgoingCallsParams>(request.params.clone())
+            .expect("could not deserialize outgoing calls request");
+
+    let calls = outgoing_calls(symbols_map, &parameters.item);
+
+    let response = Response::new_ok(request.id.clone(), calls);
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send outgoing calls response: {:?}", err);
+    }
+}
+
+/// Returns the call hierarchy item for the function whose name (at either its definition or at
+/// one of its call sites) is located at a given position.
+pub fn prepare_call_hierarchy(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    fpath: &Path,
+    pos: Position,
+) -> Option<Vec<CallHierarchyItem>> {
+    let symbols =
+        SymbolicatorRunner::root_dir(fpath).and_then(|pkg_path| symbols_map.get(&pkg_path))?;
+    let fun_id = fun_at(symbols, fpath, pos)?;
+    Some(vec![fun_item(symbols, &fun_id)?])
+}
+
+/// Returns calls of the function represented by a given item, grouped by calling function, found
+/// in all packages for which symbols have been computed.
+pub fn incoming_calls(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    item: &CallHierarchyItem,
+) -> Option<Vec<CallHierarchyIncomingCall>> {
+    let callee = item_fun(symbols_map, item)?;
+
+    // the same call site may be present in call graphs of multiple packages (e.g., if one
+    // package depends on the other), so we collect call sites first to eliminate duplicates
+    let mut callers: BTreeMap<FunId, (&Symbols, BTreeSet<Loc>)> = BTreeMap::new();
+    for symbols in symbols_map.values() {
+        for call in symbols.call_graph.incoming(&callee) {
+            callers
+                .entry(call.caller)
+                .or_insert_with(|| (symbols, BTreeSet::new()))
+                .1
+                .insert(call.loc);
+        }
+    }
+
+    Some(
+        callers
+            .into_iter()
+            .filter_map(|(caller, (symbols, locs))| {
+                Some(CallHierarchyIncomingCall {
+                    from: fun_item(symbols, &caller)?,
+                    from_ranges: locs
+                        .iter()
+                        .filter_map(|loc| symbols.files.lsp_range_opt(loc))
+                        .collect(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Returns calls made by the function represented by a given item, grouped by called function.
+pub fn outgoing_calls(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    item: &CallHierarchyItem,
+) -> Option<Vec<CallHierarchyOutgoingCall>> {
+    let caller = item_fun(symbols_map, item)?;
+    // all call sites in a given function are found in any package whose call graph contains it
+    let symbols = symbols_map
+        .values()
+        .find(|symbols| symbols.call_graph.functions.contains_key(&caller))?;
+
+    let mut callees: BTreeMap<FunId, BTreeSet<Loc>> = BTreeMap::new();
+    for call in symbols.call_graph.outgoing(&caller) {
+        callees.entry(call.callee).or_default().insert(call.loc);
+    }
+
+    Some(
+        callees
+            .into_iter()
+            .filter_map(|(callee, locs)| {
+                Some(CallHierarchyOutgoingCall {
+                    to: fun_item(symbols, &callee)?,
+                    from_ranges: locs
+                        .iter()
+                        .filter_map(|loc| symbols.files.lsp_range_opt(loc))
+                        .collect(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Finds the function represented by a call hierarchy item (whose selection range starts at the
+/// function's name in its definition). The function may be defined in a dependency rather than
+/// in a package for which symbols have been computed, so all packages are searched.
+fn item_fun(symbols_map: &BTreeMap<PathBuf, Symbols>, item: &CallHierarchyItem) -> Option<FunId> {
+    let fpath = item.uri.to_file_path().ok()?;
+    symbols_map
+        .values()
+        .find_map(|symbols| fun_at(symbols, &fpath, item.selection_range.start))
+}
+
+/// Returns the function whose name is located at a given position. If more than one use recorded
+/// for this line covers the position, the narrowest one is picked.
+fn fun_at(symbols: &Symbols, fpath: &Path, pos: Position) -> Option<FunId> {
+    let use_def = symbols
+        .line_uses(fpath, pos.line)
+        .into_iter()
+        .filter(|u| pos.character >= u.col_start && pos.character <= u.col_end)
+        .min_by_key(|u| u.col_end - u.col_start)?;
+    match symbols.def_info(&use_def.def_loc)? {
+        DefInfo::Function(mod_ident, _, _, name, ..) => Some((*mod_ident, *name)),
+        _ => None,
+    }
+}
+
+/// Creates a call hierarchy item for a given function.
+fn fun_item(symbols: &Symbols, fun_id: &FunId) -> Option<CallHierarchyItem> {
+    let (mod_ident, name) = fun_id;
+    let (name_loc, loc) = match symbols.call_graph.functions.get(fun_id) {
+        Some(node) => (node.name_loc, node.loc),
+        None => {
+            // the function is defined in a module whose typed AST was not available when the call
+            // graph was computed, so the best we can do is to point at the function's name
+            let name_loc = symbols
+                .file_mods
+                .values()
+                .flatten()
+                .find(|mod_defs| mod_defs.ident == *mod_ident)
+                .and_then(|mod_defs| mod_defs.functions.get(name))?
+                .name_loc;
+            (name_loc, name_loc)
+        }
+    };
+    let path = symbols.files.file_name_mapping().get(&loc.file_hash())?;
+    Some(CallHierarchyItem {
+        name: name.to_string(),
+        kind: SymbolKind::FUNCTION,
+        tags: None,
+        detail: Some(mod_ident_to_ide_string(
+            mod_ident, /* datatype_name_opt */ None, /* is_access_chain_prefix */ false,
+        )),
+        uri: Url::from_file_path(path).ok()?,
+        range: symbols.files.lsp_range_opt(&loc)?,
+        selection_range: symbols.files.lsp_range_opt(&name_loc)?,
+        data: None,
+    })
+}
@@ -0,0 +1,261 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains code responsible for handling rename requests. Renaming relies on the
+//! symbolicator having recorded every use of a given definition (see `symbols::use_def`), and
+//! works across all modules of all packages for which symbols have been computed in the current
+//! workspace.
+
+use crate::{
+    context::Context,
+    symbols::{Symbols, def_info::DefInfo, runner::SymbolicatorRunner, use_def::UseDef},
+};
+
+use lsp_server::{ErrorCode, Message, Request, Response};
+use lsp_types::{
+    Position, PrepareRenameResponse, Range, RenameParams, TextDocumentPositionParams, TextEdit,
+    WorkspaceEdit,
+};
+use move_core_types::identifier;
+use move_symbol_pool::Symbol;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+/// Handles prepare-rename request of the language server
+pub fn on_prepare_rename_request(context: &Context, request: &Request) {
+    let symbols_map = &context.symbols.lock().unwrap();
+    let parameters = serde_json::from_value::<TextDocumentPositionParams>(request.params.clone())
+        .expect("could not deserialize prepare rename request");
+
+    let fpath = parameters.text_document.uri.to_file_path().unwrap();
+    let result = prepare_rename(symbols_map, &fpath, parameters.position);
+
+    let response = Response::new_ok(request.id.clone(), result);
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send prepare rename response: {:?}", err);
+    }
+}
+
+/// Handles rename request of the language server
+pub fn on_rename_request(context: &Context, request: &Request) {
+    let symbols_map = &context.symbols.lock().unwrap();
+    let parameters = serde_json::from_value::<RenameParams>(request.params.clone())
+        .expect("could not deserialize rename request");
+
+    let fpath = parameters
+        .text_document_position
+        .text_document
+        .uri
+        .to_file_path()
+        .unwrap();
+    let pos = parameters.text_document_position.position;
+
+    let response = match rename(symbols_map, &fpath, pos, &parameters.new_name) {
+        Ok(edit) => Response::new_ok(request.id.clone(), edit),
+        Err(msg) => {
+            eprintln!("rename failed: {msg}");
+            Response::new_err(request.id.clone(), ErrorCode::InvalidRequest as i32, msg)
+        }
+    };
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send rename response: {:?}", err);
+    }
+}
+
+/// Returns the range of the renameable identifier at a given position, along with its current
+/// name, or `None` if there is no such identifier.
+pub fn prepare_rename(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    fpath: &Path,
+    pos: Position,
+) -> Option<PrepareRenameResponse> {
+    let symbols =
+        SymbolicatorRunner::root_dir(fpath).and_then(|pkg_path| symbols_map.get(&pkg_path))?;
+    let use_def = use_def_at(symbols, fpath, pos)?;
+    let old_name = renameable_name(symbols_map, symbols, &use_def).ok()?;
+
+    // aliases refer to the same definition but can't be renamed along with it
+    let fhash = symbols.file_hash(fpath)?;
+    let (_, content) = symbols.files.get(&fhash)?;
+    let use_name = ident_at(&content, pos.line, use_def.col_start, use_def.col_end)?;
+    if use_name != old_name.as_str() {
+        return None;
+    }
+
+    Some(PrepareRenameResponse::RangeWithPlaceholder {
+        range: Range {
+            start: Position::new(pos.line, use_def.col_start),
+            end: Position::new(pos.line, use_def.col_end),
+        },
+        placeholder: old_name.to_string(),
+    })
+}
+
+/// Computes edits renaming the definition of the identifier at a given position, and all of its
+/// uses, to `new_name`. Returns an error message if the identifier can't be renamed, or if
+/// `new_name` is not a valid name for it.
+pub fn rename(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    fpath: &Path,
+    pos: Position,
+    new_name: &str,
+) -> Result<WorkspaceEdit, String> {
+    let symbols = SymbolicatorRunner::root_dir(fpath)
+        .and_then(|pkg_path| symbols_map.get(&pkg_path))
+        .ok_or_else(|| "no symbols available for the file being edited".to_string())?;
+    let use_def = use_def_at(symbols, fpath, pos)
+        .ok_or_else(|| "no identifier to rename at the given position".to_string())?;
+    let old_name = renameable_name(symbols_map, symbols, &use_def)?;
+    validate_new_name(symbols, &use_def, old_name, new_name)?;
+
+    // Collect uses across all packages in the workspace (uses of the same definition in different
+    // packages refer to it via the same location, as file hashes are computed from file contents).
+    let mut uses: BTreeMap<PathBuf, BTreeSet<(u32, u32, u32)>> = BTreeMap::new();
+    for pkg_symbols in symbols_map.values() {
+        let Some(refs) = pkg_symbols.references.get(&use_def.def_loc) else {
+            continue;
+        };
+        for ref_loc in refs {
+            let Some(path) = pkg_symbols.files.file_name_mapping().get(&ref_loc.fhash) else {
+                continue;
+            };
+            let Some((_, content)) = pkg_symbols.files.get(&ref_loc.fhash) else {
+                continue;
+            };
+            // skip aliases (e.g., `use a::m::foo as bar`), which refer to the definition
+            // by a different name
+            let Some(use_name) = ident_at(
+                &content,
+                ref_loc.start.line,
+                ref_loc.start.character,
+                ref_loc.col_end,
+            ) else {
+                continue;
+            };
+            if use_name != old_name.as_str() {
+                continue;
+            }
+            uses.entry(path.clone()).or_default().insert((
+                ref_loc.start.line,
+                ref_loc.start.character,
+                ref_loc.col_end,
+            ));
+        }
+    }
+
+    let mut changes = HashMap::new();
+    for (path, locs) in uses {
+        let Ok(uri) = Url::from_file_path(&path) else {
+            continue;
+        };
+        let edits = locs
+            .into_iter()
+            .map(|(line, col_start, col_end)| TextEdit {
+                range: Range {
+                    start: Position::new(line, col_start),
+                    end: Position::new(line, col_end),
+                },
+                new_text: new_name.to_string(),
+            })
+            .collect::<Vec<_>>();
+        changes.insert(uri, edits);
+    }
+
+    Ok(WorkspaceEdit::new(changes))
+}
+
+/// Finds the use (or definition) of an identifier at a given position. If more than one use
+/// recorded for this line covers the position, the narrowest one is picked.
+fn use_def_at(symbols: &Symbols, fpath: &Path, pos: Position) -> Option<UseDef> {
+    symbols
+        .line_uses(fpath, pos.line)
+        .into_iter()
+        .filter(|u| pos.character >= u.col_start && pos.character <= u.col_end)
+        .min_by_key(|u| u.col_end - u.col_start)
+}
+
+/// Returns the name of the definition the given use refers to, if this definition can be renamed,
+/// and an error message otherwise.
+fn renameable_name(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    symbols: &Symbols,
+    use_def: &UseDef,
+) -> Result<Symbol, String> {
+    let name = match symbols.def_info(&use_def.def_loc) {
+        Some(DefInfo::Function(_, _, _, name, ..)) => *name,
+        Some(DefInfo::Struct(_, name, ..)) => *name,
+        Some(DefInfo::Enum(_, name, ..)) => *name,
+        Some(DefInfo::Field(_, _, name, ..)) => *name,
+        Some(DefInfo::Local(name, ..)) => *name,
+        Some(DefInfo::Const(_, name, ..)) => *name,
+        Some(DefInfo::Module(..)) => return Err("modules can't be renamed".to_string()),
+        Some(DefInfo::Variant(..)) => return Err("enum variants can't be renamed".to_string()),
+        Some(DefInfo::Type(_)) | None => {
+            return Err("the identifier at the given position can't be renamed".to_string());
+        }
+    };
+
+    // only definitions located in one of the packages in the workspace can be renamed (and not,
+    // for example, definitions in dependencies downloaded from a remote location)
+    let def_path = symbols.files.file_path(&use_def.def_loc.file_hash());
+    if !symbols_map
+        .keys()
+        .any(|pkg_path| def_path.starts_with(pkg_path))
+    {
+        return Err(format!(
+            "'{name}' is defined outside of the workspace and can't be renamed"
+        ));
+    }
+
+    Ok(name)
+}
+
+/// Checks if `new_name` is a valid replacement of `old_name` for a given definition.
+fn validate_new_name(
+    symbols: &Symbols,
+    use_def: &UseDef,
+    old_name: Symbol,
+    new_name: &str,
+) -> Result<(), String> {
+    // macro parameters must keep their `$` prefix (and other identifiers can't acquire one)
+    let (old_base, new_base) = match (old_name.strip_prefix('$'), new_name.strip_prefix('$')) {
+        (Some(old_base), Some(new_base)) => (old_base, new_base),
+        (None, None) => (old_name.as_str(), new_name),
+        _ => {
+            return Err(format!(
+                "'{new_name}' is not a valid replacement for '{old_name}'"
+            ));
+        }
+    };
+    if !identifier::is_valid(new_base) {
+        return Err(format!("'{new_name}' is not a valid Move identifier"));
+    }
+    let needs_upper = matches!(symbols.def_info(&use_def.def_loc), Some(DefInfo::Const(..)));
+    if needs_upper && !new_base.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return Err(format!(
+            "'{new_name}' is not a valid constant name (must start with 'A'..'Z')"
+        ));
+    }
+    if old_base == new_base {
+        return Err(format!("'{old_name}' already has this name"));
+    }
+    Ok(())
+}
+
+/// Returns the identifier located on a given line between two columns.
+fn ident_at(content: &str, line: u32, col_start: u32, col_end: u32) -> Option<&str> {
+    if col_end <= col_start {
+        return None;
+    }
+    let line = content.lines().nth(line as usize)?;
+    let mut indices = line
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(line.len()));
+    let start = indices.nth(col_start as usize)?;
+    let end = indices.nth((col_end - col_start - 1) as usize)?;
+    Some(&line[start..end])
+}
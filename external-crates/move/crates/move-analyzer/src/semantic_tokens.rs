@@ -0,0 +1,250 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains code responsible for handling semantic tokens requests, which allow the
+//! IDE to highlight identifiers based on what they refer to rather than on syntax alone. Tokens
+//! are computed from the symbols recorded for each identifier in a file, with the exception of
+//! abilities, which are not represented by symbols and are instead found by lexing the file.
+
+use crate::{
+    context::Context,
+    symbols::{Symbols, def_info::DefInfo, def_info::FunType, runner::SymbolicatorRunner},
+};
+
+use lsp_server::{Message, Request, Response};
+use lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensLegend,
+    SemanticTokensParams, SemanticTokensResult,
+};
+use move_command_line_common::files::FileHash;
+use move_compiler::{
+    editions::Edition,
+    naming::ast::Type_,
+    parser::lexer::{Lexer, Tok},
+};
+use std::path::{Path, PathBuf};
+
+/// Token type for abilities (not one of the predefined LSP token types)
+pub const ABILITY: SemanticTokenType = SemanticTokenType::new("ability");
+
+/// Token modifier for mutable local variables (not one of the predefined LSP token modifiers)
+pub const MUTABLE: SemanticTokenModifier = SemanticTokenModifier::new("mutable");
+
+/// Token types, in the order of their indices in the legend
+pub const TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::ENUM,
+    SemanticTokenType::ENUM_MEMBER,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::MACRO,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::TYPE_PARAMETER,
+    ABILITY,
+];
+
+/// Token modifiers, in the order of their bits in the legend
+pub const TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::READONLY,
+    MUTABLE,
+];
+
+const ABILITIES: &[&str] = &["copy", "drop", "store", "key"];
+
+/// A token before it is encoded relative to the previous one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct AbsToken {
+    line: u32,
+    col: u32,
+    len: u32,
+    token_type: u32,
+    modifiers: u32,
+}
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+/// Handles semantic tokens request of the language server
+pub fn on_semantic_tokens_full_request(context: &Context, request: &Request) {
+    let parameters = serde_json::from_value::<SemanticTokensParams>(request.params.clone())
+        .expect("could not deserialize semantic tokens request");
+
+    let fpath = parameters.text_document.uri.to_file_path().unwrap();
+    let tokens = semantic_tokens(context, fpath).unwrap_or_default();
+
+    let response = Response::new_ok(
+        request.id.clone(),
+        SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data: tokens,
+        }),
+    );
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send semantic tokens response: {:?}", err);
+    }
+}
+
+fn semantic_tokens(context: &Context, fpath: PathBuf) -> Option<Vec<SemanticToken>> {
+    let symbols_map = &context.symbols.lock().ok()?;
+    let symbols =
+        SymbolicatorRunner::root_dir(&fpath).and_then(|pkg_path| symbols_map.get(&pkg_path))?;
+    semantic_tokens_internal(symbols, &fpath)
+}
+
+/// Computes semantic tokens for a given file. Tokens are computed for the version of the file
+/// that symbols were computed for.
+pub fn semantic_tokens_internal(symbols: &Symbols, fpath: &Path) -> Option<Vec<SemanticToken>> {
+    let fhash = symbols.file_hash(fpath)?;
+    let (_, content) = symbols.files.get(&fhash)?;
+
+    let mut tokens = vec![];
+    for (line, uses) in symbols.file_use_defs.get(fpath)?.lines() {
+        for u in uses {
+            let Some((token_type, mut modifiers)) = symbols.def_info(&u.def_loc).and_then(classify)
+            else {
+                continue;
+            };
+            let is_decl = u.def_loc.file_hash() == fhash
+                && symbols
+                    .files
+                    .start_position_opt(&u.def_loc)
+                    .is_some_and(|p| {
+                        p.line_offset() as u32 == *line && p.column_offset() as u32 == u.col_start
+                    });
+            if is_decl {
+                modifiers |= modifier_bit(&SemanticTokenModifier::DECLARATION);
+            }
+            tokens.push(AbsToken {
+                line: *line,
+                col: u.col_start,
+                len: u.col_end - u.col_start,
+                token_type,
+                modifiers,
+            });
+        }
+    }
+    tokens.extend(ability_tokens(&content, fhash));
+    tokens.sort();
+
+    // encode tokens relative to each other, skipping overlapping ones (which the protocol does
+    // not allow)
+    let mut data = vec![];
+    let mut prev_line = 0;
+    let mut prev_col = 0;
+    let mut prev_end = None;
+    for t in tokens {
+        if prev_end.is_some_and(|(line, col)| line == t.line && t.col < col) {
+            continue;
+        }
+        let delta_line = t.line - prev_line;
+        let delta_start = if delta_line == 0 {
+            t.col - prev_col
+        } else {
+            t.col
+        };
+        data.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: t.len,
+            token_type: t.token_type,
+            token_modifiers_bitset: t.modifiers,
+        });
+        prev_line = t.line;
+        prev_col = t.col;
+        prev_end = Some((t.line, t.col + t.len));
+    }
+    Some(data)
+}
+
+/// Returns the token type and modifiers for an identifier referring to a given definition.
+fn classify(def_info: &DefInfo) -> Option<(u32, u32)> {
+    let (token_type, modifiers) = match def_info {
+        DefInfo::Module(..) => (SemanticTokenType::NAMESPACE, 0),
+        DefInfo::Struct(..) => (SemanticTokenType::STRUCT, 0),
+        DefInfo::Enum(..) => (SemanticTokenType::ENUM, 0),
+        DefInfo::Variant(..) => (SemanticTokenType::ENUM_MEMBER, 0),
+        DefInfo::Field(..) => (SemanticTokenType::PROPERTY, 0),
+        DefInfo::Function(_, _, FunType::Macro, ..) => (SemanticTokenType::MACRO, 0),
+        DefInfo::Function(..) => (SemanticTokenType::FUNCTION, 0),
+        DefInfo::Local(_, _, _, is_mut, _) => (
+            SemanticTokenType::VARIABLE,
+            if *is_mut { modifier_bit(&MUTABLE) } else { 0 },
+        ),
+        DefInfo::Const(..) => (
+            SemanticTokenType::VARIABLE,
+            modifier_bit(&SemanticTokenModifier::READONLY),
+        ),
+        DefInfo::Type(sp!(_, Type_::Param(_))) => (SemanticTokenType::TYPE_PARAMETER, 0),
+        DefInfo::Type(_) => return None,
+    };
+    Some((type_idx(&token_type), modifiers))
+}
+
+/// Finds abilities in a file's content, both in ability declarations (`has key, store`) and in
+/// type parameter constraints (`T: copy + drop`).
+fn ability_tokens(content: &str, fhash: FileHash) -> Vec<AbsToken> {
+    let mut lexer = Lexer::new(content, fhash, Edition::E2024);
+    let mut toks = vec![];
+    if lexer.advance().is_err() {
+        return vec![];
+    }
+    while lexer.peek() != Tok::EOF {
+        toks.push((lexer.peek(), lexer.content(), lexer.start_loc()));
+        if lexer.advance().is_err() {
+            break;
+        }
+    }
+
+    let line_starts = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect::<Vec<_>>();
+
+    let mut tokens = vec![];
+    let mut in_has_list = false;
+    for (idx, (tok, text, start)) in toks.iter().enumerate() {
+        let is_ability_name =
+            matches!(tok, Tok::Identifier | Tok::Copy) && ABILITIES.contains(text);
+        let prev = idx.checked_sub(1).map(|i| toks[i].0);
+        let next = toks.get(idx + 1).map(|t| t.0);
+
+        let is_ability = if in_has_list {
+            is_ability_name
+        } else {
+            is_ability_name
+                && matches!(prev, Some(Tok::Colon | Tok::Plus))
+                && matches!(
+                    next,
+                    Some(Tok::Comma | Tok::Plus | Tok::Greater | Tok::GreaterGreater)
+                )
+        };
+        in_has_list = (*tok == Tok::Identifier && *text == "has")
+            || (in_has_list && (is_ability_name || *tok == Tok::Comma));
+
+        if is_ability {
+            let line = line_starts.partition_point(|l| *l <= *start) - 1;
+            let col = content[line_starts[line]..*start].chars().count();
+            tokens.push(AbsToken {
+                line: line as u32,
+                col: col as u32,
+                len: text.len() as u32,
+                token_type: type_idx(&ABILITY),
+                modifiers: 0,
+            });
+        }
+    }
+    tokens
+}
+
+fn type_idx(token_type: &SemanticTokenType) -> u32 {
+    TOKEN_TYPES.iter().position(|t| t == token_type).unwrap() as u32
+}
+
+fn modifier_bit(modifier: &SemanticTokenModifier) -> u32 {
+    1 << TOKEN_MODIFIERS.iter().position(|m| m == modifier).unwrap()
+}
@@ -0,0 +1,285 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains code responsible for handling signature help requests, which display the
+//! signature of the function being called (and highlight the parameter being supplied) while the
+//! user is typing a call.
+//!
+//! As code is typically incomplete (and does not compile) while a call is being typed, the call
+//! itself is located by scanning the text of the file backwards from the cursor, and only the
+//! called function's name is resolved using symbols computed for the last version of the code
+//! that compiled.
+
+use crate::{
+    context::Context,
+    symbols::{
+        Symbols, def_info::DefInfo, ide_strings::type_to_ide_string, requests::def_info_doc_string,
+        runner::SymbolicatorRunner,
+    },
+};
+
+use lsp_server::{Message, Request, Response};
+use lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, Position,
+    SignatureHelp, SignatureHelpParams, SignatureInformation,
+};
+use std::path::Path;
+use vfs::VfsPath;
+
+/// Information about the call surrounding the cursor, extracted from the source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    /// Name of the function being called
+    pub name: String,
+    /// Module qualifying the function name (if any), as in `module::name(...)`
+    pub module: Option<String>,
+    /// Position where the function name starts
+    pub name_start: Position,
+    /// Is it a method call (`receiver.name(...)`)?
+    pub dot_call: bool,
+    /// Index of the argument the cursor is located at
+    pub arg_idx: u32,
+}
+
+/// Handles signature help request of the language server
+pub fn on_signature_help_request(context: &Context, request: &Request, ide_files_root: VfsPath) {
+    let parameters = serde_json::from_value::<SignatureHelpParams>(request.params.clone())
+        .expect("could not deserialize signature help request");
+
+    let fpath = parameters
+        .text_document_position_params
+        .text_document
+        .uri
+        .to_file_path()
+        .unwrap();
+    let pos = parameters.text_document_position_params.position;
+
+    let help = file_content(&ide_files_root, &fpath).and_then(|content| {
+        let symbols_map = context.symbols.lock().ok()?;
+        let symbols =
+            SymbolicatorRunner::root_dir(&fpath).and_then(|pkg_path| symbols_map.get(&pkg_path))?;
+        signature_help(symbols, &fpath, &content, pos)
+    });
+
+    let response = Response::new_ok(request.id.clone(), help);
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send signature help response: {:?}", err);
+    }
+}
+
+/// Computes signature help for the call surrounding a given position in the file.
+pub fn signature_help(
+    symbols: &Symbols,
+    fpath: &Path,
+    content: &str,
+    pos: Position,
+) -> Option<SignatureHelp> {
+    let call = call_site(content, pos)?;
+    let def_info = resolve_function(symbols, fpath, &call)?;
+    let DefInfo::Function(_, _, _, name, type_args, arg_names, arg_types, ret_type, _) = def_info
+    else {
+        return None;
+    };
+
+    let mut label = format!("fun {name}");
+    if !type_args.is_empty() {
+        let type_args = type_args
+            .iter()
+            .map(|t| type_to_ide_string(t, /* verbose */ false))
+            .collect::<Vec<_>>()
+            .join(", ");
+        label.push_str(&format!("<{type_args}>"));
+    }
+    label.push('(');
+    let mut parameters = vec![];
+    for (idx, (arg_name, arg_type)) in arg_names.iter().zip(arg_types).enumerate() {
+        if idx > 0 {
+            label.push_str(", ");
+        }
+        let start = label.chars().count() as u32;
+        label.push_str(&format!(
+            "{}: {}",
+            arg_name.value,
+            type_to_ide_string(arg_type, /* verbose */ false)
+        ));
+        let end = label.chars().count() as u32;
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: None,
+        });
+    }
+    label.push(')');
+    let ret = type_to_ide_string(ret_type, /* verbose */ false);
+    if ret != "()" {
+        label.push_str(&format!(": {ret}"));
+    }
+
+    // the receiver of a method call is its first argument
+    let active_parameter = call.arg_idx + if call.dot_call { 1 } else { 0 };
+    let documentation = def_info_doc_string(def_info).map(|doc| {
+        Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: doc,
+        })
+    });
+
+    Some(SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation,
+            parameters: Some(parameters),
+            active_parameter: Some(active_parameter),
+        }],
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    })
+}
+
+/// Finds the call surrounding a given position by scanning the text backwards from this position
+/// to the unmatched opening parenthesis of the call's argument list, counting the arguments along
+/// the way, and then extracting the name of the called function.
+pub fn call_site(content: &str, pos: Position) -> Option<CallSite> {
+    // positions of all characters preceding the cursor
+    let mut chars = vec![];
+    for (line_idx, line) in content.lines().enumerate().take(pos.line as usize + 1) {
+        let line_idx = line_idx as u32;
+        for (col, c) in line.chars().enumerate() {
+            if line_idx == pos.line && col as u32 >= pos.character {
+                break;
+            }
+            chars.push((Position::new(line_idx, col as u32), c));
+        }
+        chars.push((Position::new(line_idx, line.chars().count() as u32), '\n'));
+    }
+
+    let mut idx = chars.len();
+    let mut depth = 0;
+    let mut arg_idx = 0;
+    loop {
+        idx = idx.checked_sub(1)?;
+        match chars[idx].1 {
+            ')' | ']' | '}' => depth += 1,
+            '(' if depth == 0 => break,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            // the cursor is not inside an argument list
+            '[' | '{' => return None,
+            ';' if depth == 0 => return None,
+            ',' if depth == 0 => arg_idx += 1,
+            _ => (),
+        }
+    }
+
+    idx = skip_whitespace_backwards(&chars, idx);
+    // explicit type arguments, as in `name<T>(...)`
+    if idx > 0 && chars[idx - 1].1 == '>' {
+        let mut depth = 0;
+        loop {
+            idx = idx.checked_sub(1)?;
+            match chars[idx].1 {
+                '>' => depth += 1,
+                '<' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => (),
+            }
+        }
+        idx = skip_whitespace_backwards(&chars, idx);
+    }
+    // macro calls, as in `name!(...)`
+    if idx > 0 && chars[idx - 1].1 == '!' {
+        idx -= 1;
+    }
+
+    let name_end = idx;
+    while idx > 0 && is_ident_char(chars[idx - 1].1) {
+        idx -= 1;
+    }
+    if idx == name_end || chars[idx].1.is_ascii_digit() {
+        return None;
+    }
+    let name = chars[idx..name_end]
+        .iter()
+        .map(|(_, c)| c)
+        .collect::<String>();
+    let name_start = chars[idx].0;
+
+    let mut module = None;
+    let mut dot_call = false;
+    if idx > 0 && chars[idx - 1].1 == '.' {
+        dot_call = true;
+    } else if idx > 1 && chars[idx - 1].1 == ':' && chars[idx - 2].1 == ':' {
+        let mod_end = idx - 2;
+        let mut mod_start = mod_end;
+        while mod_start > 0 && is_ident_char(chars[mod_start - 1].1) {
+            mod_start -= 1;
+        }
+        if mod_start < mod_end {
+            module = Some(chars[mod_start..mod_end].iter().map(|(_, c)| c).collect());
+        }
+    }
+
+    Some(CallSite {
+        name,
+        module,
+        name_start,
+        dot_call,
+        arg_idx,
+    })
+}
+
+/// Finds information about the function called at a given call site, either using the symbol
+/// recorded for the function name's location, or (if the symbols are out of date) by looking for
+/// a function with this name in the modules of the current file or, if the call is qualified with
+/// a module name, in the module of this name.
+fn resolve_function<'a>(
+    symbols: &'a Symbols,
+    fpath: &Path,
+    call: &CallSite,
+) -> Option<&'a DefInfo> {
+    let recorded = symbols
+        .line_uses(fpath, call.name_start.line)
+        .into_iter()
+        .find(|u| u.col_start == call.name_start.character)
+        .and_then(|u| symbols.def_info(&u.def_loc));
+    if let Some(def_info @ DefInfo::Function(..)) = recorded {
+        return Some(def_info);
+    }
+
+    let name = call.name.as_str().into();
+    let candidate_mods = match &call.module {
+        Some(module) => symbols
+            .file_mods
+            .values()
+            .flatten()
+            .filter(|m| m.ident.module.to_string() == *module)
+            .collect::<Vec<_>>(),
+        None => symbols.file_mods.get(fpath)?.iter().collect(),
+    };
+    candidate_mods.into_iter().find_map(|mod_defs| {
+        let fun_def = mod_defs.functions.get(&name)?;
+        symbols.def_info(&fun_def.name_loc)
+    })
+}
+
+fn skip_whitespace_backwards(chars: &[(Position, char)], mut idx: usize) -> usize {
+    while idx > 0 && chars[idx - 1].1.is_whitespace() {
+        idx -= 1;
+    }
+    idx
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Returns the current content of a file, as seen by the IDE.
+fn file_content(ide_files_root: &VfsPath, fpath: &Path) -> Option<String> {
+    ide_files_root
+        .join(fpath.to_string_lossy())
+        .ok()
+        .and_then(|p| p.read_to_string().ok())
+        .or_else(|| std::fs::read_to_string(fpath).ok())
+}
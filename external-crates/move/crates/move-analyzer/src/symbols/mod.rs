@@ -53,8 +53,8 @@
 
 use crate::{
     analysis::{
-        DefMap, find_datatype, parsing_analysis::parsing_mod_def_to_map_key, run_parsing_analysis,
-        run_typing_analysis,
+        DefMap, call_graph::CallGraph, find_datatype, parsing_analysis::parsing_mod_def_to_map_key,
+        run_parsing_analysis, run_typing_analysis,
     },
     compiler_info::CompilerInfo,
     symbols::{
@@ -72,7 +72,7 @@ use crate::{
 };
 
 use anyhow::Result;
-use lsp_types::{Diagnostic, Position};
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position};
 use std::{
     collections::{BTreeMap, BTreeSet},
     path::{Path, PathBuf},
@@ -80,7 +80,7 @@ use std::{
     time::Instant,
     vec,
 };
-use vfs::VfsPath;
+use vfs::{VfsPath, impls::memory::MemoryFS};
 
 use move_command_line_common::files::FileHash;
 use move_compiler::{
@@ -125,6 +125,8 @@ pub struct Symbols {
     pub compiler_info: CompilerInfo,
     /// Cursor information gathered up during analysis
     pub cursor_context: Option<CursorContext>,
+    /// Calls between functions in the package (and its dependencies, if available)
+    pub call_graph: CallGraph,
 }
 
 /// Information about field order in structs and enums needed for auto-completion
@@ -405,6 +407,8 @@ pub fn compute_symbols_typed_program(
             None
         };
 
+    let call_graph = compute_call_graph(&compiled_pkg_info);
+
     let mut file_mods: FileModules = BTreeMap::new();
     for d in computation_data.mod_outer_defs.into_values() {
         let path = compiled_pkg_info.mapped_files.file_path(&d.fhash.clone());
@@ -420,12 +424,65 @@ pub fn compute_symbols_typed_program(
             files: compiled_pkg_info.mapped_files,
             compiler_info: compiled_pkg_info.compiler_info.unwrap(),
             cursor_context,
+            call_graph,
         },
         cacheable_symbols_data_opt,
         compiled_pkg_info.program,
     )
 }
 
+/// Computes the call graph for the main program and, if available, for its dependencies
+pub fn compute_call_graph(compiled_pkg_info: &CompiledPkgInfo) -> CallGraph {
+    let Some(compiler_info) = &compiled_pkg_info.compiler_info else {
+        return CallGraph::new();
+    };
+    let mut modules = vec![&compiled_pkg_info.program.typed_modules];
+    if let Some(cached_deps) = &compiled_pkg_info.cached_deps {
+        modules.push(&cached_deps.program_deps.typing.modules);
+    }
+    CallGraph::build(&modules, compiler_info)
+}
+
+/// Compiles the package at a given path and computes its call graph, outside of the IDE (e.g., to
+/// export it from the command line). Returns the call graph along with the information needed to
+/// translate its locations into file positions, or an error listing compilation errors if the
+/// package could not be compiled.
+pub fn get_call_graph(
+    pkg_path: &Path,
+    implicit_deps: Dependencies,
+) -> Result<(CallGraph, MappedFiles)> {
+    let (compiled_pkg_info_opt, diags) = get_compiled_pkg(
+        Arc::new(Mutex::new(BTreeMap::new())),
+        MemoryFS::new().into(),
+        pkg_path,
+        None,
+        LintLevel::None,
+        implicit_deps,
+    )?;
+    let Some(compiled_pkg_info) = compiled_pkg_info_opt else {
+        let errors = diags
+            .iter()
+            .flat_map(|(fpath, file_diags)| {
+                file_diags
+                    .iter()
+                    .filter(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+                    .map(move |d| {
+                        format!(
+                            "{}:{}:{}: {}",
+                            fpath.display(),
+                            d.range.start.line + 1,
+                            d.range.start.character + 1,
+                            d.message
+                        )
+                    })
+            })
+            .collect::<Vec<_>>();
+        anyhow::bail!("Failed to compile package:\n{}", errors.join("\n"));
+    };
+    let call_graph = compute_call_graph(&compiled_pkg_info);
+    Ok((call_graph, compiled_pkg_info.mapped_files))
+}
+
 // Given use-defs for a the main program or dependencies, update the per-file
 // use-def map
 fn update_file_use_defs(
@@ -598,6 +655,7 @@ pub fn empty_symbols() -> Symbols {
         files: MappedFiles::empty(),
         compiler_info: CompilerInfo::new(),
         cursor_context: None,
+        call_graph: CallGraph::new(),
     }
 }
 
@@ -12,14 +12,14 @@ use crate::{
         runner::SymbolicatorRunner,
         use_def::UseDef,
     },
-    utils::lsp_position_to_loc,
+    utils::{ignored_function, lsp_position_to_loc},
 };
 
 use lsp_server::{Message, Request, RequestId, Response};
 use lsp_types::{
     DocumentSymbol, DocumentSymbolParams, GotoDefinitionParams, Hover, HoverContents, HoverParams,
-    Location, MarkupContent, MarkupKind, Position, Range, ReferenceParams, SymbolKind,
-    request::GotoTypeDefinitionParams,
+    Location, MarkupContent, MarkupKind, Position, Range, ReferenceParams, SymbolInformation,
+    SymbolKind, WorkspaceSymbolParams, WorkspaceSymbolResponse, request::GotoTypeDefinitionParams,
 };
 use std::{
     collections::{BTreeMap, BTreeSet},
@@ -28,7 +28,7 @@ use std::{
 };
 use url::Url;
 
-use move_compiler::naming::ast::Type_;
+use move_compiler::{naming::ast::Type_, shared::Identifier};
 use move_ir_types::location::*;
 
 /// Handles go-to-def request of the language server
@@ -360,6 +360,111 @@ pub fn on_document_symbol_request(context: &Context, request: &Request) {
     }
 }
 
+/// Handles workspace symbol request of the language server
+#[allow(deprecated)]
+pub fn on_workspace_symbol_request(context: &Context, request: &Request) {
+    let symbols_map = &context.symbols.lock().unwrap();
+    let parameters = serde_json::from_value::<WorkspaceSymbolParams>(request.params.clone())
+        .expect("could not deserialize workspace symbol request");
+
+    let symbols = workspace_symbols(symbols_map, &parameters.query);
+    eprintln!(
+        "on_workspace_symbol_request: {} symbols found for '{}'",
+        symbols.len(),
+        parameters.query
+    );
+    let response = Response::new_ok(request.id.clone(), WorkspaceSymbolResponse::Flat(symbols));
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send workspace symbol response: {:?}", err);
+    }
+}
+
+/// Finds module-level definitions (modules, constants, structs, enums and functions) whose names
+/// match `query` in all packages in the workspace (including their dependencies).
+#[allow(deprecated)]
+pub fn workspace_symbols(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    query: &str,
+) -> Vec<SymbolInformation> {
+    // the same module may be present in multiple packages' symbols (e.g., as a dependency)
+    let mut seen = BTreeSet::new();
+    let mut result = vec![];
+    for symbols in symbols_map.values() {
+        for (fpath, mods) in &symbols.file_mods {
+            let Ok(uri) = Url::from_file_path(fpath) else {
+                continue;
+            };
+            for mod_def in mods {
+                let container = mod_def.ident.module.to_string();
+                let mut candidates = vec![(
+                    mod_def.ident.module.value(),
+                    mod_def.name_loc,
+                    SymbolKind::MODULE,
+                )];
+                candidates.extend(
+                    mod_def
+                        .constants
+                        .iter()
+                        .map(|(n, d)| (*n, d.name_loc, SymbolKind::CONSTANT)),
+                );
+                candidates.extend(
+                    mod_def
+                        .structs
+                        .iter()
+                        .map(|(n, d)| (*n, d.name_loc, SymbolKind::STRUCT)),
+                );
+                candidates.extend(
+                    mod_def
+                        .enums
+                        .iter()
+                        .map(|(n, d)| (*n, d.name_loc, SymbolKind::ENUM)),
+                );
+                candidates.extend(
+                    mod_def
+                        .functions
+                        .iter()
+                        .filter(|(n, _)| !ignored_function(**n))
+                        .map(|(n, d)| (*n, d.name_loc, SymbolKind::FUNCTION)),
+                );
+
+                for (name, loc, kind) in candidates {
+                    if !fuzzy_match(query, &name) || !seen.insert(loc) {
+                        continue;
+                    }
+                    let Some(range) = symbols.files.lsp_range_opt(&loc) else {
+                        continue;
+                    };
+                    result.push(SymbolInformation {
+                        name: name.to_string(),
+                        kind,
+                        tags: None,
+                        deprecated: None,
+                        location: Location {
+                            uri: uri.clone(),
+                            range,
+                        },
+                        container_name: if kind == SymbolKind::MODULE {
+                            None
+                        } else {
+                            Some(container.clone())
+                        },
+                    });
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Checks if all characters of `query` appear in `name` in the same order (ignoring case).
+fn fuzzy_match(query: &str, name: &str) -> bool {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|q| name_chars.any(|n| n == q))
+}
+
 /// Helper function that takes a DefInfo, checks if it represents
 /// a enum arm variable defintion, and if need be converts it
 /// to the one that represents an enum guard variable (which
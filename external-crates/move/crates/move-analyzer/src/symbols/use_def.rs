@@ -227,6 +227,11 @@ impl UseDefMap {
         self.0.get(&key).cloned()
     }
 
+    /// Iterates over lines (in order) and the use-defs on each line.
+    pub fn lines(&self) -> impl Iterator<Item = (&u32, &BTreeSet<UseDef>)> {
+        self.0.iter()
+    }
+
     pub fn elements(self) -> BTreeMap<u32, BTreeSet<UseDef>> {
         self.0
     }
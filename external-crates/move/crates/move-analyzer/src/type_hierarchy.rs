@@ -0,0 +1,292 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains code responsible for handling type hierarchy requests. Move has no
+//! subtyping, so the hierarchy is one of containment: the supertypes of a datatype (struct or
+//! enum) are the datatypes whose fields mention it, and its subtypes are the datatypes mentioned
+//! in its own fields (including as type arguments, e.g., `vector<Foo>` or `Option<Foo>`). Each
+//! item's detail also shows the datatype's abilities, as they constrain how the datatype can be
+//! contained by others (e.g., a `store` field is needed in a `key` struct).
+
+use crate::{
+    context::Context,
+    symbols::{
+        Symbols,
+        def_info::DefInfo,
+        ide_strings::{abilities_to_ide_string, mod_ident_to_ide_string},
+        mod_defs::{MemberDef, MemberDefInfo},
+        runner::SymbolicatorRunner,
+    },
+};
+
+use lsp_server::{Message, Request, Response};
+use lsp_types::{
+    Position, SymbolKind, TypeHierarchyItem, TypeHierarchyPrepareParams,
+    TypeHierarchySubtypesParams, TypeHierarchySupertypesParams,
+};
+use move_compiler::{
+    expansion::ast::ModuleIdent_,
+    naming::ast::{Type, Type_, TypeName_},
+    shared::Identifier,
+};
+use move_symbol_pool::Symbol;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+/// Datatype (struct or enum) identified by its defining module and name
+type DatatypeId = (ModuleIdent_, Symbol);
+
+/// Handles prepare type hierarchy request of the language server
+pub fn on_prepare_type_hierarchy_request(context: &Context, request: &Request) {
+    let symbols_map = &context.symbols.lock().unwrap();
+    let parameters = serde_json::from_value::<TypeHierarchyPrepareParams>(request.params.clone())
+        .expect("could not deserialize prepare type hierarchy request");
+
+    let fpath = parameters
+        .text_document_position_params
+        .text_document
+        .uri
+        .to_file_path()
+        .unwrap();
+    let pos = parameters.text_document_position_params.position;
+    let items = prepare_type_hierarchy(symbols_map, &fpath, pos);
+
+    let response = Response::new_ok(request.id.clone(), items);
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send prepare type hierarchy response: {:?}", err);
+    }
+}
+
+/// Handles supertypes request of the language server
+pub fn on_supertypes_request(context: &Context, request: &Request) {
+    let symbols_map = &context.symbols.lock().unwrap();
+    let parameters =
+        serde_json::from_value::<TypeHierarchySupertypesParams>(request.params.clone())
+            .expect("could not deserialize supertypes request");
+
+    let items = supertypes(symbols_map, &parameters.item);
+
+    let response = Response::new_ok(request.id.clone(), items);
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send supertypes response: {:?}", err);
+    }
+}
+
+/// Handles subtypes request of the language server
+pub fn on_subtypes_request(context: &Context, request: &Request) {
+    let symbols_map = &context.symbols.lock().unwrap();
+    let parameters = serde_json::from_value::<TypeHierarchySubtypesParams>(request.params.clone())
+        .expect("could not deserialize subtypes request");
+
+    let items = subtypes(symbols_map, &parameters.item);
+
+    let response = Response::new_ok(request.id.clone(), items);
+    if let Err(err) = context.connection.sender.send(Message::Response(response)) {
+        eprintln!("could not send subtypes response: {:?}", err);
+    }
+}
+
+/// Returns the type hierarchy item for the datatype whose name (at either its definition or at
+/// one of its uses) is located at a given position. Variant names resolve to their enum.
+pub fn prepare_type_hierarchy(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    fpath: &Path,
+    pos: Position,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let symbols =
+        SymbolicatorRunner::root_dir(fpath).and_then(|pkg_path| symbols_map.get(&pkg_path))?;
+    let datatype_id = datatype_at(symbols, fpath, pos)?;
+    Some(vec![datatype_item(symbols, &datatype_id)?])
+}
+
+/// Returns datatypes whose fields mention the datatype represented by a given item, found in all
+/// packages for which symbols have been computed.
+pub fn supertypes(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    item: &TypeHierarchyItem,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let contained = item_datatype(symbols_map, item)?;
+
+    // the same datatype may be present in symbols of multiple packages (e.g., if one package
+    // depends on the other), so we collect containing datatypes first to eliminate duplicates
+    let mut containers: BTreeMap<DatatypeId, &Symbols> = BTreeMap::new();
+    for symbols in symbols_map.values() {
+        for mod_defs in symbols.file_mods.values().flatten() {
+            for name in mod_defs.structs.keys().chain(mod_defs.enums.keys()) {
+                let datatype_id = (mod_defs.ident, *name);
+                if !containers.contains_key(&datatype_id)
+                    && field_datatypes(symbols, &datatype_id).contains(&contained)
+                {
+                    containers.insert(datatype_id, symbols);
+                }
+            }
+        }
+    }
+
+    Some(
+        containers
+            .into_iter()
+            .filter_map(|(datatype_id, symbols)| datatype_item(symbols, &datatype_id))
+            .collect(),
+    )
+}
+
+/// Returns datatypes mentioned in the fields of the datatype represented by a given item.
+pub fn subtypes(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    item: &TypeHierarchyItem,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let container = item_datatype(symbols_map, item)?;
+    // fields of a given datatype are the same in any package whose symbols contain it
+    let symbols = symbols_map
+        .values()
+        .find(|symbols| datatype_def(symbols, &container).is_some())?;
+
+    Some(
+        field_datatypes(symbols, &container)
+            .iter()
+            .filter_map(|datatype_id| {
+                symbols_map
+                    .values()
+                    .find_map(|symbols| datatype_item(symbols, datatype_id))
+            })
+            .collect(),
+    )
+}
+
+/// Finds the datatype represented by a type hierarchy item (whose selection range starts at the
+/// datatype's name in its definition). The datatype may be defined in a dependency rather than
+/// in a package for which symbols have been computed, so all packages are searched.
+fn item_datatype(
+    symbols_map: &BTreeMap<PathBuf, Symbols>,
+    item: &TypeHierarchyItem,
+) -> Option<DatatypeId> {
+    let fpath = item.uri.to_file_path().ok()?;
+    symbols_map
+        .values()
+        .find_map(|symbols| datatype_at(symbols, &fpath, item.selection_range.start))
+}
+
+/// Returns the datatype whose name is located at a given position. If more than one use recorded
+/// for this line covers the position, the narrowest one is picked.
+fn datatype_at(symbols: &Symbols, fpath: &Path, pos: Position) -> Option<DatatypeId> {
+    let use_def = symbols
+        .line_uses(fpath, pos.line)
+        .into_iter()
+        .filter(|u| pos.character >= u.col_start && pos.character <= u.col_end)
+        .min_by_key(|u| u.col_end - u.col_start)?;
+    match symbols.def_info(&use_def.def_loc)? {
+        DefInfo::Struct(mod_ident, name, ..)
+        | DefInfo::Enum(mod_ident, name, ..)
+        | DefInfo::Variant(mod_ident, name, ..) => Some((*mod_ident, *name)),
+        _ => None,
+    }
+}
+
+/// Returns the definition of a given datatype.
+fn datatype_def<'a>(symbols: &'a Symbols, datatype_id: &DatatypeId) -> Option<&'a MemberDef> {
+    let (mod_ident, name) = datatype_id;
+    symbols
+        .file_mods
+        .values()
+        .flatten()
+        .find(|mod_defs| mod_defs.ident == *mod_ident)
+        .and_then(|mod_defs| {
+            mod_defs
+                .structs
+                .get(name)
+                .or_else(|| mod_defs.enums.get(name))
+        })
+}
+
+/// Returns datatypes mentioned in the fields of a given datatype (for enums, in the fields of any
+/// of its variants).
+fn field_datatypes(symbols: &Symbols, datatype_id: &DatatypeId) -> BTreeSet<DatatypeId> {
+    let mut datatypes = BTreeSet::new();
+    let Some(def) = datatype_def(symbols, datatype_id) else {
+        return datatypes;
+    };
+    match &def.info {
+        MemberDefInfo::Struct { .. } => {
+            if let Some(DefInfo::Struct(_, _, _, _, _, _, field_types, _)) =
+                symbols.def_info(&def.name_loc)
+            {
+                field_types
+                    .iter()
+                    .for_each(|t| type_datatypes(t, &mut datatypes));
+            }
+        }
+        MemberDefInfo::Enum { variants_info } => {
+            for (variant_loc, _, _) in variants_info.values() {
+                if let Some(DefInfo::Variant(_, _, _, _, _, field_types, _)) =
+                    symbols.def_info(variant_loc)
+                {
+                    field_types
+                        .iter()
+                        .for_each(|t| type_datatypes(t, &mut datatypes));
+                }
+            }
+        }
+        MemberDefInfo::Fun { .. } | MemberDefInfo::Const => (),
+    }
+    datatypes
+}
+
+/// Collects datatypes mentioned in a given type, including in its type arguments.
+fn type_datatypes(t: &Type, datatypes: &mut BTreeSet<DatatypeId>) {
+    match &t.value {
+        Type_::Ref(_, t) => type_datatypes(t, datatypes),
+        Type_::Apply(_, sp!(_, tname), targs) => {
+            if let TypeName_::ModuleType(mod_ident, datatype_name) = tname {
+                datatypes.insert((mod_ident.value, datatype_name.value()));
+            }
+            targs.iter().for_each(|t| type_datatypes(t, datatypes));
+        }
+        Type_::Fun(args, ret) => {
+            args.iter().for_each(|t| type_datatypes(t, datatypes));
+            type_datatypes(ret, datatypes);
+        }
+        Type_::Unit
+        | Type_::Param(_)
+        | Type_::Var(_)
+        | Type_::Anything
+        | Type_::UnresolvedError => (),
+    }
+}
+
+/// Creates a type hierarchy item for a given datatype.
+fn datatype_item(symbols: &Symbols, datatype_id: &DatatypeId) -> Option<TypeHierarchyItem> {
+    let (mod_ident, name) = datatype_id;
+    let def = datatype_def(symbols, datatype_id)?;
+    let (kind, abilities) = match symbols.def_info(&def.name_loc)? {
+        DefInfo::Struct(_, _, _, _, abilities, ..) => (SymbolKind::STRUCT, abilities),
+        DefInfo::Enum(_, _, _, _, abilities, ..) => (SymbolKind::ENUM, abilities),
+        _ => return None,
+    };
+    let path = symbols
+        .files
+        .file_name_mapping()
+        .get(&def.name_loc.file_hash())?;
+    // only the location of the datatype's name is recorded, so it is used for both ranges
+    let range = symbols.files.lsp_range_opt(&def.name_loc)?;
+    Some(TypeHierarchyItem {
+        name: name.to_string(),
+        kind,
+        tags: None,
+        detail: Some(format!(
+            "{}{}",
+            mod_ident_to_ide_string(
+                mod_ident, /* datatype_name_opt */ None,
+                /* is_access_chain_prefix */ false,
+            ),
+            abilities_to_ide_string(abilities)
+        )),
+        uri: Url::from_file_path(path).ok()?,
+        range,
+        selection_range: range,
+        data: None,
+    })
+}
@@ -0,0 +1,38 @@
+module Calls::calls;
+
+public struct Counter has drop {
+    count: u64,
+}
+
+/// Adds `amount` to the counter, but not beyond `limit`.
+public fun add(c: &mut Counter, amount: u64, limit: u64) {
+    c.count = c.count + amount;
+    if (c.count > limit) c.count = limit;
+}
+
+public fun pair<T: copy + drop>(x: T, y: T): vector<T> {
+    vector[x, y]
+}
+
+public fun double(x: u64): u64 {
+    x * 2
+}
+
+public fun check(x: u64) {
+    assert!(x < 1000);
+}
+
+public macro fun apply($x: u64, $f: |u64| -> u64): u64 {
+    let x = $x;
+    check(x);
+    $f(x)
+}
+
+public fun calls() {
+    let mut c = Counter { count: 0 };
+    add(&mut c, 1, 10);
+    c.add(2, 10);
+    let v = pair(1, 2);
+    let n = apply!(3, |x| double(x));
+    add(&mut c, n, vector::length(&v));
+}
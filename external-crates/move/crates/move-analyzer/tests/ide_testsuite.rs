@@ -10,18 +10,26 @@ use std::{
 };
 
 use json_comments::StripComments;
-use lsp_types::{InlayHintKind, InlayHintLabel, InlayHintTooltip, Position};
+use lsp_types::{
+    CallHierarchyItem, Documentation, InlayHintKind, InlayHintLabel, InlayHintTooltip,
+    ParameterLabel, Position, PrepareRenameResponse, Range, TypeHierarchyItem,
+};
 use move_analyzer::{
+    call_hierarchy::{incoming_calls, outgoing_calls, prepare_call_hierarchy},
     code_action::access_chain_autofix_actions_for_error,
     completions::{compute_completions_with_symbols, utils::compute_cursor},
     inlay_hints::inlay_hints_internal,
+    rename::{prepare_rename, rename},
+    semantic_tokens::{TOKEN_MODIFIERS, TOKEN_TYPES, semantic_tokens_internal},
+    signature_help::signature_help,
     symbols::{
         Symbols,
         compilation::{CompiledPkgInfo, SymbolsComputationData, get_compiled_pkg},
         compute_symbols, compute_symbols_parsed_program, compute_symbols_pre_process,
-        requests::{def_info_doc_string, maybe_convert_for_guard},
+        requests::{def_info_doc_string, maybe_convert_for_guard, workspace_symbols},
         use_def::UseDefMap,
     },
+    type_hierarchy::{prepare_type_hierarchy, subtypes, supertypes},
 };
 use move_command_line_common::testing::insta_assert;
 use move_compiler::linters::LintLevel;
@@ -59,6 +67,36 @@ enum TestSuite {
         project: String,
         file_tests: BTreeMap<String, Vec<AccessChainQuickFixTest>>,
     },
+    Rename {
+        project: String,
+        /// Other packages that are part of the workspace, and whose definitions can be renamed
+        #[serde(default)]
+        workspace: Vec<String>,
+        file_tests: BTreeMap<String, Vec<RenameTest>>,
+    },
+    SignatureHelp {
+        project: String,
+        file_tests: BTreeMap<String, Vec<SignatureHelpTest>>,
+    },
+    SemanticTokens {
+        project: String,
+        file_tests: BTreeMap<String, Vec<SemanticTokensTest>>,
+    },
+    CallHierarchy {
+        project: String,
+        file_tests: BTreeMap<String, Vec<CallHierarchyTest>>,
+    },
+    TypeHierarchy {
+        project: String,
+        file_tests: BTreeMap<String, Vec<TypeHierarchyTest>>,
+    },
+    WorkspaceSymbol {
+        project: String,
+        /// Other packages that are part of the workspace
+        #[serde(default)]
+        workspace: Vec<String>,
+        queries: Vec<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize)]
@@ -99,6 +137,36 @@ struct AccessChainQuickFixTest {
     err_msg: String,
 }
 
+#[derive(Serialize, Deserialize)]
+struct RenameTest {
+    use_line: u32,
+    use_col: u32,
+    new_name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignatureHelpTest {
+    use_line: u32,
+    use_col: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SemanticTokensTest {
+    use_line: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CallHierarchyTest {
+    use_line: u32,
+    use_col: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TypeHierarchyTest {
+    use_line: u32,
+    use_col: u32,
+}
+
 //**************************************************************************************************
 // Test Impls
 //**************************************************************************************************
@@ -376,6 +444,233 @@ impl AccessChainQuickFixTest {
     }
 }
 
+impl RenameTest {
+    fn test(
+        &self,
+        test_idx: usize,
+        symbols_map: &BTreeMap<PathBuf, Symbols>,
+        output: &mut dyn std::io::Write,
+        use_file_path: &Path,
+    ) -> anyhow::Result<()> {
+        let RenameTest {
+            use_line,
+            use_col,
+            new_name,
+        } = self;
+        let pos = Position::new(use_line - 1, use_col - 1); // 0th-based
+        writeln!(output, "-- test {test_idx} -------------------")?;
+        writeln!(
+            output,
+            "use line: {use_line}, use_col: {use_col}, new name: {new_name}"
+        )?;
+
+        match prepare_rename(symbols_map, use_file_path, pos) {
+            Some(PrepareRenameResponse::RangeWithPlaceholder { range, placeholder }) => writeln!(
+                output,
+                "PREPARE RENAME: '{placeholder}' at {}",
+                display_range(&range)
+            )?,
+            Some(_) => writeln!(output, "PREPARE RENAME: unexpected response")?,
+            None => writeln!(output, "PREPARE RENAME: not renameable")?,
+        }
+
+        let edit = match rename(symbols_map, use_file_path, pos, new_name) {
+            Ok(edit) => edit,
+            Err(msg) => {
+                writeln!(output, "RENAME ERROR: {msg}")?;
+                return Ok(());
+            }
+        };
+        let changes = edit
+            .changes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(uri, edits)| (display_path(&uri.to_file_path().unwrap()), edits))
+            .collect::<BTreeMap<_, _>>();
+        for (path, edits) in changes {
+            writeln!(output, "EDITS IN {path}:")?;
+            for edit in edits {
+                writeln!(
+                    output,
+                    "    {} -> '{}'",
+                    display_range(&edit.range),
+                    edit.new_text
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SignatureHelpTest {
+    fn test(
+        &self,
+        test_idx: usize,
+        symbols: &Symbols,
+        output: &mut dyn std::io::Write,
+        use_file_path: &Path,
+    ) -> anyhow::Result<()> {
+        let SignatureHelpTest { use_line, use_col } = self;
+        let pos = Position::new(use_line - 1, use_col - 1); // 0th-based
+        writeln!(output, "-- test {test_idx} -------------------")?;
+        writeln!(output, "use line: {use_line}, use_col: {use_col}")?;
+
+        let content = std::fs::read_to_string(use_file_path)?;
+        let Some(help) = signature_help(symbols, use_file_path, &content, pos) else {
+            writeln!(output, "NO SIGNATURE HELP")?;
+            return Ok(());
+        };
+        for sig in help.signatures {
+            writeln!(output, "SIGNATURE: {}", sig.label)?;
+            let param = sig
+                .active_parameter
+                .and_then(|idx| sig.parameters.as_ref()?.get(idx as usize));
+            match param.map(|p| &p.label) {
+                Some(ParameterLabel::LabelOffsets([start, end])) => writeln!(
+                    output,
+                    "ACTIVE PARAMETER: {}",
+                    sig.label
+                        .chars()
+                        .skip(*start as usize)
+                        .take((end - start) as usize)
+                        .collect::<String>()
+                )?,
+                Some(ParameterLabel::Simple(label)) => {
+                    writeln!(output, "ACTIVE PARAMETER: {label}")?
+                }
+                None => writeln!(output, "ACTIVE PARAMETER: none")?,
+            }
+            if let Some(Documentation::MarkupContent(doc)) = sig.documentation {
+                writeln!(output, "DOCUMENTATION: {}", doc.value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SemanticTokensTest {
+    fn test(
+        &self,
+        test_idx: usize,
+        symbols: &Symbols,
+        output: &mut dyn std::io::Write,
+        use_file_path: &Path,
+    ) -> anyhow::Result<()> {
+        let lsp_use_line = self.use_line - 1; // 0th-based
+        writeln!(output, "-- test {test_idx} -------------------")?;
+        writeln!(output, "use line: {}", self.use_line)?;
+
+        let content = std::fs::read_to_string(use_file_path)?;
+        let Some(line) = content.lines().nth(lsp_use_line as usize) else {
+            writeln!(output, "ERROR: No use_line {} in file", self.use_line)?;
+            return Ok(());
+        };
+        writeln!(output, "{}", line.trim())?;
+
+        let tokens = semantic_tokens_internal(symbols, use_file_path).unwrap_or_default();
+        // tokens are encoded relative to each other
+        let (mut line_idx, mut col) = (0, 0);
+        for token in tokens {
+            if token.delta_line > 0 {
+                col = 0;
+            }
+            line_idx += token.delta_line;
+            col += token.delta_start;
+            if line_idx != lsp_use_line {
+                continue;
+            }
+            let text = line
+                .chars()
+                .skip(col as usize)
+                .take(token.length as usize)
+                .collect::<String>();
+            let modifiers = TOKEN_MODIFIERS
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| token.token_modifiers_bitset & (1 << bit) != 0)
+                .map(|(_, m)| m.as_str())
+                .collect::<Vec<_>>();
+            writeln!(
+                output,
+                "TOKEN {}:{} '{text}': {} [{}]",
+                self.use_line,
+                col + 1,
+                TOKEN_TYPES[token.token_type as usize].as_str(),
+                modifiers.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl CallHierarchyTest {
+    fn test(
+        &self,
+        test_idx: usize,
+        symbols_map: &BTreeMap<PathBuf, Symbols>,
+        output: &mut dyn std::io::Write,
+        use_file_path: &Path,
+    ) -> anyhow::Result<()> {
+        let CallHierarchyTest { use_line, use_col } = self;
+        let pos = Position::new(use_line - 1, use_col - 1); // 0th-based
+        writeln!(output, "-- test {test_idx} -------------------")?;
+        writeln!(output, "use line: {use_line}, use_col: {use_col}")?;
+
+        let Some(item) = prepare_call_hierarchy(symbols_map, use_file_path, pos)
+            .and_then(|items| items.into_iter().next())
+        else {
+            writeln!(output, "NO CALL HIERARCHY ITEM")?;
+            return Ok(());
+        };
+        writeln!(output, "ITEM: {}", display_call_item(&item))?;
+
+        for call in incoming_calls(symbols_map, &item).unwrap_or_default() {
+            writeln!(output, "INCOMING FROM: {}", display_call_item(&call.from))?;
+            for range in call.from_ranges {
+                writeln!(output, "    at {}", display_range(&range))?;
+            }
+        }
+        for call in outgoing_calls(symbols_map, &item).unwrap_or_default() {
+            writeln!(output, "OUTGOING TO: {}", display_call_item(&call.to))?;
+            for range in call.from_ranges {
+                writeln!(output, "    at {}", display_range(&range))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TypeHierarchyTest {
+    fn test(
+        &self,
+        test_idx: usize,
+        symbols_map: &BTreeMap<PathBuf, Symbols>,
+        output: &mut dyn std::io::Write,
+        use_file_path: &Path,
+    ) -> anyhow::Result<()> {
+        let TypeHierarchyTest { use_line, use_col } = self;
+        let pos = Position::new(use_line - 1, use_col - 1); // 0th-based
+        writeln!(output, "-- test {test_idx} -------------------")?;
+        writeln!(output, "use line: {use_line}, use_col: {use_col}")?;
+
+        let Some(item) = prepare_type_hierarchy(symbols_map, use_file_path, pos)
+            .and_then(|items| items.into_iter().next())
+        else {
+            writeln!(output, "NO TYPE HIERARCHY ITEM")?;
+            return Ok(());
+        };
+        writeln!(output, "ITEM: {}", display_type_item(&item))?;
+
+        for supertype in supertypes(symbols_map, &item).unwrap_or_default() {
+            writeln!(output, "SUPERTYPE: {}", display_type_item(&supertype))?;
+        }
+        for subtype in subtypes(symbols_map, &item).unwrap_or_default() {
+            writeln!(output, "SUBTYPE: {}", display_type_item(&subtype))?;
+        }
+        Ok(())
+    }
+}
+
 fn completion_test(
     use_line: u32,
     use_col: u32,
@@ -468,6 +763,68 @@ fn initial_symbols(
     Ok((project_path, compiled_pkg_info, symbols))
 }
 
+/// Displays a path relative to the directory containing all crates, so that test output does not
+/// depend on where the repository is located.
+fn display_path(path: &Path) -> String {
+    let crates_dir =
+        dunce::canonicalize(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")).unwrap();
+    path.strip_prefix(&crates_dir)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Displays a range using 1-based line and column numbers, as used in test descriptions.
+fn display_range(range: &Range) -> String {
+    format!(
+        "{}:{}-{}:{}",
+        range.start.line + 1,
+        range.start.character + 1,
+        range.end.line + 1,
+        range.end.character + 1
+    )
+}
+
+/// Displays a call hierarchy item as its qualified name and the location of its name.
+fn display_call_item(item: &CallHierarchyItem) -> String {
+    format!(
+        "{}::{} at {}:{}",
+        item.detail.as_deref().unwrap_or_default(),
+        item.name,
+        display_path(&item.uri.to_file_path().unwrap()),
+        display_range(&item.selection_range)
+    )
+}
+
+/// Displays a type hierarchy item as its kind, name, detail (module and abilities), and the
+/// location of its name.
+fn display_type_item(item: &TypeHierarchyItem) -> String {
+    format!(
+        "{:?} {} in {} at {}:{}",
+        item.kind,
+        item.name,
+        item.detail.as_deref().unwrap_or_default(),
+        display_path(&item.uri.to_file_path().unwrap()),
+        display_range(&item.selection_range)
+    )
+}
+
+/// Computes symbols for the packages of a workspace consisting of the package at `project_path`
+/// (whose symbols have already been computed) and the packages in `workspace`, keyed by package
+/// root, as the language server does.
+fn workspace_symbols_map(
+    project_path: &Path,
+    symbols: Symbols,
+    workspace: Vec<String>,
+) -> datatest_stable::Result<BTreeMap<PathBuf, Symbols>> {
+    let mut symbols_map = BTreeMap::from([(dunce::canonicalize(project_path)?, symbols)]);
+    for project in workspace {
+        let (pkg_path, _, pkg_symbols) = initial_symbols(project, &BTreeSet::new())?;
+        symbols_map.insert(dunce::canonicalize(pkg_path)?, pkg_symbols);
+    }
+    Ok(symbols_map)
+}
+
 fn use_def_test_suite(
     project: String,
     file_tests: BTreeMap<String, Vec<UseDefTest>>,
@@ -650,6 +1007,190 @@ fn access_chain_quick_fix_test_suite(
     Ok(result)
 }
 
+fn rename_test_suite(
+    project: String,
+    workspace: Vec<String>,
+    file_tests: BTreeMap<String, Vec<RenameTest>>,
+) -> datatest_stable::Result<String> {
+    let (project_path, _, symbols) = initial_symbols(project, &file_tests.keys().collect())?;
+    let symbols_map = workspace_symbols_map(&project_path, symbols, workspace)?;
+
+    let mut output: BufWriter<_> = BufWriter::new(Vec::new());
+    let writer: &mut dyn io::Write = output.get_mut();
+
+    for (file, tests) in file_tests {
+        writeln!(
+            writer,
+            "== {file} ========================================================"
+        )?;
+
+        let mut fpath = project_path.clone();
+
+        fpath.push(format!("sources/{file}"));
+        let cpath = dunce::canonicalize(&fpath).unwrap();
+
+        for (idx, test) in tests.iter().enumerate() {
+            test.test(idx, &symbols_map, writer, &cpath)?;
+        }
+    }
+
+    let result: String = String::from_utf8(output.into_inner().unwrap()).unwrap();
+    Ok(result)
+}
+
+fn signature_help_test_suite(
+    project: String,
+    file_tests: BTreeMap<String, Vec<SignatureHelpTest>>,
+) -> datatest_stable::Result<String> {
+    let (project_path, _, symbols) = initial_symbols(project, &file_tests.keys().collect())?;
+
+    let mut output: BufWriter<_> = BufWriter::new(Vec::new());
+    let writer: &mut dyn io::Write = output.get_mut();
+
+    for (file, tests) in file_tests {
+        writeln!(
+            writer,
+            "== {file} ========================================================"
+        )?;
+
+        let mut fpath = project_path.clone();
+
+        fpath.push(format!("sources/{file}"));
+        let cpath = dunce::canonicalize(&fpath).unwrap();
+
+        for (idx, test) in tests.iter().enumerate() {
+            test.test(idx, &symbols, writer, &cpath)?;
+        }
+    }
+
+    let result: String = String::from_utf8(output.into_inner().unwrap()).unwrap();
+    Ok(result)
+}
+
+fn semantic_tokens_test_suite(
+    project: String,
+    file_tests: BTreeMap<String, Vec<SemanticTokensTest>>,
+) -> datatest_stable::Result<String> {
+    let (project_path, _, symbols) = initial_symbols(project, &file_tests.keys().collect())?;
+
+    let mut output: BufWriter<_> = BufWriter::new(Vec::new());
+    let writer: &mut dyn io::Write = output.get_mut();
+
+    for (file, tests) in file_tests {
+        writeln!(
+            writer,
+            "== {file} ========================================================"
+        )?;
+
+        let mut fpath = project_path.clone();
+
+        fpath.push(format!("sources/{file}"));
+        let cpath = dunce::canonicalize(&fpath).unwrap();
+
+        for (idx, test) in tests.iter().enumerate() {
+            test.test(idx, &symbols, writer, &cpath)?;
+        }
+    }
+
+    let result: String = String::from_utf8(output.into_inner().unwrap()).unwrap();
+    Ok(result)
+}
+
+fn call_hierarchy_test_suite(
+    project: String,
+    file_tests: BTreeMap<String, Vec<CallHierarchyTest>>,
+) -> datatest_stable::Result<String> {
+    let (project_path, _, symbols) = initial_symbols(project, &file_tests.keys().collect())?;
+    let symbols_map = workspace_symbols_map(&project_path, symbols, vec![])?;
+
+    let mut output: BufWriter<_> = BufWriter::new(Vec::new());
+    let writer: &mut dyn io::Write = output.get_mut();
+
+    for (file, tests) in file_tests {
+        writeln!(
+            writer,
+            "== {file} ========================================================"
+        )?;
+
+        let mut fpath = project_path.clone();
+
+        fpath.push(format!("sources/{file}"));
+        let cpath = dunce::canonicalize(&fpath).unwrap();
+
+        for (idx, test) in tests.iter().enumerate() {
+            test.test(idx, &symbols_map, writer, &cpath)?;
+        }
+    }
+
+    let result: String = String::from_utf8(output.into_inner().unwrap()).unwrap();
+    Ok(result)
+}
+
+fn type_hierarchy_test_suite(
+    project: String,
+    file_tests: BTreeMap<String, Vec<TypeHierarchyTest>>,
+) -> datatest_stable::Result<String> {
+    let (project_path, _, symbols) = initial_symbols(project, &file_tests.keys().collect())?;
+    let symbols_map = workspace_symbols_map(&project_path, symbols, vec![])?;
+
+    let mut output: BufWriter<_> = BufWriter::new(Vec::new());
+    let writer: &mut dyn io::Write = output.get_mut();
+
+    for (file, tests) in file_tests {
+        writeln!(
+            writer,
+            "== {file} ========================================================"
+        )?;
+
+        let mut fpath = project_path.clone();
+
+        fpath.push(format!("sources/{file}"));
+        let cpath = dunce::canonicalize(&fpath).unwrap();
+
+        for (idx, test) in tests.iter().enumerate() {
+            test.test(idx, &symbols_map, writer, &cpath)?;
+        }
+    }
+
+    let result: String = String::from_utf8(output.into_inner().unwrap()).unwrap();
+    Ok(result)
+}
+
+#[allow(deprecated)]
+fn workspace_symbol_test_suite(
+    project: String,
+    workspace: Vec<String>,
+    queries: Vec<String>,
+) -> datatest_stable::Result<String> {
+    let (project_path, _, symbols) = initial_symbols(project, &BTreeSet::new())?;
+    let symbols_map = workspace_symbols_map(&project_path, symbols, workspace)?;
+
+    let mut output: BufWriter<_> = BufWriter::new(Vec::new());
+    let writer: &mut dyn io::Write = output.get_mut();
+
+    for (idx, query) in queries.iter().enumerate() {
+        writeln!(writer, "-- test {idx} -------------------")?;
+        writeln!(writer, "query: '{query}'")?;
+        for symbol in workspace_symbols(&symbols_map, query) {
+            writeln!(
+                writer,
+                "{:?} '{}'{} at {}:{}",
+                symbol.kind,
+                symbol.name,
+                symbol
+                    .container_name
+                    .map(|c| format!(" in '{c}'"))
+                    .unwrap_or_default(),
+                display_path(&symbol.location.uri.to_file_path().unwrap()),
+                display_range(&symbol.location.range),
+            )?;
+        }
+    }
+
+    let result: String = String::from_utf8(output.into_inner().unwrap()).unwrap();
+    Ok(result)
+}
+
 fn move_ide_testsuite(test_path: &Path) -> datatest_stable::Result<()> {
     let suite_file = io::BufReader::new(File::open(test_path)?);
     let stripped = StripComments::new(suite_file);
@@ -680,6 +1221,32 @@ fn move_ide_testsuite(test_path: &Path) -> datatest_stable::Result<()> {
             project,
             file_tests,
         } => access_chain_quick_fix_test_suite(project, file_tests),
+        TestSuite::Rename {
+            project,
+            workspace,
+            file_tests,
+        } => rename_test_suite(project, workspace, file_tests),
+        TestSuite::SignatureHelp {
+            project,
+            file_tests,
+        } => signature_help_test_suite(project, file_tests),
+        TestSuite::SemanticTokens {
+            project,
+            file_tests,
+        } => semantic_tokens_test_suite(project, file_tests),
+        TestSuite::CallHierarchy {
+            project,
+            file_tests,
+        } => call_hierarchy_test_suite(project, file_tests),
+        TestSuite::TypeHierarchy {
+            project,
+            file_tests,
+        } => type_hierarchy_test_suite(project, file_tests),
+        TestSuite::WorkspaceSymbol {
+            project,
+            workspace,
+            queries,
+        } => workspace_symbol_test_suite(project, workspace, queries),
     }?;
 
     insta_assert! {
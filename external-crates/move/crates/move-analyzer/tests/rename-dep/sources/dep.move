@@ -0,0 +1,13 @@
+module RenameDep::dep;
+
+public struct Coin has copy, drop {
+    value: u64,
+}
+
+public fun make(value: u64): Coin {
+    Coin { value }
+}
+
+public fun value(c: &Coin): u64 {
+    c.value
+}
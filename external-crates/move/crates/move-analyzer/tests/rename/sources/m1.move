@@ -0,0 +1,28 @@
+module Rename::m1;
+
+use RenameDep::dep;
+
+const LIMIT: u64 = 10;
+
+public struct Pair has drop {
+    first: u64,
+    second: dep::Coin,
+}
+
+public fun new_pair(value: u64): Pair {
+    let coin = dep::make(value);
+    Pair { first: value, second: coin }
+}
+
+public fun first(p: &Pair): u64 {
+    p.first
+}
+
+public fun under_limit(p: &Pair): bool {
+    let total = p.first + p.second.value();
+    total < LIMIT
+}
+
+public fun length(v: &vector<u64>): u64 {
+    v.length()
+}
@@ -0,0 +1,12 @@
+module Rename::m2;
+
+use Rename::m1::{Self, Pair};
+
+public fun make(): Pair {
+    m1::new_pair(1)
+}
+
+public fun check(): bool {
+    let p = make();
+    m1::first(&p) > 0 && m1::under_limit(&p)
+}
@@ -0,0 +1,26 @@
+module Types::types;
+
+public struct Coin has store {
+    value: u64,
+}
+
+public struct Wallet has key, store {
+    id: address,
+    coins: vector<Coin>,
+    spare: std::option::Option<Coin>,
+}
+
+public enum Slot has store {
+    Empty,
+    Full(Wallet),
+    Tagged { tag: std::string::String, coin: Coin },
+}
+
+public struct Vault<phantom T> has key {
+    id: address,
+    slots: vector<Slot>,
+}
+
+public fun empty_slot(): Slot {
+    Slot::Empty
+}
@@ -8,16 +8,34 @@ use clap::*;
 use move_binary_format::{CompiledModule, binary_config::BinaryConfig};
 use move_command_line_common::files::MOVE_COVERAGE_MAP_EXTENSION;
 use move_compiler::{
-    PASS_CFGIR,
+    Compiler, FullyCompiledProgram, PASS_CFGIR, PASS_TYPING,
+    command_line::compiler::construct_pre_compiled_lib,
     diagnostics::{self, Diagnostics},
-    shared::{NumberFormat, NumericalAddress},
-    unit_test::{TestPlan, plan_builder::construct_test_plan},
+    shared::{NumberFormat, NumericalAddress, files::MappedFiles},
+    unit_test::{
+        TestPlan,
+        mutation::{self, Mutation},
+        plan_builder::construct_test_plan,
+    },
 };
 use move_coverage::coverage_map::{CoverageMap, output_map_to_file};
-use move_package::{BuildConfig, compilation::build_plan::BuildPlan};
-use move_unit_test::UnitTestingConfig;
+use move_package::{
+    BuildConfig,
+    compilation::{build_plan::BuildPlan, compiled_package::ModuleFormat},
+    resolution::resolution_graph::ResolvedGraph,
+};
+use move_symbol_pool::Symbol;
+use move_unit_test::{UnitTestingConfig, test_reporter::ReportFormat};
 use move_vm_test_utils::gas_schedule::CostTable;
-use std::{io::Write, path::Path, process::ExitStatus};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::{
+    collections::BTreeSet,
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitStatus,
+    sync::Arc,
+};
 // if windows
 #[cfg(target_family = "windows")]
 use std::os::windows::process::ExitStatusExt;
@@ -72,6 +90,30 @@ pub struct Test {
     // Enable tracing for tests
     #[clap(long = "trace-execution")]
     pub trace_execution: bool,
+
+    /// Run mutation testing: rerun the tests against mutants of the package's code (each with a
+    /// single change, such as a swapped operator or a removed assertion), and report mutants that
+    /// no test detects.
+    #[clap(long = "mutate")]
+    pub mutate: bool,
+
+    /// Write a JSON report of mutation testing results to the given file.
+    #[clap(long = "mutation-report", requires = "mutate")]
+    pub mutation_report: Option<PathBuf>,
+
+    /// Write a machine-readable report of test results (each test's outcome, duration, gas used,
+    /// failure reason and debug output) in the given format. Requires `--report-path`.
+    #[clap(
+        name = "report-format",
+        long = "report-format",
+        value_enum,
+        requires = "report-path"
+    )]
+    pub report_format: Option<ReportFormat>,
+
+    /// File to write the machine-readable report of test results to. Requires `--report-format`.
+    #[clap(name = "report-path", long = "report-path", requires = "report-format")]
+    pub report_path: Option<PathBuf>,
 }
 
 impl Test {
@@ -83,6 +125,22 @@ impl Test {
         cost_table: Option<CostTable>,
     ) -> anyhow::Result<()> {
         let rerooted_path = reroot_path(path)?;
+        if self.mutate {
+            let report_path = self.mutation_report.clone();
+            let result = run_move_mutation_tests(
+                &rerooted_path,
+                config,
+                self.unit_test_config(),
+                natives,
+                cost_table,
+                report_path.as_deref(),
+                &mut std::io::stdout(),
+            )?;
+            if result == UnitTestResult::Failure {
+                std::process::exit(1)
+            }
+            return Ok(());
+        }
         let compute_coverage = self.compute_coverage;
         // save disassembly if trace execution is enabled
         let save_disassembly = self.trace_execution;
@@ -116,6 +174,10 @@ impl Test {
             seed,
             rand_num_iters,
             trace_execution,
+            mutate: _,
+            mutation_report: _,
+            report_format,
+            report_path,
         } = self;
         UnitTestingConfig {
             gas_limit,
@@ -127,6 +189,8 @@ impl Test {
             seed,
             rand_num_iters,
             trace_execution,
+            report_format,
+            report_path,
             ..UnitTestingConfig::default_with_bound(None)
         }
     }
@@ -150,46 +214,9 @@ pub fn run_move_unit_tests<W: Write + Send>(
     writer: &mut W,
 ) -> Result<(UnitTestResult, Option<Diagnostics>)> {
     let mut test_plan = None;
-    build_config.test_mode = true;
-    build_config.dev_mode = true;
     build_config.save_disassembly = save_disassembly;
-
-    // Build the resolution graph (resolution graph diagnostics are only needed for CLI commands so
-    // ignore them by passing a vector as the writer)
-    let resolution_graph =
-        build_config.resolution_graph_for_package(pkg_path, None, &mut Vec::new())?;
-
-    // Note: unit_test_config.named_address_values is always set to vec![] (the default value) before
-    // being passed in.
-    unit_test_config.named_address_values = resolution_graph
-        .extract_named_address_mapping()
-        .map(|(name, addr)| {
-            (
-                name.to_string(),
-                NumericalAddress::new(addr.into_bytes(), NumberFormat::Hex),
-            )
-        })
-        .collect();
-
-    let binary_config = BinaryConfig::new_unpublishable();
-
-    // Collect all the bytecode modules that are dependencies of the package. We need to do this
-    // because they're not returned by the compilation result, but we need to add them in the
-    // VM storage.
-    let mut bytecode_deps_modules = vec![];
-    for pkg in resolution_graph.package_table.values() {
-        let source_available = !pkg
-            .get_sources(&resolution_graph.build_options)
-            .unwrap()
-            .is_empty();
-        if source_available {
-            continue;
-        }
-        for bytes in pkg.get_bytecodes_bytes()? {
-            let module = CompiledModule::deserialize_with_config(&bytes, &binary_config)?;
-            bytecode_deps_modules.push(module);
-        }
-    }
+    let (resolution_graph, bytecode_deps_modules) =
+        resolve_for_testing(pkg_path, build_config, &mut unit_test_config)?;
 
     let root_package = resolution_graph.root_package();
     let build_plan = BuildPlan::create(&resolution_graph)?;
@@ -263,6 +290,348 @@ pub fn run_move_unit_tests<W: Write + Send>(
     Ok((UnitTestResult::Success, warning_diags))
 }
 
+/// Outcome of running the tests against a mutant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MutantStatus {
+    /// At least one test failed
+    Killed,
+    /// All tests passed
+    Survived,
+    /// The mutant did not compile
+    Invalid,
+}
+
+#[derive(Serialize)]
+struct MutationReport {
+    total: usize,
+    killed: usize,
+    survived: usize,
+    invalid: usize,
+    mutants: Vec<MutantReport>,
+}
+
+#[derive(Serialize)]
+struct MutantReport {
+    index: usize,
+    kind: String,
+    module: String,
+    function: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    description: String,
+    status: MutantStatus,
+}
+
+/// Runs mutation testing on a package. The package's tests are first run against its original
+/// code, and then rerun against each mutant (a copy of the code with a single mutation applied,
+/// see `move_compiler::unit_test::mutation`). Mutants for which all tests still pass (i.e.,
+/// surviving mutants) are reported, and the full results are written as JSON to `report_path`
+/// (if provided).
+///
+/// The package's dependencies are compiled once, and mutants are then compiled and tested in
+/// parallel, one mutant per thread.
+pub fn run_move_mutation_tests<W: Write + Send>(
+    pkg_path: &Path,
+    build_config: BuildConfig,
+    mut unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: Option<CostTable>,
+    report_path: Option<&Path>,
+    writer: &mut W,
+) -> Result<UnitTestResult> {
+    // mutants can only be detected by tests that pass on the original code
+    let (result, _) = run_move_unit_tests(
+        pkg_path,
+        build_config.clone(),
+        unit_test_config.clone(),
+        natives.clone(),
+        cost_table.clone(),
+        /* compute_coverage */ false,
+        /* save_disassembly */ false,
+        writer,
+    )?;
+    if result == UnitTestResult::Failure {
+        writeln!(writer, "Mutation testing requires all tests to pass")?;
+        return Ok(result);
+    }
+
+    let (resolution_graph, bytecode_deps_modules) =
+        resolve_for_testing(pkg_path, build_config, &mut unit_test_config)?;
+    let root_package = resolution_graph.root_package();
+    let build_plan = BuildPlan::create(&resolution_graph)?;
+
+    // dependencies are the same for all mutants, so their sources are only compiled once
+    let mut dependencies = build_plan.compute_dependencies();
+    let src_deps = dependencies
+        .make_deps_for_compiler()?
+        .into_iter()
+        .filter_map(|(paths, format)| matches!(format, ModuleFormat::Source).then_some(paths))
+        .collect::<Vec<_>>();
+    let src_dep_names = src_deps
+        .iter()
+        .filter_map(|paths| paths.name.as_ref().map(|(name, _)| *name))
+        .collect::<BTreeSet<_>>();
+    let pre_compiled_deps = match construct_pre_compiled_lib(
+        src_deps,
+        None,
+        resolution_graph.build_options.compiler_flags(),
+        None,
+    )? {
+        Ok(program) => Arc::new(program),
+        Err((files, diags)) => diagnostics::report_diagnostics(&files, diags),
+    };
+    // (bytecode dependencies were not part of the pre-compiled program)
+    dependencies.remove_deps(src_dep_names.clone());
+
+    let (files, mutations) =
+        build_plan.compile_with_driver_no_save(dependencies, &mut Vec::new(), |compiler| {
+            let (files, compiler_res) = compiler
+                .set_pre_compiled_lib(pre_compiled_deps.clone())
+                .run::<PASS_TYPING>()?;
+            let (_, typed) =
+                diagnostics::unwrap_or_report_pass_diagnostics(&files, compiler_res).into_ast();
+            let mutations = mutation::find_mutations(&typed);
+            Ok((files, mutations))
+        })?;
+    writeln!(
+        writer,
+        "Running mutation testing on {} mutant(s)",
+        mutations.len()
+    )?;
+
+    // each mutant's tests run on a single thread, with mutants running in parallel instead
+    let num_threads = unit_test_config.num_threads.max(1);
+    let mutant_config = UnitTestingConfig {
+        num_threads: 1,
+        list: false,
+        report_statistics: None,
+        verbose: false,
+        trace_execution: false,
+        report_format: None,
+        report_path: None,
+        ..unit_test_config
+    };
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()?;
+    let statuses = pool.install(|| {
+        mutations
+            .par_iter()
+            .map(|m| {
+                let mut dependencies = build_plan.compute_dependencies();
+                dependencies.remove_deps(src_dep_names.clone());
+                let test_plan = build_plan.compile_with_driver_no_save(
+                    dependencies,
+                    &mut Vec::new(),
+                    |compiler| {
+                        compile_mutant(
+                            compiler,
+                            root_package,
+                            m.index,
+                            &pre_compiled_deps,
+                            &bytecode_deps_modules,
+                        )
+                    },
+                )?;
+                let Some(test_plan) = test_plan else {
+                    return Ok(MutantStatus::Invalid);
+                };
+                let (_, passed) = mutant_config.run_and_report_unit_tests(
+                    test_plan,
+                    Some(natives.clone()),
+                    cost_table.clone(),
+                    Vec::new(),
+                )?;
+                Ok(if passed {
+                    MutantStatus::Survived
+                } else {
+                    MutantStatus::Killed
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    let report = mutation_report(pkg_path, &files, &mutations, &statuses);
+    writeln!(
+        writer,
+        "Mutation testing result: {} mutant(s), {} killed, {} survived, {} did not compile",
+        report.total, report.killed, report.survived, report.invalid
+    )?;
+    if report.killed + report.survived > 0 {
+        writeln!(
+            writer,
+            "Mutation score: {:.2}%",
+            (report.killed as f64) / ((report.killed + report.survived) as f64) * 100f64
+        )?;
+    }
+    let survivors = report
+        .mutants
+        .iter()
+        .filter(|m| m.status == MutantStatus::Survived)
+        .collect::<Vec<_>>();
+    if !survivors.is_empty() {
+        writeln!(writer, "Surviving mutants:")?;
+        for m in survivors {
+            let location = match (&m.file, m.line, m.column) {
+                (Some(file), Some(line), Some(column)) => {
+                    format!("{}:{line}:{column}", file.display())
+                }
+                _ => "<unknown location>".to_string(),
+            };
+            writeln!(
+                writer,
+                "  {location} ({}::{}): {}",
+                m.module, m.function, m.description
+            )?;
+        }
+    }
+
+    if let Some(report_path) = report_path {
+        std::fs::write(report_path, serde_json::to_string_pretty(&report)?)?;
+    }
+    Ok(UnitTestResult::Success)
+}
+
+/// Resolves the dependencies of a package built for testing, setting the named addresses of the
+/// unit test config accordingly. Also returns the modules of dependencies that are only available
+/// as bytecode: they are not returned by the compilation result, but we need to add them in the
+/// VM storage.
+fn resolve_for_testing(
+    pkg_path: &Path,
+    mut build_config: BuildConfig,
+    unit_test_config: &mut UnitTestingConfig,
+) -> Result<(ResolvedGraph, Vec<CompiledModule>)> {
+    build_config.test_mode = true;
+    build_config.dev_mode = true;
+
+    // Build the resolution graph (resolution graph diagnostics are only needed for CLI commands so
+    // ignore them by passing a vector as the writer)
+    let resolution_graph =
+        build_config.resolution_graph_for_package(pkg_path, None, &mut Vec::new())?;
+
+    // Note: unit_test_config.named_address_values is always set to vec![] (the default value) before
+    // being passed in.
+    unit_test_config.named_address_values = resolution_graph
+        .extract_named_address_mapping()
+        .map(|(name, addr)| {
+            (
+                name.to_string(),
+                NumericalAddress::new(addr.into_bytes(), NumberFormat::Hex),
+            )
+        })
+        .collect();
+
+    let binary_config = BinaryConfig::new_unpublishable();
+
+    let mut bytecode_deps_modules = vec![];
+    for pkg in resolution_graph.package_table.values() {
+        let source_available = !pkg
+            .get_sources(&resolution_graph.build_options)
+            .unwrap()
+            .is_empty();
+        if source_available {
+            continue;
+        }
+        for bytes in pkg.get_bytecodes_bytes()? {
+            let module = CompiledModule::deserialize_with_config(&bytes, &binary_config)?;
+            bytecode_deps_modules.push(module);
+        }
+    }
+
+    Ok((resolution_graph, bytecode_deps_modules))
+}
+
+/// Compiles the package with a single mutation applied against its pre-compiled source
+/// dependencies, returning the test plan for the mutant (or `None` if the mutant does not
+/// compile).
+fn compile_mutant(
+    compiler: Compiler,
+    root_package: Symbol,
+    mutation_index: usize,
+    pre_compiled_deps: &Arc<FullyCompiledProgram>,
+    bytecode_deps_modules: &[CompiledModule],
+) -> Result<Option<TestPlan>> {
+    let (_, compiler_res) = compiler
+        .set_pre_compiled_lib(pre_compiled_deps.clone())
+        .run::<PASS_TYPING>()?;
+    let Ok(compiler) = compiler_res else {
+        return Ok(None);
+    };
+    let (compiler, mut typed) = compiler.into_ast();
+    if !mutation::apply_mutation(&mut typed, mutation_index) {
+        return Ok(None);
+    }
+    let Ok(compiler) = compiler.at_typing(typed).run::<PASS_CFGIR>() else {
+        return Ok(None);
+    };
+    let (compiler, cfgir) = compiler.into_ast();
+    let compilation_env = compiler.compilation_env();
+    let Some(tests) = construct_test_plan(compilation_env, Some(root_package), &cfgir) else {
+        return Ok(None);
+    };
+    let mapped_files = compilation_env.mapped_files().clone();
+
+    let Ok((units, _warnings)) = compiler.at_cfgir(cfgir).build() else {
+        return Ok(None);
+    };
+    // modules of the pre-compiled dependencies are not part of the compilation result
+    let units = pre_compiled_deps
+        .compiled
+        .iter()
+        .map(|unit| unit.named_module.clone())
+        .chain(units.into_iter().map(|unit| unit.named_module))
+        .collect();
+    Ok(Some(TestPlan::new(
+        tests,
+        mapped_files,
+        units,
+        bytecode_deps_modules.to_vec(),
+    )))
+}
+
+fn mutation_report(
+    pkg_path: &Path,
+    files: &MappedFiles,
+    mutations: &[Mutation],
+    statuses: &[MutantStatus],
+) -> MutationReport {
+    let count = |status| statuses.iter().filter(|s| **s == status).count();
+    let mutants = mutations
+        .iter()
+        .zip(statuses)
+        .map(|(m, status)| {
+            let file = files
+                .file_name_mapping()
+                .get(&m.loc.file_hash())
+                .map(|path| path.strip_prefix(pkg_path).unwrap_or(path).to_path_buf());
+            let position = files.start_position_opt(&m.loc);
+            MutantReport {
+                index: m.index,
+                kind: m.kind.to_string(),
+                module: m.module.to_string(),
+                function: m.function.to_string(),
+                file,
+                line: position.map(|p| p.user_line()),
+                column: position.map(|p| p.user_column()),
+                description: m.description.clone(),
+                status: *status,
+            }
+        })
+        .collect();
+    MutationReport {
+        total: statuses.len(),
+        killed: count(MutantStatus::Killed),
+        survived: count(MutantStatus::Survived),
+        invalid: count(MutantStatus::Invalid),
+        mutants,
+    }
+}
+
 impl From<UnitTestResult> for ExitStatus {
     fn from(result: UnitTestResult) -> Self {
         match result {
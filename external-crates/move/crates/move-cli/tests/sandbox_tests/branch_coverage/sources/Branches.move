@@ -0,0 +1,13 @@
+module std::Branches {
+    public fun is_big(x: u64): bool {
+        if (x > 10) {
+            true
+        } else {
+            false
+        }
+    }
+
+    public fun clamp(x: u64, max: u64): u64 {
+        if (x > max) max else x
+    }
+}
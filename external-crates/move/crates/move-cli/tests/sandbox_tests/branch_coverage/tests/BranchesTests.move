@@ -0,0 +1,16 @@
+#[test_only]
+module std::BranchesTests {
+    use std::Branches;
+
+    // only the `true` branch of `is_big` is taken
+    #[test]
+    fun big() {
+        assert!(Branches::is_big(20), 0)
+    }
+
+    #[test]
+    fun clamp() {
+        assert!(Branches::clamp(5, 10) == 5, 0);
+        assert!(Branches::clamp(15, 10) == 10, 0)
+    }
+}
@@ -0,0 +1,8 @@
+module std::Checks {
+    const E_TOO_BIG: u64 = 0;
+
+    public fun checked_double(x: u64): u64 {
+        assert!(x <= 100, E_TOO_BIG);
+        x * 2
+    }
+}
@@ -0,0 +1,16 @@
+#[test_only]
+module std::ChecksTests {
+    use std::Checks;
+
+    #[test]
+    fun double() {
+        assert!(Checks::checked_double(3) == 6, 0)
+    }
+
+    // the limit itself is not tested, so replacing `<=` with `<` goes undetected
+    #[test]
+    #[expected_failure(abort_code = 0, location = std::Checks)]
+    fun too_big() {
+        Checks::checked_double(101);
+    }
+}
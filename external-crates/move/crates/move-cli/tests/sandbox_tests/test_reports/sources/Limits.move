@@ -0,0 +1,9 @@
+module std::Limits {
+    #[error]
+    const ETooBig: vector<u8> = b"Value is too big";
+
+    public fun check(x: u64): u64 {
+        assert!(x <= 100, ETooBig);
+        x
+    }
+}
@@ -0,0 +1,30 @@
+#[test_only]
+module std::LimitsTests {
+    use std::Limits;
+
+    #[test]
+    fun small_value_passes() {
+        std::debug::print(&Limits::check(7));
+    }
+
+    #[test]
+    fun plain_abort() {
+        abort 7
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1, location = Self)]
+    fun wrong_abort_code() {
+        abort 2
+    }
+
+    #[test]
+    fun clever_error() {
+        Limits::check(101);
+    }
+
+    #[random_test]
+    fun random_values_pass(x: u8) {
+        std::debug::print(&(Limits::check((x as u64) % 101) <= 100));
+    }
+}
@@ -16,6 +16,7 @@ use move_core_types::{
 use std::{collections::BTreeMap, fmt};
 
 pub mod filter_test_members;
+pub mod mutation;
 pub mod plan_builder;
 
 pub type TestName = String;
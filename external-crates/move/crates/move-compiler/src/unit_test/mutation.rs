@@ -0,0 +1,549 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mutations of the typed AST used for mutation testing. A mutation makes a single small change to
+//! the code of a (non-test) function in the root package, such as swapping an operator or dropping
+//! an assertion, while preserving types so that the mutated program can be compiled as is. Unit
+//! tests are then run against each mutant: a mutant that no test detects points at code whose
+//! behavior is not fully exercised by the tests.
+//!
+//! Mutation sites are numbered in the order in which they are encountered when traversing the
+//! program, so a mutation found in one program can be applied to another (e.g., freshly compiled)
+//! copy of the same program by its index.
+//!
+//! As macros are expanded at their call sites in the typed AST, code located in the body of a
+//! macro is mutated as part of the macro (in the macro's module) rather than as part of the
+//! functions calling it, and a single mutation applies to every expansion of the macro.
+
+use crate::{
+    diagnostics::warning_filters::WarningFilters,
+    expansion::ast::{ModuleIdent, ModuleIdent_, Value_},
+    naming::ast::Type_,
+    parser::ast::{BinOp_, ConstantName, FunctionName, TargetKind, UnaryOp_},
+    shared::{Identifier, known_attributes::AttributeKind_},
+    typing::{
+        ast::{self as T, BuiltinFunction_, SequenceItem_, UnannotatedExp_ as E},
+        visitor::TypingMutVisitorContext,
+    },
+    unit_test::filter_test_members::UNIT_TEST_POISON_FUN_NAME,
+};
+use move_core_types::u256::U256;
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use std::{
+    collections::{BTreeMap, btree_map::Entry},
+    fmt,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MutationKind {
+    /// A binary operator replaced with a related one (e.g., `<` with `<=`, or `+` with `-`)
+    BinaryOperator,
+    /// The condition of an `if` or of a `while` loop negated
+    NegatedCondition,
+    /// An `assert!` removed
+    RemovedAssert,
+    /// A literal replaced with a different value
+    ReplacedConstant,
+    /// A statement (an expression of unit type followed by other expressions) removed
+    RemovedStatement,
+}
+
+/// A single mutation of the program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mutation {
+    /// Index of the mutation site, used to apply the mutation (see `apply_mutation`)
+    pub index: usize,
+    pub kind: MutationKind,
+    /// Module containing the mutated function
+    pub module: ModuleIdent_,
+    /// Mutated function
+    pub function: Symbol,
+    /// Location of the mutated code
+    pub loc: Loc,
+    /// Description of the change, e.g. "replaced `<` with `<=`"
+    pub description: String,
+}
+
+/// Traverses the program, finding (and possibly applying) mutations
+struct Mutator {
+    /// Index of the mutation to apply, or `None` if mutations are only to be found
+    target: Option<usize>,
+    /// Indices of the mutation sites encountered so far (a site in a macro is encountered at each
+    /// of the macro's expansions)
+    sites: BTreeMap<(Loc, MutationKind), usize>,
+    /// Mutations found so far (if mutations are only to be found)
+    found: Vec<Mutation>,
+    /// Has the target mutation been applied (at least once)?
+    applied: bool,
+    /// Macros whose code can be mutated, along with their locations
+    macros: Vec<(ModuleIdent_, Symbol, Loc)>,
+    /// Is the module currently visited a test module?
+    in_test_module: bool,
+    /// Function currently visited, along with its location, if its code can be mutated
+    current_fun: Option<(ModuleIdent_, Symbol, Loc)>,
+}
+
+//**************************************************************************************************
+// Entry points
+//**************************************************************************************************
+
+/// Returns all mutations applicable to the program, in the order of their indices.
+pub fn find_mutations(prog: &T::Program) -> Vec<Mutation> {
+    // the visitor is shared with `apply_mutation`, so it operates on a copy of the program that is
+    // left unmodified
+    let mut prog = prog.clone();
+    let mut mutator = Mutator::new(&prog, None);
+    mutator.visit(&mut prog);
+    mutator.found
+}
+
+/// Applies the mutation with the given index (as returned by `find_mutations` for the same
+/// program) to the program. Returns false if there is no mutation with this index.
+pub fn apply_mutation(prog: &mut T::Program, index: usize) -> bool {
+    let mut mutator = Mutator::new(prog, Some(index));
+    mutator.visit(prog);
+    mutator.applied
+}
+
+impl fmt::Display for MutationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use MutationKind as K;
+        write!(
+            f,
+            "{}",
+            match self {
+                K::BinaryOperator => "binary_operator",
+                K::NegatedCondition => "negated_condition",
+                K::RemovedAssert => "removed_assert",
+                K::ReplacedConstant => "replaced_constant",
+                K::RemovedStatement => "removed_statement",
+            }
+        )
+    }
+}
+
+//**************************************************************************************************
+// Mutations
+//**************************************************************************************************
+
+impl Mutator {
+    fn new(prog: &T::Program, target: Option<usize>) -> Self {
+        let macros = prog
+            .modules
+            .key_cloned_iter()
+            .filter(|(_, mdef)| is_root_module(mdef) && !mdef.attributes.is_test_or_test_only())
+            .flat_map(|(mident, mdef)| {
+                mdef.functions
+                    .key_cloned_iter()
+                    .filter(|(_, fdef)| fdef.macro_.is_some() && !is_test_fun(fdef))
+                    .map(move |(name, fdef)| (mident.value, name.value(), fdef.loc))
+            })
+            .collect();
+        Self {
+            target,
+            sites: BTreeMap::new(),
+            found: vec![],
+            applied: false,
+            macros,
+            in_test_module: false,
+            current_fun: None,
+        }
+    }
+
+    /// Returns the function (or macro) the code at a given location can be mutated in, if any.
+    /// Code of macros expanded in the current function is located in the macro's definition.
+    fn mutable_in(&self, loc: &Loc) -> Option<(ModuleIdent_, Symbol)> {
+        if let Some((module, function, fun_loc)) = self.current_fun {
+            if fun_loc.contains(loc) {
+                return Some((module, function));
+            }
+        }
+        self.macros
+            .iter()
+            .find(|(_, _, macro_loc)| macro_loc.contains(loc))
+            .map(|(module, name, _)| (*module, *name))
+    }
+
+    /// Registers a mutation site, returning true if the mutation is to be applied.
+    fn site(
+        &mut self,
+        (module, function): (ModuleIdent_, Symbol),
+        loc: Loc,
+        kind: MutationKind,
+        description: impl FnOnce() -> String,
+    ) -> bool {
+        let next = self.sites.len();
+        let index = match self.sites.entry((loc, kind)) {
+            // another expansion of the same macro code
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                if self.target.is_none() {
+                    self.found.push(Mutation {
+                        index: next,
+                        kind,
+                        module,
+                        function,
+                        loc,
+                        description: description(),
+                    });
+                }
+                *entry.insert(next)
+            }
+        };
+        let apply = self.target == Some(index);
+        self.applied |= apply;
+        apply
+    }
+}
+
+fn mutated_binop(op: BinOp_) -> Option<BinOp_> {
+    use BinOp_ as B;
+    Some(match op {
+        B::Add => B::Sub,
+        B::Sub => B::Add,
+        B::Mul => B::Div,
+        B::Div => B::Mul,
+        B::Mod => B::Mul,
+        B::BitOr => B::BitAnd,
+        B::BitAnd => B::BitOr,
+        B::Xor => B::BitOr,
+        B::Shl => B::Shr,
+        B::Shr => B::Shl,
+        B::And => B::Or,
+        B::Or => B::And,
+        B::Eq => B::Neq,
+        B::Neq => B::Eq,
+        B::Lt => B::Le,
+        B::Le => B::Lt,
+        B::Gt => B::Ge,
+        B::Ge => B::Gt,
+        B::Range | B::Implies | B::Iff => return None,
+    })
+}
+
+fn mutated_value(value: &Value_) -> Option<Value_> {
+    use Value_ as V;
+    Some(match value {
+        V::Bool(b) => V::Bool(!b),
+        V::U8(n) => V::U8(if *n == 0 { 1 } else { 0 }),
+        V::U16(n) => V::U16(if *n == 0 { 1 } else { 0 }),
+        V::U32(n) => V::U32(if *n == 0 { 1 } else { 0 }),
+        V::U64(n) => V::U64(if *n == 0 { 1 } else { 0 }),
+        V::U128(n) => V::U128(if *n == 0 { 1 } else { 0 }),
+        V::U256(n) => V::U256(if *n == U256::zero() {
+            U256::one()
+        } else {
+            U256::zero()
+        }),
+        V::Address(_) | V::InferredNum(_) | V::Bytearray(_) => return None,
+    })
+}
+
+fn unit_exp(loc: Loc) -> T::Exp {
+    T::exp(sp(loc, Type_::Unit), sp(loc, E::Unit { trailing: false }))
+}
+
+fn is_root_module(mdef: &T::ModuleDefinition) -> bool {
+    matches!(
+        mdef.target_kind,
+        TargetKind::Source {
+            is_root_package: true
+        }
+    )
+}
+
+fn is_test_fun(fdef: &T::Function) -> bool {
+    fdef.attributes.is_test_or_test_only()
+        || fdef.attributes.contains_key_(&AttributeKind_::Test)
+        || fdef.attributes.contains_key_(&AttributeKind_::RandTest)
+}
+
+impl TypingMutVisitorContext for Mutator {
+    // Nothing to do -- we're not producing errors.
+    fn push_warning_filter_scope(&mut self, _filter: WarningFilters) {}
+
+    // Nothing to do -- we're not producing errors.
+    fn pop_warning_filter_scope(&mut self) {}
+
+    fn visit_module_custom(&mut self, _ident: ModuleIdent, mdef: &mut T::ModuleDefinition) -> bool {
+        // test modules are still visited, as they may expand macros
+        self.in_test_module = mdef.attributes.is_test_or_test_only();
+        !is_root_module(mdef)
+    }
+
+    fn visit_constant_custom(
+        &mut self,
+        _module: ModuleIdent,
+        _constant_name: ConstantName,
+        _cdef: &mut T::Constant,
+    ) -> bool {
+        // constants are mutated where they are used (if at all)
+        true
+    }
+
+    fn visit_function_custom(
+        &mut self,
+        module: ModuleIdent,
+        function_name: FunctionName,
+        fdef: &mut T::Function,
+    ) -> bool {
+        // test functions are still visited, as they may expand macros
+        let mutable = !self.in_test_module
+            && !is_test_fun(fdef)
+            && function_name.value() != UNIT_TEST_POISON_FUN_NAME;
+        self.current_fun = mutable.then_some((module.value, function_name.value(), fdef.loc));
+        !matches!(fdef.body.value, T::FunctionBody_::Defined(_))
+    }
+
+    fn visit_seq(&mut self, (_, seq): &mut T::Sequence) {
+        let len = seq.len();
+        for (idx, item) in seq.iter_mut().enumerate() {
+            if let SequenceItem_::Seq(e) = &mut item.value {
+                let removable = idx + 1 < len
+                    && matches!(e.ty.value, Type_::Unit)
+                    && !matches!(
+                        &e.exp.value,
+                        E::Unit { .. } | E::Builtin(_, _) | E::Return(_) | E::Abort(_)
+                    );
+                if let Some(fun) = self.mutable_in(&e.exp.loc).filter(|_| removable) {
+                    let loc = e.exp.loc;
+                    if self.site(fun, loc, MutationKind::RemovedStatement, || {
+                        "removed statement".to_string()
+                    }) {
+                        **e = unit_exp(loc);
+                        continue;
+                    }
+                }
+            }
+            self.visit_seq_item(item);
+        }
+    }
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        let Some(fun) = self.mutable_in(&exp.exp.loc) else {
+            // keep traversing, as code passed to a macro (e.g., in a lambda) is located in the
+            // current function
+            return false;
+        };
+        let exp_loc = exp.exp.loc;
+        match &mut exp.exp.value {
+            E::BinopExp(_, op, _, _) => {
+                if let Some(new_op) = mutated_binop(op.value) {
+                    let old_op = op.value;
+                    if self.site(fun, op.loc, MutationKind::BinaryOperator, || {
+                        format!("replaced `{old_op}` with `{new_op}`")
+                    }) {
+                        op.value = new_op;
+                    }
+                }
+            }
+            E::IfElse(cond, _, _) | E::While(_, cond, _) => {
+                let loc = cond.exp.loc;
+                if self.site(fun, loc, MutationKind::NegatedCondition, || {
+                    "negated condition".to_string()
+                }) {
+                    let original = std::mem::replace(&mut **cond, unit_exp(loc));
+                    **cond = T::exp(
+                        original.ty.clone(),
+                        sp(loc, E::UnaryExp(sp(loc, UnaryOp_::Not), Box::new(original))),
+                    );
+                }
+            }
+            E::Builtin(bf, _) if matches!(bf.value, BuiltinFunction_::Assert(_)) => {
+                if self.site(fun, exp_loc, MutationKind::RemovedAssert, || {
+                    "removed assertion".to_string()
+                }) {
+                    *exp = unit_exp(exp_loc);
+                    return true;
+                }
+            }
+            E::Value(value) => {
+                if let Some(new_value) = mutated_value(&value.value) {
+                    let old_value = &value.value;
+                    if self.site(fun, value.loc, MutationKind::ReplacedConstant, || {
+                        format!("replaced `{old_value}` with `{new_value}`")
+                    }) {
+                        value.value = new_value;
+                    }
+                }
+            }
+            _ => (),
+        }
+        // subexpressions are still visited, as they may contain other expansions of a macro
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Compiler, PASS_TYPING,
+        editions::Edition,
+        shared::{Flags, PackageConfig, PackagePaths},
+        typing::visitor::TypingVisitorContext,
+    };
+    use std::collections::BTreeSet;
+
+    const M: &str = r#"
+        module 0x42::m {
+            public fun f(x: u64): u64 {
+                if (x > 10) x + 1 else x
+            }
+
+            public macro fun twice($x: u64): u64 {
+                $x * 2
+            }
+
+            public fun g(x: u64): u64 {
+                twice!(x) + twice!(x + 1)
+            }
+
+            #[test_only]
+            public fun helper(x: u64): u64 {
+                x - 1
+            }
+
+            #[test]
+            fun test_f() {
+                assert!(f(11) == 12, 0);
+                assert!(twice!(helper(3)) == 4, 1);
+            }
+        }
+
+        #[test_only]
+        module 0x42::m_tests {
+            fun t(): u64 {
+                1 + 2
+            }
+        }
+    "#;
+
+    const N: &str = r#"
+        module 0x42::n {
+            public fun h(x: u64): u64 {
+                0x42::m::twice!(x)
+            }
+        }
+    "#;
+
+    fn compile(sources: &[&str]) -> T::Program {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = sources
+            .iter()
+            .enumerate()
+            .map(|(i, source)| {
+                let path = dir.path().join(format!("m{i}.move"));
+                std::fs::write(&path, source).unwrap();
+                Symbol::from(path.to_str().unwrap())
+            })
+            .collect();
+        let targets: Vec<PackagePaths> = vec![PackagePaths {
+            name: Some((
+                Symbol::from("Test"),
+                PackageConfig {
+                    edition: Edition::E2024,
+                    ..PackageConfig::default()
+                },
+            )),
+            paths,
+            named_address_map: BTreeMap::new(),
+        }];
+        let (_, res) = Compiler::from_package_paths(None, targets, vec![])
+            .unwrap()
+            .set_flags(Flags::testing())
+            .run::<PASS_TYPING>()
+            .unwrap();
+        let Ok(compiler) = res else {
+            panic!("compilation failed")
+        };
+        compiler.into_ast().1
+    }
+
+    /// Counts binary operators with the given location and value
+    struct BinopCounter {
+        loc: Loc,
+        op: BinOp_,
+        count: usize,
+    }
+
+    impl TypingVisitorContext for BinopCounter {
+        fn push_warning_filter_scope(&mut self, _filter: WarningFilters) {}
+
+        fn pop_warning_filter_scope(&mut self) {}
+
+        fn visit_exp_custom(&mut self, exp: &T::Exp) -> bool {
+            if let E::BinopExp(_, op, _, _) = &exp.exp.value {
+                if op.loc == self.loc && op.value == self.op {
+                    self.count += 1;
+                }
+            }
+            false
+        }
+    }
+
+    #[test]
+    fn indices_stable_across_recompiles() {
+        let prog = compile(&[M, N]);
+        let mutations = find_mutations(&prog);
+        assert!(!mutations.is_empty());
+        assert!(mutations.iter().enumerate().all(|(i, m)| m.index == i));
+
+        let recompiled = compile(&[M, N]);
+        assert_eq!(find_mutations(&recompiled), mutations);
+        for m in &mutations {
+            let mut mutant = recompiled.clone();
+            assert!(apply_mutation(&mut mutant, m.index));
+            if m.kind == MutationKind::BinaryOperator {
+                // the same site, found in the mutant, now has the mutated operator
+                let mutated = &find_mutations(&mutant)[m.index];
+                assert_eq!(mutated.loc, m.loc);
+                assert_ne!(mutated.description, m.description);
+            }
+        }
+        assert!(!apply_mutation(&mut recompiled.clone(), mutations.len()));
+    }
+
+    #[test]
+    fn test_code_not_mutated() {
+        let mutations = find_mutations(&compile(&[M, N]));
+        let functions = mutations
+            .iter()
+            .map(|m| m.function.as_str())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(functions, BTreeSet::from(["f", "g", "twice"]));
+        assert!(
+            mutations
+                .iter()
+                .all(|m| m.module.module.value().as_str() == "m")
+        );
+    }
+
+    #[test]
+    fn macro_code_mutated_in_macro() {
+        let prog = compile(&[M, N]);
+        let mutations = find_mutations(&prog);
+        let in_macro = mutations
+            .iter()
+            .filter(|m| m.function.as_str() == "twice")
+            .collect::<Vec<_>>();
+        // `*` and `2`, each found once although the macro is expanded in `g` (twice), in `h` and
+        // in a test
+        assert_eq!(
+            in_macro.iter().map(|m| m.kind).collect::<Vec<_>>(),
+            vec![MutationKind::BinaryOperator, MutationKind::ReplacedConstant]
+        );
+
+        // the mutation applies to every expansion of the macro
+        let mut mutant = prog.clone();
+        assert!(apply_mutation(&mut mutant, in_macro[0].index));
+        let mut counter = BinopCounter {
+            loc: in_macro[0].loc,
+            op: BinOp_::Div,
+            count: 0,
+        };
+        counter.visit(&mutant);
+        assert_eq!(counter.count, 4);
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conditional branches of a function, identified from its control-flow graph. A branch point is
+//! a block terminated by `BrTrue`/`BrFalse` (whose branches are the jump and the fall-through
+//! edges) or by `VariantSwitch` (whose branches are the distinct arms of its jump table).
+//! Unconditional jumps are not branch points, as there is no choice to cover.
+
+use move_abstract_interpreter::control_flow_graph::ControlFlowGraph;
+use move_binary_format::file_format::{Bytecode, CodeOffset, CodeUnit, JumpTableInner};
+use move_bytecode_verifier::absint::VMControlFlowGraph;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A conditional branch instruction, along with the offsets it can continue execution at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchPoint {
+    /// Offset of the branch instruction
+    pub offset: CodeOffset,
+    /// Destinations of the branch, one per branch (for `BrTrue`/`BrFalse` the jump target comes
+    /// first and the fall-through second; for `VariantSwitch` arms are listed in the order of
+    /// variant tags, with arms sharing a destination counted once).
+    pub destinations: Vec<CodeOffset>,
+}
+
+/// Returns the branch points of a function, ordered by offset.
+pub fn branch_points(code: &CodeUnit) -> Vec<BranchPoint> {
+    let cfg = VMControlFlowGraph::new(&code.code, &code.jump_tables);
+    cfg_branch_points(&cfg, code)
+}
+
+fn cfg_branch_points(cfg: &VMControlFlowGraph, code: &CodeUnit) -> Vec<BranchPoint> {
+    cfg.blocks()
+        .filter_map(|block_id| {
+            let offset = cfg.block_end(block_id);
+            let mut destinations = match &code.code[offset as usize] {
+                Bytecode::BrTrue(target) | Bytecode::BrFalse(target) => vec![*target, offset + 1],
+                Bytecode::VariantSwitch(jt_idx) => {
+                    let JumpTableInner::Full(arms) =
+                        &code.jump_tables[jt_idx.0 as usize].jump_table;
+                    arms.clone()
+                }
+                _ => return None,
+            };
+            let mut seen = BTreeSet::new();
+            destinations.retain(|dest| seen.insert(*dest));
+            // a "branch" whose every edge leads to the same place is not a choice
+            (destinations.len() > 1).then_some(BranchPoint {
+                offset,
+                destinations,
+            })
+        })
+        .collect()
+}
+
+/// Computes how many times each branch of each branch point of a function was taken, given how
+/// many times each instruction of the function was executed (instructions not in `counts` were
+/// not executed).
+///
+/// Edge counts are derived from the fact that the number of times a block is entered equals the
+/// number of times its predecessors jumped to it. This determines all edge counts in the common
+/// cases (e.g., `if`/`else` and `match` arms, or loops with a single exit), but if it does not
+/// (e.g., two blocks are both reachable through several conditional branches that were all
+/// executed), the undetermined edges are reported as not taken, so that coverage is never
+/// overstated. Counting edges from an execution trace (see `crate::lcov`) is exact.
+pub fn branch_hits(
+    code: &CodeUnit,
+    counts: &BTreeMap<CodeOffset, u64>,
+) -> Vec<(BranchPoint, Vec<u64>)> {
+    let cfg = VMControlFlowGraph::new(&code.code, &code.jump_tables);
+    let count = |pc: CodeOffset| counts.get(&pc).copied().unwrap_or(0);
+
+    let mut edges: Vec<(CodeOffset, CodeOffset)> = vec![];
+    let mut outgoing: BTreeMap<CodeOffset, Vec<usize>> = BTreeMap::new();
+    let mut incoming: BTreeMap<CodeOffset, Vec<usize>> = BTreeMap::new();
+    for block_id in cfg.blocks() {
+        // (`VariantSwitch` arms sharing a destination are listed as separate successors)
+        let successors: BTreeSet<_> = cfg.successors(block_id).collect();
+        for succ_id in successors {
+            outgoing.entry(block_id).or_default().push(edges.len());
+            incoming.entry(succ_id).or_default().push(edges.len());
+            edges.push((block_id, succ_id));
+        }
+    }
+
+    let mut edge_counts: Vec<Option<u64>> = edges
+        .iter()
+        .map(|(from, to)| {
+            if count(cfg.block_end(*from)) == 0 || count(cfg.block_start(*to)) == 0 {
+                Some(0)
+            } else if outgoing[from].len() == 1 {
+                Some(count(cfg.block_end(*from)))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // each constraint requires the counts of a set of edges to add up to a given total
+    let mut constraints: Vec<(&[usize], u64)> = vec![];
+    // (block entries come first, as they are not affected by aborts)
+    for (block_id, edge_ids) in &incoming {
+        // the entry block is also entered when the function is called
+        if *block_id != cfg.entry_block_id() {
+            constraints.push((edge_ids.as_slice(), count(cfg.block_start(*block_id))));
+        }
+    }
+    for (block_id, edge_ids) in &outgoing {
+        constraints.push((edge_ids.as_slice(), count(cfg.block_end(*block_id))));
+    }
+    let mut progress = true;
+    while progress {
+        progress = false;
+        for (edge_ids, total) in &constraints {
+            let mut unknown = edge_ids.iter().filter(|e| edge_counts[**e].is_none());
+            let (Some(edge_id), None) = (unknown.next(), unknown.next()) else {
+                continue;
+            };
+            let known: u64 = edge_ids.iter().filter_map(|e| edge_counts[*e]).sum();
+            edge_counts[*edge_id] = Some(total.saturating_sub(known));
+            progress = true;
+        }
+    }
+
+    let edge_count = |from: CodeOffset, to: CodeOffset| {
+        let edge_id = outgoing[&from]
+            .iter()
+            .copied()
+            .find(|e| edges[*e].1 == to)
+            .unwrap();
+        edge_counts[edge_id].unwrap_or(0)
+    };
+    let block_ends: BTreeMap<_, _> = cfg.blocks().map(|b| (cfg.block_end(b), b)).collect();
+    cfg_branch_points(&cfg, code)
+        .into_iter()
+        .map(|point| {
+            let block_id = block_ends[&point.offset];
+            let hits = point
+                .destinations
+                .iter()
+                .map(|dest| edge_count(block_id, *dest))
+                .collect();
+            (point, hits)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::{
+        EnumDefinitionIndex, SignatureIndex, VariantJumpTable, VariantJumpTableIndex,
+    };
+
+    fn code_unit(code: Vec<Bytecode>, jump_tables: Vec<JumpTableInner>) -> CodeUnit {
+        CodeUnit {
+            locals: SignatureIndex(0),
+            code,
+            jump_tables: jump_tables
+                .into_iter()
+                .map(|jump_table| VariantJumpTable {
+                    head_enum: EnumDefinitionIndex(0),
+                    jump_table,
+                })
+                .collect(),
+        }
+    }
+
+    /// Branch points as `(offset, destinations, hits)`
+    fn hits(code: &CodeUnit, counts: &[u64]) -> Vec<(CodeOffset, Vec<CodeOffset>, Vec<u64>)> {
+        let counts = counts
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c > 0)
+            .map(|(pc, c)| (pc as CodeOffset, *c))
+            .collect();
+        branch_hits(code, &counts)
+            .into_iter()
+            .map(|(point, hits)| (point.offset, point.destinations, hits))
+            .collect()
+    }
+
+    // if (x) 1 else 2
+    fn if_else() -> CodeUnit {
+        code_unit(
+            vec![
+                Bytecode::CopyLoc(0),
+                Bytecode::BrFalse(4),
+                Bytecode::LdU64(1),
+                Bytecode::Branch(5),
+                Bytecode::LdU64(2),
+                Bytecode::Ret,
+            ],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn if_else_both_branches() {
+        let code = if_else();
+        assert_eq!(branch_points(&code).len(), 1);
+        assert_eq!(
+            hits(&code, &[3, 3, 2, 2, 1, 3]),
+            vec![(1, vec![4, 2], vec![1, 2])]
+        );
+    }
+
+    #[test]
+    fn if_else_one_branch() {
+        let code = if_else();
+        assert_eq!(
+            hits(&code, &[2, 2, 2, 2, 0, 2]),
+            vec![(1, vec![4, 2], vec![0, 2])]
+        );
+        assert_eq!(
+            hits(&code, &[0, 0, 0, 0, 0, 0]),
+            vec![(1, vec![4, 2], vec![0, 0])]
+        );
+    }
+
+    #[test]
+    fn loop_exit() {
+        // while (x) { ... }, iterating 3 times
+        let code = code_unit(
+            vec![
+                Bytecode::LdU64(0),
+                Bytecode::CopyLoc(0),
+                Bytecode::BrFalse(5),
+                Bytecode::Pop,
+                Bytecode::Branch(1),
+                Bytecode::Ret,
+            ],
+            vec![],
+        );
+        assert_eq!(
+            hits(&code, &[1, 4, 4, 3, 3, 1]),
+            vec![(2, vec![5, 3], vec![1, 3])]
+        );
+    }
+
+    #[test]
+    fn variant_switch_shared_arms() {
+        // match (x) { A | C => 1, B => 2 }, matching `B` twice and `A` or `C` once
+        let code = code_unit(
+            vec![
+                Bytecode::MoveLoc(0),
+                Bytecode::VariantSwitch(VariantJumpTableIndex(0)),
+                Bytecode::LdU64(1),
+                Bytecode::Branch(5),
+                Bytecode::LdU64(2),
+                Bytecode::Ret,
+            ],
+            vec![JumpTableInner::Full(vec![2, 4, 2])],
+        );
+        assert_eq!(
+            hits(&code, &[3, 3, 1, 1, 2, 3]),
+            vec![(1, vec![2, 4], vec![1, 2])]
+        );
+    }
+
+    #[test]
+    fn variant_switch_single_destination() {
+        let code = code_unit(
+            vec![
+                Bytecode::MoveLoc(0),
+                Bytecode::VariantSwitch(VariantJumpTableIndex(0)),
+                Bytecode::Ret,
+            ],
+            vec![JumpTableInner::Full(vec![2, 2])],
+        );
+        assert!(branch_points(&code).is_empty());
+    }
+
+    #[test]
+    fn undetermined_diamond() {
+        // Two branches (at 3 and 6) each jumping to one of the same two blocks (at 4 and 7). Run
+        // once through 1 -> 2, 3 -> 4 and once through 1 -> 5, 6 -> 7, which block counts cannot
+        // tell apart from running through 3 -> 7 and 6 -> 4.
+        let code = code_unit(
+            vec![
+                Bytecode::CopyLoc(0),
+                Bytecode::BrTrue(5),
+                Bytecode::CopyLoc(1),
+                Bytecode::BrTrue(7),
+                Bytecode::Ret,
+                Bytecode::CopyLoc(1),
+                Bytecode::BrFalse(4),
+                Bytecode::Ret,
+            ],
+            vec![],
+        );
+        assert_eq!(
+            hits(&code, &[2, 2, 1, 1, 1, 1, 1, 1]),
+            vec![
+                (1, vec![5, 2], vec![1, 1]),
+                (3, vec![7, 4], vec![0, 0]),
+                (6, vec![4, 7], vec![0, 0]),
+            ]
+        );
+    }
+}
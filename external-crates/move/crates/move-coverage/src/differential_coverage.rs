@@ -142,10 +142,14 @@ pub fn differential_branch(
     f_branch: &LRS::branch::Value,
     t_branch: &LRS::branch::Value,
 ) -> anyhow::Result<Option<LRS::branch::Value>> {
-    let taken = if f_branch.taken.is_some() && t_branch.taken.is_some() {
+    // a branch is hit if it was taken (branches of an executed branch instruction that were not
+    // taken have a count of 0)
+    let f_hit = f_branch.taken.is_some_and(|t| t > 0);
+    let t_hit = t_branch.taken.is_some_and(|t| t > 0);
+    let taken = if f_hit && t_hit {
         // both hit -- uncovered
         Some(None)
-    } else if f_branch.taken.is_some() {
+    } else if f_hit {
         // only f_branch hit -- hit
         Some(f_branch.taken)
     } else {
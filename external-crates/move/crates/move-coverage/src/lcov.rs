@@ -1,10 +1,9 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::branches::branch_points;
 use lcov::record::Record as LRecord;
-use move_abstract_interpreter::control_flow_graph::ControlFlowGraph;
-use move_binary_format::file_format::FunctionDefinitionIndex;
-use move_bytecode_verifier::absint::VMControlFlowGraph;
+use move_binary_format::file_format::{CodeOffset, FunctionDefinitionIndex};
 use move_compiler::{
     compiled_unit::CompiledUnit, shared::files::MappedFiles,
     unit_test::filter_test_members::UNIT_TEST_POISON_FUN_NAME,
@@ -51,28 +50,30 @@ pub struct FileRecordKeeper {
     branches: BTreeMap<(u16, u16), BranchInfo>,
 }
 
+// A conditional branch (see `crate::branches`). Branch numbers reported in LCOV are indices into
+// `branches`.
 #[derive(Debug, Clone)]
 struct BranchInfo {
     line_no: LineNumber,
     block_id: BlockNumber,
-    branches: BTreeMap<BranchNumber, HitCount>,
+    // Number of times the branch instruction was executed
+    executions: HitCount,
+    // Destinations of the branch, along with the number of times each was jumped to
+    branches: Vec<(CodeOffset, HitCount)>,
 }
 
 impl BranchInfo {
-    pub fn new(line_no: LineNumber, block_id: BlockNumber) -> Self {
+    pub fn new(line_no: LineNumber, block_id: BlockNumber, destinations: &[CodeOffset]) -> Self {
         Self {
             line_no,
             block_id,
-            branches: BTreeMap::new(),
+            executions: 0,
+            branches: destinations.iter().map(|dest| (*dest, 0)).collect(),
         }
     }
 
-    pub fn add_branch(&mut self, branch: BranchNumber) {
-        self.branches.insert(branch, 0);
-    }
-
-    pub fn hit_branch(&mut self, branch: BranchNumber) {
-        if let Some(h) = self.branches.get_mut(&branch) {
+    pub fn hit_branch(&mut self, dest: CodeOffset) {
+        if let Some((_, h)) = self.branches.iter_mut().find(|(d, _)| *d == dest) {
             *h += 1;
         }
     }
@@ -171,9 +172,8 @@ impl PackageRecordKeeper {
 
                     coming_from = None;
 
-                    let branch_info_opt = record.branches.get_mut(&(*current_fn_index, pc));
-
-                    if branch_info_opt.is_some() {
+                    if let Some(info) = record.branches.get_mut(&(*current_fn_index, pc)) {
+                        info.executions += 1;
                         coming_from = Some(pc);
                     }
                 }
@@ -247,7 +247,7 @@ impl FileRecordKeeper {
             LRecord::BranchesHit {
                 hit: branches
                     .values()
-                    .map(|info| info.branches.values().filter(|hi| **hi > 0).count())
+                    .map(|info| info.branches.iter().filter(|(_, hi)| *hi > 0).count())
                     .sum::<usize>() as u32,
             },
         ];
@@ -277,15 +277,17 @@ impl FileRecordKeeper {
         for BranchInfo {
             line_no,
             block_id,
+            executions,
             branches,
         } in branches.values()
         {
-            for (&branch_id, &hit_count) in branches {
+            for (branch_id, &(_, hit_count)) in branches.iter().enumerate() {
                 records.push(LRecord::BranchData {
                     line: *line_no as u32,
                     block: *block_id as u32,
                     branch: branch_id as u32,
-                    taken: if hit_count == 0 {
+                    // `-` (rather than 0) marks branches of instructions that never executed
+                    taken: if *executions == 0 {
                         None
                     } else {
                         Some(hit_count as u64)
@@ -299,18 +301,6 @@ impl FileRecordKeeper {
         records
     }
 
-    fn build_control_flow_graph(&self, function_index: u16) -> VMControlFlowGraph {
-        let fdef = self
-            .unit
-            .module
-            .function_def_at(FunctionDefinitionIndex(function_index));
-        let code = fdef
-            .code
-            .as_ref()
-            .expect("Should only be called on non-native funs");
-        VMControlFlowGraph::new(&code.code, &code.jump_tables)
-    }
-
     // Build up the functions found, instrumented lines, and branches found.
     fn populate_info_fields(&mut self, file_mapping: &MappedFiles) {
         let mut block_id = 0;
@@ -347,18 +337,16 @@ impl FileRecordKeeper {
                     self.instrumented_lines.insert(line_no);
                 }
 
-                let cfg = self.build_control_flow_graph(index as u16);
-                for cfg_block_id in cfg.blocks() {
-                    let block_end = cfg.block_end(cfg_block_id);
-                    let loc = f_source_map.get_code_location(block_end).unwrap();
+                for point in branch_points(code) {
+                    let Some(loc) = f_source_map.get_code_location(point.offset) else {
+                        continue;
+                    };
                     let line_no = file_mapping.start_position(&loc).line_offset() + 1;
+                    self.branches.insert(
+                        (index as u16, point.offset),
+                        BranchInfo::new(line_no, block_id, &point.destinations),
+                    );
                     block_id += 1;
-                    for o in cfg.successors(cfg_block_id) {
-                        self.branches
-                            .entry((index as u16, block_end))
-                            .or_insert_with(|| BranchInfo::new(line_no, block_id - 1))
-                            .add_branch(o);
-                    }
                 }
             }
         }
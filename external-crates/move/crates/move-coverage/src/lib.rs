@@ -6,6 +6,7 @@ use crate::summary::ModuleSummary;
 use move_binary_format::CompiledModule;
 use std::io::Write;
 
+pub mod branches;
 pub mod coverage_map;
 pub mod differential_coverage;
 pub mod lcov;
@@ -27,6 +28,8 @@ pub fn format_human_summary<'a, M, F, W: Write>(
 
     let mut total_covered = 0;
     let mut total_instructions = 0;
+    let mut total_branches_covered = 0;
+    let mut total_branches = 0;
 
     for module in modules {
         let coverage_summary = summary_func(module, coverage_map);
@@ -35,16 +38,28 @@ pub fn format_human_summary<'a, M, F, W: Write>(
             .unwrap();
         total_covered += covered;
         total_instructions += total;
+        let (branches, branches_covered) = coverage_summary.branch_totals();
+        total_branches_covered += branches_covered;
+        total_branches += branches;
     }
 
-    writeln!(summary_writer, "+-------------------------+").unwrap();
-    writeln!(
-        summary_writer,
-        "| % Move Coverage: {:.2}  |",
+    let mut rows = vec![format!(
+        "% Move Coverage: {:.2}",
         (total_covered as f64 / total_instructions as f64) * 100f64
-    )
-    .unwrap();
-    writeln!(summary_writer, "+-------------------------+").unwrap();
+    )];
+    if total_branches > 0 {
+        rows.push(format!(
+            "% Move Branch Coverage: {:.2}",
+            (total_branches_covered as f64 / total_branches as f64) * 100f64
+        ));
+    }
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let border = format!("+{}+", "-".repeat(width + 2));
+    writeln!(summary_writer, "{border}").unwrap();
+    for row in rows {
+        writeln!(summary_writer, "| {row:<width$} |").unwrap();
+    }
+    writeln!(summary_writer, "{border}").unwrap();
 }
 
 pub fn format_csv_summary<'a, M, F, W: Write>(
@@ -55,7 +70,11 @@ pub fn format_csv_summary<'a, M, F, W: Write>(
 ) where
     F: Fn(&CompiledModule, &M) -> ModuleSummary,
 {
-    writeln!(summary_writer, "ModuleName,FunctionName,Covered,Uncovered").unwrap();
+    writeln!(
+        summary_writer,
+        "ModuleName,FunctionName,Covered,Uncovered,BranchesCovered,BranchesUncovered"
+    )
+    .unwrap();
 
     for module in modules {
         let coverage_summary = summary_func(module, coverage_map);
@@ -4,8 +4,9 @@
 
 #![forbid(unsafe_code)]
 
-use crate::coverage_map::{
-    ExecCoverageMap, ExecCoverageMapWithModules, ModuleCoverageMap, TraceMap,
+use crate::{
+    branches::branch_hits,
+    coverage_map::{ExecCoverageMap, ExecCoverageMapWithModules, ModuleCoverageMap, TraceMap},
 };
 use move_abstract_interpreter::control_flow_graph::{ControlFlowGraph, VMControlFlowGraph};
 use move_binary_format::{
@@ -33,6 +34,12 @@ pub struct FunctionSummary {
     pub fn_is_native: bool,
     pub total: u64,
     pub covered: u64,
+    /// Number of branches of conditional branch instructions (see `crate::branches`), only
+    /// computed for instruction coverage
+    #[serde(default)]
+    pub branches_total: u64,
+    #[serde(default)]
+    pub branches_covered: u64,
 }
 
 pub struct FunctionInfo {
@@ -52,20 +59,27 @@ impl ModuleSummary {
             self.module_name.name()
         );
 
-        let mut format_line = |fn_name, covered, uncovered| {
-            writeln!(
-                summary_writer,
-                "{},{},{},{}",
-                module, fn_name, covered, uncovered
-            )
-        };
+        let mut format_line =
+            |fn_name, covered, uncovered, branches_covered, branches_uncovered| {
+                writeln!(
+                    summary_writer,
+                    "{},{},{},{},{},{}",
+                    module, fn_name, covered, uncovered, branches_covered, branches_uncovered
+                )
+            };
 
         for (fn_name, fn_summary) in self
             .function_summaries
             .iter()
             .filter(|(_, summary)| !summary.fn_is_native)
         {
-            format_line(fn_name, fn_summary.covered, fn_summary.total)?;
+            format_line(
+                fn_name,
+                fn_summary.covered,
+                fn_summary.total,
+                fn_summary.branches_covered,
+                fn_summary.branches_total - fn_summary.branches_covered,
+            )?;
         }
 
         Ok(())
@@ -106,6 +120,23 @@ impl ModuleSummary {
                     "\t\t% coverage: {:.2}",
                     fn_summary.percent_coverage()
                 )?;
+                if fn_summary.branches_total > 0 {
+                    writeln!(
+                        summary_writer,
+                        "\t\tbranches: {}",
+                        fn_summary.branches_total
+                    )?;
+                    writeln!(
+                        summary_writer,
+                        "\t\tbranches covered: {}",
+                        fn_summary.branches_covered
+                    )?;
+                    writeln!(
+                        summary_writer,
+                        "\t\t% branch coverage: {:.2}",
+                        fn_summary.percent_branch_coverage()
+                    )?;
+                }
             }
         }
 
@@ -123,14 +154,39 @@ impl ModuleSummary {
             )?;
         }
 
+        let (branches_total, branches_covered) = self.branch_totals();
+        if branches_total > 0 {
+            writeln!(
+                summary_writer,
+                ">>> % Module branch coverage: {:.2}",
+                (branches_covered as f64) / (branches_total as f64) * 100f64
+            )?;
+        }
+
         Ok((all_total, all_covered))
     }
+
+    /// Returns the total number of branches in the module, and the number of covered ones.
+    pub fn branch_totals(&self) -> (u64, u64) {
+        self.function_summaries
+            .values()
+            .fold((0, 0), |(total, covered), fn_summary| {
+                (
+                    total + fn_summary.branches_total,
+                    covered + fn_summary.branches_covered,
+                )
+            })
+    }
 }
 
 impl FunctionSummary {
     pub fn percent_coverage(&self) -> f64 {
         (self.covered as f64) / (self.total as f64) * 100f64
     }
+
+    pub fn percent_branch_coverage(&self) -> f64 {
+        (self.branches_covered as f64) / (self.branches_total as f64) * 100f64
+    }
 }
 
 pub fn summarize_inst_cov_by_module(
@@ -150,21 +206,30 @@ pub fn summarize_inst_cov_by_module(
                     fn_is_native: true,
                     total: 0,
                     covered: 0,
+                    branches_total: 0,
+                    branches_covered: 0,
                 },
                 Some(code_unit) => {
                     let total_number_of_instructions = code_unit.code.len() as u64;
-                    let covered_instructions = module_map
-                        .and_then(|fn_map| {
-                            fn_map
-                                .function_maps
-                                .get(&fn_name)
-                                .map(|function_map| function_map.len())
-                        })
-                        .unwrap_or(0) as u64;
+                    let function_map =
+                        module_map.and_then(|fn_map| fn_map.function_maps.get(&fn_name));
+                    let covered_instructions =
+                        function_map.map_or(0, |function_map| function_map.len()) as u64;
+                    let counts = function_map
+                        .into_iter()
+                        .flatten()
+                        .map(|(pc, count)| (*pc as CodeOffset, *count))
+                        .collect();
+                    let branch_hits = branch_hits(code_unit, &counts);
                     FunctionSummary {
                         fn_is_native: false,
                         total: total_number_of_instructions,
                         covered: covered_instructions,
+                        branches_total: branch_hits.iter().map(|(_, hits)| hits.len() as u64).sum(),
+                        branches_covered: branch_hits
+                            .iter()
+                            .map(|(_, hits)| hits.iter().filter(|h| **h > 0).count() as u64)
+                            .sum(),
                     }
                 }
             };
@@ -435,6 +500,8 @@ pub fn summarize_path_cov(module: &CompiledModule, trace_map: &TraceMap) -> Modu
                     fn_is_native: true,
                     total: 0,
                     covered: 0,
+                    branches_total: 0,
+                    branches_covered: 0,
                 },
                 Some(_) => FunctionSummary {
                     fn_is_native: false,
@@ -443,6 +510,8 @@ pub fn summarize_path_cov(module: &CompiledModule, trace_map: &TraceMap) -> Modu
                         None => 0,
                         Some(pathset) => pathset.len() as u64,
                     },
+                    branches_total: 0,
+                    branches_covered: 0,
                 },
             };
 
@@ -235,6 +235,30 @@ impl<'a> BuildPlan<'a> {
         self.compile_with_driver_and_deps(dependencies, writer, compiler_driver)
     }
 
+    /// Runs the compiler driver on the package without saving the compilation results to disk, e.g.,
+    /// to compile modified versions of the package's code.
+    pub fn compile_with_driver_no_save<W: Write, T>(
+        &self,
+        dependencies: CompilationDependencies,
+        writer: &mut W,
+        compiler_driver: impl FnMut(Compiler) -> Result<T>,
+    ) -> Result<T> {
+        let CompilationDependencies {
+            root_package,
+            project_root: _,
+            transitive_dependencies,
+        } = dependencies;
+
+        CompiledPackage::build_for_result(
+            writer,
+            self.compiler_vfs_root.clone(),
+            root_package,
+            transitive_dependencies,
+            self.resolution_graph,
+            compiler_driver,
+        )
+    }
+
     pub fn compile_with_driver_and_deps<W: Write>(
         &self,
         dependencies: CompilationDependencies,
@@ -72,7 +72,7 @@ fn native_print(
             single_line,
             include_int_types,
         )?;
-        println!("{}", out);
+        testing::print_output(&out);
     }
 
     Ok(NativeResult::ok(gas_params.base_cost, smallvec![]))
@@ -134,7 +134,7 @@ fn native_print_stack_trace(
     {
         let mut s = String::new();
         context.print_stack_trace(&mut s)?;
-        println!("{}", s);
+        testing::print_output(&s);
     }
 
     Ok(NativeResult::ok(gas_params.base_cost, smallvec![]))
@@ -159,6 +159,27 @@ pub fn make_native_print_stack_trace(
     }
 }
 
+/***************************************************************************************************
+ * output capture
+ **************************************************************************************************/
+
+/// Runs `f`, returning (along with its result) the output printed by `print` and
+/// `print_stack_trace` on the current thread while it ran. The output is still written to
+/// standard output as well.
+#[cfg(feature = "testing")]
+pub fn with_captured_output<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let outer = testing::CAPTURED_OUTPUT.replace(Some(String::new()));
+    let result = f();
+    let captured = testing::CAPTURED_OUTPUT.replace(outer).unwrap_or_default();
+    // an enclosing capture sees the output as well
+    testing::CAPTURED_OUTPUT.with_borrow_mut(|outer| {
+        if let Some(outer) = outer {
+            outer.push_str(&captured);
+        }
+    });
+    (result, captured)
+}
+
 /***************************************************************************************************
  * module
  **************************************************************************************************/
@@ -196,7 +217,22 @@ mod testing {
     };
     use move_vm_runtime::native_functions::NativeContext;
     use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
-    use std::{fmt, fmt::Write};
+    use std::{cell::RefCell, fmt, fmt::Write};
+
+    thread_local! {
+        /// Output printed on the current thread, if it is being captured
+        pub(super) static CAPTURED_OUTPUT: RefCell<Option<String>> = const { RefCell::new(None) };
+    }
+
+    pub(super) fn print_output(out: &str) {
+        println!("{}", out);
+        CAPTURED_OUTPUT.with_borrow_mut(|captured| {
+            if let Some(captured) = captured {
+                captured.push_str(out);
+                captured.push('\n');
+            }
+        });
+    }
 
     const VECTOR_BEGIN: &str = "[";
 
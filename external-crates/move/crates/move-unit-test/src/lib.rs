@@ -7,7 +7,7 @@ pub mod extensions;
 pub mod test_reporter;
 pub mod test_runner;
 
-use crate::test_runner::TestRunner;
+use crate::{test_reporter::ReportFormat, test_runner::TestRunner};
 use anyhow::{Result, bail};
 use clap::*;
 use move_binary_format::CompiledModule;
@@ -22,7 +22,7 @@ use move_compiler::{
 use move_core_types::language_storage::ModuleId;
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use move_vm_test_utils::gas_schedule::CostTable;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex};
+use std::{collections::BTreeMap, io::Write, marker::Send, path::PathBuf, sync::Mutex};
 
 /// The default value bounding the amount of gas consumed in a test.
 const DEFAULT_EXECUTION_BOUND: u64 = 1_000_000;
@@ -125,6 +125,20 @@ pub struct UnitTestingConfig {
     // Enable tracing for tests
     #[clap(long = TRACE_FLAG)]
     pub trace_execution: bool,
+
+    /// Write a machine-readable report of test results in the given format (requires
+    /// `--report-path`)
+    #[clap(
+        name = "report-format",
+        long = "report-format",
+        value_enum,
+        requires = "report-path"
+    )]
+    pub report_format: Option<ReportFormat>,
+
+    /// File to write the machine-readable report of test results to (requires `--report-format`)
+    #[clap(name = "report-path", long = "report-path", requires = "report-format")]
+    pub report_path: Option<PathBuf>,
 }
 
 fn format_module_id(
@@ -157,6 +171,8 @@ impl UnitTestingConfig {
             seed: None,
             deterministic_generation: false,
             trace_execution: false,
+            report_format: None,
+            report_path: None,
         }
     }
 
@@ -295,6 +311,9 @@ impl UnitTestingConfig {
         if let Some(report_type) = &self.report_statistics {
             test_results.report_statistics(&shared_writer, report_type)?;
         }
+        if let (Some(report_format), Some(report_path)) = (self.report_format, &self.report_path) {
+            test_results.write_report(report_format, report_path)?;
+        }
 
         let ok = test_results.summarize(&shared_writer)?;
 
@@ -16,8 +16,10 @@ use move_core_types::{
 };
 use move_ir_types::location::Loc;
 use move_trace_format::format::MoveTrace;
+use serde::Serialize;
 use std::{
     collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
     io::{Result, Write},
     path::Path,
     sync::Mutex,
@@ -53,10 +55,22 @@ pub struct TestFailure {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TestRunInfo {
     pub elapsed_time: Duration,
+    pub gas_used: u64,
     pub instructions_executed: u64,
+    /// Output of `std::debug::print` (and `print_stack_trace`) during the run
+    pub output: String,
     pub trace: Option<Vec<u8>>,
 }
 
+/// Format of a machine-readable report of test results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// JUnit XML, as understood by most CI systems
+    Junit,
+    /// JSON, with one entry per test
+    Json,
+}
+
 type TestRuns<T> = BTreeMap<String, Vec<T>>;
 
 #[derive(Debug, Clone)]
@@ -71,8 +85,61 @@ pub struct TestResults {
     test_plan: TestPlan,
 }
 
-fn write_bytes_to_file(filepath: &str, content: &[u8]) -> std::io::Result<()> {
-    let path = Path::new(filepath);
+/// Machine-readable report of test results (also used to generate the JUnit report)
+#[derive(Serialize)]
+struct JsonReport {
+    total: usize,
+    passed: usize,
+    failed: usize,
+    tests: Vec<JsonTest>,
+}
+
+/// Results of a test, summed over all of its runs (a `#[random_test]` is run multiple times)
+#[derive(Serialize)]
+struct JsonTest {
+    name: String,
+    module: String,
+    passed: bool,
+    /// Number of times the test was run
+    runs: usize,
+    duration_secs: f64,
+    gas_used: u64,
+    instructions_executed: u64,
+    output: String,
+    failures: Vec<JsonFailure>,
+}
+
+#[derive(Serialize)]
+struct JsonFailure {
+    kind: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<JsonError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actual: Option<JsonError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct JsonError {
+    /// VM status code, e.g. `ABORTED`
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    abort_code: Option<u64>,
+    /// Name of the error constant of a clever error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_constant: Option<String>,
+    /// Source line of a clever error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_line: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    module: Option<String>,
+    description: String,
+}
+
+fn write_bytes_to_file(filepath: impl AsRef<Path>, content: &[u8]) -> std::io::Result<()> {
+    let path = filepath.as_ref();
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
@@ -84,12 +151,16 @@ fn write_bytes_to_file(filepath: &str, content: &[u8]) -> std::io::Result<()> {
 impl TestRunInfo {
     pub fn new(
         elapsed_time: Duration,
+        gas_used: u64,
         instructions_executed: u64,
+        output: String,
         trace: Option<MoveTrace>,
     ) -> Self {
         Self {
             elapsed_time,
+            gas_used,
             instructions_executed,
+            output,
             trace: trace.map(|t| t.into_compressed_json_bytes()),
         }
     }
@@ -178,6 +249,10 @@ impl TestFailure {
     }
 
     pub fn render_error(&self, test_plan: &TestPlan) -> String {
+        self.render_error_impl(test_plan, control::SHOULD_COLORIZE.should_colorize())
+    }
+
+    fn render_error_impl(&self, test_plan: &TestPlan, colorize: bool) -> String {
         match &self.failure_reason {
             FailureReason::NoError(message) => message.to_string(),
             FailureReason::Timeout(message) => message.to_string(),
@@ -189,7 +264,7 @@ impl TestFailure {
                         .present_tense(),
                     actual.with_context(&test_plan.module_info).past_tense(),
                 );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error, colorize)
             }
             FailureReason::WrongAbortDEPRECATED(message, expected_code, actual) => {
                 let base_message = format!(
@@ -199,7 +274,7 @@ impl TestFailure {
                     expected_code,
                     actual.with_context(&test_plan.module_info).past_tense(),
                 );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error, colorize)
             }
             FailureReason::UnexpectedError(message, error) => {
                 let prefix = match error.0.status_type() {
@@ -223,7 +298,7 @@ impl TestFailure {
                     message,
                     error.with_context(&test_plan.module_info).past_tense(),
                 );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error, colorize)
             }
             FailureReason::Property(message) => message.clone(),
         }
@@ -281,12 +356,13 @@ impl TestFailure {
         test_plan: &TestPlan,
         base_message: String,
         vm_error: &Option<VMError>,
+        colorize: bool,
     ) -> String {
         let report_diagnostics = |mapped_files, diags| {
             diagnostics::report_diagnostics_to_buffer_with_mapped_files(
                 mapped_files,
                 diags,
-                control::SHOULD_COLORIZE.should_colorize(),
+                colorize,
             )
         };
 
@@ -352,6 +428,170 @@ impl TestFailure {
     }
 }
 
+impl TestFailure {
+    fn json_failure(&self, test_plan: &TestPlan) -> JsonFailure {
+        let actual_code = self.vm_error.as_ref().and_then(|e| e.sub_status());
+        let (kind, expected, actual) = match &self.failure_reason {
+            FailureReason::NoError(_) => ("no_error", None, None),
+            FailureReason::WrongError(_, expected, actual) => (
+                "wrong_error",
+                Some(json_error(test_plan, expected, None, false)),
+                Some(json_error(test_plan, actual, actual_code, true)),
+            ),
+            FailureReason::WrongAbortDEPRECATED(_, expected_code, actual) => {
+                let (abort_code, error_constant) = match expected_code {
+                    MoveErrorType::Code(code) => (Some(*code), None),
+                    MoveErrorType::ConstantName(name) => (None, Some(name.clone())),
+                };
+                let expected = JsonError {
+                    status: format!("{:?}", StatusCode::ABORTED),
+                    abort_code,
+                    error_constant,
+                    source_line: None,
+                    module: None,
+                    description: format!("to abort with code {expected_code}"),
+                };
+                (
+                    "wrong_abort_code",
+                    Some(expected),
+                    Some(json_error(test_plan, actual, actual_code, true)),
+                )
+            }
+            FailureReason::UnexpectedError(_, actual) => (
+                "unexpected_error",
+                None,
+                Some(json_error(test_plan, actual, actual_code, true)),
+            ),
+            FailureReason::Timeout(_) => ("timeout", None, None),
+            FailureReason::Property(_) => ("property", None, None),
+        };
+        JsonFailure {
+            kind,
+            message: self.render_error_impl(test_plan, /* colorize */ false),
+            expected,
+            actual,
+            seed: self.prng_seed,
+        }
+    }
+}
+
+/// Describes an (expected or actual) error. `abort_code` is the raw abort code (if any), which
+/// for clever errors also encodes the source line of the abort.
+fn json_error(
+    test_plan: &TestPlan,
+    error: &MoveError,
+    abort_code: Option<u64>,
+    is_past_tense: bool,
+) -> JsonError {
+    let MoveError(status, sub_status, location) = error;
+    let (code, error_constant) = match sub_status {
+        Some(MoveErrorType::Code(code)) => (Some(*code), None),
+        Some(MoveErrorType::ConstantName(name)) => (None, Some(name.clone())),
+        None => (None, None),
+    };
+    let description = error.with_context(&test_plan.module_info);
+    let description = if is_past_tense {
+        description.past_tense()
+    } else {
+        description.present_tense()
+    };
+    JsonError {
+        status: format!("{status:?}"),
+        abort_code: abort_code.or(code),
+        error_constant,
+        source_line: abort_code
+            .and_then(ErrorBitset::from_u64)
+            .and_then(|bitset| bitset.line_number()),
+        module: match location {
+            Location::Module(module_id) => {
+                Some(format_module_id(&test_plan.module_info, module_id))
+            }
+            Location::Undefined => None,
+        },
+        description: description.to_string(),
+    }
+}
+
+/// Renders a report in the JUnit XML format, with one test suite per module
+fn junit_report(report: &JsonReport) -> String {
+    let mut modules: BTreeMap<&str, Vec<&JsonTest>> = BTreeMap::new();
+    for test in &report.tests {
+        modules.entry(test.module.as_str()).or_default().push(test);
+    }
+    let time = |tests: &[&JsonTest]| tests.iter().map(|t| t.duration_secs).sum::<f64>();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let all_tests = report.tests.iter().collect::<Vec<_>>();
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"Move unit tests\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+        report.total,
+        report.failed,
+        time(&all_tests),
+    );
+    for (module, tests) in modules {
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+            xml_escape(module),
+            tests.len(),
+            tests.iter().filter(|t| !t.passed).count(),
+            time(&tests),
+        );
+        for test in tests {
+            let _ = writeln!(
+                xml,
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">",
+                xml_escape(&test.name),
+                xml_escape(module),
+                test.duration_secs,
+            );
+            for failure in &test.failures {
+                let message = failure.message.lines().next().unwrap_or_default();
+                let _ = writeln!(
+                    xml,
+                    "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+                    failure.kind,
+                    xml_escape(message),
+                    xml_escape(&failure.message),
+                );
+            }
+            // JUnit has no standard place for per-test metrics, so they are reported in the
+            // test's output, ahead of anything the test printed
+            let _ = writeln!(
+                xml,
+                "      <system-out>runs: {}\ngas used: {}\ninstructions executed: {}\n{}</system-out>",
+                test.runs,
+                test.gas_used,
+                test.instructions_executed,
+                xml_escape(&test.output),
+            );
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Escapes text for use in XML attributes and elements, dropping characters XML does not allow
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => (),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 impl Default for TestStatistics {
     fn default() -> Self {
         Self::new()
@@ -419,10 +659,10 @@ fn calculate_run_statistics<'a, I: IntoIterator<Item = &'a TestRunInfo>>(
 ) -> (Duration, u64) {
     test_results.into_iter().fold(
         (Duration::new(0, 0), 0),
-        |(mut acc_time, mut acc_instrs), test_run_info| {
+        |(mut acc_time, mut acc_gas), test_run_info| {
             acc_time += test_run_info.elapsed_time;
-            acc_instrs += test_run_info.instructions_executed;
-            (acc_time, acc_instrs)
+            acc_gas += test_run_info.gas_used;
+            (acc_time, acc_gas)
         },
     )
 }
@@ -567,6 +807,62 @@ impl TestResults {
         writeln!(writer.lock().unwrap())
     }
 
+    /// Writes a machine-readable report of the test results to the given file
+    pub fn write_report(&self, format: ReportFormat, path: &Path) -> Result<()> {
+        let report = self.json_report();
+        let content = match format {
+            ReportFormat::Json => serde_json::to_string_pretty(&report)?,
+            ReportFormat::Junit => junit_report(&report),
+        };
+        write_bytes_to_file(path, content.as_bytes())
+    }
+
+    fn json_report(&self) -> JsonReport {
+        type Runs<'a> = (Vec<&'a TestRunInfo>, Vec<&'a TestFailure>);
+        let mut tests: BTreeMap<(&ModuleId, &String), Runs> = BTreeMap::new();
+        for (module_id, test_results) in &self.final_statistics.passed {
+            for (function_name, runs) in test_results {
+                let entry = tests.entry((module_id, function_name)).or_default();
+                entry.0.extend(runs);
+            }
+        }
+        for (module_id, test_failures) in &self.final_statistics.failed {
+            for (function_name, failures) in test_failures {
+                let entry = tests.entry((module_id, function_name)).or_default();
+                entry.0.extend(failures.iter().map(|f| &f.test_run_info));
+                entry.1.extend(failures);
+            }
+        }
+
+        let tests: Vec<_> = tests
+            .into_iter()
+            .map(|((module_id, function_name), (runs, failures))| {
+                let (time, gas_used) = calculate_run_statistics(runs.iter().copied());
+                JsonTest {
+                    name: function_name.clone(),
+                    module: format_module_id(&self.test_plan.module_info, module_id),
+                    passed: failures.is_empty(),
+                    runs: runs.len(),
+                    duration_secs: time.as_secs_f64(),
+                    gas_used,
+                    instructions_executed: runs.iter().map(|r| r.instructions_executed).sum(),
+                    output: runs.iter().map(|r| r.output.as_str()).collect(),
+                    failures: failures
+                        .into_iter()
+                        .map(|f| f.json_failure(&self.test_plan))
+                        .collect(),
+                }
+            })
+            .collect();
+        let passed = tests.iter().filter(|t| t.passed).count();
+        JsonReport {
+            total: tests.len(),
+            passed,
+            failed: tests.len() - passed,
+            tests,
+        }
+    }
+
     /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
     pub fn summarize<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
         let num_failed_tests = self
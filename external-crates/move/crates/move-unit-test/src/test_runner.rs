@@ -297,14 +297,17 @@ impl SharedTestingConfig {
 
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
         let now = Instant::now();
-        let serialized_return_values_result = session.execute_function_bypass_visibility(
-            &test_plan.module_id,
-            IdentStr::new(function_name).unwrap(),
-            vec![], // no ty args, at least for now
-            serialize_values(arguments.iter()),
-            &mut gas_meter,
-            tracer,
-        );
+        let (serialized_return_values_result, output) =
+            move_stdlib_natives::debug::with_captured_output(|| {
+                session.execute_function_bypass_visibility(
+                    &test_plan.module_id,
+                    IdentStr::new(function_name).unwrap(),
+                    vec![], // no ty args, at least for now
+                    serialize_values(arguments.iter()),
+                    &mut gas_meter,
+                    tracer,
+                )
+            });
         let mut return_result = serialized_return_values_result.map(|res| {
             res.return_values
                 .into_iter()
@@ -323,12 +326,12 @@ impl SharedTestingConfig {
         };
         let test_run_info = TestRunInfo::new(
             now.elapsed(),
-            // TODO(Gas): This doesn't look quite right...
-            //            We're not computing the number of instructions executed even with a unit gas schedule.
             Gas::new(self.execution_bound)
                 .checked_sub(gas_meter.remaining_gas())
                 .unwrap()
                 .into(),
+            gas_meter.instructions_executed(),
+            output,
             trace,
         );
         match session.finish_with_extensions().0 {
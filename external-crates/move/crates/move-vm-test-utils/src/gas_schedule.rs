@@ -112,6 +112,7 @@ pub struct GasStatus<'a> {
     gas_left: InternalGas,
     charge: bool,
     profiler: Option<GasProfiler>,
+    instructions_executed: u64,
 }
 
 impl<'a> GasStatus<'a> {
@@ -125,6 +126,7 @@ impl<'a> GasStatus<'a> {
             cost_table,
             charge: true,
             profiler: None,
+            instructions_executed: 0,
         }
     }
 
@@ -138,6 +140,7 @@ impl<'a> GasStatus<'a> {
             cost_table: &ZERO_COST_SCHEDULE,
             charge: false,
             profiler: None,
+            instructions_executed: 0,
         }
     }
 
@@ -151,6 +154,11 @@ impl<'a> GasStatus<'a> {
         self.gas_left.to_unit_round_down()
     }
 
+    /// Return the number of instructions charged for so far (whether metering is enabled or not).
+    pub fn instructions_executed(&self) -> u64 {
+        self.instructions_executed
+    }
+
     /// Charge a given amount of gas and fail if not enough gas units are left.
     pub fn deduct_gas(&mut self, amount: InternalGas) -> PartialVMResult<()> {
         if !self.charge {
@@ -170,6 +178,7 @@ impl<'a> GasStatus<'a> {
     }
 
     fn charge_instr(&mut self, opcode: Opcodes) -> PartialVMResult<()> {
+        self.instructions_executed += 1;
         self.deduct_gas(
             self.cost_table
                 .instruction_cost(opcode as u8)
@@ -184,6 +193,7 @@ impl<'a> GasStatus<'a> {
         opcode: Opcodes,
         size: AbstractMemorySize,
     ) -> PartialVMResult<()> {
+        self.instructions_executed += 1;
         // Make sure that the size is always non-zero
         let size = std::cmp::max(1.into(), size);
         debug_assert!(size > 0.into());